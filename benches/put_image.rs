@@ -0,0 +1,77 @@
+use std::io::{BufReader, BufWriter};
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main, Throughput};
+use xwindow::read_util::{ByteOrder, Decode, Encode, ReadableRead, SliceReader, SliceWriter, WritableWrite};
+use xwindow::request::put_image::{PutImageFormat, PutImageRequest, PutImageRequestRef};
+
+//BIG-REQUESTSを使わずに送れる最大に近い大きさ(256x255、32bppのZPixmap)
+fn request() -> PutImageRequest {
+    PutImageRequest {
+        format: PutImageFormat::ZPixmap,
+        drawable: 1,
+        gc: 2,
+        width: 256,
+        height: 255,
+        dst_x: 0,
+        dst_y: 0,
+        left_pad: 0,
+        depth: 24,
+        data: (0..256 * 255 * 4).map(|i| i as u8).collect(),
+    }
+}
+
+fn request_ref(request: &PutImageRequest) -> PutImageRequestRef<'_> {
+    PutImageRequestRef {
+        format: request.format.clone(),
+        drawable: request.drawable,
+        gc: request.gc,
+        width: request.width,
+        height: request.height,
+        dst_x: request.dst_x,
+        dst_y: request.dst_y,
+        left_pad: request.left_pad,
+        depth: request.depth,
+        data: &request.data[..],
+    }
+}
+
+fn decode(c: &mut Criterion) {
+    let request = request();
+    let order = ByteOrder::LSBFirst;
+    let mut input = Vec::new();
+    request_ref(&request).encode_to_vec(&mut input, &order).unwrap();
+    //opcodeは読まない
+    let input = &input[1..];
+
+    let mut group = c.benchmark_group("put_image_decode");
+    group.throughput(Throughput::Bytes(input.len() as u64 + 1));
+    group.bench_function("buf_reader", |b| b.iter(|| {
+        let value: PutImageRequest = BufReader::new(input).read_value(&order).unwrap();
+        value
+    }));
+    group.bench_function("slice_reader", |b| b.iter(|| {
+        PutImageRequestRef::decode_from(&mut SliceReader::new(input), &order).unwrap()
+    }));
+    group.finish();
+}
+
+fn encode(c: &mut Criterion) {
+    let request = request();
+    let order = ByteOrder::LSBFirst;
+    let length = request_ref(&request).encoded_length();
+    let mut buffer = vec![0; length];
+
+    let mut group = c.benchmark_group("put_image_encode");
+    group.throughput(Throughput::Bytes(length as u64));
+    //write_valueは値を消費するので、複製はsetupで作って計測に含めない
+    group.bench_function("buf_writer", |b| b.iter_batched(|| request.clone(), |request| {
+        BufWriter::new(&mut buffer[..]).write_value(request, &order).unwrap();
+    }, BatchSize::LargeInput));
+    group.bench_function("slice_writer", |b| b.iter(|| {
+        request_ref(&request).encode_to(&mut SliceWriter::new(&mut buffer[..]), &order).unwrap();
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, decode, encode);
+criterion_main!(benches);
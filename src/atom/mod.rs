@@ -0,0 +1,233 @@
+/// atomの名前とidの対応
+/// 1から68までは定義済みのatomで、それ以降はInternAtomで順に割り当てる
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::request::get_atom_name::{GetAtomNameRequest, GetAtomNameResponse};
+use crate::request::intern_atom::{InternAtomRequest, InternAtomResponse};
+
+mod test;
+
+pub const PRIMARY: u32 = 1;
+pub const SECONDARY: u32 = 2;
+pub const ARC: u32 = 3;
+pub const ATOM: u32 = 4;
+pub const BITMAP: u32 = 5;
+pub const CARDINAL: u32 = 6;
+pub const COLORMAP: u32 = 7;
+pub const CURSOR: u32 = 8;
+pub const CUT_BUFFER0: u32 = 9;
+pub const CUT_BUFFER1: u32 = 10;
+pub const CUT_BUFFER2: u32 = 11;
+pub const CUT_BUFFER3: u32 = 12;
+pub const CUT_BUFFER4: u32 = 13;
+pub const CUT_BUFFER5: u32 = 14;
+pub const CUT_BUFFER6: u32 = 15;
+pub const CUT_BUFFER7: u32 = 16;
+pub const DRAWABLE: u32 = 17;
+pub const FONT: u32 = 18;
+pub const INTEGER: u32 = 19;
+pub const PIXMAP: u32 = 20;
+pub const POINT: u32 = 21;
+pub const RECTANGLE: u32 = 22;
+pub const RESOURCE_MANAGER: u32 = 23;
+pub const RGB_COLOR_MAP: u32 = 24;
+pub const RGB_BEST_MAP: u32 = 25;
+pub const RGB_BLUE_MAP: u32 = 26;
+pub const RGB_DEFAULT_MAP: u32 = 27;
+pub const RGB_GRAY_MAP: u32 = 28;
+pub const RGB_GREEN_MAP: u32 = 29;
+pub const RGB_RED_MAP: u32 = 30;
+pub const STRING: u32 = 31;
+pub const VISUALID: u32 = 32;
+pub const WINDOW: u32 = 33;
+pub const WM_COMMAND: u32 = 34;
+pub const WM_HINTS: u32 = 35;
+pub const WM_CLIENT_MACHINE: u32 = 36;
+pub const WM_ICON_NAME: u32 = 37;
+pub const WM_ICON_SIZE: u32 = 38;
+pub const WM_NAME: u32 = 39;
+pub const WM_NORMAL_HINTS: u32 = 40;
+pub const WM_SIZE_HINTS: u32 = 41;
+pub const WM_ZOOM_HINTS: u32 = 42;
+pub const MIN_SPACE: u32 = 43;
+pub const NORM_SPACE: u32 = 44;
+pub const MAX_SPACE: u32 = 45;
+pub const END_SPACE: u32 = 46;
+pub const SUPERSCRIPT_X: u32 = 47;
+pub const SUPERSCRIPT_Y: u32 = 48;
+pub const SUBSCRIPT_X: u32 = 49;
+pub const SUBSCRIPT_Y: u32 = 50;
+pub const UNDERLINE_POSITION: u32 = 51;
+pub const UNDERLINE_THICKNESS: u32 = 52;
+pub const STRIKEOUT_ASCENT: u32 = 53;
+pub const STRIKEOUT_DESCENT: u32 = 54;
+pub const ITALIC_ANGLE: u32 = 55;
+pub const X_HEIGHT: u32 = 56;
+pub const QUAD_WIDTH: u32 = 57;
+pub const WEIGHT: u32 = 58;
+pub const POINT_SIZE: u32 = 59;
+pub const RESOLUTION: u32 = 60;
+pub const COPYRIGHT: u32 = 61;
+pub const NOTICE: u32 = 62;
+pub const FONT_NAME: u32 = 63;
+pub const FAMILY_NAME: u32 = 64;
+pub const FULL_NAME: u32 = 65;
+pub const CAP_HEIGHT: u32 = 66;
+pub const WM_CLASS: u32 = 67;
+pub const WM_TRANSIENT_FOR: u32 = 68;
+
+/// 定義済みのatomの名前で、添字+1がid
+pub const PREDEFINED_ATOMS: [&str; 68] = [
+    "PRIMARY",
+    "SECONDARY",
+    "ARC",
+    "ATOM",
+    "BITMAP",
+    "CARDINAL",
+    "COLORMAP",
+    "CURSOR",
+    "CUT_BUFFER0",
+    "CUT_BUFFER1",
+    "CUT_BUFFER2",
+    "CUT_BUFFER3",
+    "CUT_BUFFER4",
+    "CUT_BUFFER5",
+    "CUT_BUFFER6",
+    "CUT_BUFFER7",
+    "DRAWABLE",
+    "FONT",
+    "INTEGER",
+    "PIXMAP",
+    "POINT",
+    "RECTANGLE",
+    "RESOURCE_MANAGER",
+    "RGB_COLOR_MAP",
+    "RGB_BEST_MAP",
+    "RGB_BLUE_MAP",
+    "RGB_DEFAULT_MAP",
+    "RGB_GRAY_MAP",
+    "RGB_GREEN_MAP",
+    "RGB_RED_MAP",
+    "STRING",
+    "VISUALID",
+    "WINDOW",
+    "WM_COMMAND",
+    "WM_HINTS",
+    "WM_CLIENT_MACHINE",
+    "WM_ICON_NAME",
+    "WM_ICON_SIZE",
+    "WM_NAME",
+    "WM_NORMAL_HINTS",
+    "WM_SIZE_HINTS",
+    "WM_ZOOM_HINTS",
+    "MIN_SPACE",
+    "NORM_SPACE",
+    "MAX_SPACE",
+    "END_SPACE",
+    "SUPERSCRIPT_X",
+    "SUPERSCRIPT_Y",
+    "SUBSCRIPT_X",
+    "SUBSCRIPT_Y",
+    "UNDERLINE_POSITION",
+    "UNDERLINE_THICKNESS",
+    "STRIKEOUT_ASCENT",
+    "STRIKEOUT_DESCENT",
+    "ITALIC_ANGLE",
+    "X_HEIGHT",
+    "QUAD_WIDTH",
+    "WEIGHT",
+    "POINT_SIZE",
+    "RESOLUTION",
+    "COPYRIGHT",
+    "NOTICE",
+    "FONT_NAME",
+    "FAMILY_NAME",
+    "FULL_NAME",
+    "CAP_HEIGHT",
+    "WM_CLASS",
+    "WM_TRANSIENT_FOR",
+];
+
+/// atomの上位3bitは常に0
+const MAX_ATOM: u32 = 0x1fffffff;
+
+#[derive(Debug)]
+struct Atoms {
+    names: Vec<String>,
+    ids: HashMap<String, u32>,
+}
+
+/// cloneしたものは同じ表を共有するので、connectionごとに渡して使う
+#[derive(Clone, Debug)]
+pub struct AtomTable {
+    atoms: Arc<RwLock<Atoms>>,
+}
+
+impl Default for AtomTable {
+    fn default() -> Self {
+        AtomTable::new()
+    }
+}
+
+impl AtomTable {
+    pub fn new() -> Self {
+        let names = PREDEFINED_ATOMS.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+        let ids = names.iter().enumerate().map(|(i, name)| (name.clone(), i as u32 + 1)).collect();
+        AtomTable {
+            atoms: Arc::new(RwLock::new(Atoms { names, ids })),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<u32> {
+        self.atoms.read().unwrap().ids.get(name).cloned()
+    }
+
+    /// 無ければBadAtom
+    pub fn name(&self, atom: u32) -> Result<String> {
+        let atoms = self.atoms.read().unwrap();
+        match atom {
+            0 => None,
+            atom => atoms.names.get(atom as usize - 1).cloned(),
+        }.ok_or(Error::ProtocolError(ErrorCode::Atom, atom))
+    }
+
+    /// only_if_existsで無ければNone
+    /// 割り当てられるidが無くなったらBadAlloc
+    pub fn intern(&self, name: &str, only_if_exists: bool) -> Result<Option<u32>> {
+        if let Some(atom) = self.get(name) {
+            return Ok(Some(atom));
+        }
+        if only_if_exists {
+            return Ok(None);
+        }
+        let mut atoms = self.atoms.write().unwrap();
+        //readとwriteの間に別のconnectionが登録しているかもしれない
+        if let Some(atom) = atoms.ids.get(name) {
+            return Ok(Some(*atom));
+        }
+        let atom = atoms.names.len() as u32 + 1;
+        if atom > MAX_ATOM {
+            return Err(Error::ProtocolError(ErrorCode::Alloc, 0));
+        }
+        atoms.names.push(name.to_string());
+        atoms.ids.insert(name.to_string(), atom);
+        Ok(Some(atom))
+    }
+
+    pub fn intern_atom(&self, request: &InternAtomRequest) -> Result<InternAtomResponse> {
+        Ok(InternAtomResponse {
+            sequence_number: 0,
+            atom: self.intern(&request.name, request.only_if_exists)?,
+        })
+    }
+
+    pub fn get_atom_name(&self, request: &GetAtomNameRequest) -> Result<GetAtomNameResponse> {
+        Ok(GetAtomNameResponse {
+            sequence_number: 0,
+            name: self.name(request.atom)?,
+        })
+    }
+}
@@ -0,0 +1,59 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+use std::thread;
+
+use crate::Error;
+use crate::error::ErrorCode;
+use crate::request::get_atom_name::{GetAtomNameRequest, GetAtomNameResponse};
+use crate::request::intern_atom::{InternAtomRequest, InternAtomResponse};
+use super::*;
+
+#[test]
+fn predefined_test() {
+    let table = AtomTable::new();
+    assert_eq!(table.get("PRIMARY"), Some(PRIMARY));
+    assert_eq!(table.get("STRING"), Some(31));
+    assert_eq!(table.get("WM_TRANSIENT_FOR"), Some(68));
+    assert_eq!(table.name(WM_NAME).unwrap(), "WM_NAME");
+    assert_eq!(table.intern("CUT_BUFFER7", false).unwrap(), Some(CUT_BUFFER7));
+    assert!(matches!(table.name(0), Err(Error::ProtocolError(ErrorCode::Atom, 0))));
+    assert!(matches!(table.name(69), Err(Error::ProtocolError(ErrorCode::Atom, 69))));
+}
+
+#[test]
+fn intern_atom_test() {
+    let table = AtomTable::new();
+    let response = table.intern_atom(&InternAtomRequest { only_if_exists: true, name: "WM_PROTOCOLS".to_string() }).unwrap();
+    assert_eq!(response, InternAtomResponse { sequence_number: 0, atom: None });
+    let response = table.intern_atom(&InternAtomRequest { only_if_exists: false, name: "WM_PROTOCOLS".to_string() }).unwrap();
+    assert_eq!(response, InternAtomResponse { sequence_number: 0, atom: Some(69) });
+    let response = table.intern_atom(&InternAtomRequest { only_if_exists: true, name: "WM_PROTOCOLS".to_string() }).unwrap();
+    assert_eq!(response, InternAtomResponse { sequence_number: 0, atom: Some(69) });
+    //名前は大文字と小文字を区別する
+    assert_eq!(table.intern("wm_protocols", false).unwrap(), Some(70));
+
+    let response = table.get_atom_name(&GetAtomNameRequest { atom: 69 }).unwrap();
+    assert_eq!(response, GetAtomNameResponse { sequence_number: 0, name: "WM_PROTOCOLS".to_string() });
+    assert!(matches!(table.get_atom_name(&GetAtomNameRequest { atom: 71 }), Err(Error::ProtocolError(ErrorCode::Atom, 71))));
+}
+
+#[test]
+fn shared_test() {
+    let table = AtomTable::new();
+    let handles = (0..4).map(|i| {
+        let table = table.clone();
+        thread::spawn(move || {
+            (0..100).map(|j| table.intern(&format!("ATOM_{}", (i + j) % 50), false).unwrap().unwrap()).collect::<Vec<_>>()
+        })
+    }).collect::<Vec<_>>();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    for i in 0..50 {
+        let atom = table.intern(&format!("ATOM_{}", i), true).unwrap().unwrap();
+        assert!((69..69 + 50).contains(&atom));
+        assert_eq!(table.name(atom).unwrap(), format!("ATOM_{}", i));
+    }
+    assert!(table.name(69 + 50).is_err());
+}
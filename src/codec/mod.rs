@@ -0,0 +1,155 @@
+/// tokio_util::codecで使うserver側のcodec
+/// 1つ分のbyte列が揃うまではNoneを返して続きを待つ
+use std::io::{BufReader, BufWriter, Write};
+
+use bytes::{Buf, BufMut, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, Writable, WritableWrite};
+use crate::request::{Request, RequestHeader, RequestReader};
+use crate::server_packet::ServerPacket;
+use crate::setup::{ConnectionSetupInformation, ConnectionSetupResponse, read_setup};
+
+mod test;
+
+/// clientから送られてくるもの
+/// 最初の1つだけがSetupで、それ以降はRequest
+pub enum ClientMessage {
+    Setup(ByteOrder, ConnectionSetupInformation),
+    Request(RequestHeader, Result<Request>),
+}
+
+#[derive(Debug)]
+pub struct ServerCodec {
+    order: Option<ByteOrder>,
+    reader: RequestReader,
+    skip: usize,
+}
+
+impl ServerCodec {
+    pub fn new(reader: RequestReader) -> Self {
+        ServerCodec {
+            order: None,
+            reader,
+            skip: 0,
+        }
+    }
+
+    /// connection setupが終わるまではNone
+    pub fn byte_order(&self) -> Option<&ByteOrder> {
+        self.order.as_ref()
+    }
+
+    /// BIG-REQUESTSを有効にするときなどに使う
+    pub fn request_reader_mut(&mut self) -> &mut RequestReader {
+        &mut self.reader
+    }
+
+    fn decode_setup(&mut self, src: &mut BytesMut) -> Result<Option<ClientMessage>> {
+        if src.len() < 12 {
+            return Ok(None);
+        }
+        let order = match src[0] {
+            0o102 => ByteOrder::MSBFirst,
+            0o154 => ByteOrder::LSBFirst,
+            _ => return Err(Error::InvalidValue("byte order")),
+        };
+        let name_length = order.decode::<u16>(&src[6..8]) as usize;
+        let data_length = order.decode::<u16>(&src[8..10]) as usize;
+        let length = 12 + ((name_length + 3) & !3) + ((data_length + 3) & !3);
+        if src.len() < length {
+            src.reserve(length - src.len());
+            return Ok(None);
+        }
+        let mut buffer = [0; 1024];
+        let (order, information) = read_setup(&mut BufReader::new(&src[..length]), &mut buffer)?;
+        src.advance(length);
+        self.order = Some(order.clone());
+        Ok(Some(ClientMessage::Setup(order, information)))
+    }
+
+    fn decode_request(&mut self, src: &mut BytesMut, order: ByteOrder) -> Result<Option<ClientMessage>> {
+        if src.len() < 4 {
+            return Ok(None);
+        }
+        let mut length = order.decode::<u16>(&src[2..4]) as usize;
+        let mut header_length = 4;
+        if length == 0 && self.reader.big_requests {
+            if src.len() < 8 {
+                return Ok(None);
+            }
+            length = order.decode::<u32>(&src[4..8]) as usize;
+            header_length = 8;
+        }
+        //長すぎるrequestはbufferに溜めずに、届いた分から捨てる
+        if length > self.reader.maximum_request_length as usize {
+            let header = RequestHeader {
+                opcode: src[0],
+                data: src[1],
+                length: length as u32,
+            };
+            src.advance(header_length);
+            self.skip = (length << 2) - header_length;
+            self.skip_body(src);
+            return Ok(Some(ClientMessage::Request(header, Err(Error::ProtocolError(ErrorCode::Length, 0)))));
+        }
+        let total = (length << 2).max(header_length);
+        if src.len() < total {
+            src.reserve(total - src.len());
+            return Ok(None);
+        }
+        let frame = src.split_to(total);
+        //bodyは各requestの値に複製するだけで、一度Vecに移すことはしない
+        let (header, request) = self.reader.read_slice(&frame[..], &order)?;
+        Ok(Some(ClientMessage::Request(header, request.map(|request| request.into_request(&order)))))
+    }
+
+    fn skip_body(&mut self, src: &mut BytesMut) {
+        let length = self.skip.min(src.len());
+        src.advance(length);
+        self.skip -= length;
+    }
+
+    fn encode_value<T: Writable>(&mut self, item: T, dst: &mut BytesMut) -> Result<()> {
+        let order = self.order.as_ref().ok_or(Error::InvalidValue("byte order"))?;
+        let mut writer = BufWriter::new(dst.writer());
+        writer.write_value(item, order)?;
+        writer.flush().map_err(Error::IoError)
+    }
+}
+
+impl Decoder for ServerCodec {
+    type Item = ClientMessage;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>> {
+        if self.skip > 0 {
+            self.skip_body(src);
+            if self.skip > 0 {
+                return Ok(None);
+            }
+        }
+        match self.order.clone() {
+            None => self.decode_setup(src),
+            Some(order) => self.decode_request(src, order),
+        }
+    }
+}
+
+impl Encoder<ConnectionSetupResponse> for ServerCodec {
+    type Error = Error;
+
+    fn encode(&mut self, item: ConnectionSetupResponse, dst: &mut BytesMut) -> Result<()> {
+        self.encode_value(item, dst)
+    }
+}
+
+impl Encoder<ServerPacket> for ServerCodec {
+    type Error = Error;
+
+    fn encode(&mut self, item: ServerPacket, dst: &mut BytesMut) -> Result<()> {
+        self.encode_value(item, dst)
+    }
+}
@@ -0,0 +1,110 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod server_codec {
+    use bytes::BytesMut;
+    use tokio_util::codec::{Decoder, Encoder};
+
+    use crate::Error;
+    use crate::codec::{ClientMessage, ServerCodec};
+    use crate::error::{ErrorCode, XError};
+    use crate::read_util::ByteOrder;
+    use crate::request::{Request, RequestHeader, RequestReader};
+    use crate::server_packet::ServerPacket;
+    use crate::setup::{ConnectionSetupFailed, ConnectionSetupInformation, ConnectionSetupResponse};
+
+    #[test]
+    fn decode_setup_test() {
+        let mut codec = ServerCodec::new(RequestReader::new(0xffff));
+        let input = [0o154, 0, 11, 0, 0, 0, 3, 0, 0, 0, 0, 0, b'a', b'b', b'c', 0];
+        let mut buffer = BytesMut::new();
+        //1byteずつ届いても最後まで揃うまでは何も返さない
+        for i in 0..input.len() - 1 {
+            buffer.extend_from_slice(&input[i..i + 1]);
+            assert!(codec.decode(&mut buffer).unwrap().is_none());
+        }
+        buffer.extend_from_slice(&input[input.len() - 1..]);
+        match codec.decode(&mut buffer).unwrap() {
+            Some(ClientMessage::Setup(order, information)) => {
+                assert_eq!(order, ByteOrder::LSBFirst);
+                assert_eq!(information, ConnectionSetupInformation {
+                    protocol_major_version: 11,
+                    protocol_minor_version: 0,
+                    authorization_protocol_name: "abc".to_string(),
+                    authorization_protocol_data: "".to_string(),
+                });
+            }
+            _ => panic!(),
+        }
+        assert!(buffer.is_empty());
+        assert_eq!(codec.byte_order(), Some(&ByteOrder::LSBFirst));
+    }
+
+    #[test]
+    fn decode_request_test() {
+        let mut codec = ServerCodec::new(RequestReader::new(4));
+        let mut buffer = BytesMut::new();
+        buffer.extend_from_slice(&[0o102, 0, 0, 11, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert!(matches!(codec.decode(&mut buffer).unwrap(), Some(ClientMessage::Setup(ByteOrder::MSBFirst, _))));
+        buffer.extend_from_slice(&[8, 0, 0, 2, 0, 0]);
+        assert!(codec.decode(&mut buffer).unwrap().is_none());
+        //残りと次のrequestがまとめて届く
+        buffer.extend_from_slice(&[0, 1, 120, 0, 0, 1, 10, 0]);
+        match codec.decode(&mut buffer).unwrap() {
+            Some(ClientMessage::Request(header, Ok(Request::MapWindow(request)))) => {
+                assert_eq!(header, RequestHeader { opcode: 8, data: 0, length: 2 });
+                assert_eq!(request.window, 1);
+            }
+            _ => panic!(),
+        }
+        match codec.decode(&mut buffer).unwrap() {
+            Some(ClientMessage::Request(header, Err(Error::ProtocolError(ErrorCode::Request, _)))) => {
+                assert_eq!(header, RequestHeader { opcode: 120, data: 0, length: 1 });
+            }
+            _ => panic!(),
+        }
+        assert!(codec.decode(&mut buffer).unwrap().is_none());
+        //maximum_request_lengthを超えるrequestは本体が届く前にエラーを返す
+        buffer.extend_from_slice(&[0, 5, 0, 0, 0, 2]);
+        match codec.decode(&mut buffer).unwrap() {
+            Some(ClientMessage::Request(header, Err(Error::ProtocolError(ErrorCode::Length, _)))) => {
+                assert_eq!(header, RequestHeader { opcode: 10, data: 0, length: 5 });
+            }
+            _ => panic!(),
+        }
+        buffer.extend_from_slice(&[0; 12]);
+        assert!(codec.decode(&mut buffer).unwrap().is_none());
+        buffer.extend_from_slice(&[10, 0, 0, 2, 0, 0, 0, 3]);
+        match codec.decode(&mut buffer).unwrap() {
+            Some(ClientMessage::Request(_, Ok(Request::UnmapWindow(request)))) => assert_eq!(request.window, 3),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn encode_test() {
+        let mut codec = ServerCodec::new(RequestReader::new(0xffff));
+        let mut buffer = BytesMut::new();
+        let packet = ServerPacket::Error(XError {
+            code: ErrorCode::Request,
+            sequence_number: 1,
+            bad_value: 0,
+            minor_opcode: 0,
+            major_opcode: 120,
+        });
+        //byte orderが決まるまでは書けない
+        assert!(codec.encode(packet.clone(), &mut buffer).is_err());
+        let mut input = BytesMut::from(&[0o154, 0, 11, 0, 0, 0, 0, 0, 0, 0, 0, 0][..]);
+        codec.decode(&mut input).unwrap();
+        let response = ConnectionSetupResponse::Failed(ConnectionSetupFailed {
+            protocol_major_version: 11,
+            protocol_minor_version: 0,
+            reason: "abcde".to_string(),
+        });
+        codec.encode(response, &mut buffer).unwrap();
+        assert_eq!(&buffer[..], &[0, 5, 11, 0, 0, 0, 2, 0, b'a', b'b', b'c', b'd', b'e', 0, 0, 0][..]);
+        buffer.clear();
+        codec.encode(packet, &mut buffer).unwrap();
+        assert_eq!(&buffer[..], &[0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 120, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0][..]);
+    }
+}
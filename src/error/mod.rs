@@ -0,0 +1,141 @@
+/// https://www.x.org/releases/current/doc/xproto/x11protocol.html#Encoding::Errors
+use std::io::{Read, Write};
+
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+
+mod test;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    Request,
+    Value,
+    Window,
+    Pixmap,
+    Atom,
+    Cursor,
+    Font,
+    Match,
+    Drawable,
+    Access,
+    Alloc,
+    Colormap,
+    GContext,
+    IDChoice,
+    Name,
+    Length,
+    Implementation,
+    Extension(u8),
+}
+
+impl Readable for ErrorCode {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            1 => Ok(Self::Request),
+            2 => Ok(Self::Value),
+            3 => Ok(Self::Window),
+            4 => Ok(Self::Pixmap),
+            5 => Ok(Self::Atom),
+            6 => Ok(Self::Cursor),
+            7 => Ok(Self::Font),
+            8 => Ok(Self::Match),
+            9 => Ok(Self::Drawable),
+            10 => Ok(Self::Access),
+            11 => Ok(Self::Alloc),
+            12 => Ok(Self::Colormap),
+            13 => Ok(Self::GContext),
+            14 => Ok(Self::IDChoice),
+            15 => Ok(Self::Name),
+            16 => Ok(Self::Length),
+            17 => Ok(Self::Implementation),
+            other if other >= 128 => Ok(Self::Extension(other)),
+            _ => Err(Error::InvalidValue("ErrorCode")),
+        }
+    }
+}
+
+impl Writable for ErrorCode {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::Request => 1,
+            Self::Value => 2,
+            Self::Window => 3,
+            Self::Pixmap => 4,
+            Self::Atom => 5,
+            Self::Cursor => 6,
+            Self::Font => 7,
+            Self::Match => 8,
+            Self::Drawable => 9,
+            Self::Access => 10,
+            Self::Alloc => 11,
+            Self::Colormap => 12,
+            Self::GContext => 13,
+            Self::IDChoice => 14,
+            Self::Name => 15,
+            Self::Length => 16,
+            Self::Implementation => 17,
+            Self::Extension(code) => code,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+/// bad_valueはcodeによって不正なresource id、atom、値のいずれかが入る
+/// それ以外のcodeでは使われない
+#[derive(Clone, Debug, PartialEq)]
+pub struct XError {
+    pub code: ErrorCode,
+    pub sequence_number: u16,
+    pub bad_value: u32,
+    pub minor_opcode: u16,
+    pub major_opcode: u8,
+}
+
+impl Readable for XError {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let code = stream.read_value(order)?;
+        let sequence_number = stream.read_value(order)?;
+        let bad_value = stream.read_value(order)?;
+        let minor_opcode = stream.read_value(order)?;
+        let major_opcode = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 21], 21)?;
+        Ok(XError {
+            code,
+            sequence_number,
+            bad_value,
+            minor_opcode,
+            major_opcode,
+        })
+    }
+}
+
+impl Writable for XError {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(0, order)?;
+        stream.write_value(data.code, order)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.bad_value, order)?;
+        stream.write_value(data.minor_opcode, order)?;
+        stream.write_value(data.major_opcode, order)?;
+        stream.write_all(&[0; 21]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+impl Error {
+    /// リクエストの処理中に発生したエラーをクライアントに返すXErrorに変換する
+    /// 接続自体の問題でエラーを返せない場合はNone
+    pub fn to_x_error(&self, sequence_number: u16, major_opcode: u8, minor_opcode: u16) -> Option<XError> {
+        match self {
+            Error::ProtocolError(code, bad_value) => Some(XError {
+                code: code.clone(),
+                sequence_number,
+                bad_value: *bad_value,
+                minor_opcode,
+                major_opcode,
+            }),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,114 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod x_error {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::Error;
+    use crate::error::{ErrorCode, XError};
+    use crate::read_util::{ByteOrder, Readable, Writable};
+
+    #[test]
+    fn read_test() {
+        let input = [0, 3, 0, 1, 0, 0, 0, 2, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let value = XError::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, XError {
+            code: ErrorCode::Window,
+            sequence_number: 1,
+            bad_value: 2,
+            minor_opcode: 0,
+            major_opcode: 4,
+        });
+        let input = [0, 140, 1, 0, 2, 0, 0, 0, 3, 0, 130, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let value = XError::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, XError {
+            code: ErrorCode::Extension(140),
+            sequence_number: 1,
+            bad_value: 2,
+            minor_opcode: 3,
+            major_opcode: 130,
+        });
+    }
+
+    #[test]
+    fn write_test() {
+        let value = XError {
+            code: ErrorCode::Length,
+            sequence_number: 1,
+            bad_value: 0,
+            minor_opcode: 0,
+            major_opcode: 55,
+        };
+        let mut buffer = [0; 32];
+        XError::write(&mut BufWriter::new(&mut buffer[..]), value.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [0, 16, 0, 1, 0, 0, 0, 0, 0, 0, 55, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let mut buffer = [0; 32];
+        XError::write(&mut BufWriter::new(&mut buffer[..]), value, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [0, 16, 1, 0, 0, 0, 0, 0, 0, 0, 55, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn all_codes_test() {
+        for code in 1..=17u8 {
+            let input = [0, code, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+            let value = XError::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+            let mut buffer = [0; 32];
+            XError::write(&mut BufWriter::new(&mut buffer[..]), value, &ByteOrder::MSBFirst).unwrap();
+            assert_eq!(buffer, input);
+        }
+        let input = [0, 18, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert!(XError::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).is_err());
+    }
+
+    #[test]
+    fn to_x_error_test() {
+        let error = Error::ProtocolError(ErrorCode::Value, 20);
+        assert_eq!(error.to_x_error(7, 55, 0), Some(XError {
+            code: ErrorCode::Value,
+            sequence_number: 7,
+            bad_value: 20,
+            minor_opcode: 0,
+            major_opcode: 55,
+        }));
+        assert_eq!(Error::UnknownError.to_x_error(7, 55, 0), None);
+    }
+}
+
+mod request {
+    use std::io::BufReader;
+
+    use crate::Error;
+    use crate::error::ErrorCode;
+    use crate::read_util::{ByteOrder, Readable};
+    use crate::request::change_save_set::ChangeSaveSetRequest;
+    use crate::request::create_window::CreateWindowRequest;
+    use crate::request::Request;
+
+    #[test]
+    fn bad_value_test() {
+        let input = [2, 0, 2, 0, 0, 0, 1];
+        match ChangeSaveSetRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst) {
+            Err(Error::ProtocolError(ErrorCode::Value, 2)) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn bad_length_test() {
+        let input = [0, 0, 7, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0];
+        match CreateWindowRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst) {
+            Err(Error::ProtocolError(ErrorCode::Length, _)) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn bad_request_test() {
+        let input = [120, 0, 0, 1];
+        match Request::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst) {
+            Err(Error::ProtocolError(ErrorCode::Request, _)) => {}
+            Err(other) => panic!("{:?}", other),
+            Ok(_) => panic!(),
+        }
+    }
+}
@@ -0,0 +1,1920 @@
+/// https://www.x.org/releases/current/doc/xproto/x11protocol.html#Encoding::Events
+use std::collections::HashSet;
+use std::io::{BufWriter, Read, Write};
+
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+use crate::request::configure_window::{ConfigureWindowValueMaskValue, ConfigureWindowValueStackMode};
+
+mod test;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum KeyButMask {
+    Shift,
+    Lock,
+    Control,
+    Mod1,
+    Mod2,
+    Mod3,
+    Mod4,
+    Mod5,
+    Button1,
+    Button2,
+    Button3,
+    Button4,
+    Button5,
+}
+
+impl KeyButMask {
+    /// buttonの番号に対応するmask
+    /// 6以上のbuttonにはmaskが無いのでButton5とする
+    pub fn button(button: u8) -> Self {
+        match button {
+            1 => KeyButMask::Button1,
+            2 => KeyButMask::Button2,
+            3 => KeyButMask::Button3,
+            4 => KeyButMask::Button4,
+            _ => KeyButMask::Button5,
+        }
+    }
+}
+
+impl Readable for HashSet<KeyButMask> {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        const VALUES: [KeyButMask; 13] = [
+            KeyButMask::Shift,
+            KeyButMask::Lock,
+            KeyButMask::Control,
+            KeyButMask::Mod1,
+            KeyButMask::Mod2,
+            KeyButMask::Mod3,
+            KeyButMask::Mod4,
+            KeyButMask::Mod5,
+            KeyButMask::Button1,
+            KeyButMask::Button2,
+            KeyButMask::Button3,
+            KeyButMask::Button4,
+            KeyButMask::Button5,
+        ];
+        let value = stream.read_value::<u16>(order)?;
+        let mut result = HashSet::new();
+        for (i, v) in VALUES.iter().enumerate() {
+            if ((value >> i) & 1) == 1 {
+                result.insert(v.clone());
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl Writable for HashSet<KeyButMask> {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let mut value = 0u16;
+        for data in data {
+            let mask = match data {
+                KeyButMask::Shift => 0x0001,
+                KeyButMask::Lock => 0x0002,
+                KeyButMask::Control => 0x0004,
+                KeyButMask::Mod1 => 0x0008,
+                KeyButMask::Mod2 => 0x0010,
+                KeyButMask::Mod3 => 0x0020,
+                KeyButMask::Mod4 => 0x0040,
+                KeyButMask::Mod5 => 0x0080,
+                KeyButMask::Button1 => 0x0100,
+                KeyButMask::Button2 => 0x0200,
+                KeyButMask::Button3 => 0x0400,
+                KeyButMask::Button4 => 0x0800,
+                KeyButMask::Button5 => 0x1000,
+            };
+            value |= mask;
+        }
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MotionNotifyDetail {
+    Normal,
+    Hint,
+}
+
+impl Readable for MotionNotifyDetail {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::Normal),
+            1 => Ok(Self::Hint),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for MotionNotifyDetail {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::Normal => 0,
+            Self::Hint => 1,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+/// Pointer, PointerRoot, NoneはFocusIn/FocusOutのみ
+#[derive(Clone, Debug, PartialEq)]
+pub enum NotifyDetail {
+    Ancestor,
+    Virtual,
+    Inferior,
+    Nonlinear,
+    NonlinearVirtual,
+    Pointer,
+    PointerRoot,
+    None,
+}
+
+impl Readable for NotifyDetail {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::Ancestor),
+            1 => Ok(Self::Virtual),
+            2 => Ok(Self::Inferior),
+            3 => Ok(Self::Nonlinear),
+            4 => Ok(Self::NonlinearVirtual),
+            5 => Ok(Self::Pointer),
+            6 => Ok(Self::PointerRoot),
+            7 => Ok(Self::None),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for NotifyDetail {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::Ancestor => 0,
+            Self::Virtual => 1,
+            Self::Inferior => 2,
+            Self::Nonlinear => 3,
+            Self::NonlinearVirtual => 4,
+            Self::Pointer => 5,
+            Self::PointerRoot => 6,
+            Self::None => 7,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+/// WhileGrabbedはFocusIn/FocusOutのみ
+#[derive(Clone, Debug, PartialEq)]
+pub enum NotifyMode {
+    Normal,
+    Grab,
+    Ungrab,
+    WhileGrabbed,
+}
+
+impl Readable for NotifyMode {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::Normal),
+            1 => Ok(Self::Grab),
+            2 => Ok(Self::Ungrab),
+            3 => Ok(Self::WhileGrabbed),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for NotifyMode {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::Normal => 0,
+            Self::Grab => 1,
+            Self::Ungrab => 2,
+            Self::WhileGrabbed => 3,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum VisibilityNotifyState {
+    Unobscured,
+    PartiallyObscured,
+    FullyObscured,
+}
+
+impl Readable for VisibilityNotifyState {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::Unobscured),
+            1 => Ok(Self::PartiallyObscured),
+            2 => Ok(Self::FullyObscured),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for VisibilityNotifyState {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::Unobscured => 0,
+            Self::PartiallyObscured => 1,
+            Self::FullyObscured => 2,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CirculatePlace {
+    Top,
+    Bottom,
+}
+
+impl Readable for CirculatePlace {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::Top),
+            1 => Ok(Self::Bottom),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for CirculatePlace {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::Top => 0,
+            Self::Bottom => 1,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyNotifyState {
+    NewValue,
+    Deleted,
+}
+
+impl Readable for PropertyNotifyState {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::NewValue),
+            1 => Ok(Self::Deleted),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for PropertyNotifyState {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::NewValue => 0,
+            Self::Deleted => 1,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColormapNotifyState {
+    Uninstalled,
+    Installed,
+}
+
+impl Readable for ColormapNotifyState {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::Uninstalled),
+            1 => Ok(Self::Installed),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for ColormapNotifyState {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::Uninstalled => 0,
+            Self::Installed => 1,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MappingNotifyRequest {
+    Modifier,
+    Keyboard,
+    Pointer,
+}
+
+impl Readable for MappingNotifyRequest {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::Modifier),
+            1 => Ok(Self::Keyboard),
+            2 => Ok(Self::Pointer),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for MappingNotifyRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::Modifier => 0,
+            Self::Keyboard => 1,
+            Self::Pointer => 2,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+/// KeyPress, KeyRelease, ButtonPress, ButtonReleaseで共通の形式
+/// detailはKeyPress/KeyReleaseではKEYCODE、ButtonPress/ButtonReleaseではBUTTON
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeviceEvent {
+    pub detail: u8,
+    pub sequence_number: u16,
+    pub time: u32,
+    pub root: u32,
+    pub event: u32,
+    pub child: Option<u32>,
+    pub root_x: i16,
+    pub root_y: i16,
+    pub event_x: i16,
+    pub event_y: i16,
+    pub state: HashSet<KeyButMask>,
+    pub same_screen: bool,
+}
+
+impl Readable for DeviceEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let detail = stream.read_value(order)?;
+        let sequence_number = stream.read_value(order)?;
+        let time = stream.read_value(order)?;
+        let root = stream.read_value(order)?;
+        let event = stream.read_value(order)?;
+        let child = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let root_x = stream.read_value(order)?;
+        let root_y = stream.read_value(order)?;
+        let event_x = stream.read_value(order)?;
+        let event_y = stream.read_value(order)?;
+        let state = stream.read_value(order)?;
+        let same_screen = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        Ok(DeviceEvent {
+            detail,
+            sequence_number,
+            time,
+            root,
+            event,
+            child,
+            root_x,
+            root_y,
+            event_x,
+            event_y,
+            state,
+            same_screen,
+        })
+    }
+}
+
+impl Writable for DeviceEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value(data.detail, order)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.time, order)?;
+        stream.write_value(data.root, order)?;
+        stream.write_value(data.event, order)?;
+        stream.write_value(data.child.unwrap_or(0), order)?;
+        stream.write_value(data.root_x, order)?;
+        stream.write_value(data.root_y, order)?;
+        stream.write_value(data.event_x, order)?;
+        stream.write_value(data.event_y, order)?;
+        stream.write_value(data.state, order)?;
+        stream.write_value(data.same_screen, order)?;
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MotionNotifyEvent {
+    pub detail: MotionNotifyDetail,
+    pub sequence_number: u16,
+    pub time: u32,
+    pub root: u32,
+    pub event: u32,
+    pub child: Option<u32>,
+    pub root_x: i16,
+    pub root_y: i16,
+    pub event_x: i16,
+    pub event_y: i16,
+    pub state: HashSet<KeyButMask>,
+    pub same_screen: bool,
+}
+
+impl Readable for MotionNotifyEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let detail = stream.read_value(order)?;
+        let sequence_number = stream.read_value(order)?;
+        let time = stream.read_value(order)?;
+        let root = stream.read_value(order)?;
+        let event = stream.read_value(order)?;
+        let child = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let root_x = stream.read_value(order)?;
+        let root_y = stream.read_value(order)?;
+        let event_x = stream.read_value(order)?;
+        let event_y = stream.read_value(order)?;
+        let state = stream.read_value(order)?;
+        let same_screen = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        Ok(MotionNotifyEvent {
+            detail,
+            sequence_number,
+            time,
+            root,
+            event,
+            child,
+            root_x,
+            root_y,
+            event_x,
+            event_y,
+            state,
+            same_screen,
+        })
+    }
+}
+
+impl Writable for MotionNotifyEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value(data.detail, order)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.time, order)?;
+        stream.write_value(data.root, order)?;
+        stream.write_value(data.event, order)?;
+        stream.write_value(data.child.unwrap_or(0), order)?;
+        stream.write_value(data.root_x, order)?;
+        stream.write_value(data.root_y, order)?;
+        stream.write_value(data.event_x, order)?;
+        stream.write_value(data.event_y, order)?;
+        stream.write_value(data.state, order)?;
+        stream.write_value(data.same_screen, order)?;
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+/// EnterNotify, LeaveNotifyで共通の形式
+#[derive(Clone, Debug, PartialEq)]
+pub struct CrossingEvent {
+    pub detail: NotifyDetail,
+    pub sequence_number: u16,
+    pub time: u32,
+    pub root: u32,
+    pub event: u32,
+    pub child: Option<u32>,
+    pub root_x: i16,
+    pub root_y: i16,
+    pub event_x: i16,
+    pub event_y: i16,
+    pub state: HashSet<KeyButMask>,
+    pub mode: NotifyMode,
+    pub same_screen: bool,
+    pub focus: bool,
+}
+
+impl Readable for CrossingEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let detail = stream.read_value(order)?;
+        let sequence_number = stream.read_value(order)?;
+        let time = stream.read_value(order)?;
+        let root = stream.read_value(order)?;
+        let event = stream.read_value(order)?;
+        let child = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let root_x = stream.read_value(order)?;
+        let root_y = stream.read_value(order)?;
+        let event_x = stream.read_value(order)?;
+        let event_y = stream.read_value(order)?;
+        let state = stream.read_value(order)?;
+        let mode = stream.read_value(order)?;
+        let same_screen_focus = stream.read_value::<u8>(order)?;
+        let same_screen = same_screen_focus & 2 != 0;
+        let focus = same_screen_focus & 1 != 0;
+        Ok(CrossingEvent {
+            detail,
+            sequence_number,
+            time,
+            root,
+            event,
+            child,
+            root_x,
+            root_y,
+            event_x,
+            event_y,
+            state,
+            mode,
+            same_screen,
+            focus,
+        })
+    }
+}
+
+impl Writable for CrossingEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value(data.detail, order)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.time, order)?;
+        stream.write_value(data.root, order)?;
+        stream.write_value(data.event, order)?;
+        stream.write_value(data.child.unwrap_or(0), order)?;
+        stream.write_value(data.root_x, order)?;
+        stream.write_value(data.root_y, order)?;
+        stream.write_value(data.event_x, order)?;
+        stream.write_value(data.event_y, order)?;
+        stream.write_value(data.state, order)?;
+        stream.write_value(data.mode, order)?;
+        stream.write_value::<u8>(if data.same_screen { 2 } else { 0 } | if data.focus { 1 } else { 0 }, order)?;
+        Ok(())
+    }
+}
+
+/// FocusIn, FocusOutで共通の形式
+#[derive(Clone, Debug, PartialEq)]
+pub struct FocusEvent {
+    pub detail: NotifyDetail,
+    pub sequence_number: u16,
+    pub event: u32,
+    pub mode: NotifyMode,
+}
+
+impl Readable for FocusEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let detail = stream.read_value(order)?;
+        let sequence_number = stream.read_value(order)?;
+        let event = stream.read_value(order)?;
+        let mode = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 23], 23)?;
+        Ok(FocusEvent {
+            detail,
+            sequence_number,
+            event,
+            mode,
+        })
+    }
+}
+
+impl Writable for FocusEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value(data.detail, order)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.event, order)?;
+        stream.write_value(data.mode, order)?;
+        stream.write_all(&[0; 23]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExposeEvent {
+    pub sequence_number: u16,
+    pub window: u32,
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+    pub count: u16,
+}
+
+impl Readable for ExposeEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let window = stream.read_value(order)?;
+        let x = stream.read_value(order)?;
+        let y = stream.read_value(order)?;
+        let width = stream.read_value(order)?;
+        let height = stream.read_value(order)?;
+        let count = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 14], 14)?;
+        Ok(ExposeEvent {
+            sequence_number,
+            window,
+            x,
+            y,
+            width,
+            height,
+            count,
+        })
+    }
+}
+
+impl Writable for ExposeEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_value(data.x, order)?;
+        stream.write_value(data.y, order)?;
+        stream.write_value(data.width, order)?;
+        stream.write_value(data.height, order)?;
+        stream.write_value(data.count, order)?;
+        stream.write_all(&[0; 14]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphicsExposureEvent {
+    pub sequence_number: u16,
+    pub drawable: u32,
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+    pub minor_opcode: u16,
+    pub count: u16,
+    pub major_opcode: u8,
+}
+
+impl Readable for GraphicsExposureEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let drawable = stream.read_value(order)?;
+        let x = stream.read_value(order)?;
+        let y = stream.read_value(order)?;
+        let width = stream.read_value(order)?;
+        let height = stream.read_value(order)?;
+        let minor_opcode = stream.read_value(order)?;
+        let count = stream.read_value(order)?;
+        let major_opcode = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 11], 11)?;
+        Ok(GraphicsExposureEvent {
+            sequence_number,
+            drawable,
+            x,
+            y,
+            width,
+            height,
+            minor_opcode,
+            count,
+            major_opcode,
+        })
+    }
+}
+
+impl Writable for GraphicsExposureEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.drawable, order)?;
+        stream.write_value(data.x, order)?;
+        stream.write_value(data.y, order)?;
+        stream.write_value(data.width, order)?;
+        stream.write_value(data.height, order)?;
+        stream.write_value(data.minor_opcode, order)?;
+        stream.write_value(data.count, order)?;
+        stream.write_value(data.major_opcode, order)?;
+        stream.write_all(&[0; 11]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NoExposureEvent {
+    pub sequence_number: u16,
+    pub drawable: u32,
+    pub minor_opcode: u16,
+    pub major_opcode: u8,
+}
+
+impl Readable for NoExposureEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let drawable = stream.read_value(order)?;
+        let minor_opcode = stream.read_value(order)?;
+        let major_opcode = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 21], 21)?;
+        Ok(NoExposureEvent {
+            sequence_number,
+            drawable,
+            minor_opcode,
+            major_opcode,
+        })
+    }
+}
+
+impl Writable for NoExposureEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.drawable, order)?;
+        stream.write_value(data.minor_opcode, order)?;
+        stream.write_value(data.major_opcode, order)?;
+        stream.write_all(&[0; 21]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VisibilityNotifyEvent {
+    pub sequence_number: u16,
+    pub window: u32,
+    pub state: VisibilityNotifyState,
+}
+
+impl Readable for VisibilityNotifyEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let window = stream.read_value(order)?;
+        let state = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 23], 23)?;
+        Ok(VisibilityNotifyEvent {
+            sequence_number,
+            window,
+            state,
+        })
+    }
+}
+
+impl Writable for VisibilityNotifyEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_value(data.state, order)?;
+        stream.write_all(&[0; 23]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CreateNotifyEvent {
+    pub sequence_number: u16,
+    pub parent: u32,
+    pub window: u32,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    pub border_width: u16,
+    pub override_redirect: bool,
+}
+
+impl Readable for CreateNotifyEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let parent = stream.read_value(order)?;
+        let window = stream.read_value(order)?;
+        let x = stream.read_value(order)?;
+        let y = stream.read_value(order)?;
+        let width = stream.read_value(order)?;
+        let height = stream.read_value(order)?;
+        let border_width = stream.read_value(order)?;
+        let override_redirect = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 9], 9)?;
+        Ok(CreateNotifyEvent {
+            sequence_number,
+            parent,
+            window,
+            x,
+            y,
+            width,
+            height,
+            border_width,
+            override_redirect,
+        })
+    }
+}
+
+impl Writable for CreateNotifyEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.parent, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_value(data.x, order)?;
+        stream.write_value(data.y, order)?;
+        stream.write_value(data.width, order)?;
+        stream.write_value(data.height, order)?;
+        stream.write_value(data.border_width, order)?;
+        stream.write_value(data.override_redirect, order)?;
+        stream.write_all(&[0; 9]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DestroyNotifyEvent {
+    pub sequence_number: u16,
+    pub event: u32,
+    pub window: u32,
+}
+
+impl Readable for DestroyNotifyEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let event = stream.read_value(order)?;
+        let window = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 20], 20)?;
+        Ok(DestroyNotifyEvent {
+            sequence_number,
+            event,
+            window,
+        })
+    }
+}
+
+impl Writable for DestroyNotifyEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.event, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_all(&[0; 20]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnmapNotifyEvent {
+    pub sequence_number: u16,
+    pub event: u32,
+    pub window: u32,
+    pub from_configure: bool,
+}
+
+impl Readable for UnmapNotifyEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let event = stream.read_value(order)?;
+        let window = stream.read_value(order)?;
+        let from_configure = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 19], 19)?;
+        Ok(UnmapNotifyEvent {
+            sequence_number,
+            event,
+            window,
+            from_configure,
+        })
+    }
+}
+
+impl Writable for UnmapNotifyEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.event, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_value(data.from_configure, order)?;
+        stream.write_all(&[0; 19]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapNotifyEvent {
+    pub sequence_number: u16,
+    pub event: u32,
+    pub window: u32,
+    pub override_redirect: bool,
+}
+
+impl Readable for MapNotifyEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let event = stream.read_value(order)?;
+        let window = stream.read_value(order)?;
+        let override_redirect = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 19], 19)?;
+        Ok(MapNotifyEvent {
+            sequence_number,
+            event,
+            window,
+            override_redirect,
+        })
+    }
+}
+
+impl Writable for MapNotifyEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.event, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_value(data.override_redirect, order)?;
+        stream.write_all(&[0; 19]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapRequestEvent {
+    pub sequence_number: u16,
+    pub parent: u32,
+    pub window: u32,
+}
+
+impl Readable for MapRequestEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let parent = stream.read_value(order)?;
+        let window = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 20], 20)?;
+        Ok(MapRequestEvent {
+            sequence_number,
+            parent,
+            window,
+        })
+    }
+}
+
+impl Writable for MapRequestEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.parent, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_all(&[0; 20]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReparentNotifyEvent {
+    pub sequence_number: u16,
+    pub event: u32,
+    pub window: u32,
+    pub parent: u32,
+    pub x: i16,
+    pub y: i16,
+    pub override_redirect: bool,
+}
+
+impl Readable for ReparentNotifyEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let event = stream.read_value(order)?;
+        let window = stream.read_value(order)?;
+        let parent = stream.read_value(order)?;
+        let x = stream.read_value(order)?;
+        let y = stream.read_value(order)?;
+        let override_redirect = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 11], 11)?;
+        Ok(ReparentNotifyEvent {
+            sequence_number,
+            event,
+            window,
+            parent,
+            x,
+            y,
+            override_redirect,
+        })
+    }
+}
+
+impl Writable for ReparentNotifyEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.event, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_value(data.parent, order)?;
+        stream.write_value(data.x, order)?;
+        stream.write_value(data.y, order)?;
+        stream.write_value(data.override_redirect, order)?;
+        stream.write_all(&[0; 11]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigureNotifyEvent {
+    pub sequence_number: u16,
+    pub event: u32,
+    pub window: u32,
+    pub above_sibling: Option<u32>,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    pub border_width: u16,
+    pub override_redirect: bool,
+}
+
+impl Readable for ConfigureNotifyEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let event = stream.read_value(order)?;
+        let window = stream.read_value(order)?;
+        let above_sibling = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let x = stream.read_value(order)?;
+        let y = stream.read_value(order)?;
+        let width = stream.read_value(order)?;
+        let height = stream.read_value(order)?;
+        let border_width = stream.read_value(order)?;
+        let override_redirect = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 5], 5)?;
+        Ok(ConfigureNotifyEvent {
+            sequence_number,
+            event,
+            window,
+            above_sibling,
+            x,
+            y,
+            width,
+            height,
+            border_width,
+            override_redirect,
+        })
+    }
+}
+
+impl Writable for ConfigureNotifyEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.event, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_value(data.above_sibling.unwrap_or(0), order)?;
+        stream.write_value(data.x, order)?;
+        stream.write_value(data.y, order)?;
+        stream.write_value(data.width, order)?;
+        stream.write_value(data.height, order)?;
+        stream.write_value(data.border_width, order)?;
+        stream.write_value(data.override_redirect, order)?;
+        stream.write_all(&[0; 5]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigureRequestEvent {
+    pub stack_mode: ConfigureWindowValueStackMode,
+    pub sequence_number: u16,
+    pub parent: u32,
+    pub window: u32,
+    pub sibling: Option<u32>,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    pub border_width: u16,
+    pub value_mask: HashSet<ConfigureWindowValueMaskValue>,
+}
+
+impl Readable for ConfigureRequestEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let stack_mode = stream.read_value(order)?;
+        let sequence_number = stream.read_value(order)?;
+        let parent = stream.read_value(order)?;
+        let window = stream.read_value(order)?;
+        let sibling = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let x = stream.read_value(order)?;
+        let y = stream.read_value(order)?;
+        let width = stream.read_value(order)?;
+        let height = stream.read_value(order)?;
+        let border_width = stream.read_value(order)?;
+        let value_mask = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 4], 4)?;
+        Ok(ConfigureRequestEvent {
+            stack_mode,
+            sequence_number,
+            parent,
+            window,
+            sibling,
+            x,
+            y,
+            width,
+            height,
+            border_width,
+            value_mask,
+        })
+    }
+}
+
+impl Writable for ConfigureRequestEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value(data.stack_mode, order)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.parent, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_value(data.sibling.unwrap_or(0), order)?;
+        stream.write_value(data.x, order)?;
+        stream.write_value(data.y, order)?;
+        stream.write_value(data.width, order)?;
+        stream.write_value(data.height, order)?;
+        stream.write_value(data.border_width, order)?;
+        stream.write_value(data.value_mask, order)?;
+        stream.write_all(&[0; 4]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GravityNotifyEvent {
+    pub sequence_number: u16,
+    pub event: u32,
+    pub window: u32,
+    pub x: i16,
+    pub y: i16,
+}
+
+impl Readable for GravityNotifyEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let event = stream.read_value(order)?;
+        let window = stream.read_value(order)?;
+        let x = stream.read_value(order)?;
+        let y = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 16], 16)?;
+        Ok(GravityNotifyEvent {
+            sequence_number,
+            event,
+            window,
+            x,
+            y,
+        })
+    }
+}
+
+impl Writable for GravityNotifyEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.event, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_value(data.x, order)?;
+        stream.write_value(data.y, order)?;
+        stream.write_all(&[0; 16]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResizeRequestEvent {
+    pub sequence_number: u16,
+    pub window: u32,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Readable for ResizeRequestEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let window = stream.read_value(order)?;
+        let width = stream.read_value(order)?;
+        let height = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 20], 20)?;
+        Ok(ResizeRequestEvent {
+            sequence_number,
+            window,
+            width,
+            height,
+        })
+    }
+}
+
+impl Writable for ResizeRequestEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_value(data.width, order)?;
+        stream.write_value(data.height, order)?;
+        stream.write_all(&[0; 20]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+/// CirculateNotify, CirculateRequestで共通の形式
+/// CirculateRequestではeventにparentが入る
+#[derive(Clone, Debug, PartialEq)]
+pub struct CirculateEvent {
+    pub sequence_number: u16,
+    pub event: u32,
+    pub window: u32,
+    pub place: CirculatePlace,
+}
+
+impl Readable for CirculateEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let event = stream.read_value(order)?;
+        let window = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 4], 4)?;
+        let place = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 15], 15)?;
+        Ok(CirculateEvent {
+            sequence_number,
+            event,
+            window,
+            place,
+        })
+    }
+}
+
+impl Writable for CirculateEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.event, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_all(&[0; 4]).map_err(Error::IoError)?;
+        stream.write_value(data.place, order)?;
+        stream.write_all(&[0; 15]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropertyNotifyEvent {
+    pub sequence_number: u16,
+    pub window: u32,
+    pub atom: u32,
+    pub time: u32,
+    pub state: PropertyNotifyState,
+}
+
+impl Readable for PropertyNotifyEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let window = stream.read_value(order)?;
+        let atom = stream.read_value(order)?;
+        let time = stream.read_value(order)?;
+        let state = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 15], 15)?;
+        Ok(PropertyNotifyEvent {
+            sequence_number,
+            window,
+            atom,
+            time,
+            state,
+        })
+    }
+}
+
+impl Writable for PropertyNotifyEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_value(data.atom, order)?;
+        stream.write_value(data.time, order)?;
+        stream.write_value(data.state, order)?;
+        stream.write_all(&[0; 15]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectionClearEvent {
+    pub sequence_number: u16,
+    pub time: u32,
+    pub owner: u32,
+    pub selection: u32,
+}
+
+impl Readable for SelectionClearEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let time = stream.read_value(order)?;
+        let owner = stream.read_value(order)?;
+        let selection = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 16], 16)?;
+        Ok(SelectionClearEvent {
+            sequence_number,
+            time,
+            owner,
+            selection,
+        })
+    }
+}
+
+impl Writable for SelectionClearEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.time, order)?;
+        stream.write_value(data.owner, order)?;
+        stream.write_value(data.selection, order)?;
+        stream.write_all(&[0; 16]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectionRequestEvent {
+    pub sequence_number: u16,
+    pub time: u32,
+    pub owner: u32,
+    pub requestor: u32,
+    pub selection: u32,
+    pub target: u32,
+    pub property: Option<u32>,
+}
+
+impl Readable for SelectionRequestEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let time = stream.read_value(order)?;
+        let owner = stream.read_value(order)?;
+        let requestor = stream.read_value(order)?;
+        let selection = stream.read_value(order)?;
+        let target = stream.read_value(order)?;
+        let property = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        read_specified_length(stream, &mut [0; 4], 4)?;
+        Ok(SelectionRequestEvent {
+            sequence_number,
+            time,
+            owner,
+            requestor,
+            selection,
+            target,
+            property,
+        })
+    }
+}
+
+impl Writable for SelectionRequestEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.time, order)?;
+        stream.write_value(data.owner, order)?;
+        stream.write_value(data.requestor, order)?;
+        stream.write_value(data.selection, order)?;
+        stream.write_value(data.target, order)?;
+        stream.write_value(data.property.unwrap_or(0), order)?;
+        stream.write_all(&[0; 4]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectionNotifyEvent {
+    pub sequence_number: u16,
+    pub time: u32,
+    pub requestor: u32,
+    pub selection: u32,
+    pub target: u32,
+    pub property: Option<u32>,
+}
+
+impl Readable for SelectionNotifyEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let time = stream.read_value(order)?;
+        let requestor = stream.read_value(order)?;
+        let selection = stream.read_value(order)?;
+        let target = stream.read_value(order)?;
+        let property = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        read_specified_length(stream, &mut [0; 8], 8)?;
+        Ok(SelectionNotifyEvent {
+            sequence_number,
+            time,
+            requestor,
+            selection,
+            target,
+            property,
+        })
+    }
+}
+
+impl Writable for SelectionNotifyEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.time, order)?;
+        stream.write_value(data.requestor, order)?;
+        stream.write_value(data.selection, order)?;
+        stream.write_value(data.target, order)?;
+        stream.write_value(data.property.unwrap_or(0), order)?;
+        stream.write_all(&[0; 8]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColormapNotifyEvent {
+    pub sequence_number: u16,
+    pub window: u32,
+    pub colormap: Option<u32>,
+    pub new: bool,
+    pub state: ColormapNotifyState,
+}
+
+impl Readable for ColormapNotifyEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let window = stream.read_value(order)?;
+        let colormap = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let new = stream.read_value(order)?;
+        let state = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 18], 18)?;
+        Ok(ColormapNotifyEvent {
+            sequence_number,
+            window,
+            colormap,
+            new,
+            state,
+        })
+    }
+}
+
+impl Writable for ColormapNotifyEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_value(data.colormap.unwrap_or(0), order)?;
+        stream.write_value(data.new, order)?;
+        stream.write_value(data.state, order)?;
+        stream.write_all(&[0; 18]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MappingNotifyEvent {
+    pub sequence_number: u16,
+    pub request: MappingNotifyRequest,
+    pub first_keycode: u8,
+    pub count: u8,
+}
+
+impl Readable for MappingNotifyEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let sequence_number = stream.read_value(order)?;
+        let request = stream.read_value(order)?;
+        let first_keycode = stream.read_value(order)?;
+        let count = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 25], 25)?;
+        Ok(MappingNotifyEvent {
+            sequence_number,
+            request,
+            first_keycode,
+            count,
+        })
+    }
+}
+
+impl Writable for MappingNotifyEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_all(&[0; 1]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.request, order)?;
+        stream.write_value(data.first_keycode, order)?;
+        stream.write_value(data.count, order)?;
+        stream.write_all(&[0; 25]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+/// keysはkeycode 8から255までのビットマップ
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeymapNotifyEvent {
+    pub keys: [u8; 31],
+}
+
+impl Readable for KeymapNotifyEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
+        let mut keys = [0; 31];
+        read_specified_length(stream, &mut keys, 31)?;
+        Ok(KeymapNotifyEvent { keys })
+    }
+}
+
+impl Writable for KeymapNotifyEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, _order: &ByteOrder) -> Result<()> {
+        stream.write_all(&data.keys).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClientMessageData {
+    Format8([u8; 20]),
+    Format16([u16; 10]),
+    Format32([u32; 5]),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClientMessageEvent {
+    pub sequence_number: u16,
+    pub window: u32,
+    pub type_: u32,
+    pub data: ClientMessageData,
+}
+
+impl Readable for ClientMessageEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let format = stream.read_value::<u8>(order)?;
+        let sequence_number = stream.read_value(order)?;
+        let window = stream.read_value(order)?;
+        let type_ = stream.read_value(order)?;
+        let data = match format {
+            8 => {
+                let mut data = [0; 20];
+                read_specified_length(stream, &mut data, 20)?;
+                ClientMessageData::Format8(data)
+            }
+            16 => {
+                let mut data = [0; 10];
+                for value in data.iter_mut() {
+                    *value = stream.read_value(order)?;
+                }
+                ClientMessageData::Format16(data)
+            }
+            32 => {
+                let mut data = [0; 5];
+                for value in data.iter_mut() {
+                    *value = stream.read_value(order)?;
+                }
+                ClientMessageData::Format32(data)
+            }
+            other => return Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        };
+        Ok(ClientMessageEvent {
+            sequence_number,
+            window,
+            type_,
+            data,
+        })
+    }
+}
+
+impl Writable for ClientMessageEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let format: u8 = match data.data {
+            ClientMessageData::Format8(_) => 8,
+            ClientMessageData::Format16(_) => 16,
+            ClientMessageData::Format32(_) => 32,
+        };
+        stream.write_value(format, order)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_value(data.type_, order)?;
+        match data.data {
+            ClientMessageData::Format8(data) => {
+                stream.write_all(&data).map_err(Error::IoError)?;
+            }
+            ClientMessageData::Format16(data) => {
+                for value in data.iter() {
+                    stream.write_value(*value, order)?;
+                }
+            }
+            ClientMessageData::Format32(data) => {
+                for value in data.iter() {
+                    stream.write_value(*value, order)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// GenericEvent(35)
+/// dataはevent typeより後ろの部分で、22byte以上の可変長
+#[derive(Clone, Debug, PartialEq)]
+pub struct GenericEvent {
+    pub extension: u8,
+    pub sequence_number: u16,
+    pub event_type: u16,
+    pub data: Vec<u8>,
+}
+
+impl Readable for GenericEvent {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let extension = stream.read_value(order)?;
+        let sequence_number = stream.read_value(order)?;
+        let length = stream.read_value::<u32>(order)? as usize;
+        let event_type = stream.read_value(order)?;
+        let len = 22 + (length << 2);
+        let mut data = vec![0; len];
+        read_specified_length(stream, &mut data[..], len)?;
+        Ok(GenericEvent {
+            extension,
+            sequence_number,
+            event_type,
+            data,
+        })
+    }
+}
+
+impl Writable for GenericEvent {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let len = data.data.len().max(22);
+        let len = (len - 22 + 3) & !3;
+        stream.write_value(data.extension, order)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value((len >> 2) as u32, order)?;
+        stream.write_value(data.event_type, order)?;
+        stream.write_all(&data.data[..]).map_err(Error::IoError)?;
+        stream.write_all(&vec![0; 22 + len - data.data.len()][..]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+/// 拡張が定義する32byte固定長のイベント
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtensionEvent {
+    pub code: u8,
+    pub data: [u8; 31],
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    KeyPress(DeviceEvent),
+    KeyRelease(DeviceEvent),
+    ButtonPress(DeviceEvent),
+    ButtonRelease(DeviceEvent),
+    MotionNotify(MotionNotifyEvent),
+    EnterNotify(CrossingEvent),
+    LeaveNotify(CrossingEvent),
+    FocusIn(FocusEvent),
+    FocusOut(FocusEvent),
+    KeymapNotify(KeymapNotifyEvent),
+    Expose(ExposeEvent),
+    GraphicsExposure(GraphicsExposureEvent),
+    NoExposure(NoExposureEvent),
+    VisibilityNotify(VisibilityNotifyEvent),
+    CreateNotify(CreateNotifyEvent),
+    DestroyNotify(DestroyNotifyEvent),
+    UnmapNotify(UnmapNotifyEvent),
+    MapNotify(MapNotifyEvent),
+    MapRequest(MapRequestEvent),
+    ReparentNotify(ReparentNotifyEvent),
+    ConfigureNotify(ConfigureNotifyEvent),
+    ConfigureRequest(ConfigureRequestEvent),
+    GravityNotify(GravityNotifyEvent),
+    ResizeRequest(ResizeRequestEvent),
+    CirculateNotify(CirculateEvent),
+    CirculateRequest(CirculateEvent),
+    PropertyNotify(PropertyNotifyEvent),
+    SelectionClear(SelectionClearEvent),
+    SelectionRequest(SelectionRequestEvent),
+    SelectionNotify(SelectionNotifyEvent),
+    ColormapNotify(ColormapNotifyEvent),
+    ClientMessage(ClientMessageEvent),
+    MappingNotify(MappingNotifyEvent),
+    Generic(GenericEvent),
+    Extension(ExtensionEvent),
+}
+
+impl Event {
+    /// send_eventのビットを除いたcodeを読んだ後の部分を読む
+    pub(crate) fn read_body(code: u8, stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match code {
+            2 => Ok(Event::KeyPress(stream.read_value(order)?)),
+            3 => Ok(Event::KeyRelease(stream.read_value(order)?)),
+            4 => Ok(Event::ButtonPress(stream.read_value(order)?)),
+            5 => Ok(Event::ButtonRelease(stream.read_value(order)?)),
+            6 => Ok(Event::MotionNotify(stream.read_value(order)?)),
+            7 => Ok(Event::EnterNotify(stream.read_value(order)?)),
+            8 => Ok(Event::LeaveNotify(stream.read_value(order)?)),
+            9 => Ok(Event::FocusIn(stream.read_value(order)?)),
+            10 => Ok(Event::FocusOut(stream.read_value(order)?)),
+            11 => Ok(Event::KeymapNotify(stream.read_value(order)?)),
+            12 => Ok(Event::Expose(stream.read_value(order)?)),
+            13 => Ok(Event::GraphicsExposure(stream.read_value(order)?)),
+            14 => Ok(Event::NoExposure(stream.read_value(order)?)),
+            15 => Ok(Event::VisibilityNotify(stream.read_value(order)?)),
+            16 => Ok(Event::CreateNotify(stream.read_value(order)?)),
+            17 => Ok(Event::DestroyNotify(stream.read_value(order)?)),
+            18 => Ok(Event::UnmapNotify(stream.read_value(order)?)),
+            19 => Ok(Event::MapNotify(stream.read_value(order)?)),
+            20 => Ok(Event::MapRequest(stream.read_value(order)?)),
+            21 => Ok(Event::ReparentNotify(stream.read_value(order)?)),
+            22 => Ok(Event::ConfigureNotify(stream.read_value(order)?)),
+            23 => Ok(Event::ConfigureRequest(stream.read_value(order)?)),
+            24 => Ok(Event::GravityNotify(stream.read_value(order)?)),
+            25 => Ok(Event::ResizeRequest(stream.read_value(order)?)),
+            26 => Ok(Event::CirculateNotify(stream.read_value(order)?)),
+            27 => Ok(Event::CirculateRequest(stream.read_value(order)?)),
+            28 => Ok(Event::PropertyNotify(stream.read_value(order)?)),
+            29 => Ok(Event::SelectionClear(stream.read_value(order)?)),
+            30 => Ok(Event::SelectionRequest(stream.read_value(order)?)),
+            31 => Ok(Event::SelectionNotify(stream.read_value(order)?)),
+            32 => Ok(Event::ColormapNotify(stream.read_value(order)?)),
+            33 => Ok(Event::ClientMessage(stream.read_value(order)?)),
+            34 => Ok(Event::MappingNotify(stream.read_value(order)?)),
+            35 => Ok(Event::Generic(stream.read_value(order)?)),
+            36..=127 => {
+                let mut data = [0; 31];
+                read_specified_length(stream, &mut data, 31)?;
+                Ok(Event::Extension(ExtensionEvent { code, data }))
+            }
+            _ => Err(Error::ProtocolError(ErrorCode::Value, code as u32)),
+        }
+    }
+
+    /// KeymapNotifyにはsequence numberが無い
+    /// 拡張のeventはbyte順が分からないのでNone
+    pub fn sequence_number(&self) -> Option<u16> {
+        match self {
+            Event::KeyPress(event) => Some(event.sequence_number),
+            Event::KeyRelease(event) => Some(event.sequence_number),
+            Event::ButtonPress(event) => Some(event.sequence_number),
+            Event::ButtonRelease(event) => Some(event.sequence_number),
+            Event::MotionNotify(event) => Some(event.sequence_number),
+            Event::EnterNotify(event) => Some(event.sequence_number),
+            Event::LeaveNotify(event) => Some(event.sequence_number),
+            Event::FocusIn(event) => Some(event.sequence_number),
+            Event::FocusOut(event) => Some(event.sequence_number),
+            Event::Expose(event) => Some(event.sequence_number),
+            Event::GraphicsExposure(event) => Some(event.sequence_number),
+            Event::NoExposure(event) => Some(event.sequence_number),
+            Event::VisibilityNotify(event) => Some(event.sequence_number),
+            Event::CreateNotify(event) => Some(event.sequence_number),
+            Event::DestroyNotify(event) => Some(event.sequence_number),
+            Event::UnmapNotify(event) => Some(event.sequence_number),
+            Event::MapNotify(event) => Some(event.sequence_number),
+            Event::MapRequest(event) => Some(event.sequence_number),
+            Event::ReparentNotify(event) => Some(event.sequence_number),
+            Event::ConfigureNotify(event) => Some(event.sequence_number),
+            Event::ConfigureRequest(event) => Some(event.sequence_number),
+            Event::GravityNotify(event) => Some(event.sequence_number),
+            Event::ResizeRequest(event) => Some(event.sequence_number),
+            Event::CirculateNotify(event) => Some(event.sequence_number),
+            Event::CirculateRequest(event) => Some(event.sequence_number),
+            Event::PropertyNotify(event) => Some(event.sequence_number),
+            Event::SelectionClear(event) => Some(event.sequence_number),
+            Event::SelectionRequest(event) => Some(event.sequence_number),
+            Event::SelectionNotify(event) => Some(event.sequence_number),
+            Event::ColormapNotify(event) => Some(event.sequence_number),
+            Event::ClientMessage(event) => Some(event.sequence_number),
+            Event::MappingNotify(event) => Some(event.sequence_number),
+            Event::Generic(event) => Some(event.sequence_number),
+            Event::KeymapNotify(_) | Event::Extension(_) => None,
+        }
+    }
+
+    pub fn code(&self) -> u8 {
+        match self {
+            Event::KeyPress(_) => 2,
+            Event::KeyRelease(_) => 3,
+            Event::ButtonPress(_) => 4,
+            Event::ButtonRelease(_) => 5,
+            Event::MotionNotify(_) => 6,
+            Event::EnterNotify(_) => 7,
+            Event::LeaveNotify(_) => 8,
+            Event::FocusIn(_) => 9,
+            Event::FocusOut(_) => 10,
+            Event::KeymapNotify(_) => 11,
+            Event::Expose(_) => 12,
+            Event::GraphicsExposure(_) => 13,
+            Event::NoExposure(_) => 14,
+            Event::VisibilityNotify(_) => 15,
+            Event::CreateNotify(_) => 16,
+            Event::DestroyNotify(_) => 17,
+            Event::UnmapNotify(_) => 18,
+            Event::MapNotify(_) => 19,
+            Event::MapRequest(_) => 20,
+            Event::ReparentNotify(_) => 21,
+            Event::ConfigureNotify(_) => 22,
+            Event::ConfigureRequest(_) => 23,
+            Event::GravityNotify(_) => 24,
+            Event::ResizeRequest(_) => 25,
+            Event::CirculateNotify(_) => 26,
+            Event::CirculateRequest(_) => 27,
+            Event::PropertyNotify(_) => 28,
+            Event::SelectionClear(_) => 29,
+            Event::SelectionRequest(_) => 30,
+            Event::SelectionNotify(_) => 31,
+            Event::ColormapNotify(_) => 32,
+            Event::ClientMessage(_) => 33,
+            Event::MappingNotify(_) => 34,
+            Event::Generic(_) => 35,
+            Event::Extension(event) => event.code,
+        }
+    }
+}
+
+impl Readable for Event {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let code = stream.read_value::<u8>(order)?;
+        Event::read_body(code & 0x7f, stream, order)
+    }
+}
+
+impl Writable for Event {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value(data.code(), order)?;
+        match data {
+            Event::KeyPress(data) => stream.write_value(data, order),
+            Event::KeyRelease(data) => stream.write_value(data, order),
+            Event::ButtonPress(data) => stream.write_value(data, order),
+            Event::ButtonRelease(data) => stream.write_value(data, order),
+            Event::MotionNotify(data) => stream.write_value(data, order),
+            Event::EnterNotify(data) => stream.write_value(data, order),
+            Event::LeaveNotify(data) => stream.write_value(data, order),
+            Event::FocusIn(data) => stream.write_value(data, order),
+            Event::FocusOut(data) => stream.write_value(data, order),
+            Event::KeymapNotify(data) => stream.write_value(data, order),
+            Event::Expose(data) => stream.write_value(data, order),
+            Event::GraphicsExposure(data) => stream.write_value(data, order),
+            Event::NoExposure(data) => stream.write_value(data, order),
+            Event::VisibilityNotify(data) => stream.write_value(data, order),
+            Event::CreateNotify(data) => stream.write_value(data, order),
+            Event::DestroyNotify(data) => stream.write_value(data, order),
+            Event::UnmapNotify(data) => stream.write_value(data, order),
+            Event::MapNotify(data) => stream.write_value(data, order),
+            Event::MapRequest(data) => stream.write_value(data, order),
+            Event::ReparentNotify(data) => stream.write_value(data, order),
+            Event::ConfigureNotify(data) => stream.write_value(data, order),
+            Event::ConfigureRequest(data) => stream.write_value(data, order),
+            Event::GravityNotify(data) => stream.write_value(data, order),
+            Event::ResizeRequest(data) => stream.write_value(data, order),
+            Event::CirculateNotify(data) => stream.write_value(data, order),
+            Event::CirculateRequest(data) => stream.write_value(data, order),
+            Event::PropertyNotify(data) => stream.write_value(data, order),
+            Event::SelectionClear(data) => stream.write_value(data, order),
+            Event::SelectionRequest(data) => stream.write_value(data, order),
+            Event::SelectionNotify(data) => stream.write_value(data, order),
+            Event::ColormapNotify(data) => stream.write_value(data, order),
+            Event::ClientMessage(data) => stream.write_value(data, order),
+            Event::MappingNotify(data) => stream.write_value(data, order),
+            Event::Generic(data) => stream.write_value(data, order),
+            Event::Extension(data) => stream.write_all(&data.data).map_err(Error::IoError),
+        }
+    }
+}
+
+/// サーバーからクライアントに送られるイベント
+/// SendEventで送られたものはcodeの最上位ビットが立つ
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventPacket {
+    pub send_event: bool,
+    pub event: Event,
+}
+
+impl Readable for EventPacket {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let code = stream.read_value::<u8>(order)?;
+        let event = Event::read_body(code & 0x7f, stream, order)?;
+        Ok(EventPacket {
+            send_event: code & 0x80 != 0,
+            event,
+        })
+    }
+}
+
+impl Writable for EventPacket {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let mut buffer = BufWriter::new(Vec::with_capacity(32));
+        buffer.write_value(data.event, order)?;
+        let mut buffer = buffer.into_inner().map_err(|e| Error::IoError(e.into_error()))?;
+        if data.send_event {
+            buffer[0] |= 0x80;
+        }
+        stream.write_all(&buffer[..]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,316 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod event {
+    use std::collections::HashSet;
+    use std::io::{BufReader, BufWriter};
+    use std::iter::FromIterator;
+
+    use crate::event::{ClientMessageData, ClientMessageEvent, ConfigureNotifyEvent, CrossingEvent, DeviceEvent, Event, KeyButMask, NotifyDetail, NotifyMode};
+    use crate::read_util::{ByteOrder, Readable, Writable};
+
+    #[test]
+    fn key_press_test() {
+        let msb = [2, 38, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0, 0, 0, 5, 0xff, 0xfa, 0, 7, 0, 8, 0x01, 0x05, 1, 0];
+        let lsb = [2, 38, 1, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 5, 0, 0xfa, 0xff, 7, 0, 8, 0, 0x05, 0x01, 1, 0];
+        let expected = Event::KeyPress(DeviceEvent {
+            detail: 38,
+            sequence_number: 1,
+            time: 2,
+            root: 3,
+            event: 4,
+            child: None,
+            root_x: 5,
+            root_y: -6,
+            event_x: 7,
+            event_y: 8,
+            state: HashSet::from_iter([KeyButMask::Shift, KeyButMask::Control, KeyButMask::Button1].to_vec()),
+            same_screen: true,
+        });
+        assert_eq!(Event::read(&mut BufReader::new(&msb[..]), &ByteOrder::MSBFirst).unwrap(), expected);
+        assert_eq!(Event::read(&mut BufReader::new(&lsb[..]), &ByteOrder::LSBFirst).unwrap(), expected);
+        let mut buffer = [0; 32];
+        Event::write(&mut BufWriter::new(&mut buffer[..]), expected.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, msb);
+        let mut buffer = [0; 32];
+        Event::write(&mut BufWriter::new(&mut buffer[..]), expected, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, lsb);
+    }
+
+    #[test]
+    fn enter_notify_test() {
+        let msb = [7, 3, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0, 5, 0, 6, 0, 7, 0, 8, 0, 9, 0, 0, 1, 3];
+        let expected = Event::EnterNotify(CrossingEvent {
+            detail: NotifyDetail::Nonlinear,
+            sequence_number: 1,
+            time: 2,
+            root: 3,
+            event: 4,
+            child: Some(5),
+            root_x: 6,
+            root_y: 7,
+            event_x: 8,
+            event_y: 9,
+            state: HashSet::new(),
+            mode: NotifyMode::Grab,
+            same_screen: true,
+            focus: true,
+        });
+        assert_eq!(Event::read(&mut BufReader::new(&msb[..]), &ByteOrder::MSBFirst).unwrap(), expected);
+        let mut buffer = [0; 32];
+        Event::write(&mut BufWriter::new(&mut buffer[..]), expected, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, msb);
+    }
+
+    #[test]
+    fn configure_notify_test() {
+        let msb = [22, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0, 0xff, 0xff, 0, 5, 0, 6, 0, 7, 0, 8, 1, 0, 0, 0, 0, 0];
+        let lsb = [22, 0, 1, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 5, 0, 6, 0, 7, 0, 8, 0, 1, 0, 0, 0, 0, 0];
+        let expected = Event::ConfigureNotify(ConfigureNotifyEvent {
+            sequence_number: 1,
+            event: 2,
+            window: 3,
+            above_sibling: None,
+            x: -1,
+            y: 5,
+            width: 6,
+            height: 7,
+            border_width: 8,
+            override_redirect: true,
+        });
+        assert_eq!(Event::read(&mut BufReader::new(&msb[..]), &ByteOrder::MSBFirst).unwrap(), expected);
+        assert_eq!(Event::read(&mut BufReader::new(&lsb[..]), &ByteOrder::LSBFirst).unwrap(), expected);
+        let mut buffer = [0; 32];
+        Event::write(&mut BufWriter::new(&mut buffer[..]), expected.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, msb);
+        let mut buffer = [0; 32];
+        Event::write(&mut BufWriter::new(&mut buffer[..]), expected, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, lsb);
+    }
+
+    #[test]
+    fn client_message_test() {
+        let msb = [33, 16, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 4, 0, 5, 0, 6, 0, 7, 0, 8, 0, 9, 0, 10, 0, 11, 0, 12, 0, 13];
+        let lsb = [33, 16, 1, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 5, 0, 6, 0, 7, 0, 8, 0, 9, 0, 10, 0, 11, 0, 12, 0, 13, 0];
+        let expected = Event::ClientMessage(ClientMessageEvent {
+            sequence_number: 1,
+            window: 2,
+            type_: 3,
+            data: ClientMessageData::Format16([4, 5, 6, 7, 8, 9, 10, 11, 12, 13]),
+        });
+        assert_eq!(Event::read(&mut BufReader::new(&msb[..]), &ByteOrder::MSBFirst).unwrap(), expected);
+        assert_eq!(Event::read(&mut BufReader::new(&lsb[..]), &ByteOrder::LSBFirst).unwrap(), expected);
+        let mut buffer = [0; 32];
+        Event::write(&mut BufWriter::new(&mut buffer[..]), expected.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, msb);
+        let mut buffer = [0; 32];
+        Event::write(&mut BufWriter::new(&mut buffer[..]), expected, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, lsb);
+    }
+}
+
+mod event_packet {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::event::{Event, EventPacket, ExtensionEvent, GenericEvent, MapRequestEvent};
+    use crate::read_util::{ByteOrder, Readable, Writable};
+
+    #[test]
+    fn send_event_test() {
+        let msb = [20 | 0x80, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let expected = EventPacket {
+            send_event: true,
+            event: Event::MapRequest(MapRequestEvent {
+                sequence_number: 1,
+                parent: 2,
+                window: 3,
+            }),
+        };
+        assert_eq!(EventPacket::read(&mut BufReader::new(&msb[..]), &ByteOrder::MSBFirst).unwrap(), expected);
+        let mut buffer = [0; 32];
+        EventPacket::write(&mut BufWriter::new(&mut buffer[..]), expected.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, msb);
+        assert_eq!(Event::read(&mut BufReader::new(&msb[..]), &ByteOrder::MSBFirst).unwrap(), expected.event);
+        let mut buffer = [0; 32];
+        Event::write(&mut BufWriter::new(&mut buffer[..]), expected.event, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer[0], 20);
+    }
+
+    #[test]
+    fn generic_event_test() {
+        let mut lsb = vec![35, 131, 1, 0, 2, 0, 0, 0, 7, 0];
+        lsb.extend((0..30).map(|i| i as u8));
+        let expected = EventPacket {
+            send_event: false,
+            event: Event::Generic(GenericEvent {
+                extension: 131,
+                sequence_number: 1,
+                event_type: 7,
+                data: (0..30).collect(),
+            }),
+        };
+        let mut input = lsb.clone();
+        input.extend_from_slice(&[0xff; 4]);
+        let mut reader = BufReader::new(&input[..]);
+        assert_eq!(EventPacket::read(&mut reader, &ByteOrder::LSBFirst).unwrap(), expected);
+        assert_eq!(u32::read(&mut reader, &ByteOrder::LSBFirst).unwrap(), 0xffffffff);
+        let mut buffer = [0; 40];
+        EventPacket::write(&mut BufWriter::new(&mut buffer[..]), expected, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(&buffer[..], &lsb[..]);
+    }
+
+    #[test]
+    fn extension_event_test() {
+        let mut msb = [0; 32];
+        msb[0] = 85 | 0x80;
+        msb[1] = 3;
+        msb[31] = 4;
+        let mut data = [0; 31];
+        data[0] = 3;
+        data[30] = 4;
+        let expected = EventPacket {
+            send_event: true,
+            event: Event::Extension(ExtensionEvent { code: 85, data }),
+        };
+        assert_eq!(EventPacket::read(&mut BufReader::new(&msb[..]), &ByteOrder::MSBFirst).unwrap(), expected);
+        let mut buffer = [0; 32];
+        EventPacket::write(&mut BufWriter::new(&mut buffer[..]), expected, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, msb);
+    }
+
+    #[test]
+    fn invalid_code_test() {
+        let msb = [1; 32];
+        assert!(EventPacket::read(&mut BufReader::new(&msb[..]), &ByteOrder::MSBFirst).is_err());
+    }
+}
+
+mod round_trip {
+    use std::collections::HashSet;
+    use std::io::{BufReader, BufWriter};
+    use std::iter::FromIterator;
+
+    use crate::event::*;
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::configure_window::{ConfigureWindowValueMaskValue, ConfigureWindowValueStackMode};
+
+    #[test]
+    fn all_core_events_test() {
+        let device = DeviceEvent {
+            detail: 1,
+            sequence_number: 2,
+            time: 3,
+            root: 4,
+            event: 5,
+            child: Some(6),
+            root_x: -7,
+            root_y: 8,
+            event_x: 9,
+            event_y: -10,
+            state: HashSet::from_iter([KeyButMask::Mod4, KeyButMask::Button5].to_vec()),
+            same_screen: false,
+        };
+        let crossing = CrossingEvent {
+            detail: NotifyDetail::Virtual,
+            sequence_number: 1,
+            time: 2,
+            root: 3,
+            event: 4,
+            child: None,
+            root_x: 5,
+            root_y: 6,
+            event_x: 7,
+            event_y: 8,
+            state: HashSet::from_iter([KeyButMask::Lock].to_vec()),
+            mode: NotifyMode::Ungrab,
+            same_screen: true,
+            focus: false,
+        };
+        let focus = FocusEvent {
+            detail: NotifyDetail::PointerRoot,
+            sequence_number: 1,
+            event: 2,
+            mode: NotifyMode::WhileGrabbed,
+        };
+        let circulate = CirculateEvent {
+            sequence_number: 1,
+            event: 2,
+            window: 3,
+            place: CirculatePlace::Bottom,
+        };
+        let mut keys = [0; 31];
+        keys[3] = 0x81;
+        let events = vec![
+            Event::KeyPress(device.clone()),
+            Event::KeyRelease(device.clone()),
+            Event::ButtonPress(device.clone()),
+            Event::ButtonRelease(device),
+            Event::MotionNotify(MotionNotifyEvent {
+                detail: MotionNotifyDetail::Hint,
+                sequence_number: 1,
+                time: 2,
+                root: 3,
+                event: 4,
+                child: None,
+                root_x: 5,
+                root_y: 6,
+                event_x: 7,
+                event_y: 8,
+                state: HashSet::new(),
+                same_screen: true,
+            }),
+            Event::EnterNotify(crossing.clone()),
+            Event::LeaveNotify(crossing),
+            Event::FocusIn(focus.clone()),
+            Event::FocusOut(focus),
+            Event::KeymapNotify(KeymapNotifyEvent { keys }),
+            Event::Expose(ExposeEvent { sequence_number: 1, window: 2, x: 3, y: 4, width: 5, height: 6, count: 7 }),
+            Event::GraphicsExposure(GraphicsExposureEvent { sequence_number: 1, drawable: 2, x: 3, y: 4, width: 5, height: 6, minor_opcode: 7, count: 8, major_opcode: 62 }),
+            Event::NoExposure(NoExposureEvent { sequence_number: 1, drawable: 2, minor_opcode: 3, major_opcode: 63 }),
+            Event::VisibilityNotify(VisibilityNotifyEvent { sequence_number: 1, window: 2, state: VisibilityNotifyState::PartiallyObscured }),
+            Event::CreateNotify(CreateNotifyEvent { sequence_number: 1, parent: 2, window: 3, x: -4, y: 5, width: 6, height: 7, border_width: 8, override_redirect: true }),
+            Event::DestroyNotify(DestroyNotifyEvent { sequence_number: 1, event: 2, window: 3 }),
+            Event::UnmapNotify(UnmapNotifyEvent { sequence_number: 1, event: 2, window: 3, from_configure: true }),
+            Event::MapNotify(MapNotifyEvent { sequence_number: 1, event: 2, window: 3, override_redirect: false }),
+            Event::MapRequest(MapRequestEvent { sequence_number: 1, parent: 2, window: 3 }),
+            Event::ReparentNotify(ReparentNotifyEvent { sequence_number: 1, event: 2, window: 3, parent: 4, x: -5, y: 6, override_redirect: true }),
+            Event::ConfigureNotify(ConfigureNotifyEvent { sequence_number: 1, event: 2, window: 3, above_sibling: Some(4), x: 5, y: 6, width: 7, height: 8, border_width: 9, override_redirect: false }),
+            Event::ConfigureRequest(ConfigureRequestEvent {
+                stack_mode: ConfigureWindowValueStackMode::Opposite,
+                sequence_number: 1,
+                parent: 2,
+                window: 3,
+                sibling: None,
+                x: 4,
+                y: 5,
+                width: 6,
+                height: 7,
+                border_width: 8,
+                value_mask: HashSet::from_iter([ConfigureWindowValueMaskValue::X, ConfigureWindowValueMaskValue::StackMode].to_vec()),
+            }),
+            Event::GravityNotify(GravityNotifyEvent { sequence_number: 1, event: 2, window: 3, x: 4, y: -5 }),
+            Event::ResizeRequest(ResizeRequestEvent { sequence_number: 1, window: 2, width: 3, height: 4 }),
+            Event::CirculateNotify(circulate.clone()),
+            Event::CirculateRequest(circulate),
+            Event::PropertyNotify(PropertyNotifyEvent { sequence_number: 1, window: 2, atom: 3, time: 4, state: PropertyNotifyState::Deleted }),
+            Event::SelectionClear(SelectionClearEvent { sequence_number: 1, time: 2, owner: 3, selection: 4 }),
+            Event::SelectionRequest(SelectionRequestEvent { sequence_number: 1, time: 0, owner: 3, requestor: 4, selection: 5, target: 6, property: None }),
+            Event::SelectionNotify(SelectionNotifyEvent { sequence_number: 1, time: 2, requestor: 3, selection: 4, target: 5, property: Some(6) }),
+            Event::ColormapNotify(ColormapNotifyEvent { sequence_number: 1, window: 2, colormap: None, new: true, state: ColormapNotifyState::Installed }),
+            Event::ClientMessage(ClientMessageEvent { sequence_number: 1, window: 2, type_: 3, data: ClientMessageData::Format8([9; 20]) }),
+            Event::MappingNotify(MappingNotifyEvent { sequence_number: 1, request: MappingNotifyRequest::Keyboard, first_keycode: 8, count: 248 }),
+        ];
+        assert_eq!(events.len(), 33);
+        for (i, event) in events.into_iter().enumerate() {
+            assert_eq!(event.code() as usize, i + 2);
+            for order in [ByteOrder::MSBFirst, ByteOrder::LSBFirst].iter() {
+                let mut buffer = [0; 33];
+                Event::write(&mut BufWriter::new(&mut buffer[..]), event.clone(), order).unwrap();
+                assert_eq!(buffer[0] as usize, i + 2);
+                assert_eq!(buffer[32], 0);
+                let mut reader = BufReader::new(&buffer[..32]);
+                assert_eq!(Event::read(&mut reader, order).unwrap(), event);
+            }
+        }
+    }
+}
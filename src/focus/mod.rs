@@ -0,0 +1,296 @@
+/// keyboardのfocusと、focusが変わったときのFocusIn、FocusOut
+/// pointerのあるwindowは呼び出し側で決めて渡す
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::event::{Event, FocusEvent, NotifyDetail, NotifyMode};
+use crate::request::get_input_focus::GetInputFocusResponse;
+use crate::request::set_input_focus::{FocusRevertTo, InputFocus, SetInputFocusRequest};
+use crate::resources::ClientId;
+use crate::setup;
+use crate::time;
+use crate::window::WindowTree;
+
+mod test;
+
+#[derive(Clone, Debug)]
+pub struct FocusState {
+    focus: InputFocus,
+    revert_to: FocusRevertTo,
+    last_focus_time: u32,
+    /// focusしているwindowの祖先(親から根まで)
+    /// windowが破棄されたあとでもrevert_toのParentで戻る先を探せるように持っておく
+    ancestors: Vec<u32>,
+    /// keyboardをgrabしているwindow
+    grab: Option<u32>,
+    events: Vec<(ClientId, Event)>,
+}
+
+impl Default for FocusState {
+    fn default() -> Self {
+        FocusState {
+            focus: InputFocus::PointerRoot,
+            revert_to: FocusRevertTo::None,
+            last_focus_time: 0,
+            ancestors: Vec::new(),
+            grab: None,
+            events: Vec::new(),
+        }
+    }
+}
+
+impl FocusState {
+    /// 最初はPointerRoot
+    pub fn new() -> Self {
+        FocusState::default()
+    }
+
+    pub fn take_events(&mut self) -> Vec<(ClientId, Event)> {
+        std::mem::take(&mut self.events)
+    }
+
+    pub fn focus(&self) -> &InputFocus {
+        &self.focus
+    }
+
+    /// windowがfocusしているwindowかその子孫ならtrue
+    /// PointerRootなら常にtrue
+    pub fn has_focus(&self, windows: &WindowTree, window: u32) -> bool {
+        match self.focus {
+            InputFocus::None => false,
+            InputFocus::PointerRoot => true,
+            InputFocus::Window(focus) => window == focus || windows.is_inferior(window, focus),
+        }
+    }
+
+    /// keyboardのeventを報告し始めるwindow
+    /// focusがNoneならNoneで、eventは捨てる
+    /// pointerがfocusしているwindowの中にあればpointerのあるwindowから報告する
+    pub fn key_window(&self, windows: &WindowTree, pointer: u32) -> Option<u32> {
+        match self.focus {
+            InputFocus::None => None,
+            InputFocus::PointerRoot => Some(pointer),
+            InputFocus::Window(window) if pointer == window || windows.is_inferior(pointer, window) => Some(pointer),
+            InputFocus::Window(window) => Some(window),
+        }
+    }
+
+    /// focusするwindowが無いか表示されていなければBadWindowかBadMatch
+    /// timeが最後にfocusを変えた時刻より前か、nowより後なら何もしない
+    pub fn set_input_focus(&mut self, windows: &WindowTree, request: &SetInputFocusRequest, now: u32, pointer: u32) -> Result<()> {
+        if let InputFocus::Window(window) = request.focus {
+            windows.lookup(window)?;
+            if !windows.is_viewable(window) {
+                return Err(Error::ProtocolError(ErrorCode::Match, window));
+            }
+        }
+        let time = time::resolve(request.time, now);
+        if time::is_later(time, now) || time::is_later(self.last_focus_time, time) {
+            return Ok(());
+        }
+        self.last_focus_time = time;
+        self.revert_to = request.revert_to.clone();
+        self.move_focus(windows, request.focus.clone(), pointer);
+        Ok(())
+    }
+
+    pub fn get_input_focus(&self) -> GetInputFocusResponse {
+        GetInputFocusResponse {
+            revert_to: self.revert_to.clone(),
+            sequence_number: 0,
+            focus: self.focus.clone(),
+        }
+    }
+
+    /// windowの変更のあとに呼ぶ
+    /// focusしているwindowが表示されなくなっていれば、revert_toに従ってfocusを移す
+    pub fn revert_if_unviewable(&mut self, windows: &WindowTree, pointer: u32) {
+        let window = match self.focus {
+            InputFocus::Window(window) => window,
+            _ => return,
+        };
+        if windows.is_viewable(window) {
+            self.ancestors = windows.ancestors(window)[1..].to_vec();
+            return;
+        }
+        let focus = match self.revert_to {
+            FocusRevertTo::None => InputFocus::None,
+            FocusRevertTo::PointerRoot => InputFocus::PointerRoot,
+            FocusRevertTo::Parent => {
+                self.revert_to = FocusRevertTo::None;
+                //rootは常に表示されている
+                let parent = self.ancestors.iter().find(|parent| windows.is_viewable(**parent)).cloned();
+                parent.map(InputFocus::Window).unwrap_or(InputFocus::PointerRoot)
+            }
+        };
+        self.move_focus(windows, focus, pointer);
+    }
+
+    /// keyboardのgrabが始まったときに呼ぶ
+    /// focusがgrab_windowに移ったようにGrabのeventを送る
+    pub fn grab(&mut self, windows: &WindowTree, grab_window: u32, pointer: u32) {
+        let focus = self.focus.clone();
+        self.change(windows, &focus, &InputFocus::Window(grab_window), pointer, NotifyMode::Grab);
+        self.grab = Some(grab_window);
+    }
+
+    /// keyboardのgrabが終わったときに呼ぶ
+    /// grab_windowからfocusに戻ったようにUngrabのeventを送る
+    pub fn ungrab(&mut self, windows: &WindowTree, pointer: u32) {
+        if let Some(grab_window) = self.grab.take() {
+            let focus = self.focus.clone();
+            self.change(windows, &InputFocus::Window(grab_window), &focus, pointer, NotifyMode::Ungrab);
+        }
+    }
+
+    fn move_focus(&mut self, windows: &WindowTree, focus: InputFocus, pointer: u32) {
+        let mode = if self.grab.is_some() { NotifyMode::WhileGrabbed } else { NotifyMode::Normal };
+        let from = self.focus.clone();
+        self.change(windows, &from, &focus, pointer, mode);
+        self.focus = focus.clone();
+        self.ancestors = match focus {
+            InputFocus::Window(window) => windows.ancestors(window)[1..].to_vec(),
+            _ => Vec::new(),
+        };
+    }
+
+    fn send(&mut self, windows: &WindowTree, window: u32, focus_in: bool, detail: NotifyDetail, mode: &NotifyMode) {
+        let clients = match windows.get(window) {
+            Some(w) => w.clients_selecting(&setup::Event::FocusChange),
+            None => return,
+        };
+        for client in clients {
+            let event = FocusEvent { detail: detail.clone(), sequence_number: 0, event: window, mode: mode.clone() };
+            self.events.push((client, if focus_in { Event::FocusIn(event) } else { Event::FocusOut(event) }));
+        }
+    }
+
+    /// windowから根へ向かう経路
+    /// 破棄されたwindowなら覚えておいた祖先を使う
+    fn chain(&self, windows: &WindowTree, window: u32) -> Vec<u32> {
+        if windows.get(window).is_none() && self.focus == InputFocus::Window(window) {
+            return [&[window][..], &self.ancestors[..]].concat();
+        }
+        windows.ancestors(window)
+    }
+
+    /// fromからtoへfocusが移ったときのeventを順に作る
+    fn change(&mut self, windows: &WindowTree, from: &InputFocus, to: &InputFocus, pointer: u32, mode: NotifyMode) {
+        match (from, to) {
+            (InputFocus::Window(a), InputFocus::Window(b)) if a == b => {}
+            (InputFocus::Window(a), InputFocus::Window(b)) => {
+                let from_chain = self.chain(windows, *a);
+                let to_chain = windows.ancestors(*b);
+                let common = from_chain.iter().find(|w| to_chain.contains(w)).cloned();
+                let pointer_chain = windows.ancestors(pointer);
+                if common == Some(*a) {
+                    //bがaの子孫
+                    if windows.is_inferior(pointer, *a) && !pointer_chain.contains(b) && !to_chain.contains(&pointer) {
+                        self.pointer_out(windows, pointer, Some(*a), &mode);
+                    }
+                    self.send(windows, *a, false, NotifyDetail::Inferior, &mode);
+                    self.virtual_in(windows, &to_chain, Some(*a), NotifyDetail::Virtual, &mode);
+                    self.send(windows, *b, true, NotifyDetail::Ancestor, &mode);
+                } else if common == Some(*b) {
+                    //aがbの子孫
+                    self.send(windows, *a, false, NotifyDetail::Ancestor, &mode);
+                    self.virtual_out(windows, &from_chain, Some(*b), NotifyDetail::Virtual, &mode);
+                    self.send(windows, *b, true, NotifyDetail::Inferior, &mode);
+                    if windows.is_inferior(pointer, *b) && !pointer_chain.contains(a) && !from_chain.contains(&pointer) {
+                        self.pointer_in(windows, &pointer_chain, Some(*b), &mode);
+                    }
+                } else {
+                    self.leave_window(windows, &from_chain, pointer, common, &mode);
+                    self.enter_window(windows, &to_chain, pointer, common, &mode);
+                }
+            }
+            (InputFocus::Window(a), _) => {
+                let from_chain = self.chain(windows, *a);
+                self.leave_window(windows, &from_chain, pointer, None, &mode);
+                self.enter_root(windows, to, pointer, &mode);
+            }
+            (_, InputFocus::Window(b)) => {
+                let to_chain = windows.ancestors(*b);
+                self.leave_root(windows, from, pointer, &mode);
+                self.enter_window(windows, &to_chain, pointer, None, &mode);
+            }
+            _ => {
+                self.leave_root(windows, from, pointer, &mode);
+                self.enter_root(windows, to, pointer, &mode);
+            }
+        }
+    }
+
+    /// pointerからstopの手前まで(stopがNoneなら根まで)のFocusOut Pointer
+    fn pointer_out(&mut self, windows: &WindowTree, pointer: u32, stop: Option<u32>, mode: &NotifyMode) {
+        for window in windows.ancestors(pointer) {
+            if Some(window) == stop {
+                break;
+            }
+            self.send(windows, window, false, NotifyDetail::Pointer, mode);
+        }
+    }
+
+    /// startの下から(startがNoneなら根から)pointerまでのFocusIn Pointer
+    fn pointer_in(&mut self, windows: &WindowTree, pointer_chain: &[u32], start: Option<u32>, mode: &NotifyMode) {
+        let below = pointer_chain.iter().position(|w| Some(*w) == start).unwrap_or(pointer_chain.len());
+        for window in pointer_chain[..below].iter().rev() {
+            self.send(windows, *window, true, NotifyDetail::Pointer, mode);
+        }
+    }
+
+    /// from_chainの先頭の次からstopの手前までのFocusOut
+    fn virtual_out(&mut self, windows: &WindowTree, from_chain: &[u32], stop: Option<u32>, detail: NotifyDetail, mode: &NotifyMode) {
+        for window in from_chain[1..].iter().take_while(|w| Some(**w) != stop) {
+            self.send(windows, *window, false, detail.clone(), mode);
+        }
+    }
+
+    /// startの下からto_chainの先頭の手前まで、上から順にFocusIn
+    fn virtual_in(&mut self, windows: &WindowTree, to_chain: &[u32], start: Option<u32>, detail: NotifyDetail, mode: &NotifyMode) {
+        let windows_between = to_chain[1..].iter().take_while(|w| Some(**w) != start).cloned().collect::<Vec<_>>();
+        for window in windows_between.into_iter().rev() {
+            self.send(windows, window, true, detail.clone(), mode);
+        }
+    }
+
+    fn leave_window(&mut self, windows: &WindowTree, from_chain: &[u32], pointer: u32, common: Option<u32>, mode: &NotifyMode) {
+        let a = from_chain[0];
+        if windows.is_inferior(pointer, a) {
+            self.pointer_out(windows, pointer, Some(a), mode);
+        }
+        self.send(windows, a, false, NotifyDetail::Nonlinear, mode);
+        self.virtual_out(windows, from_chain, common, NotifyDetail::NonlinearVirtual, mode);
+    }
+
+    fn enter_window(&mut self, windows: &WindowTree, to_chain: &[u32], pointer: u32, common: Option<u32>, mode: &NotifyMode) {
+        let b = to_chain[0];
+        self.virtual_in(windows, to_chain, common, NotifyDetail::NonlinearVirtual, mode);
+        self.send(windows, b, true, NotifyDetail::Nonlinear, mode);
+        if windows.is_inferior(pointer, b) {
+            self.pointer_in(windows, &windows.ancestors(pointer), Some(b), mode);
+        }
+    }
+
+    fn leave_root(&mut self, windows: &WindowTree, from: &InputFocus, pointer: u32, mode: &NotifyMode) {
+        let detail = if *from == InputFocus::PointerRoot {
+            self.pointer_out(windows, pointer, None, mode);
+            NotifyDetail::PointerRoot
+        } else {
+            NotifyDetail::None
+        };
+        for root in windows.roots() {
+            self.send(windows, root, false, detail.clone(), mode);
+        }
+    }
+
+    fn enter_root(&mut self, windows: &WindowTree, to: &InputFocus, pointer: u32, mode: &NotifyMode) {
+        let detail = if *to == InputFocus::PointerRoot { NotifyDetail::PointerRoot } else { NotifyDetail::None };
+        for root in windows.roots() {
+            self.send(windows, root, true, detail.clone(), mode);
+        }
+        //leave_rootと同じくpointerの根も含める
+        if *to == InputFocus::PointerRoot {
+            self.pointer_in(windows, &windows.ancestors(pointer), None, mode);
+        }
+    }
+}
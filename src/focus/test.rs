@@ -0,0 +1,143 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+use crate::Error;
+use crate::error::ErrorCode;
+use crate::event::{Event, FocusEvent, NotifyDetail, NotifyMode};
+use crate::request::change_window_attributes::ChangeWindowAttributesRequest;
+use crate::request::create_window::{CreateWindowClass, CreateWindowRequest, CreateWindowValue, CreateWindowValueMaskValue};
+use crate::request::destroy_window::DestroyWindowRequest;
+use crate::request::get_input_focus::GetInputFocusResponse;
+use crate::request::map_window::MapWindowRequest;
+use crate::request::set_input_focus::{FocusRevertTo, InputFocus, SetInputFocusRequest};
+use crate::request::unmap_window::UnmapWindowRequest;
+use crate::setup;
+#[cfg(test)]
+use crate::test_util::{create_window, screen, select_input};
+use crate::window::WindowTree;
+use super::*;
+
+#[test]
+fn focus_test() {
+    const A: u32 = 0x00200001;
+    const B: u32 = 0x00200002;
+    const C: u32 = 0x00200003;
+    const D: u32 = 0x00200004;
+    fn create(wid: u32, parent: u32) -> CreateWindowRequest {
+        select_input(create_window(wid, parent, 0, 0, 10, 10), &[setup::Event::FocusChange])
+    }
+    fn focus_in(window: u32, detail: NotifyDetail, mode: NotifyMode) -> (u32, Event) {
+        (1, Event::FocusIn(FocusEvent { detail, sequence_number: 0, event: window, mode }))
+    }
+    fn focus_out(window: u32, detail: NotifyDetail, mode: NotifyMode) -> (u32, Event) {
+        (1, Event::FocusOut(FocusEvent { detail, sequence_number: 0, event: window, mode }))
+    }
+    fn set(window: u32, revert_to: FocusRevertTo) -> SetInputFocusRequest {
+        SetInputFocusRequest { revert_to, focus: InputFocus::Window(window), time: 0 }
+    }
+
+    //根の子のAとC、Aの子のB、Cの子でmapしないD
+    let mut tree = WindowTree::new(&[screen()]);
+    tree.change_window_attributes(1, &ChangeWindowAttributesRequest {
+        window: 1,
+        value_mask: HashSet::from_iter([CreateWindowValueMaskValue::EventMask].to_vec()),
+        value: CreateWindowValue { event_mask: HashSet::from_iter([setup::Event::FocusChange].to_vec()), ..Default::default() },
+    }).unwrap();
+    tree.create_window(1, &create(A, 1)).unwrap();
+    tree.create_window(1, &create(B, A)).unwrap();
+    tree.create_window(1, &create(C, 1)).unwrap();
+    tree.create_window(1, &create(D, C)).unwrap();
+    for window in [A, B, C] {
+        tree.map_window(1, &MapWindowRequest { window }).unwrap();
+    }
+    tree.take_events();
+    let mut focus = FocusState::new();
+    assert_eq!(focus.focus(), &InputFocus::PointerRoot);
+    assert_eq!(focus.key_window(&tree, B), Some(B));
+
+    //PointerRootからAへ
+    focus.set_input_focus(&tree, &set(A, FocusRevertTo::None), 100, B).unwrap();
+    assert_eq!(focus.take_events(), vec![
+        focus_out(B, NotifyDetail::Pointer, NotifyMode::Normal),
+        focus_out(A, NotifyDetail::Pointer, NotifyMode::Normal),
+        focus_out(1, NotifyDetail::Pointer, NotifyMode::Normal),
+        focus_out(1, NotifyDetail::PointerRoot, NotifyMode::Normal),
+        focus_in(1, NotifyDetail::NonlinearVirtual, NotifyMode::Normal),
+        focus_in(A, NotifyDetail::Nonlinear, NotifyMode::Normal),
+        focus_in(B, NotifyDetail::Pointer, NotifyMode::Normal),
+    ]);
+    assert_eq!(focus.key_window(&tree, B), Some(B));
+    assert_eq!(focus.key_window(&tree, C), Some(A));
+
+    //子孫へ
+    focus.set_input_focus(&tree, &set(B, FocusRevertTo::Parent), 110, B).unwrap();
+    assert_eq!(focus.take_events(), vec![
+        focus_out(A, NotifyDetail::Inferior, NotifyMode::Normal),
+        focus_in(B, NotifyDetail::Ancestor, NotifyMode::Normal),
+    ]);
+
+    //古い時刻と未来の時刻は無視する
+    focus.set_input_focus(&tree, &SetInputFocusRequest { time: 109, ..set(C, FocusRevertTo::None) }, 120, B).unwrap();
+    focus.set_input_focus(&tree, &SetInputFocusRequest { time: 121, ..set(C, FocusRevertTo::None) }, 120, B).unwrap();
+    assert!(focus.take_events().is_empty());
+    assert_eq!(focus.get_input_focus(), GetInputFocusResponse { revert_to: FocusRevertTo::Parent, sequence_number: 0, focus: InputFocus::Window(B) });
+
+    assert!(matches!(focus.set_input_focus(&tree, &set(D, FocusRevertTo::None), 120, B), Err(Error::ProtocolError(ErrorCode::Match, D))));
+    assert!(matches!(focus.set_input_focus(&tree, &set(5, FocusRevertTo::None), 120, B), Err(Error::ProtocolError(ErrorCode::Window, 5))));
+
+    //Bが表示されなくなれば親のAに戻り、revert_toはNoneになる
+    tree.unmap_window(&UnmapWindowRequest { window: B }).unwrap();
+    focus.revert_if_unviewable(&tree, A);
+    assert_eq!(focus.take_events(), vec![
+        focus_out(B, NotifyDetail::Ancestor, NotifyMode::Normal),
+        focus_in(A, NotifyDetail::Inferior, NotifyMode::Normal),
+    ]);
+    assert_eq!(focus.get_input_focus(), GetInputFocusResponse { revert_to: FocusRevertTo::None, sequence_number: 0, focus: InputFocus::Window(A) });
+
+    //関係の無いwindowへ
+    focus.set_input_focus(&tree, &set(C, FocusRevertTo::None), 130, A).unwrap();
+    assert_eq!(focus.take_events(), vec![
+        focus_out(A, NotifyDetail::Nonlinear, NotifyMode::Normal),
+        focus_in(C, NotifyDetail::Nonlinear, NotifyMode::Normal),
+    ]);
+
+    //grab中の変更はWhileGrabbed
+    focus.grab(&tree, 1, A);
+    assert_eq!(focus.take_events(), vec![
+        focus_out(C, NotifyDetail::Ancestor, NotifyMode::Grab),
+        focus_in(1, NotifyDetail::Inferior, NotifyMode::Grab),
+        focus_in(A, NotifyDetail::Pointer, NotifyMode::Grab),
+    ]);
+    focus.set_input_focus(&tree, &set(A, FocusRevertTo::None), 140, A).unwrap();
+    assert_eq!(focus.take_events(), vec![
+        focus_out(C, NotifyDetail::Nonlinear, NotifyMode::WhileGrabbed),
+        focus_in(A, NotifyDetail::Nonlinear, NotifyMode::WhileGrabbed),
+    ]);
+    focus.ungrab(&tree, A);
+    assert_eq!(focus.take_events(), vec![
+        focus_out(1, NotifyDetail::Inferior, NotifyMode::Ungrab),
+        focus_in(A, NotifyDetail::Ancestor, NotifyMode::Ungrab),
+    ]);
+
+    //破棄されたwindowにはeventを送らない
+    let destroyed = tree.destroy_window(&DestroyWindowRequest { window: A }).unwrap();
+    assert_eq!(destroyed.len(), 2);
+    focus.revert_if_unviewable(&tree, 1);
+    assert_eq!(focus.take_events(), vec![
+        focus_out(1, NotifyDetail::NonlinearVirtual, NotifyMode::Normal),
+        focus_in(1, NotifyDetail::None, NotifyMode::Normal),
+    ]);
+    assert_eq!(focus.key_window(&tree, 1), None);
+
+    //NoneからPointerRootへ
+    focus.set_input_focus(&tree, &SetInputFocusRequest { revert_to: FocusRevertTo::None, focus: InputFocus::PointerRoot, time: 0 }, 150, C).unwrap();
+    assert_eq!(focus.take_events(), vec![
+        focus_out(1, NotifyDetail::None, NotifyMode::Normal),
+        focus_in(1, NotifyDetail::PointerRoot, NotifyMode::Normal),
+        focus_in(1, NotifyDetail::Pointer, NotifyMode::Normal),
+        focus_in(C, NotifyDetail::Pointer, NotifyMode::Normal),
+    ]);
+}
@@ -0,0 +1,141 @@
+/// BDF(Glyph Bitmap Distribution Format 2.1/2.2)の読み込み
+/// 横書きの情報だけを使い、METRICSSETの縦書き用の値は無視する
+use std::io::{BufRead, Read};
+
+use crate::{Error, Result};
+use crate::atom::AtomTable;
+use crate::font::{Font, Glyph, PropertyValue};
+use crate::request::query_font::CharInfo;
+
+mod test;
+
+/// 文字列はISO 8859-1として読む
+fn latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| *b as char).collect()
+}
+
+fn number<T: std::str::FromStr>(word: Option<&str>) -> Result<T> {
+    word.and_then(|word| word.parse().ok()).ok_or(Error::InvalidValue("BDF"))
+}
+
+/// "で囲まれた値か整数
+/// 文字列の中の""は"1つ
+fn property_value(value: &str) -> Result<PropertyValue> {
+    let value = value.trim();
+    if let Some(quoted) = value.strip_prefix('"') {
+        let quoted = quoted.strip_suffix('"').ok_or(Error::InvalidValue("BDF"))?;
+        return Ok(PropertyValue::String(quoted.replace("\"\"", "\"")));
+    }
+    number(Some(value)).map(PropertyValue::Integer)
+}
+
+/// ENCODINGが-1で代わりの符号も無い文字は読み飛ばす
+pub fn read_bdf(stream: &mut std::io::BufReader<impl Read>, atoms: &AtomTable) -> Result<Font> {
+    let mut lines = Vec::new();
+    loop {
+        let mut line = Vec::new();
+        if stream.read_until(b'\n', &mut line).map_err(Error::IoError)? == 0 {
+            break;
+        }
+        let line = latin1(&line);
+        let line = line.trim_end_matches(['\r', '\n']);
+        if !line.trim().is_empty() && !line.starts_with("COMMENT") {
+            lines.push(line.to_string());
+        }
+    }
+    let mut lines = lines.iter().map(|line| line.as_str());
+
+    let mut name = String::new();
+    let mut bounding_box = (0i32, 0i32, 0i32, 0i32);
+    let mut font_width = None;
+    let mut properties = Vec::new();
+    let mut glyphs = Vec::new();
+    match lines.next().map(|line| line.split_whitespace().next()) {
+        Some(Some("STARTFONT")) => {}
+        _ => return Err(Error::InvalidValue("BDF")),
+    }
+    while let Some(line) = lines.next() {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("FONT") => name = line["FONT".len()..].trim().to_string(),
+            Some("FONTBOUNDINGBOX") => {
+                bounding_box = (number(words.next())?, number(words.next())?, number(words.next())?, number(words.next())?);
+            }
+            Some("DWIDTH") => font_width = Some(number::<i32>(words.next())?),
+            Some("STARTPROPERTIES") => {
+                for line in lines.by_ref() {
+                    if line.starts_with("ENDPROPERTIES") {
+                        break;
+                    }
+                    let (key, value) = line.trim().split_once(char::is_whitespace).ok_or(Error::InvalidValue("BDF"))?;
+                    properties.push((key.to_string(), property_value(value)?));
+                }
+            }
+            Some("STARTCHAR") => {
+                let mut encoding = None;
+                let mut width = font_width;
+                let mut bbx = bounding_box;
+                let mut bitmap = Vec::new();
+                while let Some(line) = lines.next() {
+                    let mut words = line.split_whitespace();
+                    match words.next() {
+                        Some("ENCODING") => {
+                            let standard = number::<i32>(words.next())?;
+                            encoding = match standard {
+                                -1 => words.next().map(|word| number::<i32>(Some(word))).transpose()?,
+                                standard => Some(standard),
+                            };
+                        }
+                        Some("DWIDTH") => width = Some(number(words.next())?),
+                        Some("BBX") => bbx = (number(words.next())?, number(words.next())?, number(words.next())?, number(words.next())?),
+                        Some("BITMAP") => {
+                            let row_bytes = ((bbx.0.max(0) + 7) / 8) as usize;
+                            for _ in 0..bbx.1.max(0) {
+                                let row = lines.next().ok_or(Error::InvalidValue("BDF"))?.trim();
+                                //行の16進数が足りなければ右を0で埋め、余分な桁は捨てる
+                                let mut nibbles = row.chars().map(|c| c.to_digit(16).ok_or(Error::InvalidValue("BDF")));
+                                for _ in 0..row_bytes {
+                                    let high = nibbles.next().transpose()?.unwrap_or(0);
+                                    let low = nibbles.next().transpose()?.unwrap_or(0);
+                                    bitmap.push((high << 4 | low) as u8);
+                                }
+                                //幅より右のbitは消す
+                                if bbx.0 & 7 != 0 {
+                                    if let Some(last) = bitmap.last_mut() {
+                                        *last &= 0xff << (8 - (bbx.0 & 7));
+                                    }
+                                }
+                            }
+                        }
+                        Some("ENDCHAR") => break,
+                        _ => {}
+                    }
+                }
+                let (w, h, x, y) = bbx;
+                let encoding = match encoding {
+                    Some(encoding) if (0..=0xffff).contains(&encoding) => encoding as u16,
+                    _ => continue,
+                };
+                glyphs.push(Glyph {
+                    encoding,
+                    metrics: CharInfo {
+                        left_side_bearing: x as i16,
+                        right_side_bearing: (x + w) as i16,
+                        character_width: width.unwrap_or(w) as i16,
+                        ascent: (y + h) as i16,
+                        descent: -y as i16,
+                        attributes: 0,
+                    },
+                    bitmap,
+                });
+            }
+            Some("ENDFONT") => break,
+            _ => {}
+        }
+    }
+    let (_, h, _, y) = bounding_box;
+    let has = |key: &str| properties.iter().any(|(name, _)| name == key);
+    let font_ascent = if has("FONT_ASCENT") { None } else { Some((y + h) as i16) };
+    let font_descent = if has("FONT_DESCENT") { None } else { Some(-y as i16) };
+    Ok(Font::new(atoms, name, glyphs, properties, None, font_ascent, font_descent))
+}
@@ -0,0 +1,142 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+use std::io::BufReader;
+
+use crate::Error;
+use crate::atom::AtomTable;
+use crate::font::bdf::read_bdf;
+use crate::request::query_font::{CharInfo, DrawDirection, FontProp};
+
+#[test]
+fn read_bdf_test() {
+    let input = b"STARTFONT 2.1
+COMMENT sample
+FONT -misc-fixed-medium-r-normal--4-40-75-75-c-40-iso8859-1
+SIZE 4 75 75
+FONTBOUNDINGBOX 4 5 0 -1
+STARTPROPERTIES 4
+FOUNDRY \"Misc\"
+POINT_SIZE 40
+DEFAULT_CHAR 32
+COPYRIGHT \"say \"\"hi\"\"\"
+ENDPROPERTIES
+CHARS 3
+STARTCHAR space
+ENCODING 32
+SWIDTH 666 0
+DWIDTH 4 0
+BBX 4 5 0 -1
+BITMAP
+00
+00
+00
+00
+00
+ENDCHAR
+STARTCHAR A
+ENCODING 65
+SWIDTH 666 0
+DWIDTH 4 0
+BBX 3 4 0 0
+BITMAP
+40
+A0
+E0
+B0
+ENDCHAR
+STARTCHAR unencoded
+ENCODING -1
+DWIDTH 4 0
+BBX 1 1 0 0
+BITMAP
+80
+ENDCHAR
+ENDFONT
+";
+    let atoms = AtomTable::new();
+    let font = read_bdf(&mut BufReader::new(&input[..]), &atoms).unwrap();
+    assert_eq!(font.name, "-misc-fixed-medium-r-normal--4-40-75-75-c-40-iso8859-1");
+    let info = &font.info;
+    assert_eq!((info.min_byte1, info.max_byte1, info.min_char_or_byte2, info.max_char_or_byte2), (0, 0, 32, 65));
+    assert_eq!(info.default_char, 32);
+    assert_eq!(info.draw_direction, DrawDirection::LeftToRight);
+    assert!(!info.all_chars_exist);
+    assert_eq!((info.font_ascent, info.font_descent), (4, 1));
+    assert_eq!(info.min_bounds, CharInfo { left_side_bearing: 0, right_side_bearing: 3, character_width: 4, ascent: 4, descent: 0, attributes: 0 });
+    assert_eq!(info.max_bounds, CharInfo { left_side_bearing: 0, right_side_bearing: 4, character_width: 4, ascent: 4, descent: 1, attributes: 0 });
+    assert_eq!(font.char_infos.len(), 34);
+    assert_eq!(font.char_infos[1], CharInfo::default());
+    assert_eq!(font.char_infos[33], CharInfo { left_side_bearing: 0, right_side_bearing: 3, character_width: 4, ascent: 4, descent: 0, attributes: 0 });
+    //幅より右のbitは消える
+    assert_eq!(font.bitmaps[33], vec![0x40, 0xa0, 0xe0, 0xa0]);
+    assert_eq!(font.bitmaps[0], vec![0; 5]);
+
+    let property = |name: &str| info.properties.iter().find(|p| p.name == atoms.get(name).unwrap()).unwrap().value;
+    assert_eq!(property("FOUNDRY"), atoms.get("Misc").unwrap());
+    assert_eq!(property("POINT_SIZE"), 40);
+    assert_eq!(property("DEFAULT_CHAR"), 32);
+    assert_eq!(atoms.name(property("COPYRIGHT")).unwrap(), "say \"hi\"");
+    assert_eq!(atoms.name(property("FONT")).unwrap(), font.name);
+    assert_eq!(info.properties.len(), 5);
+}
+
+#[test]
+fn read_bdf_two_byte_test() {
+    let input = b"STARTFONT 2.1
+FONT -misc-fixed-medium-r-normal--2-20-75-75-c-20-iso10646-1
+FONTBOUNDINGBOX 2 2 0 0
+STARTPROPERTIES 2
+FONT_ASCENT 3
+FONT_DESCENT 1
+ENDPROPERTIES
+CHARS 2
+STARTCHAR U+0141
+ENCODING 321
+DWIDTH 2 0
+BBX 2 2 0 0
+BITMAP
+C0
+40
+ENDCHAR
+STARTCHAR U+0242
+ENCODING 578
+DWIDTH 2 0
+BBX 1 2 1 0
+BITMAP
+80
+80
+ENDCHAR
+ENDFONT
+";
+    let font = read_bdf(&mut BufReader::new(&input[..]), &AtomTable::new()).unwrap();
+    let info = &font.info;
+    assert_eq!((info.min_byte1, info.max_byte1, info.min_char_or_byte2, info.max_char_or_byte2), (1, 2, 0x41, 0x42));
+    assert_eq!((info.font_ascent, info.font_descent), (3, 1));
+    assert_eq!(font.char_infos.len(), 4);
+    assert_eq!(font.index(1, 0x41), Some(0));
+    assert_eq!(font.index(2, 0x42), Some(3));
+    assert_eq!(font.bitmaps[3], vec![0x80, 0x80]);
+    assert_eq!(font.char_infos[1], CharInfo::default());
+    assert_eq!(font.char_infos[3].left_side_bearing, 1);
+}
+
+#[test]
+fn read_bdf_error_test() {
+    let input = b"FONT x\nENDFONT\n";
+    assert!(matches!(read_bdf(&mut BufReader::new(&input[..]), &AtomTable::new()), Err(Error::InvalidValue(_))));
+    let input = b"STARTFONT 2.1\nSTARTCHAR A\nENCODING 65\nBBX 8 2 0 0\nBITMAP\nFF\nENDCHAR\nENDFONT\n";
+    assert!(matches!(read_bdf(&mut BufReader::new(&input[..]), &AtomTable::new()), Err(Error::InvalidValue(_))));
+    let input = b"STARTFONT 2.1\nSTARTPROPERTIES 1\nFOUNDRY \"Misc\nENDPROPERTIES\nENDFONT\n";
+    assert!(matches!(read_bdf(&mut BufReader::new(&input[..]), &AtomTable::new()), Err(Error::InvalidValue(_))));
+    let input = b"STARTFONT 2.1\nSTARTCHAR A\nENCODING 65\nBBX 8 1 0 0\nBITMAP\nG0\nENDCHAR\nENDFONT\n";
+    assert!(matches!(read_bdf(&mut BufReader::new(&input[..]), &AtomTable::new()), Err(Error::InvalidValue(_))));
+}
+
+#[test]
+fn read_bdf_odd_row_test() {
+    //奇数桁の行は右を0で埋める
+    let input = b"STARTFONT 2.1\nSTARTCHAR A\nENCODING 65\nBBX 12 3 0 0\nBITMAP\nFFF\n8\n12345\nENDCHAR\nENDFONT\n";
+    let font = read_bdf(&mut BufReader::new(&input[..]), &AtomTable::new()).unwrap();
+    assert_eq!(font.bitmaps[0], vec![0xff, 0xf0, 0x80, 0x00, 0x12, 0x30]);
+}
@@ -0,0 +1,40 @@
+/// font pathにfontが無くても使えるように、crateに埋め込んだfixedとcursorのfont
+/// fixedはmisc-fixedの6x13から、ucs2anyと同じくISO8859-1の範囲を取り出したもの
+/// cursorは標準のcursor fontと同じ番号にglyphとmaskを並べたもので、形とhotspotは
+/// TkがX cursorを真似て作った16x16のcursor(tkMacOSXXCursors.h)から変換した
+/// X.Orgのcursor fontそのものではないので、そのXLFDは名乗らない
+use std::io::BufReader;
+
+use crate::atom::AtomTable;
+use crate::font::bdf::read_bdf;
+use crate::font::catalogue::matches;
+use crate::font::Font;
+
+mod test;
+
+pub const FIXED: &str = "-misc-fixed-medium-r-semicondensed--13-120-75-75-c-60-iso8859-1";
+pub const CURSOR: &str = "-xwindow-cursor-medium-r-normal--0-0-0-0-p-0-xwindow-fontspecific";
+
+const FIXED_BDF: &[u8] = include_bytes!("6x13.bdf");
+const CURSOR_BDF: &[u8] = include_bytes!("cursor.bdf");
+
+/// ListFontsで見せる名前と、そのBDF
+/// fonts.aliasと同じく、fixedと6x13とcursorも名前として持つ
+const FONTS: [(&str, &[u8]); 5] = [
+    (FIXED, FIXED_BDF),
+    (CURSOR, CURSOR_BDF),
+    ("fixed", FIXED_BDF),
+    ("6x13", FIXED_BDF),
+    ("cursor", CURSOR_BDF),
+];
+
+pub fn names<'a>() -> impl Iterator<Item=&'a str> {
+    FONTS.iter().map(|(name, _)| *name)
+}
+
+/// patternに一致するfontが無ければNone
+pub fn open(atoms: &AtomTable, pattern: &str) -> Option<Font> {
+    let (_, bdf) = FONTS.iter().find(|(name, _)| matches(pattern, name))?;
+    //埋め込んだBDFは壊れていないので、読めなければbug
+    Some(read_bdf(&mut BufReader::new(*bdf), atoms).expect("built-in BDF"))
+}
@@ -0,0 +1,75 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+use crate::atom::AtomTable;
+use crate::font::builtin::{CURSOR, FIXED, names, open};
+use crate::request::query_font::CharInfo;
+
+#[test]
+fn fixed_test() {
+    let atoms = AtomTable::new();
+    let font = open(&atoms, "fixed").unwrap();
+    assert_eq!(font.name.to_lowercase(), FIXED);
+    assert_eq!(open(&atoms, "6X13").unwrap(), font);
+    assert_eq!(open(&atoms, "-*-fixed-*-13-*-iso8859-1").unwrap(), font);
+    let info = &font.info;
+    assert_eq!((info.min_char_or_byte2, info.max_char_or_byte2, info.default_char), (0, 255, 0));
+    assert_eq!((info.font_ascent, info.font_descent), (11, 2));
+    let cell = CharInfo { left_side_bearing: 0, right_side_bearing: 6, character_width: 6, ascent: 11, descent: 2, attributes: 0 };
+    assert_eq!((info.min_bounds, info.max_bounds), (cell, cell));
+    //ISO8859-1の図形文字は全てある
+    for c in (b' '..=b'~').chain(0xa0..=0xff) {
+        assert_eq!(font.char_infos[c as usize], cell);
+    }
+    assert_eq!(font.char_glyph(0x80), None);
+    assert_eq!(font.glyph(0, b'A').unwrap().1, &[0x00, 0x00, 0x20, 0x50, 0x88, 0x88, 0x88, 0xf8, 0x88, 0x88, 0x88, 0x00, 0x00][..]);
+    let property = |name: &str| info.properties.iter().find(|p| p.name == atoms.get(name).unwrap()).unwrap().value;
+    assert_eq!(atoms.name(property("CHARSET_REGISTRY")).unwrap(), "ISO8859");
+    assert_eq!(atoms.name(property("CHARSET_ENCODING")).unwrap(), "1");
+    assert!(open(&atoms, "-*-fixed-bold-*").is_none());
+}
+
+#[test]
+fn cursor_test() {
+    let atoms = AtomTable::new();
+    let font = open(&atoms, "cursor").unwrap();
+    assert_eq!(font.name, CURSOR);
+    //X.Orgのcursor fontではないので、その名前では開けない
+    assert!(open(&atoms, "-xfree86-cursor-*").is_none());
+    //XC_X_cursorからXC_xtermまでの77個の形と、それぞれのmask
+    assert_eq!((font.info.min_char_or_byte2, font.info.max_char_or_byte2), (0, 153));
+    assert!(font.info.all_chars_exist);
+    //XC_left_ptrは左上を指す矢印で、hotspotはその先端
+    let (source, source_bitmap) = font.glyph(0, 68).unwrap();
+    assert_eq!((-source.left_side_bearing, source.ascent), (4, 1));
+    assert_eq!(&source_bitmap[..6], &[0x00, 0x00, 0x08, 0x00, 0x0c, 0x00]);
+    //shapeごとに形が違い、maskはshapeを覆う
+    for c in (0..154).step_by(2) {
+        let (_, source_bitmap) = font.glyph(0, c).unwrap();
+        let (_, mask_bitmap) = font.glyph(0, c + 1).unwrap();
+        assert!(source_bitmap.iter().zip(mask_bitmap).all(|(s, m)| s & !m == 0));
+        assert!((0..c).step_by(2).all(|other| font.glyph(0, other).unwrap().1 != source_bitmap));
+    }
+    //XC_xtermは縦棒で、maskは形と同じ
+    let (source, source_bitmap) = font.glyph(0, 152).unwrap();
+    assert_eq!((-source.left_side_bearing, source.ascent), (7, 11));
+    assert_eq!(source_bitmap, &[
+        0x0c, 0x60, 0x02, 0x80, 0x01, 0x00, 0x01, 0x00, 0x01, 0x00, 0x01, 0x00, 0x01, 0x00, 0x01, 0x00,
+        0x01, 0x00, 0x01, 0x00, 0x01, 0x00, 0x01, 0x00, 0x01, 0x00, 0x01, 0x00, 0x02, 0x80, 0x0c, 0x60,
+    ][..]);
+    assert_eq!(font.glyph(0, 153).unwrap().1, source_bitmap);
+    //XC_watchは竜頭が右にあり、hotspotもそこ
+    let (source, source_bitmap) = font.glyph(0, 150).unwrap();
+    assert_eq!((-source.left_side_bearing, source.ascent), (13, 8));
+    assert_eq!(source_bitmap, &[
+        0x07, 0xe0, 0x07, 0xe0, 0x07, 0xe0, 0x07, 0xe0, 0x08, 0x10, 0x10, 0x88, 0x10, 0x88, 0x10, 0x8c,
+        0x13, 0x8c, 0x10, 0x08, 0x10, 0x08, 0x08, 0x10, 0x07, 0xe0, 0x07, 0xe0, 0x07, 0xe0, 0x07, 0xe0,
+    ][..]);
+    assert_eq!(font.glyph(0, 151).unwrap().1, &[
+        0x07, 0xe0, 0x07, 0xe0, 0x07, 0xe0, 0x07, 0xe0, 0x0f, 0xf0, 0x1f, 0xf8, 0x1f, 0xf8, 0x1f, 0xfc,
+        0x1f, 0xfc, 0x1f, 0xf8, 0x1f, 0xf8, 0x0f, 0xf0, 0x07, 0xe0, 0x07, 0xe0, 0x07, 0xe0, 0x07, 0xe0,
+    ][..]);
+
+    assert_eq!(names().count(), 5);
+    assert!(names().any(|name| name == "cursor"));
+}
@@ -0,0 +1,273 @@
+/// font pathの各directoryにあるfonts.dirとfonts.aliasから、fontの名前とfileの対応を作る
+/// 名前の比較は大文字と小文字を区別しない
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use crate::{Error, Result};
+use crate::atom::AtomTable;
+use crate::error::ErrorCode;
+use crate::font::bdf::read_bdf;
+#[cfg(feature = "builtin-fonts")]
+use crate::font::builtin;
+use crate::font::Font;
+use crate::font::pcf::read_pcf;
+use crate::request::get_font_path::GetFontPathResponse;
+use crate::request::list_fonts::{ListFontsRequest, ListFontsResponse};
+use crate::request::list_fonts_with_info::{FontWithInfo, ListFontsWithInfoRequest, ListFontsWithInfoResponse};
+use crate::request::set_font_path::SetFontPathRequest;
+
+mod test;
+
+/// aliasの先がaliasのとき、たどる回数の上限
+const MAX_ALIAS_DEPTH: usize = 8;
+
+/// XLFDのpatternで、*は0文字以上、?は1文字に一致する
+pub fn matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.to_lowercase().chars().collect::<Vec<_>>();
+    let name = name.to_lowercase().chars().collect::<Vec<_>>();
+    let (mut p, mut n) = (0, 0);
+    //最後に見た*の位置と、そのとき対応させたnameの位置
+    let mut star = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// 拡張子で形式を決めて読む
+pub fn load_file(file: &Path, atoms: &AtomTable) -> Result<Font> {
+    let name = file.to_string_lossy().to_lowercase();
+    let mut stream = BufReader::new(File::open(file).map_err(Error::IoError)?);
+    if name.ends_with(".pcf") || name.ends_with(".pcf.gz") {
+        read_pcf(&mut stream, atoms)
+    } else if name.ends_with(".bdf") {
+        read_bdf(&mut stream, atoms)
+    } else {
+        Err(Error::InvalidValue("font file"))
+    }
+}
+
+enum Source<'a> {
+    File(&'a Path),
+    #[cfg(feature = "builtin-fonts")]
+    Builtin(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Entry {
+    name: String,
+    file: PathBuf,
+}
+
+/// fonts.aliasの別名と、その先の名前かpattern
+type Alias = (String, String);
+
+/// fonts.aliasの1行を、"で囲まれたものを1語として分ける
+fn alias_words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut chars = line.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        let mut word = String::new();
+        if c == '"' {
+            for c in chars.by_ref() {
+                if c == '"' {
+                    break;
+                }
+                word.push(c);
+            }
+        } else {
+            word.push(c);
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                word.push(c);
+            }
+        }
+        words.push(word);
+    }
+    words
+}
+
+/// font pathの1要素の、fonts.dirとfonts.aliasの中身
+#[derive(Clone, Debug, PartialEq)]
+struct Directory {
+    fonts: Vec<Entry>,
+    aliases: Vec<Alias>,
+}
+
+/// fonts.dirが無いか読めなければNone
+fn scan_directory(directory: &Path) -> Option<Directory> {
+    let fonts_dir = std::fs::read_to_string(directory.join("fonts.dir")).ok()?;
+    let mut lines = fonts_dir.lines();
+    lines.next()?.trim().parse::<usize>().ok()?;
+    let fonts = lines.filter_map(|line| {
+        let (file, name) = line.trim().split_once(char::is_whitespace)?;
+        Some(Entry { name: name.trim().to_string(), file: directory.join(file) })
+    }).collect();
+    let aliases = std::fs::read_to_string(directory.join("fonts.alias")).unwrap_or_default().lines()
+        .filter(|line| !line.trim_start().starts_with('!'))
+        .filter_map(|line| match &alias_words(line)[..] {
+            [alias, name] if alias != "FILE_NAMES_ALIASES" => Some((alias.clone(), name.clone())),
+            _ => None,
+        })
+        .collect();
+    Some(Directory { fonts, aliases })
+}
+
+#[derive(Clone, Debug)]
+pub struct FontCatalogue {
+    default_path: Vec<String>,
+    path: Vec<String>,
+    /// pathの順
+    directories: Vec<Directory>,
+}
+
+impl FontCatalogue {
+    /// 既定のpathは検証せず、fonts.dirの無いdirectoryは空として扱う
+    pub fn new(default_path: Vec<String>) -> Self {
+        let mut catalogue = FontCatalogue {
+            default_path: default_path.clone(),
+            path: Vec::new(),
+            directories: Vec::new(),
+        };
+        catalogue.scan(default_path);
+        catalogue
+    }
+
+    fn scan(&mut self, path: Vec<String>) {
+        self.directories = path.iter().filter_map(|directory| scan_directory(Path::new(directory))).collect();
+        self.path = path;
+    }
+
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    /// 空なら既定のpathに戻す
+    /// fonts.dirの無い要素があればBadValueで、pathは変えない
+    pub fn set_font_path(&mut self, request: &SetFontPathRequest) -> Result<()> {
+        if request.path.is_empty() {
+            self.scan(self.default_path.clone());
+            return Ok(());
+        }
+        if request.path.iter().any(|directory| !Path::new(directory).join("fonts.dir").is_file()) {
+            return Err(Error::ProtocolError(ErrorCode::Value, 0));
+        }
+        self.scan(request.path.clone());
+        Ok(())
+    }
+
+    pub fn get_font_path(&self) -> GetFontPathResponse {
+        GetFontPathResponse {
+            sequence_number: 0,
+            path: self.path.clone(),
+        }
+    }
+
+    /// fontの名前とaliasのうちpatternに一致するものを、pathの順に重複なく返す
+    pub fn names(&self, pattern: &str, max_names: usize) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        let candidates = self.directories.iter().flat_map(|directory| {
+            directory.fonts.iter().map(|entry| entry.name.as_str()).chain(directory.aliases.iter().map(|(alias, _)| alias.as_str()))
+        });
+        #[cfg(feature = "builtin-fonts")]
+        let candidates = candidates.chain(builtin::names());
+        for name in candidates {
+            if names.len() >= max_names {
+                break;
+            }
+            if matches(pattern, name) && !names.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+                names.push(name.to_string());
+            }
+        }
+        names
+    }
+
+    /// aliasをたどり、patternに最初に一致したfontのfileを返す
+    /// pathの順に、各directoryのfonts.dirとfonts.aliasを合わせて探す
+    /// aliasの先は最初のdirectoryから探し直す
+    /// font pathで見つからなければ、埋め込んだfontのpatternを返す
+    fn resolve(&self, pattern: &str) -> Option<Source<'_>> {
+        let mut pattern = pattern.to_string();
+        for _ in 0..MAX_ALIAS_DEPTH {
+            let mut alias = None;
+            for directory in &self.directories {
+                if let Some(entry) = directory.fonts.iter().find(|entry| matches(&pattern, &entry.name)) {
+                    return Some(Source::File(&entry.file));
+                }
+                alias = directory.aliases.iter().find(|(alias, _)| matches(&pattern, alias));
+                if alias.is_some() {
+                    break;
+                }
+            }
+            match alias {
+                Some((_, name)) => pattern = name.clone(),
+                #[cfg(feature = "builtin-fonts")]
+                None if builtin::names().any(|name| matches(&pattern, name)) => return Some(Source::Builtin(pattern)),
+                None => return None,
+            }
+        }
+        None
+    }
+
+    /// font pathの中でpatternに最初に一致したfontのfile
+    pub fn lookup(&self, pattern: &str) -> Option<&Path> {
+        match self.resolve(pattern)? {
+            Source::File(file) => Some(file),
+            #[cfg(feature = "builtin-fonts")]
+            Source::Builtin(_) => None,
+        }
+    }
+
+    /// OpenFontで使う
+    /// 見つからないか読めなければBadName
+    pub fn open(&self, atoms: &AtomTable, name: &str) -> Result<Font> {
+        match self.resolve(name).ok_or(Error::ProtocolError(ErrorCode::Name, 0))? {
+            Source::File(file) => load_file(file, atoms).map_err(|_| Error::ProtocolError(ErrorCode::Name, 0)),
+            #[cfg(feature = "builtin-fonts")]
+            Source::Builtin(pattern) => builtin::open(atoms, &pattern).ok_or(Error::ProtocolError(ErrorCode::Name, 0)),
+        }
+    }
+
+    pub fn list_fonts(&self, request: &ListFontsRequest) -> ListFontsResponse {
+        ListFontsResponse {
+            sequence_number: 0,
+            names: self.names(&request.pattern, request.max_names as usize),
+        }
+    }
+
+    /// 読めないfontは飛ばす
+    pub fn list_fonts_with_info(&self, atoms: &AtomTable, request: &ListFontsWithInfoRequest) -> ListFontsWithInfoResponse {
+        let fonts = self.names(&request.pattern, request.max_names as usize).into_iter()
+            .filter_map(|name| self.open(atoms, &name).ok().map(|font| (name, font.info)))
+            .collect::<Vec<_>>();
+        let count = fonts.len();
+        ListFontsWithInfoResponse {
+            sequence_number: 0,
+            fonts: fonts.into_iter().enumerate().map(|(i, (name, info))| FontWithInfo {
+                name,
+                info,
+                replies_hint: (count - i - 1) as u32,
+            }).collect(),
+        }
+    }
+}
@@ -0,0 +1,200 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+use std::path::{Path, PathBuf};
+
+use crate::Error;
+use crate::atom::AtomTable;
+use crate::error::ErrorCode;
+use crate::font::catalogue::{FontCatalogue, matches};
+use crate::request::list_fonts::ListFontsRequest;
+use crate::request::list_fonts_with_info::ListFontsWithInfoRequest;
+use crate::request::set_font_path::SetFontPathRequest;
+
+#[test]
+fn matches_test() {
+    let name = "-misc-fixed-medium-r-semicondensed--13-120-75-75-c-60-iso8859-1";
+    assert!(matches(name, name));
+    assert!(matches("*", name));
+    assert!(matches("-MISC-Fixed-*", name));
+    assert!(matches("*-13-*-iso8859-?", name));
+    assert!(matches("-*-*-*-*-*-*-13-*", name));
+    assert!(matches("*iso8859-1", name));
+    assert!(!matches("*iso8859-15", name));
+    assert!(!matches("-misc-fixed-bold-*", name));
+    assert!(!matches("?", name));
+    assert!(matches("", ""));
+    assert!(!matches("", "a"));
+    assert!(matches("**a*", "xay"));
+    assert!(!matches("a?", "a"));
+}
+
+#[test]
+fn catalogue_test() {
+    //testごとに別のdirectoryを作る
+    fn directory(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("xwindow-catalogue-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    fn misc(directory: &Path) {
+        std::fs::write(directory.join("8x16.pcf"), &include_bytes!("../pcf/fixtures/8x16.pcf")[..]).unwrap();
+        std::fs::write(directory.join("4x6.pcf.gz"), &include_bytes!("../pcf/fixtures/4x6.pcf.gz")[..]).unwrap();
+        std::fs::write(directory.join("broken.pcf"), b"broken").unwrap();
+        std::fs::write(directory.join("fonts.dir"), "3
+8x16.pcf -sony-fixed-medium-r-normal--16-120-100-100-c-80-iso8859-1
+4x6.pcf.gz -misc-fixed-medium-r-normal--6-60-75-75-c-40-iso10646-1
+broken.pcf -misc-broken-medium-r-normal--13-120-75-75-c-60-iso8859-1
+").unwrap();
+        std::fs::write(directory.join("fonts.alias"), "! comment
+FILE_NAMES_ALIASES
+fixed        -sony-fixed-medium-r-normal--16-120-100-100-c-80-iso8859-1
+8x16         fixed
+\"fixed unicode\" -misc-fixed-*-iso10646-1
+loop1 loop2
+loop2 loop1
+").unwrap();
+    }
+
+    fn lookup() {
+        let directory = directory("lookup");
+        misc(&directory);
+        let catalogue = FontCatalogue::new(vec![directory.to_string_lossy().to_string()]);
+        assert_eq!(catalogue.lookup("FIXED"), Some(directory.join("8x16.pcf").as_path()));
+        assert_eq!(catalogue.lookup("8x16"), Some(directory.join("8x16.pcf").as_path()));
+        assert_eq!(catalogue.lookup("fixed unicode"), Some(directory.join("4x6.pcf.gz").as_path()));
+        assert_eq!(catalogue.lookup("*iso10646-1"), Some(directory.join("4x6.pcf.gz").as_path()));
+        assert_eq!(catalogue.lookup("loop1"), None);
+        assert_eq!(catalogue.lookup("cursor"), None);
+
+        let atoms = AtomTable::new();
+        let font = catalogue.open(&atoms, "fixed").unwrap();
+        assert_eq!(font.name, "-Sony-Fixed-Medium-R-Normal--16-120-100-100-C-80-ISO8859-1");
+        #[cfg(not(feature = "builtin-fonts"))]
+        assert!(matches!(catalogue.open(&atoms, "cursor"), Err(Error::ProtocolError(ErrorCode::Name, _))));
+        //font pathに無ければ埋め込んだfontを使う
+        #[cfg(feature = "builtin-fonts")]
+        assert_eq!(catalogue.open(&atoms, "cursor").unwrap().name, crate::font::builtin::CURSOR);
+        assert!(matches!(catalogue.open(&atoms, "-*-bold-*"), Err(Error::ProtocolError(ErrorCode::Name, _))));
+        assert!(matches!(catalogue.open(&atoms, "*broken*"), Err(Error::ProtocolError(ErrorCode::Name, _))));
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    fn list() {
+        let directory = directory("list");
+        misc(&directory);
+        let catalogue = FontCatalogue::new(vec![directory.to_string_lossy().to_string()]);
+        let response = catalogue.list_fonts(&ListFontsRequest { max_names: 100, pattern: "*".to_string() });
+        #[cfg(not(feature = "builtin-fonts"))]
+        assert_eq!(response.names.len(), 8);
+        //fixedは重複する
+        #[cfg(feature = "builtin-fonts")]
+        assert_eq!(response.names.len(), 12);
+        let response = catalogue.list_fonts(&ListFontsRequest { max_names: 1, pattern: "*".to_string() });
+        assert_eq!(response.names, vec!["-sony-fixed-medium-r-normal--16-120-100-100-c-80-iso8859-1".to_string()]);
+        let response = catalogue.list_fonts(&ListFontsRequest { max_names: 100, pattern: "*FIXED*".to_string() });
+        #[allow(unused_mut)]
+        let mut expected = vec![
+            "-sony-fixed-medium-r-normal--16-120-100-100-c-80-iso8859-1".to_string(),
+            "-misc-fixed-medium-r-normal--6-60-75-75-c-40-iso10646-1".to_string(),
+            "fixed".to_string(),
+            "fixed unicode".to_string(),
+        ];
+        //font pathの後ろに埋め込んだfontが続く
+        #[cfg(feature = "builtin-fonts")]
+        expected.push(crate::font::builtin::FIXED.to_string());
+        assert_eq!(response.names, expected);
+
+        let atoms = AtomTable::new();
+        let response = catalogue.list_fonts_with_info(&atoms, &ListFontsWithInfoRequest { max_names: 100, pattern: "*-medium-r-normal--*-c-*".to_string() });
+        assert_eq!(response.fonts.len(), 2);
+        assert_eq!(response.fonts[0].replies_hint, 1);
+        assert_eq!(response.fonts[0].info.font_ascent, 14);
+        assert_eq!(response.fonts[1].replies_hint, 0);
+        assert_eq!(response.fonts[1].info.max_byte1, 38);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    fn set_font_path() {
+        let first = directory("path1");
+        misc(&first);
+        let second = directory("path2");
+        std::fs::write(second.join("fonts.dir"), "0\n").unwrap();
+        std::fs::write(second.join("fonts.alias"), "other fixed\n").unwrap();
+        let empty = directory("path3");
+        let directories = [first.clone(), second.clone(), empty.clone()];
+        let first = first.to_string_lossy().to_string();
+        let second = second.to_string_lossy().to_string();
+
+        let mut catalogue = FontCatalogue::new(vec![second.clone()]);
+        assert_eq!(catalogue.get_font_path().path, vec![second.clone()]);
+        assert_eq!(catalogue.lookup("other"), None);
+
+        catalogue.set_font_path(&SetFontPathRequest { path: vec![second.clone(), first.clone()] }).unwrap();
+        assert_eq!(catalogue.get_font_path().path, vec![second.clone(), first.clone()]);
+        assert_eq!(catalogue.lookup("other"), catalogue.lookup("fixed"));
+        assert!(catalogue.lookup("other").is_some());
+
+        //1つでも使えない要素があれば変えない
+        let bad = vec![first.clone(), empty.to_string_lossy().to_string()];
+        assert!(matches!(catalogue.set_font_path(&SetFontPathRequest { path: bad }), Err(Error::ProtocolError(ErrorCode::Value, _))));
+        let bad = vec!["/nonexistent/fonts".to_string()];
+        assert!(matches!(catalogue.set_font_path(&SetFontPathRequest { path: bad }), Err(Error::ProtocolError(ErrorCode::Value, _))));
+        assert_eq!(catalogue.path(), &[second.clone(), first][..]);
+
+        catalogue.set_font_path(&SetFontPathRequest { path: vec![] }).unwrap();
+        assert_eq!(catalogue.path(), &[second][..]);
+        assert_eq!(catalogue.lookup("fixed"), None);
+        for directory in directories.iter() {
+            std::fs::remove_dir_all(directory).unwrap();
+        }
+    }
+
+    fn path_order() {
+        //前のdirectoryのaliasは、後のdirectoryのfontより優先する
+        let aliased = directory("order1");
+        std::fs::write(aliased.join("8x16.pcf"), &include_bytes!("../pcf/fixtures/8x16.pcf")[..]).unwrap();
+        std::fs::write(aliased.join("fonts.dir"), "1\n8x16.pcf -sony-fixed-medium-r-normal--16-120-100-100-c-80-iso8859-1\n").unwrap();
+        std::fs::write(aliased.join("fonts.alias"), "fixed -sony-fixed-medium-r-normal--16-120-100-100-c-80-iso8859-1\n").unwrap();
+        let named = directory("order2");
+        std::fs::write(named.join("4x6.pcf.gz"), &include_bytes!("../pcf/fixtures/4x6.pcf.gz")[..]).unwrap();
+        std::fs::write(named.join("fonts.dir"), "1\n4x6.pcf.gz fixed\n").unwrap();
+        let directories = [aliased.clone(), named.clone()];
+        let aliased = aliased.to_string_lossy().to_string();
+        let named = named.to_string_lossy().to_string();
+
+        let catalogue = FontCatalogue::new(vec![aliased.clone(), named.clone()]);
+        assert_eq!(catalogue.lookup("fixed"), Some(directories[0].join("8x16.pcf").as_path()));
+        let response = catalogue.list_fonts(&ListFontsRequest { max_names: 100, pattern: "*".to_string() });
+        assert_eq!(&response.names[..2], &["-sony-fixed-medium-r-normal--16-120-100-100-c-80-iso8859-1".to_string(), "fixed".to_string()][..]);
+        let catalogue = FontCatalogue::new(vec![named, aliased]);
+        assert_eq!(catalogue.lookup("fixed"), Some(directories[1].join("4x6.pcf.gz").as_path()));
+        assert_eq!(catalogue.open(&AtomTable::new(), "fixed").unwrap().info.max_byte1, 38);
+        for directory in directories.iter() {
+            std::fs::remove_dir_all(directory).unwrap();
+        }
+    }
+
+    lookup();
+    list();
+    set_font_path();
+    path_order();
+}
+
+#[cfg(feature = "builtin-fonts")]
+#[test]
+fn builtin_test() {
+    //font pathが空でもfixedとcursorは開ける
+    let catalogue = FontCatalogue::new(vec![]);
+    let atoms = AtomTable::new();
+    assert_eq!(catalogue.open(&atoms, "fixed").unwrap().name.to_lowercase(), crate::font::builtin::FIXED);
+    assert_eq!(catalogue.open(&atoms, "cursor").unwrap().name, crate::font::builtin::CURSOR);
+    assert_eq!(catalogue.lookup("fixed"), None);
+    let response = catalogue.list_fonts(&ListFontsRequest { max_names: 100, pattern: "*cursor*".to_string() });
+    assert_eq!(response.names, vec![crate::font::builtin::CURSOR.to_string(), "cursor".to_string()]);
+    let response = catalogue.list_fonts_with_info(&atoms, &ListFontsWithInfoRequest { max_names: 100, pattern: "fixed".to_string() });
+    assert_eq!(response.fonts.len(), 1);
+    assert_eq!(response.fonts[0].info.font_ascent, 11);
+}
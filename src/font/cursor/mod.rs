@@ -0,0 +1,71 @@
+/// CreateGlyphCursorで、fontのglyphからcursorの形を作る
+/// bitmapはFontと同じく、1行をbyte境界までpaddingし、各byteの上位bitが左のpixel
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::font::Font;
+use crate::request::query_font::CharInfo;
+
+mod test;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GlyphCursor {
+    pub width: u16,
+    pub height: u16,
+    /// sourceのglyphの原点で、左上からの位置
+    pub x_hot: i16,
+    pub y_hot: i16,
+    pub source: Vec<u8>,
+    pub mask: Vec<u8>,
+}
+
+impl GlyphCursor {
+    /// 大きさはmaskのglyphで決め、sourceのglyphは原点をそろえて置く
+    /// maskのfontが無ければsourceの大きさで、全てのpixelを表示する
+    /// 文字がfontに無ければBadValue
+    pub fn new(source_font: &Font, source_char: u16, mask: Option<(&Font, u16)>) -> Result<Self> {
+        let source = source_font.char_glyph(source_char).ok_or(Error::ProtocolError(ErrorCode::Value, source_char as u32))?;
+        let mask = match mask {
+            Some((font, mask_char)) => Some(font.char_glyph(mask_char).ok_or(Error::ProtocolError(ErrorCode::Value, mask_char as u32))?),
+            None => None,
+        };
+        let bounds = mask.map(|(metrics, _)| metrics).unwrap_or(source.0);
+        let width = (bounds.right_side_bearing - bounds.left_side_bearing).max(0) as u16;
+        let height = (bounds.ascent + bounds.descent).max(0) as u16;
+        let render = |(metrics, bitmap): (CharInfo, &[u8])| {
+            let row_bytes = (width as usize).div_ceil(8);
+            let mut bits = vec![0; row_bytes * height as usize];
+            for y in 0..height as i16 {
+                for x in 0..width as i16 {
+                    if pixel(&metrics, bitmap, x + bounds.left_side_bearing, y - bounds.ascent) {
+                        bits[y as usize * row_bytes + (x as usize >> 3)] |= 0x80 >> (x & 7);
+                    }
+                }
+            }
+            bits
+        };
+        let mask = match mask {
+            Some(mask) => render(mask),
+            None => render((bounds, &vec![0xff; (width as usize).div_ceil(8) * height as usize][..])),
+        };
+        Ok(GlyphCursor {
+            width,
+            height,
+            x_hot: -bounds.left_side_bearing,
+            y_hot: bounds.ascent,
+            source: render(source),
+            mask,
+        })
+    }
+}
+
+/// 原点からの位置で、yは下が正
+fn pixel(metrics: &CharInfo, bitmap: &[u8], x: i16, y: i16) -> bool {
+    let (x, y) = (x - metrics.left_side_bearing, y + metrics.ascent);
+    let width = metrics.right_side_bearing - metrics.left_side_bearing;
+    let height = metrics.ascent + metrics.descent;
+    if x < 0 || width <= x || y < 0 || height <= y {
+        return false;
+    }
+    let row_bytes = (width as usize).div_ceil(8);
+    bitmap.get(y as usize * row_bytes + (x as usize >> 3)).is_some_and(|byte| byte & (0x80 >> (x & 7)) != 0)
+}
@@ -0,0 +1,89 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+use std::io::BufReader;
+
+use crate::Error;
+use crate::atom::AtomTable;
+use crate::error::ErrorCode;
+use crate::font::{Font, Glyph};
+use crate::font::catalogue::FontCatalogue;
+use crate::font::cursor::GlyphCursor;
+use crate::read_util::ByteOrder;
+use crate::request::{Request, RequestReader};
+use crate::request::query_font::CharInfo;
+
+#[test]
+fn glyph_cursor_test() {
+    let source = CharInfo { left_side_bearing: 1, right_side_bearing: 3, character_width: 4, ascent: 1, descent: 1, attributes: 0 };
+    let mask = CharInfo { left_side_bearing: 0, right_side_bearing: 4, character_width: 4, ascent: 2, descent: 1, attributes: 0 };
+    let glyphs = vec![
+        Glyph { encoding: 0x30, metrics: source, bitmap: vec![0xc0, 0x40] },
+        Glyph { encoding: 0x32, metrics: mask, bitmap: vec![0xf0, 0xf0, 0xf0] },
+    ];
+    let font = Font::new(&AtomTable::new(), String::new(), glyphs, vec![], Some(0x32), None, None);
+
+    //maskの大きさで、sourceは原点をそろえて1つ右、1つ下にずれる
+    let cursor = GlyphCursor::new(&font, 0x30, Some((&font, 0x32))).unwrap();
+    assert_eq!(cursor, GlyphCursor {
+        width: 4,
+        height: 3,
+        x_hot: 0,
+        y_hot: 2,
+        source: vec![0x00, 0x60, 0x20],
+        mask: vec![0xf0, 0xf0, 0xf0],
+    });
+    //maskが無ければsourceの大きさで全て表示する
+    let cursor = GlyphCursor::new(&font, 0x30, None).unwrap();
+    assert_eq!(cursor, GlyphCursor {
+        width: 2,
+        height: 2,
+        x_hot: -1,
+        y_hot: 1,
+        source: vec![0xc0, 0x40],
+        mask: vec![0xc0, 0xc0],
+    });
+    //default_charがあっても、無い文字はBadValue
+    assert!(matches!(GlyphCursor::new(&font, 0x31, None), Err(Error::ProtocolError(ErrorCode::Value, 0x31))));
+    assert!(matches!(GlyphCursor::new(&font, 0x30, Some((&font, 0x7f))), Err(Error::ProtocolError(ErrorCode::Value, 0x7f))));
+}
+
+#[cfg(feature = "builtin-fonts")]
+#[test]
+fn open_font_test() {
+    let input = [
+        //OpenFont fid 0x00200001 "cursor"
+        45, 0, 5, 0, 1, 0, 0x20, 0, 6, 0, 0, 0, b'c', b'u', b'r', b's', b'o', b'r', 0, 0,
+        //CreateGlyphCursor XC_left_ptrとそのmask
+        94, 0, 8, 0, 2, 0, 0x20, 0, 1, 0, 0x20, 0, 1, 0, 0x20, 0, 68, 0, 69, 0,
+        0, 0, 0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    ];
+    let reader = RequestReader::new(0xffff);
+    let mut stream = BufReader::new(&input[..]);
+    let open_font = match reader.read(&mut stream, &ByteOrder::LSBFirst).unwrap().1 {
+        Ok(Request::OpenFont(request)) => request,
+        _ => panic!(),
+    };
+    let request = match reader.read(&mut stream, &ByteOrder::LSBFirst).unwrap().1 {
+        Ok(Request::CreateGlyphCursor(request)) => request,
+        _ => panic!(),
+    };
+    assert_eq!((request.cid, request.source_font, request.mask_font), (0x00200002, open_font.fid, Some(open_font.fid)));
+    assert_eq!((request.back_red, request.back_green, request.back_blue), (0xffff, 0xffff, 0xffff));
+
+    let atoms = AtomTable::new();
+    let font = FontCatalogue::new(vec![]).open(&atoms, &open_font.name).unwrap();
+    let cursor = GlyphCursor::new(&font, request.source_char, Some((&font, request.mask_char))).unwrap();
+    //同じ大きさのglyphなので、そのまま使われる
+    let (source, source_bitmap) = font.char_glyph(68).unwrap();
+    let (_, mask_bitmap) = font.char_glyph(69).unwrap();
+    assert_eq!((cursor.width, cursor.height), (16, 16));
+    assert_eq!((cursor.x_hot, cursor.y_hot), (-source.left_side_bearing, source.ascent));
+    assert_eq!(cursor.source, source_bitmap.to_vec());
+    assert_eq!(cursor.mask, mask_bitmap.to_vec());
+    assert_eq!((cursor.x_hot, cursor.y_hot), (4, 1));
+    assert_eq!(&cursor.source[..6], &[0x00, 0x00, 0x08, 0x00, 0x0c, 0x00]);
+    assert!(cursor.source.iter().zip(&cursor.mask).all(|(s, m)| s & !m == 0));
+    //cursor fontの範囲外
+    assert!(matches!(GlyphCursor::new(&font, 154, None), Err(Error::ProtocolError(ErrorCode::Value, 154))));
+}
@@ -0,0 +1,175 @@
+/// 読み込んだfontを、QueryFontの返事にそのまま使える形で持つ
+/// bitmapは1行をbyte境界までpaddingし、各byteの上位bitが左のpixel
+use crate::atom::AtomTable;
+use crate::request::query_font::{CharInfo, DrawDirection, FontInfo, FontProp, QueryFontResponse};
+
+pub mod bdf;
+#[cfg(feature = "builtin-fonts")]
+pub mod builtin;
+pub mod catalogue;
+pub mod cursor;
+pub mod pcf;
+
+mod test;
+
+/// 各loaderが読み出す1文字分
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Glyph {
+    pub encoding: u16,
+    pub metrics: CharInfo,
+    pub bitmap: Vec<u8>,
+}
+
+/// FONTPROPのvalueで、文字列はatomにしてから格納する
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum PropertyValue {
+    Integer(i32),
+    String(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Font {
+    /// FONT propertyのXLFD
+    pub name: String,
+    pub info: FontInfo,
+    /// QueryFontResponseのchar_infosと同じ順で、存在しない文字は全て0
+    pub char_infos: Vec<CharInfo>,
+    /// char_infosと同じ順
+    pub bitmaps: Vec<Vec<u8>>,
+}
+
+impl Font {
+    /// glyphsの符号化の範囲から1byteか2byteの配置を決め、min_boundsなどを計算する
+    /// default_charやfont_ascentが無ければpropertiesとglyphsから決める
+    pub(crate) fn new(atoms: &AtomTable, name: String, glyphs: Vec<Glyph>, properties: Vec<(String, PropertyValue)>, default_char: Option<u16>, font_ascent: Option<i16>, font_descent: Option<i16>) -> Font {
+        let two_byte = glyphs.iter().any(|glyph| glyph.encoding > 0xff);
+        let byte1 = |encoding: u16| if two_byte { (encoding >> 8) as u8 } else { 0 };
+        let byte2 = |encoding: u16| if two_byte { encoding & 0xff } else { encoding };
+        let min_byte1 = glyphs.iter().map(|glyph| byte1(glyph.encoding)).min().unwrap_or(0);
+        let max_byte1 = glyphs.iter().map(|glyph| byte1(glyph.encoding)).max().unwrap_or(0);
+        let min_char_or_byte2 = glyphs.iter().map(|glyph| byte2(glyph.encoding)).min().unwrap_or(0);
+        let max_char_or_byte2 = glyphs.iter().map(|glyph| byte2(glyph.encoding)).max().unwrap_or(0);
+        let columns = (max_char_or_byte2 - min_char_or_byte2) as usize + 1;
+        let rows = (max_byte1 - min_byte1) as usize + 1;
+
+        let mut char_infos = vec![CharInfo::default(); rows * columns];
+        let mut bitmaps = vec![Vec::new(); rows * columns];
+        let mut exists = vec![false; rows * columns];
+        let mut min_bounds: Option<CharInfo> = None;
+        let mut max_bounds: Option<CharInfo> = None;
+        let mut total_width = 0i32;
+        for glyph in glyphs {
+            let index = (byte1(glyph.encoding) - min_byte1) as usize * columns + (byte2(glyph.encoding) - min_char_or_byte2) as usize;
+            let m = glyph.metrics;
+            total_width += m.character_width as i32;
+            min_bounds = Some(match min_bounds {
+                None => m,
+                Some(b) => CharInfo {
+                    left_side_bearing: b.left_side_bearing.min(m.left_side_bearing),
+                    right_side_bearing: b.right_side_bearing.min(m.right_side_bearing),
+                    character_width: b.character_width.min(m.character_width),
+                    ascent: b.ascent.min(m.ascent),
+                    descent: b.descent.min(m.descent),
+                    attributes: b.attributes & m.attributes,
+                },
+            });
+            max_bounds = Some(match max_bounds {
+                None => m,
+                Some(b) => CharInfo {
+                    left_side_bearing: b.left_side_bearing.max(m.left_side_bearing),
+                    right_side_bearing: b.right_side_bearing.max(m.right_side_bearing),
+                    character_width: b.character_width.max(m.character_width),
+                    ascent: b.ascent.max(m.ascent),
+                    descent: b.descent.max(m.descent),
+                    attributes: b.attributes | m.attributes,
+                },
+            });
+            char_infos[index] = m;
+            bitmaps[index] = glyph.bitmap;
+            exists[index] = true;
+        }
+        let min_bounds = min_bounds.unwrap_or_default();
+        let max_bounds = max_bounds.unwrap_or_default();
+
+        let integer = |key: &str| properties.iter().find_map(|(name, value)| match value {
+            PropertyValue::Integer(value) if name == key => Some(*value),
+            _ => None,
+        });
+        let default_char = default_char.or_else(|| integer("DEFAULT_CHAR").map(|value| value as u16)).unwrap_or(0);
+        let font_ascent = font_ascent.or_else(|| integer("FONT_ASCENT").map(|value| value as i16)).unwrap_or(max_bounds.ascent);
+        let font_descent = font_descent.or_else(|| integer("FONT_DESCENT").map(|value| value as i16)).unwrap_or(max_bounds.descent);
+
+        let mut properties = properties;
+        if !name.is_empty() && !properties.iter().any(|(key, _)| key == "FONT") {
+            properties.push(("FONT".to_string(), PropertyValue::String(name.clone())));
+        }
+        //InternAtomと同じく、atomを使い切ったpropertyは返さない
+        let properties = properties.into_iter().filter_map(|(key, value)| {
+            let name = atoms.intern(&key, false).ok()??;
+            let value = match value {
+                PropertyValue::Integer(value) => value as u32,
+                PropertyValue::String(value) => atoms.intern(&value, false).ok()??,
+            };
+            Some(FontProp { name, value })
+        }).collect();
+
+        Font {
+            name,
+            info: FontInfo {
+                min_bounds,
+                max_bounds,
+                min_char_or_byte2,
+                max_char_or_byte2,
+                default_char,
+                //文字幅の合計が負なら右から左へ書くfont
+                draw_direction: if total_width < 0 { DrawDirection::RightToLeft } else { DrawDirection::LeftToRight },
+                min_byte1,
+                max_byte1,
+                all_chars_exist: exists.iter().all(|exists| *exists),
+                font_ascent,
+                font_descent,
+                properties,
+            },
+            char_infos,
+            bitmaps,
+        }
+    }
+
+    /// 1byteのfontではbyte1を0とみなす
+    /// 範囲外ならNone
+    pub fn index(&self, byte1: u8, byte2: u8) -> Option<usize> {
+        let info = &self.info;
+        let byte2 = byte2 as u16;
+        if byte1 < info.min_byte1 || info.max_byte1 < byte1 || byte2 < info.min_char_or_byte2 || info.max_char_or_byte2 < byte2 {
+            return None;
+        }
+        let columns = (info.max_char_or_byte2 - info.min_char_or_byte2) as usize + 1;
+        Some((byte1 - info.min_byte1) as usize * columns + (byte2 - info.min_char_or_byte2) as usize)
+    }
+
+    /// 存在しない文字ならdefault_charを使い、それも無ければNone
+    pub fn glyph(&self, byte1: u8, byte2: u8) -> Option<(CharInfo, &[u8])> {
+        let exists = |index: usize| self.char_infos[index] != CharInfo::default();
+        let index = self.index(byte1, byte2).filter(|index| exists(*index)).or_else(|| {
+            let default_char = self.info.default_char;
+            self.index((default_char >> 8) as u8, default_char as u8).filter(|index| exists(*index))
+        })?;
+        Some((self.char_infos[index], &self.bitmaps[index][..]))
+    }
+
+    /// default_charを使わず、存在しない文字ならNone
+    /// 2byteのfontでは上位byteをbyte1とする
+    pub fn char_glyph(&self, character: u16) -> Option<(CharInfo, &[u8])> {
+        let index = self.index((character >> 8) as u8, character as u8)
+            .filter(|index| self.char_infos[*index] != CharInfo::default())?;
+        Some((self.char_infos[index], &self.bitmaps[index][..]))
+    }
+
+    pub fn query_font(&self) -> QueryFontResponse {
+        QueryFontResponse {
+            sequence_number: 0,
+            info: self.info.clone(),
+            char_infos: self.char_infos.clone(),
+        }
+    }
+}
@@ -0,0 +1,289 @@
+/// PCF(Portable Compiled Format)の読み込み
+/// gzipで圧縮されたものはその場で展開する
+use std::io::Read;
+
+use flate2::read::GzDecoder;
+
+use crate::{Error, Result};
+use crate::atom::AtomTable;
+use crate::font::{Font, Glyph, PropertyValue};
+use crate::request::query_font::CharInfo;
+
+mod test;
+
+const PCF_PROPERTIES: u32 = 1 << 0;
+const PCF_ACCELERATORS: u32 = 1 << 1;
+const PCF_METRICS: u32 = 1 << 2;
+const PCF_BITMAPS: u32 = 1 << 3;
+const PCF_BDF_ENCODINGS: u32 = 1 << 5;
+const PCF_BDF_ACCELERATORS: u32 = 1 << 8;
+
+const PCF_COMPRESSED_METRICS: u32 = 0x100;
+const PCF_FORMAT_MASK: u32 = 0xffffff00;
+const PCF_DEFAULT_FORMAT: u32 = 0;
+const PCF_ACCEL_W_INKBOUNDS: u32 = 0x100;
+
+/// BDF_ENCODINGSで文字が無いことを表す
+const NO_SUCH_CHAR: u16 = 0xffff;
+
+/// tableごとのformatで、byte順などを決める
+#[derive(Clone, Copy, Debug)]
+struct Format(u32);
+
+impl Format {
+    fn msb_byte_first(self) -> bool {
+        self.0 & 4 != 0
+    }
+
+    fn msb_bit_first(self) -> bool {
+        self.0 & 8 != 0
+    }
+
+    /// 各行の長さの境界(byte)
+    fn glyph_pad(self) -> usize {
+        1 << (self.0 & 3)
+    }
+
+    /// byteを入れ替える単位(byte)
+    fn scan_unit(self) -> usize {
+        1 << ((self.0 >> 4) & 3)
+    }
+}
+
+/// tableの中を先頭から読む
+struct Table<'a> {
+    data: &'a [u8],
+    position: usize,
+    format: Format,
+}
+
+impl<'a> Table<'a> {
+    /// 先頭のformatは常にLSBFirst
+    fn new(data: &'a [u8]) -> Result<Self> {
+        let mut table = Table { data, position: 0, format: Format(0) };
+        table.format = Format(table.u32()?);
+        Ok(table)
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let bytes = self.data.get(self.position..self.position + len).ok_or(Error::InvalidValue("PCF"))?;
+        self.position += len;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        let mut bytes = [0; 2];
+        bytes.copy_from_slice(self.bytes(2)?);
+        Ok(if self.format.msb_byte_first() { u16::from_be_bytes(bytes) } else { u16::from_le_bytes(bytes) })
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.bytes(4)?);
+        Ok(if self.format.msb_byte_first() { u32::from_be_bytes(bytes) } else { u32::from_le_bytes(bytes) })
+    }
+
+    fn i16(&mut self) -> Result<i16> {
+        self.u16().map(|value| value as i16)
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        self.u32().map(|value| value as i32)
+    }
+
+    fn count(&mut self) -> Result<usize> {
+        let count = self.i32()?;
+        if count < 0 {
+            return Err(Error::InvalidValue("PCF"));
+        }
+        Ok(count as usize)
+    }
+
+    fn metric(&mut self, compressed: bool) -> Result<CharInfo> {
+        if compressed {
+            let mut value = || self.u8().map(|value| value as i16 - 0x80);
+            return Ok(CharInfo {
+                left_side_bearing: value()?,
+                right_side_bearing: value()?,
+                character_width: value()?,
+                ascent: value()?,
+                descent: value()?,
+                attributes: 0,
+            });
+        }
+        Ok(CharInfo {
+            left_side_bearing: self.i16()?,
+            right_side_bearing: self.i16()?,
+            character_width: self.i16()?,
+            ascent: self.i16()?,
+            descent: self.i16()?,
+            attributes: self.u16()?,
+        })
+    }
+}
+
+/// table of contentsから指定の種類のtableを探す
+fn find_table(data: &[u8], kind: u32) -> Result<Option<Table<'_>>> {
+    let mut toc = Table { data, position: 4, format: Format(0) };
+    let count = toc.count()?;
+    for _ in 0..count {
+        let (table_kind, _format, size, offset) = (toc.u32()?, toc.u32()?, toc.u32()? as usize, toc.u32()? as usize);
+        if table_kind == kind {
+            //bdftopcfはaccelerator tableの長さを実際より長く書くので、fileの終わりで切る
+            let data = data.get(offset..data.len().min(offset + size)).ok_or(Error::InvalidValue("PCF"))?;
+            return Table::new(data).map(Some);
+        }
+    }
+    Ok(None)
+}
+
+fn read_properties(mut table: Table) -> Result<Vec<(String, PropertyValue)>> {
+    let count = table.count()?;
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        entries.push((table.count()?, table.u8()? != 0, table.i32()?));
+    }
+    table.bytes((4 - (count & 3)) & 3)?;
+    let strings_len = table.count()?;
+    let strings = table.bytes(strings_len)?;
+    //文字列はISO 8859-1で、NULで終わる
+    let string = |offset: usize| -> Result<String> {
+        let bytes = strings.get(offset..).ok_or(Error::InvalidValue("PCF"))?;
+        Ok(bytes.iter().take_while(|b| **b != 0).map(|b| *b as char).collect())
+    };
+    entries.into_iter().map(|(name, is_string, value)| {
+        let value = if is_string {
+            PropertyValue::String(string(value as usize)?)
+        } else {
+            PropertyValue::Integer(value)
+        };
+        Ok((string(name)?, value))
+    }).collect()
+}
+
+fn read_metrics(mut table: Table) -> Result<Vec<CharInfo>> {
+    let compressed = table.format.0 & PCF_FORMAT_MASK == PCF_COMPRESSED_METRICS;
+    let count = if compressed { table.i16()? as usize } else { table.count()? };
+    (0..count).map(|_| table.metric(compressed)).collect()
+}
+
+/// 各行をbyte境界までのpaddingにし、上位bitを左のpixelにそろえる
+fn read_bitmaps(mut table: Table, metrics: &[CharInfo]) -> Result<Vec<Vec<u8>>> {
+    let format = table.format;
+    let count = table.count()?;
+    if count != metrics.len() {
+        return Err(Error::InvalidValue("PCF"));
+    }
+    let offsets = (0..count).map(|_| table.count()).collect::<Result<Vec<_>>>()?;
+    let mut sizes = [0; 4];
+    for size in sizes.iter_mut() {
+        *size = table.count()?;
+    }
+    let data = table.bytes(sizes[(format.0 & 3) as usize])?;
+    let scan_unit = format.scan_unit();
+    offsets.iter().zip(metrics).map(|(offset, metric)| {
+        let width = (metric.right_side_bearing - metric.left_side_bearing).max(0) as usize;
+        let height = (metric.ascent + metric.descent).max(0) as usize;
+        let row_bytes = width.div_ceil(8);
+        let stride = row_bytes.div_ceil(format.glyph_pad()) * format.glyph_pad();
+        let mut bitmap = Vec::with_capacity(row_bytes * height);
+        for y in 0..height {
+            let start = offset + y * stride;
+            let mut row = data.get(start..start + stride).ok_or(Error::InvalidValue("PCF"))?.to_vec();
+            if !format.msb_bit_first() {
+                row.iter_mut().for_each(|b| *b = b.reverse_bits());
+            }
+            //byte順とbit順が違えば、scan unitの中でbyteを入れ替える
+            if format.msb_byte_first() != format.msb_bit_first() && scan_unit > 1 {
+                row.chunks_mut(scan_unit).for_each(|unit| unit.reverse());
+            }
+            bitmap.extend_from_slice(&row[..row_bytes]);
+        }
+        Ok(bitmap)
+    }).collect()
+}
+
+/// 文字の符号と、glyphの番号の組
+fn read_encodings(mut table: Table) -> Result<(Vec<(u16, usize)>, u16)> {
+    let min_char_or_byte2 = table.i16()? as u16;
+    let max_char_or_byte2 = table.i16()? as u16;
+    let min_byte1 = table.i16()? as u16;
+    let max_byte1 = table.i16()? as u16;
+    let default_char = table.i16()? as u16;
+    let two_byte = min_byte1 != 0 || max_byte1 != 0;
+    let mut encodings = Vec::new();
+    for byte1 in min_byte1..=max_byte1 {
+        for byte2 in min_char_or_byte2..=max_char_or_byte2 {
+            let index = table.u16()?;
+            if index != NO_SUCH_CHAR {
+                let encoding = if two_byte { byte1 << 8 | byte2 } else { byte2 };
+                encodings.push((encoding, index as usize));
+            }
+        }
+    }
+    Ok((encodings, default_char))
+}
+
+/// font_ascentとfont_descent
+fn read_accelerators(mut table: Table) -> Result<(i16, i16)> {
+    if table.format.0 & PCF_FORMAT_MASK != PCF_DEFAULT_FORMAT && table.format.0 & PCF_FORMAT_MASK != PCF_ACCEL_W_INKBOUNDS {
+        return Err(Error::InvalidValue("PCF"));
+    }
+    table.bytes(8)?;
+    Ok((table.i32()? as i16, table.i32()? as i16))
+}
+
+/// ink metricsやglyph名などのtableは使わない
+pub fn read_pcf(stream: &mut std::io::BufReader<impl Read>, atoms: &AtomTable) -> Result<Font> {
+    let mut data = Vec::new();
+    stream.read_to_end(&mut data).map_err(Error::IoError)?;
+    if data.starts_with(&[0x1f, 0x8b]) {
+        let mut decoded = Vec::new();
+        GzDecoder::new(&data[..]).read_to_end(&mut decoded).map_err(Error::IoError)?;
+        data = decoded;
+    }
+    if !data.starts_with(b"\x01fcp") {
+        return Err(Error::InvalidValue("PCF"));
+    }
+    let data = &data[..];
+    let required = |kind: u32| find_table(data, kind)?.ok_or(Error::InvalidValue("PCF"));
+
+    let properties = match find_table(data, PCF_PROPERTIES)? {
+        Some(table) => read_properties(table)?,
+        None => Vec::new(),
+    };
+    let metrics = read_metrics(required(PCF_METRICS)?)?;
+    let bitmaps = read_bitmaps(required(PCF_BITMAPS)?, &metrics)?;
+    let (encodings, default_char) = read_encodings(required(PCF_BDF_ENCODINGS)?)?;
+    //BDF_ACCELERATORSの方が正確なので優先する
+    let accelerators = match find_table(data, PCF_BDF_ACCELERATORS)? {
+        Some(table) => Some(table),
+        None => find_table(data, PCF_ACCELERATORS)?,
+    };
+    let (font_ascent, font_descent) = match accelerators {
+        Some(table) => {
+            let (ascent, descent) = read_accelerators(table)?;
+            (Some(ascent), Some(descent))
+        }
+        None => (None, None),
+    };
+
+    let mut glyphs = Vec::with_capacity(encodings.len());
+    for (encoding, index) in encodings {
+        let metrics = *metrics.get(index).ok_or(Error::InvalidValue("PCF"))?;
+        glyphs.push(Glyph {
+            encoding,
+            metrics,
+            bitmap: bitmaps[index].clone(),
+        });
+    }
+    let name = properties.iter().find_map(|(key, value)| match value {
+        PropertyValue::String(value) if key == "FONT" => Some(value.clone()),
+        _ => None,
+    }).unwrap_or_default();
+    Ok(Font::new(atoms, name, glyphs, properties, Some(default_char), font_ascent, font_descent))
+}
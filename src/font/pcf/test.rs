@@ -0,0 +1,102 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+use std::io::BufReader;
+
+use crate::Error;
+use crate::atom::AtomTable;
+use crate::font::bdf::read_bdf;
+use crate::font::pcf::read_pcf;
+use crate::request::query_font::{CharInfo, DrawDirection};
+
+//fixturesはX.Orgのbdftopcfで作られたもので、fontconfigのtestから取った
+//4x6.bdfはmisc-fixedの元のBDF
+
+#[test]
+fn read_pcf_test() {
+    //圧縮したmetricsで、LSBFirstのbyte順とbit順、4byteのglyph pad
+    //最後のBDF_ACCELERATORSはtable of contentsの長さがfileの終わりを越えている
+    let input = include_bytes!("fixtures/4x6.pcf");
+    let atoms = AtomTable::new();
+    let font = read_pcf(&mut BufReader::new(&input[..]), &atoms).unwrap();
+    assert_eq!(font.name, "-Misc-Fixed-Medium-R-Normal--6-60-75-75-C-40-ISO10646-1");
+    let info = &font.info;
+    assert_eq!((info.min_byte1, info.max_byte1, info.min_char_or_byte2, info.max_char_or_byte2), (0, 38, 0, 255));
+    assert_eq!((info.font_ascent, info.font_descent), (5, 1));
+    assert_eq!(info.default_char, 0);
+    assert_eq!(info.draw_direction, DrawDirection::LeftToRight);
+    assert!(!info.all_chars_exist);
+    let cell = CharInfo { left_side_bearing: 0, right_side_bearing: 4, character_width: 4, ascent: 5, descent: 1, attributes: 0 };
+    assert_eq!((info.min_bounds, info.max_bounds), (cell, cell));
+    assert_eq!(font.char_infos.len(), 39 * 256);
+    let (metrics, bitmap) = font.glyph(0, b'A').unwrap();
+    assert_eq!(metrics, cell);
+    assert_eq!(bitmap, &[0x40, 0xa0, 0xe0, 0xa0, 0xa0, 0x00][..]);
+    //U+20AC EURO SIGN
+    let (_, bitmap) = font.glyph(0x20, 0xac).unwrap();
+    assert_eq!(bitmap, &[0x20, 0x40, 0xe0, 0x40, 0x20, 0x00][..]);
+
+    let property = |name: &str| info.properties.iter().find(|p| p.name == atoms.get(name).unwrap()).unwrap().value;
+    assert_eq!(atoms.name(property("FONT")).unwrap(), font.name);
+    assert_eq!(atoms.name(property("FAMILY_NAME")).unwrap(), "Fixed");
+    assert_eq!(atoms.name(property("CHARSET_REGISTRY")).unwrap(), "ISO10646");
+    assert_eq!(atoms.name(property("ADD_STYLE_NAME")).unwrap(), "");
+    assert_eq!(property("PIXEL_SIZE"), 6);
+    assert_eq!(info.properties.len(), 24);
+
+    //元のBDFと同じglyphになる
+    let bdf = read_bdf(&mut BufReader::new(&include_bytes!("fixtures/4x6.bdf")[..]), &atoms).unwrap();
+    for byte1 in 0..=38 {
+        for byte2 in 0..=255 {
+            assert_eq!(font.glyph(byte1, byte2), bdf.glyph(byte1, byte2));
+        }
+    }
+}
+
+#[test]
+fn read_pcf_gz_test() {
+    let atoms = AtomTable::new();
+    let font = read_pcf(&mut BufReader::new(&include_bytes!("fixtures/4x6.pcf")[..]), &atoms).unwrap();
+    let gz = read_pcf(&mut BufReader::new(&include_bytes!("fixtures/4x6.pcf.gz")[..]), &atoms).unwrap();
+    assert_eq!(gz.name, font.name);
+    assert_eq!(gz.info, font.info);
+    assert_eq!(gz.char_infos, font.char_infos);
+    assert_eq!(gz.bitmaps, font.bitmaps);
+}
+
+#[test]
+fn read_pcf_single_byte_test() {
+    let input = include_bytes!("fixtures/8x16.pcf");
+    let atoms = AtomTable::new();
+    let font = read_pcf(&mut BufReader::new(&input[..]), &atoms).unwrap();
+    assert_eq!(font.name, "-Sony-Fixed-Medium-R-Normal--16-120-100-100-C-80-ISO8859-1");
+    let info = &font.info;
+    assert_eq!((info.min_byte1, info.max_byte1, info.min_char_or_byte2, info.max_char_or_byte2), (0, 0, 1, 255));
+    assert_eq!((info.font_ascent, info.font_descent), (14, 2));
+    assert_eq!(info.default_char, 0x20);
+    assert_eq!(font.char_infos.len(), 255);
+    let (_, bitmap) = font.glyph(0, b'A').unwrap();
+    assert_eq!(bitmap, &[0x00, 0x10, 0x28, 0x28, 0x28, 0x44, 0x44, 0x44, 0x44, 0x7c, 0x82, 0x82, 0x82, 0xc6, 0x00, 0x00][..]);
+    //é
+    let (_, bitmap) = font.glyph(0, 0xe9).unwrap();
+    assert_eq!(bitmap, &[0x00, 0x18, 0x10, 0x20, 0x00, 0x38, 0x44, 0x82, 0xfe, 0x80, 0x80, 0x82, 0x42, 0x3c, 0x00, 0x00][..]);
+    //範囲外の文字はdefault_charの空白
+    let (_, bitmap) = font.glyph(0, 0).unwrap();
+    assert_eq!(bitmap, &[0; 16][..]);
+    let property = |name: &str| info.properties.iter().find(|p| p.name == atoms.get(name).unwrap()).unwrap().value;
+    assert_eq!(atoms.name(property("CHARSET_REGISTRY")).unwrap(), "ISO8859");
+    assert_eq!(atoms.name(property("COPYRIGHT")).unwrap(), "Copyright (c) 1987, 1988 Sony Corp.");
+}
+
+#[test]
+fn read_pcf_error_test() {
+    let input = b"STARTFONT 2.1\n";
+    assert!(matches!(read_pcf(&mut BufReader::new(&input[..]), &AtomTable::new()), Err(Error::InvalidValue(_))));
+    //table of contentsが途中で切れている
+    let input = include_bytes!("fixtures/8x16.pcf");
+    assert!(matches!(read_pcf(&mut BufReader::new(&input[..40]), &AtomTable::new()), Err(Error::InvalidValue(_))));
+    //tableの途中で切れている
+    assert!(matches!(read_pcf(&mut BufReader::new(&input[..1000]), &AtomTable::new()), Err(Error::InvalidValue(_))));
+    let input = [0x1f, 0x8b, 0, 0];
+    assert!(matches!(read_pcf(&mut BufReader::new(&input[..]), &AtomTable::new()), Err(Error::IoError(_))));
+}
@@ -0,0 +1,43 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+use crate::atom::AtomTable;
+use crate::font::{Font, Glyph, PropertyValue};
+use crate::request::query_font::{CharInfo, DrawDirection, FontProp};
+
+#[test]
+fn glyph_test() {
+    let metrics = CharInfo { left_side_bearing: 0, right_side_bearing: 2, character_width: 2, ascent: 2, descent: 0, attributes: 0 };
+    let glyphs = vec![
+        Glyph { encoding: 0x30, metrics, bitmap: vec![0x40, 0x80] },
+        Glyph { encoding: 0x32, metrics, bitmap: vec![0xc0, 0xc0] },
+    ];
+    let font = Font::new(&AtomTable::new(), String::new(), glyphs, vec![("DEFAULT_CHAR".to_string(), PropertyValue::Integer(0x32))], None, None, None);
+    assert_eq!(font.glyph(0, 0x30), Some((metrics, &[0x40, 0x80][..])));
+    //存在しない文字と範囲外の文字はdefault_char
+    assert_eq!(font.glyph(0, 0x31), Some((metrics, &[0xc0, 0xc0][..])));
+    assert_eq!(font.glyph(0, 0x7f), Some((metrics, &[0xc0, 0xc0][..])));
+    assert_eq!(font.glyph(1, 0x30), Some((metrics, &[0xc0, 0xc0][..])));
+    assert_eq!((font.info.font_ascent, font.info.font_descent), (2, 0));
+    assert_eq!(font.info.properties.len(), 1);
+
+    let response = font.query_font();
+    assert_eq!(response.info, font.info);
+    assert_eq!(response.char_infos, vec![metrics, CharInfo::default(), metrics]);
+
+    //char_glyphはdefault_charを使わない
+    assert_eq!(font.char_glyph(0x30), font.glyph(0, 0x30));
+    assert_eq!(font.char_glyph(0x31), None);
+    assert_eq!(font.char_glyph(0x130), None);
+
+    let font = Font::new(&AtomTable::new(), String::new(), vec![Glyph { encoding: 0x30, metrics, bitmap: vec![] }], vec![], Some(0x31), None, None);
+    assert_eq!(font.glyph(0, 0x31), None);
+    assert!(font.info.all_chars_exist);
+}
+
+#[test]
+fn draw_direction_test() {
+    let metrics = CharInfo { left_side_bearing: -2, right_side_bearing: 0, character_width: -2, ascent: 2, descent: 0, attributes: 0 };
+    let font = Font::new(&AtomTable::new(), String::new(), vec![Glyph { encoding: 0x30, metrics, bitmap: vec![] }], vec![], None, None, None);
+    assert_eq!(font.info.draw_direction, DrawDirection::RightToLeft);
+}
@@ -0,0 +1,788 @@
+/// pointerとkeyboardのactive grab、passive grabと、Synchronousモードでの凍結
+/// device eventはeventのfieldに本来の送り先(pointerの下やfocusから決めたwindow)を入れて渡し、
+/// grabや伝播の規則で送り先のwindowとclientを決める
+use std::collections::{HashSet, VecDeque};
+
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::event::{Event, KeyButMask, MotionNotifyDetail};
+use crate::request::allow_events::{AllowEventsMode, AllowEventsRequest};
+use crate::request::change_active_pointer_grab::ChangeActivePointerGrabRequest;
+use crate::request::grab_button::{GrabButtonRequest, GrabModifiers};
+use crate::request::grab_key::GrabKeyRequest;
+use crate::request::grab_keyboard::{GrabKeyboardRequest, GrabKeyboardResponse};
+use crate::request::grab_pointer::{GrabMode, GrabPointerRequest, GrabPointerResponse, GrabStatus};
+use crate::request::ungrab_button::UngrabButtonRequest;
+use crate::request::ungrab_key::UngrabKeyRequest;
+use crate::request::ungrab_keyboard::UngrabKeyboardRequest;
+use crate::request::ungrab_pointer::UngrabPointerRequest;
+use crate::resources::ClientId;
+use crate::setup;
+use crate::time;
+use crate::window::WindowTree;
+
+mod test;
+
+/// grabで報告できるpointerのevent
+const POINTER_EVENTS: [setup::Event; 13] = [
+    setup::Event::ButtonPress,
+    setup::Event::ButtonRelease,
+    setup::Event::EnterWindow,
+    setup::Event::LeaveWindow,
+    setup::Event::PointerMotion,
+    setup::Event::PointerMotionHint,
+    setup::Event::Button1Motion,
+    setup::Event::Button2Motion,
+    setup::Event::Button3Motion,
+    setup::Event::Button4Motion,
+    setup::Event::Button5Motion,
+    setup::Event::ButtonMotion,
+    setup::Event::KeymapState,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Device {
+    Pointer,
+    Keyboard,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActiveGrab {
+    pub client: ClientId,
+    pub window: u32,
+    pub owner_events: bool,
+    /// keyboardのgrabではKeyPressとKeyReleaseを常に報告するので使わない
+    pub event_mask: HashSet<setup::Event>,
+    pub pointer_mode: GrabMode,
+    pub keyboard_mode: GrabMode,
+    pub confine_to: Option<u32>,
+    pub cursor: Option<u32>,
+    pub time: u32,
+    /// passive grabか暗黙のgrabで始まったときの、押されたbuttonかkey
+    /// buttonなら全てのbuttonが離されたとき、keyならそのkeyが離されたときに終わる
+    pub detail: Option<u8>,
+}
+
+/// GrabButtonとGrabKeyで登録したgrab
+/// detailがNoneならAnyButtonかAnyKey
+#[derive(Clone, Debug, PartialEq)]
+pub struct PassiveGrab {
+    pub client: ClientId,
+    pub window: u32,
+    pub detail: Option<u8>,
+    pub modifiers: GrabModifiers,
+    pub owner_events: bool,
+    pub event_mask: HashSet<setup::Event>,
+    pub pointer_mode: GrabMode,
+    pub keyboard_mode: GrabMode,
+    pub confine_to: Option<u32>,
+    pub cursor: Option<u32>,
+}
+
+impl PassiveGrab {
+    fn matches(&self, detail: u8, state: &HashSet<KeyButMask>) -> bool {
+        self.detail.map(|d| d == detail).unwrap_or(true) && match &self.modifiers {
+            GrabModifiers::AnyModifier => true,
+            GrabModifiers::Modifiers(modifiers) => *modifiers == modifiers_of(state),
+        }
+    }
+
+    /// 同じwindowで、detailとmodifiersの組み合わせが重なるならtrue
+    fn overlaps(&self, other: &PassiveGrab) -> bool {
+        self.window == other.window
+            && (self.detail.is_none() || other.detail.is_none() || self.detail == other.detail)
+            && match (&self.modifiers, &other.modifiers) {
+                (GrabModifiers::Modifiers(a), GrabModifiers::Modifiers(b)) => a == b,
+                _ => true,
+            }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct DeviceState {
+    grab: Option<ActiveGrab>,
+    last_grab_time: u32,
+    /// 凍結させたclientと、原因になったgrabのdevice
+    frozen: Option<(ClientId, Device)>,
+    /// SyncPointerなどのあとで、次のpressかreleaseを報告したら再び凍結する
+    sync_next: bool,
+    /// 凍結の直前にgrabを通して報告したevent
+    /// ReplayPointerとReplayKeyboardでgrabを外して処理し直す
+    replay: Option<Event>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct GrabTable {
+    pointer: DeviceState,
+    keyboard: DeviceState,
+    button_grabs: Vec<PassiveGrab>,
+    key_grabs: Vec<PassiveGrab>,
+    /// 凍結中のdeviceのevent
+    queue: VecDeque<Event>,
+    /// PointerMotionHintでMotionNotifyを一度送ったclientとwindow
+    motion_hints: HashSet<(ClientId, u32)>,
+    /// 最後のMotionNotifyのwindow。変わればmotion_hintsを消す
+    motion_window: Option<u32>,
+    events: Vec<(ClientId, Event)>,
+}
+
+impl GrabTable {
+    pub fn new() -> Self {
+        GrabTable::default()
+    }
+
+    /// 送り先のclientとeventを取り出す
+    pub fn take_events(&mut self) -> Vec<(ClientId, Event)> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// QueryPointerやGetMotionEventsのあとに呼び、clientに次のMotionNotifyを送るようにする
+    pub fn reset_motion_hints(&mut self, client: ClientId) {
+        self.motion_hints.retain(|(c, _)| *c != client);
+    }
+
+    pub fn pointer_grab(&self) -> Option<&ActiveGrab> {
+        self.pointer.grab.as_ref()
+    }
+
+    pub fn keyboard_grab(&self) -> Option<&ActiveGrab> {
+        self.keyboard.grab.as_ref()
+    }
+
+    pub fn is_frozen(&self, device: Device) -> bool {
+        self.state(device).frozen.is_some()
+    }
+
+    /// pointerを閉じ込めるwindow
+    pub fn confine_to(&self) -> Option<u32> {
+        self.pointer.grab.as_ref().and_then(|grab| grab.confine_to)
+    }
+
+    /// grab中に表示するcursor
+    pub fn cursor(&self) -> Option<u32> {
+        self.pointer.grab.as_ref().and_then(|grab| grab.cursor)
+    }
+
+    fn state(&self, device: Device) -> &DeviceState {
+        match device {
+            Device::Pointer => &self.pointer,
+            Device::Keyboard => &self.keyboard,
+        }
+    }
+
+    fn state_mut(&mut self, device: Device) -> &mut DeviceState {
+        match device {
+            Device::Pointer => &mut self.pointer,
+            Device::Keyboard => &mut self.keyboard,
+        }
+    }
+
+    /// KeyPress、KeyRelease、ButtonPress、ButtonRelease、MotionNotifyを処理する
+    /// それ以外のeventは無視する
+    pub fn process(&mut self, windows: &WindowTree, event: Event) {
+        self.handle(windows, event, None);
+    }
+
+    /// replay_belowがあれば、そのwindowとその祖先のpassive grabは起動しない
+    fn handle(&mut self, windows: &WindowTree, event: Event, replay_below: Option<u32>) {
+        let device = match device_of(&event) {
+            Some(device) => device,
+            None => return,
+        };
+        if self.state(device).frozen.is_some() {
+            self.queue.push_back(event);
+            return;
+        }
+        if windows.get(source_of(&event)).is_none() {
+            return;
+        }
+        match &event {
+            Event::ButtonPress(_) | Event::ButtonRelease(_) => self.motion_hints.clear(),
+            Event::MotionNotify(motion) if self.motion_window != Some(motion.event) => {
+                self.motion_hints.clear();
+                self.motion_window = Some(motion.event);
+            }
+            _ => {}
+        }
+        let mut activated = false;
+        if self.state(device).grab.is_none() {
+            if let Some(grab) = self.find_passive(windows, &event, replay_below) {
+                let state = self.state_mut(device);
+                state.last_grab_time = time_of(&event);
+                state.grab = Some(ActiveGrab {
+                    client: grab.client,
+                    window: grab.window,
+                    owner_events: grab.owner_events,
+                    event_mask: grab.event_mask,
+                    pointer_mode: grab.pointer_mode,
+                    keyboard_mode: grab.keyboard_mode,
+                    confine_to: grab.confine_to,
+                    cursor: grab.cursor,
+                    time: time_of(&event),
+                    detail: Some(detail_of(&event)),
+                });
+                activated = true;
+            }
+        }
+        match self.state(device).grab.clone() {
+            Some(grab) => {
+                let reported = self.deliver_grabbed(windows, &event, &grab, device);
+                let released = self.release_if_done(windows, &event, device);
+                if released {
+                    return;
+                }
+                if activated {
+                    self.freeze_for(&grab, device, &event);
+                } else if reported && is_press_or_release(&event) && self.state(device).sync_next {
+                    self.state_mut(device).sync_next = false;
+                    self.state_mut(device).frozen = Some((grab.client, device));
+                    self.state_mut(device).replay = Some(event);
+                }
+            }
+            None => {
+                let targets = deliver_targets(windows, &event, None);
+                for (client, window) in targets.iter() {
+                    self.report(windows, *client, *window, &event, None);
+                }
+                //ButtonPressを報告したclientには暗黙のpointer grabを始める
+                if let (Event::ButtonPress(press), [(client, window)]) = (&event, &targets[..]) {
+                    let masks = windows.get(*window).and_then(|w| w.event_masks.get(client)).cloned().unwrap_or_default();
+                    self.pointer.last_grab_time = press.time;
+                    self.pointer.grab = Some(ActiveGrab {
+                        client: *client,
+                        window: *window,
+                        owner_events: masks.contains(&setup::Event::OwnerGrabButton),
+                        event_mask: masks.into_iter().filter(|mask| POINTER_EVENTS.contains(mask)).collect(),
+                        pointer_mode: GrabMode::Asynchronous,
+                        keyboard_mode: GrabMode::Asynchronous,
+                        confine_to: None,
+                        cursor: None,
+                        time: press.time,
+                        detail: Some(press.detail),
+                    });
+                }
+            }
+        }
+    }
+
+    /// passive grabで始まったgrabを起動したeventを報告したあとの凍結
+    fn freeze_for(&mut self, grab: &ActiveGrab, device: Device, event: &Event) {
+        if grab.pointer_mode == GrabMode::Synchronous {
+            self.pointer.frozen = Some((grab.client, device));
+        }
+        if grab.keyboard_mode == GrabMode::Synchronous {
+            self.keyboard.frozen = Some((grab.client, device));
+        }
+        self.state_mut(device).replay = Some(event.clone());
+    }
+
+    /// 根から順に、eventのwindowまでの経路でpassive grabを探す
+    fn find_passive(&self, windows: &WindowTree, event: &Event, replay_below: Option<u32>) -> Option<PassiveGrab> {
+        let grabs = match event {
+            Event::ButtonPress(_) => &self.button_grabs,
+            Event::KeyPress(_) => &self.key_grabs,
+            _ => return None,
+        };
+        let mut path = windows.ancestors(source_of(event));
+        path.reverse();
+        if let Some(below) = replay_below {
+            match path.iter().position(|window| *window == below) {
+                Some(i) => path.drain(..=i),
+                None => path.drain(..),
+            };
+        }
+        let state = state_of(event);
+        path.iter().find_map(|window| grabs.iter().find(|grab| {
+            grab.window == *window
+                && grab.matches(detail_of(event), state)
+                && grab.confine_to.map(|confine_to| windows.is_viewable(confine_to)).unwrap_or(true)
+        })).cloned()
+    }
+
+    /// grab中のeventを報告し、grabしているclientに報告したらtrue
+    fn deliver_grabbed(&mut self, windows: &WindowTree, event: &Event, grab: &ActiveGrab, device: Device) -> bool {
+        if grab.owner_events {
+            let targets = deliver_targets(windows, event, Some(grab.client));
+            if let Some((client, window)) = targets.first() {
+                self.report(windows, *client, *window, event, None);
+                return true;
+            }
+        }
+        if device == Device::Keyboard || selects(&grab.event_mask, event) {
+            self.report(windows, grab.client, grab.window, event, Some(&grab.event_mask));
+            return true;
+        }
+        false
+    }
+
+    /// eventをwindowから見たものにしてclientに送る
+    /// maskがPointerMotionHintを含めば、MotionNotifyはwindowごとに一度だけHintとして送る
+    /// grab_maskがNoneならclientがwindowで選択しているmaskを使う
+    fn report(&mut self, windows: &WindowTree, client: ClientId, window: u32, event: &Event, grab_mask: Option<&HashSet<setup::Event>>) {
+        let mut event = retarget(windows, event, window);
+        if let Event::MotionNotify(motion) = &mut event {
+            let hint = match grab_mask {
+                Some(mask) => mask.contains(&setup::Event::PointerMotionHint),
+                None => windows.get(window)
+                    .and_then(|w| w.event_masks.get(&client))
+                    .map(|mask| mask.contains(&setup::Event::PointerMotionHint))
+                    .unwrap_or(false),
+            };
+            if hint {
+                if !self.motion_hints.insert((client, window)) {
+                    return;
+                }
+                motion.detail = MotionNotifyDetail::Hint;
+            }
+        }
+        self.events.push((client, event));
+    }
+
+    /// passive grabか暗黙のgrabで始まったgrabを、buttonかkeyが離されたら終える
+    fn release_if_done(&mut self, windows: &WindowTree, event: &Event, device: Device) -> bool {
+        let detail = match self.state(device).grab.as_ref().and_then(|grab| grab.detail) {
+            Some(detail) => detail,
+            None => return false,
+        };
+        let done = match event {
+            Event::ButtonRelease(release) => release.state.iter().filter(|mask| is_button(mask) && **mask != KeyButMask::button(release.detail)).count() == 0,
+            Event::KeyRelease(release) => release.detail == detail,
+            _ => false,
+        };
+        if done {
+            self.release(windows, device);
+        }
+        done
+    }
+
+    /// grabを終え、そのgrabによる凍結を解いて溜まっていたeventを処理する
+    fn release(&mut self, windows: &WindowTree, device: Device) {
+        if self.release_grab(device) {
+            self.replay_queue(windows);
+        }
+    }
+
+    /// grabを終え、そのgrabによる凍結を解いたらtrue
+    fn release_grab(&mut self, device: Device) -> bool {
+        let state = self.state_mut(device);
+        state.grab = None;
+        state.sync_next = false;
+        state.replay = None;
+        let mut thawed = false;
+        for state in [&mut self.pointer, &mut self.keyboard] {
+            if state.frozen.map(|(_, source)| source == device).unwrap_or(false) {
+                state.frozen = None;
+                thawed = true;
+            }
+        }
+        thawed
+    }
+
+    /// 凍結が解けたdeviceのeventを順に処理する
+    fn replay_queue(&mut self, windows: &WindowTree) {
+        for event in std::mem::take(&mut self.queue) {
+            self.handle(windows, event, None);
+        }
+    }
+
+    /// viewableはgrab_windowとconfine_toが表示されていればtrue
+    fn grab_status(&self, client: ClientId, device: Device, viewable: bool, time: u32, now: u32) -> GrabStatus {
+        let state = self.state(device);
+        if state.grab.as_ref().map(|grab| grab.client != client).unwrap_or(false) {
+            return GrabStatus::AlreadyGrabbed;
+        }
+        if time::is_later(time, now) || time::is_later(state.last_grab_time, time) {
+            return GrabStatus::InvalidTime;
+        }
+        if !viewable {
+            return GrabStatus::NotViewable;
+        }
+        let frozen_by_other = [&self.pointer, &self.keyboard].iter().any(|state| state.frozen.map(|(c, _)| c != client).unwrap_or(false));
+        if frozen_by_other {
+            return GrabStatus::Frozen;
+        }
+        GrabStatus::Success
+    }
+
+    fn activate(&mut self, windows: &WindowTree, device: Device, grab: ActiveGrab) {
+        //同じclientのgrabを置き換えるときは前のgrabによる凍結を解く
+        if self.state(device).grab.is_some() {
+            self.release(windows, device);
+        }
+        if grab.pointer_mode == GrabMode::Synchronous {
+            self.pointer.frozen = Some((grab.client, device));
+        }
+        if grab.keyboard_mode == GrabMode::Synchronous {
+            self.keyboard.frozen = Some((grab.client, device));
+        }
+        let state = self.state_mut(device);
+        state.last_grab_time = grab.time;
+        state.grab = Some(grab);
+    }
+
+    /// nowはserverの現在時刻
+    pub fn grab_pointer(&mut self, client: ClientId, windows: &WindowTree, request: &GrabPointerRequest, now: u32) -> Result<GrabPointerResponse> {
+        windows.lookup(request.grab_window)?;
+        if let Some(confine_to) = request.confine_to {
+            windows.lookup(confine_to)?;
+        }
+        let time = time::resolve(request.time, now);
+        let viewable = windows.is_viewable(request.grab_window) && request.confine_to.map(|window| windows.is_viewable(window)).unwrap_or(true);
+        let status = self.grab_status(client, Device::Pointer, viewable, time, now);
+        if status == GrabStatus::Success {
+            self.activate(windows, Device::Pointer, ActiveGrab {
+                client,
+                window: request.grab_window,
+                owner_events: request.owner_events,
+                event_mask: request.event_mask.clone(),
+                pointer_mode: request.pointer_mode.clone(),
+                keyboard_mode: request.keyboard_mode.clone(),
+                confine_to: request.confine_to,
+                cursor: request.cursor,
+                time,
+                detail: None,
+            });
+        }
+        Ok(GrabPointerResponse { status, sequence_number: 0 })
+    }
+
+    pub fn ungrab_pointer(&mut self, client: ClientId, windows: &WindowTree, request: &UngrabPointerRequest, now: u32) {
+        self.ungrab(client, windows, Device::Pointer, request.time, now);
+    }
+
+    fn ungrab(&mut self, client: ClientId, windows: &WindowTree, device: Device, time: u32, now: u32) {
+        let time = time::resolve(time, now);
+        let state = self.state(device);
+        if state.grab.as_ref().map(|grab| grab.client == client).unwrap_or(false) && !time::is_later(time, now) && !time::is_later(state.last_grab_time, time) {
+            self.release(windows, device);
+        }
+    }
+
+    pub fn change_active_pointer_grab(&mut self, client: ClientId, request: &ChangeActivePointerGrabRequest, now: u32) {
+        let time = time::resolve(request.time, now);
+        if time::is_later(time, now) || time::is_later(self.pointer.last_grab_time, time) {
+            return;
+        }
+        if let Some(grab) = self.pointer.grab.as_mut().filter(|grab| grab.client == client) {
+            grab.cursor = request.cursor;
+            grab.event_mask = request.event_mask.clone();
+        }
+    }
+
+    pub fn grab_keyboard(&mut self, client: ClientId, windows: &WindowTree, request: &GrabKeyboardRequest, now: u32) -> Result<GrabKeyboardResponse> {
+        windows.lookup(request.grab_window)?;
+        let time = time::resolve(request.time, now);
+        let status = self.grab_status(client, Device::Keyboard, windows.is_viewable(request.grab_window), time, now);
+        if status == GrabStatus::Success {
+            self.activate(windows, Device::Keyboard, ActiveGrab {
+                client,
+                window: request.grab_window,
+                owner_events: request.owner_events,
+                event_mask: HashSet::new(),
+                pointer_mode: request.pointer_mode.clone(),
+                keyboard_mode: request.keyboard_mode.clone(),
+                confine_to: None,
+                cursor: None,
+                time,
+                detail: None,
+            });
+        }
+        Ok(GrabKeyboardResponse { status, sequence_number: 0 })
+    }
+
+    pub fn ungrab_keyboard(&mut self, client: ClientId, windows: &WindowTree, request: &UngrabKeyboardRequest, now: u32) {
+        self.ungrab(client, windows, Device::Keyboard, request.time, now);
+    }
+
+    /// 他のclientが重なるgrabを登録していればBadAccess
+    /// 同じclientの重なるgrabは置き換える
+    pub fn grab_button(&mut self, client: ClientId, windows: &WindowTree, request: &GrabButtonRequest) -> Result<()> {
+        windows.lookup(request.grab_window)?;
+        if let Some(confine_to) = request.confine_to {
+            windows.lookup(confine_to)?;
+        }
+        let grab = PassiveGrab {
+            client,
+            window: request.grab_window,
+            detail: request.button,
+            modifiers: request.modifiers.clone(),
+            owner_events: request.owner_events,
+            event_mask: request.event_mask.clone(),
+            pointer_mode: request.pointer_mode.clone(),
+            keyboard_mode: request.keyboard_mode.clone(),
+            confine_to: request.confine_to,
+            cursor: request.cursor,
+        };
+        add_passive(&mut self.button_grabs, grab)
+    }
+
+    pub fn ungrab_button(&mut self, client: ClientId, windows: &WindowTree, request: &UngrabButtonRequest) -> Result<()> {
+        windows.lookup(request.grab_window)?;
+        remove_passive(&mut self.button_grabs, client, request.grab_window, request.button, &request.modifiers);
+        Ok(())
+    }
+
+    pub fn grab_key(&mut self, client: ClientId, windows: &WindowTree, request: &GrabKeyRequest) -> Result<()> {
+        windows.lookup(request.grab_window)?;
+        let grab = PassiveGrab {
+            client,
+            window: request.grab_window,
+            detail: request.key,
+            modifiers: request.modifiers.clone(),
+            owner_events: request.owner_events,
+            event_mask: HashSet::new(),
+            pointer_mode: request.pointer_mode.clone(),
+            keyboard_mode: request.keyboard_mode.clone(),
+            confine_to: None,
+            cursor: None,
+        };
+        add_passive(&mut self.key_grabs, grab)
+    }
+
+    pub fn ungrab_key(&mut self, client: ClientId, windows: &WindowTree, request: &UngrabKeyRequest) -> Result<()> {
+        windows.lookup(request.grab_window)?;
+        remove_passive(&mut self.key_grabs, client, request.grab_window, request.key, &request.modifiers);
+        Ok(())
+    }
+
+    /// timeが最後のgrabの時刻より前か、現在時刻より後なら何もしない
+    pub fn allow_events(&mut self, client: ClientId, windows: &WindowTree, request: &AllowEventsRequest, now: u32) {
+        let time = time::resolve(request.time, now);
+        if time::is_later(time, now) {
+            return;
+        }
+        let frozen_by = |state: &DeviceState| state.frozen.map(|(c, _)| c == client).unwrap_or(false);
+        let grabbed_by = |state: &DeviceState| state.grab.as_ref().map(|grab| grab.client == client).unwrap_or(false);
+        let (device, other) = match request.mode {
+            AllowEventsMode::AsyncPointer | AllowEventsMode::SyncPointer | AllowEventsMode::ReplayPointer => (Device::Pointer, None),
+            AllowEventsMode::AsyncKeyboard | AllowEventsMode::SyncKeyboard | AllowEventsMode::ReplayKeyboard => (Device::Keyboard, None),
+            AllowEventsMode::AsyncBoth | AllowEventsMode::SyncBoth => (Device::Pointer, Some(Device::Keyboard)),
+        };
+        if time::is_later(self.state(device).last_grab_time, time) || other.map(|other| time::is_later(self.state(other).last_grab_time, time)).unwrap_or(false) {
+            return;
+        }
+        match request.mode {
+            AllowEventsMode::AsyncPointer | AllowEventsMode::AsyncKeyboard => {
+                if frozen_by(self.state(device)) {
+                    self.state_mut(device).frozen = None;
+                }
+            }
+            AllowEventsMode::SyncPointer | AllowEventsMode::SyncKeyboard => {
+                if frozen_by(self.state(device)) && grabbed_by(self.state(device)) {
+                    let state = self.state_mut(device);
+                    state.frozen = None;
+                    state.sync_next = true;
+                }
+            }
+            AllowEventsMode::ReplayPointer | AllowEventsMode::ReplayKeyboard => {
+                let state = self.state(device);
+                if frozen_by(state) && grabbed_by(state) && state.replay.is_some() {
+                    let replay = self.state_mut(device).replay.take().unwrap();
+                    let window = self.state(device).grab.as_ref().unwrap().window;
+                    //溜まっていたeventより先に処理し直す
+                    self.release_grab(device);
+                    self.state_mut(device).frozen = None;
+                    self.handle(windows, replay, Some(window));
+                }
+            }
+            AllowEventsMode::AsyncBoth | AllowEventsMode::SyncBoth => {
+                if frozen_by(&self.pointer) && frozen_by(&self.keyboard) {
+                    let sync = request.mode == AllowEventsMode::SyncBoth;
+                    for state in [&mut self.pointer, &mut self.keyboard] {
+                        state.frozen = None;
+                        state.sync_next = sync && state.grab.as_ref().map(|grab| grab.client == client).unwrap_or(false);
+                    }
+                }
+            }
+        }
+        self.replay_queue(windows);
+    }
+
+    /// 破棄したwindowのpassive grabを消し、grab_windowかconfine_toが破棄されたgrabを終える
+    pub fn remove_windows(&mut self, windows: &WindowTree, destroyed: &[u32]) {
+        self.button_grabs.retain(|grab| !destroyed.contains(&grab.window) && !grab.confine_to.map(|window| destroyed.contains(&window)).unwrap_or(false));
+        self.key_grabs.retain(|grab| !destroyed.contains(&grab.window));
+        for device in [Device::Pointer, Device::Keyboard] {
+            let uses = self.state(device).grab.as_ref()
+                .map(|grab| destroyed.contains(&grab.window) || grab.confine_to.map(|window| destroyed.contains(&window)).unwrap_or(false))
+                .unwrap_or(false);
+            if uses {
+                self.release(windows, device);
+            }
+        }
+    }
+
+    /// 接続が切れたclientのgrabを消す
+    pub fn remove_client(&mut self, windows: &WindowTree, client: ClientId) {
+        self.button_grabs.retain(|grab| grab.client != client);
+        self.key_grabs.retain(|grab| grab.client != client);
+        for device in [Device::Pointer, Device::Keyboard] {
+            if self.state(device).grab.as_ref().map(|grab| grab.client == client).unwrap_or(false) {
+                self.release(windows, device);
+            }
+        }
+        //grabを持たずに凍結させていた場合
+        let mut thawed = false;
+        for state in [&mut self.pointer, &mut self.keyboard] {
+            if state.frozen.map(|(c, _)| c == client).unwrap_or(false) {
+                state.frozen = None;
+                thawed = true;
+            }
+        }
+        if thawed {
+            self.replay_queue(windows);
+        }
+    }
+}
+
+fn add_passive(grabs: &mut Vec<PassiveGrab>, grab: PassiveGrab) -> Result<()> {
+    if grabs.iter().any(|other| other.client != grab.client && other.overlaps(&grab)) {
+        return Err(Error::ProtocolError(ErrorCode::Access, grab.window));
+    }
+    grabs.retain(|other| !other.overlaps(&grab));
+    grabs.push(grab);
+    Ok(())
+}
+
+/// AnyButton、AnyKey、AnyModifierなら当てはまるものを全て消す
+fn remove_passive(grabs: &mut Vec<PassiveGrab>, client: ClientId, window: u32, detail: Option<u8>, modifiers: &GrabModifiers) {
+    grabs.retain(|grab| {
+        let covered = grab.client == client
+            && grab.window == window
+            && (detail.is_none() || grab.detail == detail)
+            && (*modifiers == GrabModifiers::AnyModifier || grab.modifiers == *modifiers);
+        !covered
+    });
+}
+
+fn device_of(event: &Event) -> Option<Device> {
+    match event {
+        Event::KeyPress(_) | Event::KeyRelease(_) => Some(Device::Keyboard),
+        Event::ButtonPress(_) | Event::ButtonRelease(_) | Event::MotionNotify(_) => Some(Device::Pointer),
+        _ => None,
+    }
+}
+
+fn source_of(event: &Event) -> u32 {
+    match event {
+        Event::KeyPress(e) | Event::KeyRelease(e) | Event::ButtonPress(e) | Event::ButtonRelease(e) => e.event,
+        Event::MotionNotify(e) => e.event,
+        _ => 0,
+    }
+}
+
+fn time_of(event: &Event) -> u32 {
+    match event {
+        Event::KeyPress(e) | Event::KeyRelease(e) | Event::ButtonPress(e) | Event::ButtonRelease(e) => e.time,
+        Event::MotionNotify(e) => e.time,
+        _ => 0,
+    }
+}
+
+fn detail_of(event: &Event) -> u8 {
+    match event {
+        Event::KeyPress(e) | Event::KeyRelease(e) | Event::ButtonPress(e) | Event::ButtonRelease(e) => e.detail,
+        _ => 0,
+    }
+}
+
+fn state_of(event: &Event) -> &HashSet<KeyButMask> {
+    match event {
+        Event::KeyPress(e) | Event::KeyRelease(e) | Event::ButtonPress(e) | Event::ButtonRelease(e) => &e.state,
+        Event::MotionNotify(e) => &e.state,
+        _ => unreachable!(),
+    }
+}
+
+fn is_press_or_release(event: &Event) -> bool {
+    matches!(event, Event::KeyPress(_) | Event::KeyRelease(_) | Event::ButtonPress(_) | Event::ButtonRelease(_))
+}
+
+fn is_button(mask: &KeyButMask) -> bool {
+    matches!(mask, KeyButMask::Button1 | KeyButMask::Button2 | KeyButMask::Button3 | KeyButMask::Button4 | KeyButMask::Button5)
+}
+
+/// stateのうちmodifierのkeyだけ
+fn modifiers_of(state: &HashSet<KeyButMask>) -> HashSet<KeyButMask> {
+    state.iter().filter(|mask| !is_button(mask)).cloned().collect()
+}
+
+/// maskがeventを選択していればtrue
+/// MotionNotifyは押されているbuttonに応じてButtonMotionなども見る
+pub fn selects(mask: &HashSet<setup::Event>, event: &Event) -> bool {
+    match event {
+        Event::KeyPress(_) => mask.contains(&setup::Event::KeyPress),
+        Event::KeyRelease(_) => mask.contains(&setup::Event::KeyRelease),
+        Event::ButtonPress(_) => mask.contains(&setup::Event::ButtonPress),
+        Event::ButtonRelease(_) => mask.contains(&setup::Event::ButtonRelease),
+        Event::MotionNotify(motion) => {
+            let buttons = [
+                (KeyButMask::Button1, setup::Event::Button1Motion),
+                (KeyButMask::Button2, setup::Event::Button2Motion),
+                (KeyButMask::Button3, setup::Event::Button3Motion),
+                (KeyButMask::Button4, setup::Event::Button4Motion),
+                (KeyButMask::Button5, setup::Event::Button5Motion),
+            ];
+            mask.contains(&setup::Event::PointerMotion)
+                || (mask.contains(&setup::Event::ButtonMotion) && motion.state.iter().any(is_button))
+                || buttons.iter().any(|(button, event)| motion.state.contains(button) && mask.contains(event))
+        }
+        _ => false,
+    }
+}
+
+/// grabが無いときの送り先
+/// eventのwindowから祖先へ、eventを選択しているclientがいるwindowまで伝播する
+/// onlyがあればそのclientだけを見る
+fn deliver_targets(windows: &WindowTree, event: &Event, only: Option<ClientId>) -> Vec<(ClientId, u32)> {
+    for window in windows.ancestors(source_of(event)) {
+        let w = windows.get(window).unwrap();
+        let mut clients = w.event_masks.iter()
+            .filter(|(client, masks)| only.map(|only| only == **client).unwrap_or(true) && selects(masks, event))
+            .map(|(client, _)| (*client, window))
+            .collect::<Vec<_>>();
+        if !clients.is_empty() {
+            clients.sort();
+            return clients;
+        }
+        if selects(&w.attributes.do_not_propagate_mask, event) {
+            break;
+        }
+    }
+    Vec::new()
+}
+
+/// eventのwindow、child、座標をwindowから見たものにする
+fn retarget(windows: &WindowTree, event: &Event, window: u32) -> Event {
+    let source = source_of(event);
+    let path = windows.ancestors(source);
+    let child = path.iter().position(|w| *w == window).filter(|i| *i > 0).map(|i| path[i - 1]);
+    let (x, y) = windows.origin(window).unwrap_or((0, 0));
+    let same_screen = windows.root_of(window) == path.last().cloned();
+    match event {
+        Event::KeyPress(e) | Event::KeyRelease(e) | Event::ButtonPress(e) | Event::ButtonRelease(e) => {
+            let mut e = e.clone();
+            e.event = window;
+            e.child = child;
+            e.event_x = if same_screen { (e.root_x as i32 - x) as i16 } else { 0 };
+            e.event_y = if same_screen { (e.root_y as i32 - y) as i16 } else { 0 };
+            e.same_screen = same_screen;
+            match event {
+                Event::KeyPress(_) => Event::KeyPress(e),
+                Event::KeyRelease(_) => Event::KeyRelease(e),
+                Event::ButtonPress(_) => Event::ButtonPress(e),
+                _ => Event::ButtonRelease(e),
+            }
+        }
+        Event::MotionNotify(e) => {
+            let mut e = e.clone();
+            e.event = window;
+            e.child = child;
+            e.event_x = if same_screen { (e.root_x as i32 - x) as i16 } else { 0 };
+            e.event_y = if same_screen { (e.root_y as i32 - y) as i16 } else { 0 };
+            e.same_screen = same_screen;
+            Event::MotionNotify(e)
+        }
+        other => other.clone(),
+    }
+}
@@ -0,0 +1,277 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+use crate::Error;
+use crate::error::ErrorCode;
+use crate::event::{DeviceEvent, Event, KeyButMask, MotionNotifyDetail, MotionNotifyEvent};
+use crate::request::allow_events::{AllowEventsMode, AllowEventsRequest};
+use crate::request::change_window_attributes::ChangeWindowAttributesRequest;
+use crate::request::create_window::{CreateWindowClass, CreateWindowRequest, CreateWindowValue, CreateWindowValueMaskValue};
+use crate::request::destroy_window::DestroyWindowRequest;
+use crate::request::grab_button::{GrabButtonRequest, GrabModifiers};
+use crate::request::grab_key::GrabKeyRequest;
+use crate::request::grab_keyboard::GrabKeyboardRequest;
+use crate::request::grab_pointer::{GrabMode, GrabPointerRequest, GrabStatus};
+use crate::request::map_window::MapWindowRequest;
+use crate::request::ungrab_button::UngrabButtonRequest;
+use crate::request::ungrab_keyboard::UngrabKeyboardRequest;
+use crate::setup;
+#[cfg(test)]
+use crate::test_util::{create_window, screen, select_input};
+use crate::window::WindowTree;
+use super::*;
+
+#[test]
+fn grab_test() {
+    const A: u32 = 0x00200001;
+    const B: u32 = 0x00200002;
+    const C: u32 = 0x00200003;
+
+
+    /// 根(1)の子のA(10, 10)と、Aの子のB(5, 5)をmapし、Cはmapしない
+    /// client 1がAでButtonPressとButtonReleaseを選択する
+    fn tree() -> WindowTree {
+        fn create(wid: u32, parent: u32, x: i16, y: i16, event_mask: &[setup::Event]) -> CreateWindowRequest {
+            select_input(create_window(wid, parent, x, y, 100, 100), event_mask)
+        }
+        let mut tree = WindowTree::new(&[screen()]);
+        tree.create_window(1, &create(A, 1, 10, 10, &[setup::Event::ButtonPress, setup::Event::ButtonRelease])).unwrap();
+        tree.create_window(1, &create(B, A, 5, 5, &[])).unwrap();
+        tree.create_window(1, &create(C, 1, 200, 200, &[])).unwrap();
+        tree.map_window(1, &MapWindowRequest { window: A }).unwrap();
+        tree.map_window(1, &MapWindowRequest { window: B }).unwrap();
+        tree.take_events();
+        tree
+    }
+
+    /// Bの上の(20, 20)でのevent
+    fn device(detail: u8, time: u32, window: u32, event_x: i16, event_y: i16, child: Option<u32>, state: &[KeyButMask]) -> DeviceEvent {
+        DeviceEvent {
+            detail,
+            sequence_number: 0,
+            time,
+            root: 1,
+            event: window,
+            child,
+            root_x: 20,
+            root_y: 20,
+            event_x,
+            event_y,
+            state: HashSet::from_iter(state.to_vec()),
+            same_screen: true,
+        }
+    }
+
+    fn motion(time: u32, window: u32, event_x: i16, event_y: i16, state: &[KeyButMask]) -> Event {
+        Event::MotionNotify(MotionNotifyEvent {
+            detail: MotionNotifyDetail::Normal,
+            sequence_number: 0,
+            time,
+            root: 1,
+            event: window,
+            child: None,
+            root_x: 20,
+            root_y: 20,
+            event_x,
+            event_y,
+            state: HashSet::from_iter(state.to_vec()),
+            same_screen: true,
+        })
+    }
+
+    fn grab_button(button: Option<u8>, modifiers: GrabModifiers, pointer_mode: GrabMode) -> GrabButtonRequest {
+        GrabButtonRequest {
+            owner_events: false,
+            grab_window: 1,
+            event_mask: HashSet::from_iter([setup::Event::ButtonPress, setup::Event::ButtonRelease].to_vec()),
+            pointer_mode,
+            keyboard_mode: GrabMode::Asynchronous,
+            confine_to: None,
+            cursor: None,
+            button,
+            modifiers,
+        }
+    }
+
+    fn implicit_grab_test() {
+        let mut tree = tree();
+        let mut grabs = GrabTable::new();
+        tree.change_window_attributes(2, &ChangeWindowAttributesRequest {
+            window: B,
+            value_mask: HashSet::from_iter([CreateWindowValueMaskValue::EventMask].to_vec()),
+            value: CreateWindowValue { event_mask: HashSet::from_iter([setup::Event::PointerMotion].to_vec()), ..Default::default() },
+        }).unwrap();
+
+        //Bで押されたbuttonは選択しているAに伝播し、client 1の暗黙のgrabが始まる
+        grabs.process(&tree, Event::ButtonPress(device(1, 10, B, 5, 5, None, &[])));
+        assert_eq!(grabs.take_events(), vec![(1, Event::ButtonPress(device(1, 10, A, 10, 10, Some(B), &[])))]);
+        let grab = grabs.pointer_grab().unwrap();
+        assert_eq!((grab.client, grab.window, grab.detail, grab.owner_events), (1, A, Some(1), false));
+
+        //grab中はclient 2が選択していても報告しない
+        grabs.process(&tree, motion(11, B, 5, 5, &[KeyButMask::Button1]));
+        assert!(grabs.take_events().is_empty());
+
+        //全てのbuttonが離されればgrabは終わる
+        grabs.process(&tree, Event::ButtonRelease(device(1, 12, B, 5, 5, None, &[KeyButMask::Button1])));
+        assert_eq!(grabs.take_events(), vec![(1, Event::ButtonRelease(device(1, 12, A, 10, 10, Some(B), &[KeyButMask::Button1])))]);
+        assert_eq!(grabs.pointer_grab(), None);
+        grabs.process(&tree, motion(13, B, 5, 5, &[]));
+        assert_eq!(grabs.take_events(), vec![(2, motion(13, B, 5, 5, &[]))]);
+    }
+
+    fn passive_grab_test() {
+        let tree = tree();
+        let mut grabs = GrabTable::new();
+        grabs.grab_button(3, &tree, &grab_button(None, GrabModifiers::AnyModifier, GrabMode::Synchronous)).unwrap();
+
+        //他のclientの重なるgrabはBadAccess
+        assert!(matches!(grabs.grab_button(1, &tree, &grab_button(Some(1), GrabModifiers::Modifiers(HashSet::new()), GrabMode::Asynchronous)), Err(Error::ProtocolError(ErrorCode::Access, 1))));
+        assert!(matches!(grabs.grab_button(1, &tree, &GrabButtonRequest { grab_window: 5, ..grab_button(None, GrabModifiers::AnyModifier, GrabMode::Asynchronous) }), Err(Error::ProtocolError(ErrorCode::Window, 5))));
+
+        //根のpassive grabが起動し、pointerは凍結する
+        grabs.process(&tree, Event::ButtonPress(device(1, 10, B, 5, 5, None, &[KeyButMask::Shift])));
+        assert_eq!(grabs.take_events(), vec![(3, Event::ButtonPress(device(1, 10, 1, 20, 20, Some(A), &[KeyButMask::Shift])))]);
+        assert!(grabs.is_frozen(Device::Pointer));
+        assert!(!grabs.is_frozen(Device::Keyboard));
+        grabs.process(&tree, Event::ButtonRelease(device(1, 11, B, 5, 5, None, &[KeyButMask::Shift, KeyButMask::Button1])));
+        assert!(grabs.take_events().is_empty());
+
+        //他のclientは凍結を解けない
+        grabs.allow_events(1, &tree, &AllowEventsRequest { mode: AllowEventsMode::AsyncPointer, time: 0 }, 100);
+        assert!(grabs.is_frozen(Device::Pointer));
+
+        //ReplayPointerでgrabを外して処理し直すと、Aに伝播して暗黙のgrabになり、
+        //溜まっていたButtonReleaseで終わる
+        grabs.allow_events(3, &tree, &AllowEventsRequest { mode: AllowEventsMode::ReplayPointer, time: 0 }, 100);
+        assert_eq!(grabs.take_events(), vec![
+            (1, Event::ButtonPress(device(1, 10, A, 10, 10, Some(B), &[KeyButMask::Shift]))),
+            (1, Event::ButtonRelease(device(1, 11, A, 10, 10, Some(B), &[KeyButMask::Shift, KeyButMask::Button1]))),
+        ]);
+        assert!(!grabs.is_frozen(Device::Pointer));
+        assert_eq!(grabs.pointer_grab(), None);
+
+        //SyncPointerのあとは次のbuttonのeventで再び凍結する
+        grabs.process(&tree, Event::ButtonPress(device(1, 20, B, 5, 5, None, &[])));
+        grabs.allow_events(3, &tree, &AllowEventsRequest { mode: AllowEventsMode::SyncPointer, time: 0 }, 100);
+        assert!(!grabs.is_frozen(Device::Pointer));
+        grabs.process(&tree, motion(21, B, 5, 5, &[KeyButMask::Button1]));
+        grabs.process(&tree, Event::ButtonPress(device(2, 22, B, 5, 5, None, &[KeyButMask::Button1])));
+        assert!(grabs.is_frozen(Device::Pointer));
+        grabs.process(&tree, Event::ButtonRelease(device(2, 23, B, 5, 5, None, &[KeyButMask::Button1, KeyButMask::Button2])));
+        grabs.process(&tree, Event::ButtonRelease(device(1, 24, B, 5, 5, None, &[KeyButMask::Button1])));
+        assert_eq!(grabs.take_events(), vec![
+            (3, Event::ButtonPress(device(1, 20, 1, 20, 20, Some(A), &[]))),
+            (3, Event::ButtonPress(device(2, 22, 1, 20, 20, Some(A), &[KeyButMask::Button1]))),
+        ]);
+        //AsyncPointerで溜まっていたeventを報告し、最後のButtonReleaseでgrabが終わる
+        grabs.allow_events(3, &tree, &AllowEventsRequest { mode: AllowEventsMode::AsyncPointer, time: 0 }, 100);
+        assert_eq!(grabs.take_events(), vec![
+            (3, Event::ButtonRelease(device(2, 23, 1, 20, 20, Some(A), &[KeyButMask::Button1, KeyButMask::Button2]))),
+            (3, Event::ButtonRelease(device(1, 24, 1, 20, 20, Some(A), &[KeyButMask::Button1]))),
+        ]);
+        assert_eq!(grabs.pointer_grab(), None);
+
+        //ungrabしたあとはpassive grabは起動しない
+        grabs.ungrab_button(3, &tree, &UngrabButtonRequest { button: None, grab_window: 1, modifiers: GrabModifiers::AnyModifier }).unwrap();
+        grabs.grab_button(1, &tree, &grab_button(Some(1), GrabModifiers::Modifiers(HashSet::new()), GrabMode::Asynchronous)).unwrap();
+        grabs.process(&tree, Event::ButtonPress(device(1, 30, B, 5, 5, None, &[])));
+        assert_eq!(grabs.take_events(), vec![(1, Event::ButtonPress(device(1, 30, 1, 20, 20, Some(A), &[])))]);
+        assert_eq!(grabs.pointer_grab().unwrap().window, 1);
+    }
+
+    fn key_grab_test() {
+        let tree = tree();
+        let mut grabs = GrabTable::new();
+        grabs.grab_key(2, &tree, &GrabKeyRequest {
+            owner_events: false,
+            grab_window: A,
+            modifiers: GrabModifiers::Modifiers(HashSet::from_iter([KeyButMask::Shift].to_vec())),
+            key: Some(38),
+            pointer_mode: GrabMode::Asynchronous,
+            keyboard_mode: GrabMode::Asynchronous,
+        }).unwrap();
+
+        //modifierが違えば起動せず、誰も選択していないので報告しない
+        grabs.process(&tree, Event::KeyPress(device(38, 10, B, 5, 5, None, &[])));
+        assert!(grabs.take_events().is_empty());
+        //keyのgrabはKeyReleaseを選択していなくても報告し、そのkeyが離されたら終わる
+        grabs.process(&tree, Event::KeyPress(device(38, 11, B, 5, 5, None, &[KeyButMask::Shift])));
+        grabs.process(&tree, Event::KeyRelease(device(38, 12, B, 5, 5, None, &[KeyButMask::Shift])));
+        assert_eq!(grabs.take_events(), vec![
+            (2, Event::KeyPress(device(38, 11, A, 10, 10, Some(B), &[KeyButMask::Shift]))),
+            (2, Event::KeyRelease(device(38, 12, A, 10, 10, Some(B), &[KeyButMask::Shift]))),
+        ]);
+        assert_eq!(grabs.keyboard_grab(), None);
+    }
+
+    fn active_grab_test() {
+        let mut tree = tree();
+        let mut grabs = GrabTable::new();
+        let keyboard = GrabKeyboardRequest {
+            owner_events: false,
+            grab_window: 1,
+            time: 0,
+            pointer_mode: GrabMode::Asynchronous,
+            keyboard_mode: GrabMode::Asynchronous,
+        };
+        assert_eq!(grabs.grab_keyboard(2, &tree, &keyboard, 1000).unwrap().status, GrabStatus::Success);
+        assert_eq!(grabs.grab_keyboard(1, &tree, &keyboard, 1000).unwrap().status, GrabStatus::AlreadyGrabbed);
+        grabs.process(&tree, Event::KeyPress(device(38, 1001, B, 5, 5, None, &[])));
+        assert_eq!(grabs.take_events(), vec![(2, Event::KeyPress(device(38, 1001, 1, 20, 20, Some(A), &[])))]);
+
+        //最後のgrabより前の時刻のungrabは無視する
+        grabs.ungrab_keyboard(2, &tree, &UngrabKeyboardRequest { time: 999 }, 1000);
+        assert!(grabs.keyboard_grab().is_some());
+        grabs.ungrab_keyboard(2, &tree, &UngrabKeyboardRequest { time: 0 }, 1000);
+        assert_eq!(grabs.keyboard_grab(), None);
+
+        assert_eq!(grabs.grab_keyboard(1, &tree, &GrabKeyboardRequest { time: 500, ..keyboard.clone() }, 1000).unwrap().status, GrabStatus::InvalidTime);
+        assert_eq!(grabs.grab_keyboard(1, &tree, &GrabKeyboardRequest { time: 2000, ..keyboard.clone() }, 1000).unwrap().status, GrabStatus::InvalidTime);
+        assert_eq!(grabs.grab_keyboard(1, &tree, &GrabKeyboardRequest { grab_window: C, ..keyboard.clone() }, 1000).unwrap().status, GrabStatus::NotViewable);
+        assert!(matches!(grabs.grab_keyboard(1, &tree, &GrabKeyboardRequest { grab_window: 5, ..keyboard.clone() }, 1000), Err(Error::ProtocolError(ErrorCode::Window, 5))));
+
+        //owner_eventsなら通常の送り先がgrabしているclientのときはそのまま報告する
+        let pointer = GrabPointerRequest {
+            owner_events: true,
+            grab_window: B,
+            event_mask: HashSet::from_iter([setup::Event::PointerMotion].to_vec()),
+            pointer_mode: GrabMode::Asynchronous,
+            keyboard_mode: GrabMode::Synchronous,
+            confine_to: Some(A),
+            cursor: Some(0x00200010),
+            time: 0,
+        };
+        assert_eq!(grabs.grab_pointer(1, &tree, &pointer, 1100).unwrap().status, GrabStatus::Success);
+        assert_eq!((grabs.confine_to(), grabs.cursor()), (Some(A), Some(0x00200010)));
+        assert!(grabs.is_frozen(Device::Keyboard));
+        assert_eq!(grabs.grab_keyboard(2, &tree, &keyboard, 1100).unwrap().status, GrabStatus::Frozen);
+        grabs.process(&tree, Event::ButtonPress(device(1, 1101, B, 5, 5, None, &[])));
+        grabs.process(&tree, motion(1102, B, 5, 5, &[KeyButMask::Button1]));
+        grabs.process(&tree, Event::KeyPress(device(38, 1103, B, 5, 5, None, &[KeyButMask::Button1])));
+        assert_eq!(grabs.take_events(), vec![
+            (1, Event::ButtonPress(device(1, 1101, A, 10, 10, Some(B), &[]))),
+            (1, motion(1102, B, 5, 5, &[KeyButMask::Button1])),
+        ]);
+
+        //grab_windowが破棄されるとgrabが終わり、凍結も解ける
+        let destroyed = tree.destroy_window(&DestroyWindowRequest { window: B }).unwrap();
+        grabs.remove_windows(&tree, &destroyed);
+        assert_eq!(grabs.pointer_grab(), None);
+        assert!(!grabs.is_frozen(Device::Keyboard));
+        //送り先のwindowが無くなったeventは捨てる
+        assert!(grabs.take_events().is_empty());
+
+        grabs.grab_button(1, &tree, &grab_button(None, GrabModifiers::AnyModifier, GrabMode::Asynchronous)).unwrap();
+        grabs.remove_client(&tree, 1);
+        grabs.grab_button(2, &tree, &grab_button(None, GrabModifiers::AnyModifier, GrabMode::Asynchronous)).unwrap();
+    }
+
+    implicit_grab_test();
+    passive_grab_test();
+    key_grab_test();
+    active_grab_test();
+}
@@ -2,17 +2,46 @@
 
 use std::str::Utf8Error;
 
+use crate::error::{ErrorCode, XError};
+
 pub mod read_util;
 pub mod setup;
 pub mod request;
+pub mod event;
+pub mod error;
+pub mod server_packet;
+pub mod resources;
+pub mod window;
+pub mod atom;
+pub mod property;
+pub mod time;
+pub mod selection;
+pub mod grab;
+pub mod focus;
+pub mod pointer;
+pub mod motion;
+pub mod font;
+#[cfg(feature = "tokio-codec")]
+pub mod codec;
+#[cfg(test)]
+pub(crate) mod test_util;
 
 #[derive(Debug)]
 pub enum Error {
     IoError(std::io::Error),
     StringError(Utf8Error),
     InvalidValue(&'static str),
+    ProtocolError(ErrorCode, u32),
+    /// serverから返ってきたerror
+    XError(XError),
     UnknownError,
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IoError(e)
+    }
+}
@@ -0,0 +1,94 @@
+/// pointerの位置の履歴と、GetMotionEvents
+/// 古いものから捨てる固定長のbufferで、容量は接続時にmotion_buffer_sizeとして伝える
+use std::collections::VecDeque;
+
+use crate::Result;
+use crate::request::get_motion_events::{GetMotionEventsRequest, GetMotionEventsResponse, TimeCoord};
+use crate::time;
+use crate::window::WindowTree;
+
+mod test;
+
+/// ConnectionSetupSuccessのmotion_buffer_size
+pub const MOTION_BUFFER_SIZE: usize = 256;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Motion {
+    time: u32,
+    root: u32,
+    x: i16,
+    y: i16,
+}
+
+#[derive(Clone, Debug)]
+pub struct MotionHistory {
+    capacity: usize,
+    motions: VecDeque<Motion>,
+}
+
+impl Default for MotionHistory {
+    fn default() -> Self {
+        MotionHistory::new(MOTION_BUFFER_SIZE)
+    }
+}
+
+impl MotionHistory {
+    pub fn new(capacity: usize) -> Self {
+        MotionHistory {
+            capacity,
+            motions: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.motions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.motions.is_empty()
+    }
+
+    /// rootからの座標を記録する
+    /// 一杯なら一番古いものを捨てる
+    pub fn record(&mut self, time: u32, root: u32, x: i16, y: i16) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.motions.len() == self.capacity {
+            self.motions.pop_front();
+        }
+        self.motions.push_back(Motion { time, root, x, y });
+    }
+
+    /// startからstopまで(両端を含む)の、今のwindowの中(borderを含む)にある位置をwindowからの座標で返す
+    /// startがstopより後か、nowより後なら空で、stopがnowより後ならnowとみなす
+    pub fn get_motion_events(&self, windows: &WindowTree, request: &GetMotionEventsRequest, now: u32) -> Result<GetMotionEventsResponse> {
+        let window = windows.lookup(request.window)?;
+        let start = time::resolve(request.start, now);
+        let stop = match time::resolve(request.stop, now) {
+            stop if time::is_later(stop, now) => now,
+            stop => stop,
+        };
+        let mut events = Vec::new();
+        if !time::is_later(start, stop) && !time::is_later(start, now) {
+            let root = windows.root_of(window.id).unwrap();
+            let (x, y) = windows.origin(window.id).unwrap();
+            let border = window.border_width as i32;
+            let (left, top) = (x - border, y - border);
+            let (right, bottom) = (x + window.width as i32 + border, y + window.height as i32 + border);
+            for motion in self.motions.iter() {
+                let (mx, my) = (motion.x as i32, motion.y as i32);
+                if motion.root == root
+                    && !time::is_later(start, motion.time) && !time::is_later(motion.time, stop)
+                    && left <= mx && mx < right && top <= my && my < bottom {
+                    events.push(TimeCoord { time: motion.time, x: (mx - x) as i16, y: (my - y) as i16 });
+                }
+            }
+        }
+        Ok(GetMotionEventsResponse { sequence_number: 0, events })
+    }
+}
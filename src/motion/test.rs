@@ -0,0 +1,62 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+use std::collections::HashSet;
+
+use crate::Error;
+use crate::error::ErrorCode;
+use crate::request::create_window::{CreateWindowClass, CreateWindowRequest, CreateWindowValue};
+use crate::request::get_motion_events::{GetMotionEventsRequest, GetMotionEventsResponse, TimeCoord};
+#[cfg(test)]
+use crate::test_util::{create_window, screen};
+use crate::window::WindowTree;
+use super::*;
+
+#[test]
+fn motion_history_test() {
+    const A: u32 = 0x00200001;
+    fn get(history: &MotionHistory, tree: &WindowTree, start: u32, stop: u32) -> Vec<u32> {
+        let request = GetMotionEventsRequest { window: A, start, stop };
+        history.get_motion_events(tree, &request, 1000).unwrap().events.iter().map(|event| event.time).collect()
+    }
+
+    //borderの外側は(10, 10)から(114, 114)の手前まで
+    let mut tree = WindowTree::new(&[screen()]);
+    tree.create_window(1, &CreateWindowRequest { border_width: 2, ..create_window(A, 1, 10, 10, 100, 100) }).unwrap();
+    let mut history = MotionHistory::default();
+    assert_eq!(history.capacity(), MOTION_BUFFER_SIZE);
+    history.record(100, 1, 5, 5);
+    history.record(200, 1, 10, 10);
+    history.record(300, 1, 50, 50);
+    history.record(400, 1, 113, 113);
+    history.record(500, 1, 114, 50);
+
+    assert_eq!(history.get_motion_events(&tree, &GetMotionEventsRequest { window: A, start: 1, stop: 0 }, 1000).unwrap(), GetMotionEventsResponse {
+        sequence_number: 0,
+        events: vec![
+            TimeCoord { time: 200, x: -2, y: -2 },
+            TimeCoord { time: 300, x: 38, y: 38 },
+            TimeCoord { time: 400, x: 101, y: 101 },
+        ],
+    });
+    assert_eq!(get(&history, &tree, 150, 300), vec![200, 300]);
+    //stopが現在時刻より後なら現在時刻
+    assert_eq!(get(&history, &tree, 300, 2000), vec![300, 400]);
+    //startがstopか現在時刻より後なら空
+    assert_eq!(get(&history, &tree, 500, 400), Vec::<u32>::new());
+    assert_eq!(get(&history, &tree, 1500, 2000), Vec::<u32>::new());
+    //startがCurrentTimeなら現在時刻から
+    assert_eq!(get(&history, &tree, 0, 0), Vec::<u32>::new());
+    assert!(matches!(history.get_motion_events(&tree, &GetMotionEventsRequest { window: 5, start: 0, stop: 0 }, 1000), Err(Error::ProtocolError(ErrorCode::Window, 5))));
+
+    //一杯なら古いものから捨てる
+    let mut history = MotionHistory::new(2);
+    for time in [200, 300, 400] {
+        history.record(time, 1, 50, 50);
+    }
+    assert_eq!(history.len(), 2);
+    assert_eq!(get(&history, &tree, 1, 0), vec![300, 400]);
+    let mut history = MotionHistory::new(0);
+    history.record(200, 1, 50, 50);
+    assert!(history.is_empty());
+}
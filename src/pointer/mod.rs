@@ -0,0 +1,319 @@
+/// 仮想的なpointerの位置、下にあるwindow、buttonの状態と、EnterNotify、LeaveNotify
+/// MotionNotifyとButtonPress、ButtonReleaseは返り値をGrabTable::processに渡して報告する
+use std::collections::HashSet;
+
+use crate::Result;
+use crate::event::{CrossingEvent, DeviceEvent, Event, KeyButMask, MotionNotifyDetail, MotionNotifyEvent, NotifyDetail, NotifyMode};
+use crate::focus::FocusState;
+use crate::grab::ActiveGrab;
+use crate::motion::MotionHistory;
+use crate::request::query_pointer::{QueryPointerRequest, QueryPointerResponse};
+use crate::request::warp_pointer::WarpPointerRequest;
+use crate::resources::ClientId;
+use crate::setup;
+use crate::window::WindowTree;
+
+mod test;
+
+/// 位置を変えずにcrossing eventを作るときに共通の引数
+pub struct Context<'a> {
+    pub windows: &'a WindowTree,
+    /// pointerのgrab
+    pub grab: Option<&'a ActiveGrab>,
+    pub focus: &'a FocusState,
+    pub time: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct PointerState {
+    root: u32,
+    x: i16,
+    y: i16,
+    /// pointerの下の一番深いwindow
+    window: u32,
+    buttons: HashSet<KeyButMask>,
+    history: MotionHistory,
+    events: Vec<(ClientId, Event)>,
+}
+
+impl PointerState {
+    /// 最初のscreenの中央から始める
+    pub fn new(windows: &WindowTree) -> Self {
+        let root = windows.roots()[0];
+        let w = windows.get(root).unwrap();
+        let (x, y) = ((w.width / 2) as i16, (w.height / 2) as i16);
+        PointerState {
+            root,
+            x,
+            y,
+            window: windows.window_at(root, x as i32, y as i32),
+            buttons: HashSet::new(),
+            history: MotionHistory::default(),
+            events: Vec::new(),
+        }
+    }
+
+    pub fn take_events(&mut self) -> Vec<(ClientId, Event)> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// rootと、rootからの座標
+    pub fn position(&self) -> (u32, i16, i16) {
+        (self.root, self.x, self.y)
+    }
+
+    pub fn window(&self) -> u32 {
+        self.window
+    }
+
+    pub fn buttons(&self) -> &HashSet<KeyButMask> {
+        &self.buttons
+    }
+
+    /// move_toで動いた位置の履歴
+    pub fn history(&self) -> &MotionHistory {
+        &self.history
+    }
+
+    /// pointerを動かし、下のwindowが変わればcrossing eventを作る
+    /// 画面の外やgrabのconfine_toの外へは出ない
+    /// 位置が変わらなければNone
+    pub fn move_to(&mut self, context: &Context, position: (u32, i16, i16)) -> Option<Event> {
+        let (mut root, x, y) = position;
+        let confine_to = context.grab.and_then(|grab| grab.confine_to);
+        let (left, top, width, height) = match confine_to.and_then(|window| Some((window, context.windows.origin(window)?))) {
+            Some((window, (cx, cy))) => {
+                let w = context.windows.get(window).unwrap();
+                root = context.windows.root_of(window).unwrap();
+                (cx, cy, w.width as i32, w.height as i32)
+            }
+            None => {
+                let w = context.windows.get(root)?;
+                (0, 0, w.width as i32, w.height as i32)
+            }
+        };
+        let x = (x as i32).clamp(left, left + width - 1) as i16;
+        let y = (y as i32).clamp(top, top + height - 1) as i16;
+        if (root, x, y) == (self.root, self.x, self.y) {
+            return None;
+        }
+        self.root = root;
+        self.x = x;
+        self.y = y;
+        self.history.record(context.time, root, x, y);
+        self.update(context);
+        let (event_x, event_y) = self.relative(context.windows, self.window);
+        Some(Event::MotionNotify(MotionNotifyEvent {
+            detail: MotionNotifyDetail::Normal,
+            sequence_number: 0,
+            time: context.time,
+            root: self.root,
+            event: self.window,
+            child: None,
+            root_x: self.x,
+            root_y: self.y,
+            event_x,
+            event_y,
+            state: self.buttons.clone(),
+            same_screen: true,
+        }))
+    }
+
+    /// src_windowがあれば、pointerがその中のsrcの矩形にあるときだけ動かす
+    /// dst_windowがNoneなら今の位置から相対的に動かす
+    pub fn warp_pointer(&mut self, context: &Context, request: &WarpPointerRequest) -> Result<Option<Event>> {
+        if let Some(src) = request.src_window {
+            context.windows.lookup(src)?;
+        }
+        if let Some(dst) = request.dst_window {
+            context.windows.lookup(dst)?;
+        }
+        if let Some(src) = request.src_window {
+            if !self.window_contains_pointer(context.windows, src) {
+                return Ok(None);
+            }
+            let w = context.windows.get(src).unwrap();
+            let (x, y) = self.relative(context.windows, src);
+            let (x, y) = (x as i32, y as i32);
+            //幅と高さが0ならwindowの端まで
+            let right = if request.src_width == 0 { w.width as i32 } else { request.src_x as i32 + request.src_width as i32 };
+            let bottom = if request.src_height == 0 { w.height as i32 } else { request.src_y as i32 + request.src_height as i32 };
+            if x < request.src_x as i32 || right <= x || y < request.src_y as i32 || bottom <= y {
+                return Ok(None);
+            }
+        }
+        let position = match request.dst_window {
+            Some(dst) => {
+                let (ox, oy) = context.windows.origin(dst).unwrap();
+                let root = context.windows.root_of(dst).unwrap();
+                (root, (ox + request.dst_x as i32) as i16, (oy + request.dst_y as i32) as i16)
+            }
+            None => (self.root, self.x.wrapping_add(request.dst_x), self.y.wrapping_add(request.dst_y)),
+        };
+        Ok(self.move_to(context, position))
+    }
+
+    /// buttonを押したときのButtonPress
+    /// stateは押す前の状態
+    pub fn press(&mut self, windows: &WindowTree, button: u8, time: u32) -> Event {
+        let event = Event::ButtonPress(self.button_event(windows, button, time));
+        self.buttons.insert(KeyButMask::button(button));
+        event
+    }
+
+    /// buttonを離したときのButtonRelease
+    pub fn release(&mut self, windows: &WindowTree, button: u8, time: u32) -> Event {
+        let event = Event::ButtonRelease(self.button_event(windows, button, time));
+        self.buttons.remove(&KeyButMask::button(button));
+        event
+    }
+
+    fn button_event(&self, windows: &WindowTree, button: u8, time: u32) -> DeviceEvent {
+        let (event_x, event_y) = self.relative(windows, self.window);
+        DeviceEvent {
+            detail: button,
+            sequence_number: 0,
+            time,
+            root: self.root,
+            event: self.window,
+            child: None,
+            root_x: self.x,
+            root_y: self.y,
+            event_x,
+            event_y,
+            state: self.buttons.clone(),
+            same_screen: true,
+        }
+    }
+
+    /// windowの変更のあとに呼ぶ
+    /// pointerの下のwindowが変わっていればcrossing eventを作る
+    pub fn update(&mut self, context: &Context) {
+        let window = context.windows.window_at(self.root, self.x as i32, self.y as i32);
+        if window != self.window {
+            let from = std::mem::replace(&mut self.window, window);
+            self.crossing(context, from, window, NotifyMode::Normal);
+        }
+    }
+
+    /// pointerのgrabが始まったときに呼ぶ
+    /// pointerがgrab_windowに移ったようにGrabのcrossing eventを作る
+    pub fn grab(&mut self, context: &Context, grab_window: u32) {
+        self.crossing(context, self.window, grab_window, NotifyMode::Grab);
+    }
+
+    /// pointerのgrabが終わったときに呼ぶ
+    pub fn ungrab(&mut self, context: &Context, grab_window: u32) {
+        self.crossing(context, grab_window, self.window, NotifyMode::Ungrab);
+    }
+
+    /// windowが別のscreenにあればsame_screenはfalseで、childと座標は0
+    pub fn query_pointer(&self, windows: &WindowTree, request: &QueryPointerRequest) -> Result<QueryPointerResponse> {
+        windows.lookup(request.window)?;
+        let same_screen = windows.root_of(request.window) == Some(self.root);
+        let (child, win_x, win_y) = if same_screen {
+            let (x, y) = self.relative(windows, request.window);
+            (child_toward(windows, request.window, self.window), x, y)
+        } else {
+            (None, 0, 0)
+        };
+        Ok(QueryPointerResponse {
+            same_screen,
+            sequence_number: 0,
+            root: self.root,
+            child,
+            root_x: self.x,
+            root_y: self.y,
+            win_x,
+            win_y,
+            mask: self.buttons.clone(),
+        })
+    }
+
+    /// windowの内側の左上からのpointerの座標
+    fn relative(&self, windows: &WindowTree, window: u32) -> (i16, i16) {
+        let (x, y) = windows.origin(window).unwrap_or((0, 0));
+        ((self.x as i32 - x) as i16, (self.y as i32 - y) as i16)
+    }
+
+    fn window_contains_pointer(&self, windows: &WindowTree, window: u32) -> bool {
+        self.window == window || windows.is_inferior(self.window, window)
+    }
+
+    /// fromからtoへpointerが移ったときのLeaveNotifyとEnterNotifyを順に作る
+    fn crossing(&mut self, context: &Context, from: u32, to: u32, mode: NotifyMode) {
+        if from == to {
+            return;
+        }
+        let from_chain = context.windows.ancestors(from);
+        let to_chain = context.windows.ancestors(to);
+        let common = from_chain.iter().find(|w| to_chain.contains(w)).cloned();
+        let (leave, enter, virtual_detail) = if common == Some(from) {
+            //toがfromの子孫
+            (NotifyDetail::Inferior, NotifyDetail::Ancestor, NotifyDetail::Virtual)
+        } else if common == Some(to) {
+            (NotifyDetail::Ancestor, NotifyDetail::Inferior, NotifyDetail::Virtual)
+        } else {
+            (NotifyDetail::Nonlinear, NotifyDetail::Nonlinear, NotifyDetail::NonlinearVirtual)
+        };
+        //Inferiorならpointerのある側の子がchild
+        self.send(context, from, child_toward(context.windows, from, to), false, leave, &mode);
+        //fromとcommonの間は下から、commonとtoの間は上から
+        //commonが無ければ根まで
+        let below = |chain: &[u32]| common.and_then(|c| chain.iter().position(|w| *w == c)).unwrap_or(chain.len());
+        for pair in from_chain[..below(&from_chain)].windows(2) {
+            self.send(context, pair[1], Some(pair[0]), false, virtual_detail.clone(), &mode);
+        }
+        for pair in to_chain[..below(&to_chain)].windows(2).rev() {
+            self.send(context, pair[1], Some(pair[0]), true, virtual_detail.clone(), &mode);
+        }
+        self.send(context, to, child_toward(context.windows, to, from), true, enter, &mode);
+    }
+
+    /// grab中はgrabしているclientにだけ、grab_windowならgrabのevent_maskで報告する
+    fn send(&mut self, context: &Context, window: u32, child: Option<u32>, enter: bool, detail: NotifyDetail, mode: &NotifyMode) {
+        let w = match context.windows.get(window) {
+            Some(w) => w,
+            None => return,
+        };
+        let mask = if enter { setup::Event::EnterWindow } else { setup::Event::LeaveWindow };
+        let clients = match context.grab {
+            Some(grab) => {
+                let selected = (grab.window == window && grab.event_mask.contains(&mask))
+                    || (grab.owner_events && w.event_masks.get(&grab.client).map(|masks| masks.contains(&mask)).unwrap_or(false));
+                if selected { vec![grab.client] } else { Vec::new() }
+            }
+            None => w.clients_selecting(&mask),
+        };
+        if clients.is_empty() {
+            return;
+        }
+        let same_screen = context.windows.root_of(window) == Some(self.root);
+        let (event_x, event_y) = if same_screen { self.relative(context.windows, window) } else { (0, 0) };
+        let event = CrossingEvent {
+            detail,
+            sequence_number: 0,
+            time: context.time,
+            root: self.root,
+            event: window,
+            child,
+            root_x: self.x,
+            root_y: self.y,
+            event_x,
+            event_y,
+            state: self.buttons.clone(),
+            mode: mode.clone(),
+            same_screen,
+            focus: context.focus.has_focus(context.windows, window),
+        };
+        for client in clients {
+            self.events.push((client, if enter { Event::EnterNotify(event.clone()) } else { Event::LeaveNotify(event.clone()) }));
+        }
+    }
+}
+
+/// windowの子のうちdescendantかその祖先であるもの
+fn child_toward(windows: &WindowTree, window: u32, descendant: u32) -> Option<u32> {
+    let path = windows.ancestors(descendant);
+    path.iter().position(|w| *w == window).filter(|i| *i > 0).map(|i| path[i - 1])
+}
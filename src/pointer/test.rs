@@ -0,0 +1,204 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+use crate::Error;
+use crate::error::ErrorCode;
+use crate::event::{CrossingEvent, DeviceEvent, Event, KeyButMask, MotionNotifyDetail, MotionNotifyEvent, NotifyDetail, NotifyMode};
+use crate::focus::FocusState;
+use crate::grab::{ActiveGrab, GrabTable};
+use crate::request::change_window_attributes::ChangeWindowAttributesRequest;
+use crate::request::create_window::{CreateWindowClass, CreateWindowRequest, CreateWindowValue, CreateWindowValueMaskValue};
+use crate::request::grab_pointer::GrabMode;
+use crate::request::map_window::MapWindowRequest;
+use crate::request::query_pointer::{QueryPointerRequest, QueryPointerResponse};
+use crate::request::unmap_window::UnmapWindowRequest;
+use crate::request::warp_pointer::WarpPointerRequest;
+use crate::setup;
+#[cfg(test)]
+use crate::test_util::{create_window, screen, select_input};
+use crate::window::WindowTree;
+use super::*;
+
+#[test]
+fn pointer_test() {
+    const A: u32 = 0x00200001;
+    const B: u32 = 0x00200002;
+    const C: u32 = 0x00200003;
+    fn select(event_mask: &[setup::Event]) -> (HashSet<CreateWindowValueMaskValue>, CreateWindowValue) {
+        (HashSet::from_iter([CreateWindowValueMaskValue::EventMask].to_vec()), CreateWindowValue { event_mask: HashSet::from_iter(event_mask.to_vec()), ..Default::default() })
+    }
+    fn create(wid: u32, parent: u32, x: i16, y: i16, size: u16) -> CreateWindowRequest {
+        select_input(create_window(wid, parent, x, y, size, size), &[setup::Event::EnterWindow, setup::Event::LeaveWindow])
+    }
+    /// (320, 240)にpointerがあるときの、windowのclient 1へのcrossing event
+    fn crossing(enter: bool, window: u32, child: Option<u32>, detail: NotifyDetail, position: (i16, i16, i16, i16), mode: NotifyMode) -> (u32, Event) {
+        let event = CrossingEvent {
+            detail,
+            sequence_number: 0,
+            time: 10,
+            root: 1,
+            event: window,
+            child,
+            root_x: position.0,
+            root_y: position.1,
+            event_x: position.2,
+            event_y: position.3,
+            state: HashSet::new(),
+            mode,
+            same_screen: true,
+            focus: true,
+        };
+        (1, if enter { Event::EnterNotify(event) } else { Event::LeaveNotify(event) })
+    }
+    fn motion(window: u32, position: (i16, i16, i16, i16)) -> Event {
+        Event::MotionNotify(MotionNotifyEvent {
+            detail: MotionNotifyDetail::Normal,
+            sequence_number: 0,
+            time: 10,
+            root: 1,
+            event: window,
+            child: None,
+            root_x: position.0,
+            root_y: position.1,
+            event_x: position.2,
+            event_y: position.3,
+            state: HashSet::new(),
+            same_screen: true,
+        })
+    }
+
+    //根の子のA(10, 10)とC(200, 200)、Aの子のB(5, 5)
+    let mut tree = WindowTree::new(&[screen()]);
+    let (value_mask, value) = select(&[setup::Event::EnterWindow, setup::Event::LeaveWindow]);
+    tree.change_window_attributes(1, &ChangeWindowAttributesRequest { window: 1, value_mask, value }).unwrap();
+    tree.create_window(1, &create(A, 1, 10, 10, 100)).unwrap();
+    tree.create_window(1, &create(B, A, 5, 5, 20)).unwrap();
+    tree.create_window(1, &create(C, 1, 200, 200, 50)).unwrap();
+    for window in [A, B, C] {
+        tree.map_window(1, &MapWindowRequest { window }).unwrap();
+    }
+    tree.take_events();
+    let focus = FocusState::new();
+    let mut pointer = PointerState::new(&tree);
+    assert_eq!((pointer.position(), pointer.window()), ((1, 320, 240), 1));
+    let context = Context { windows: &tree, grab: None, focus: &focus, time: 10 };
+
+    //根から子孫のBへ
+    assert_eq!(pointer.move_to(&context, (1, 20, 20)), Some(motion(B, (20, 20, 5, 5))));
+    assert_eq!(pointer.take_events(), vec![
+        crossing(false, 1, Some(A), NotifyDetail::Inferior, (20, 20, 20, 20), NotifyMode::Normal),
+        crossing(true, A, Some(B), NotifyDetail::Virtual, (20, 20, 10, 10), NotifyMode::Normal),
+        crossing(true, B, None, NotifyDetail::Ancestor, (20, 20, 5, 5), NotifyMode::Normal),
+    ]);
+    assert_eq!(pointer.move_to(&context, (1, 20, 20)), None);
+    assert_eq!(pointer.history().len(), 1);
+
+    //関係の無いwindowへ
+    pointer.move_to(&context, (1, 220, 220)).unwrap();
+    assert_eq!(pointer.take_events(), vec![
+        crossing(false, B, None, NotifyDetail::Nonlinear, (220, 220, 205, 205), NotifyMode::Normal),
+        crossing(false, A, Some(B), NotifyDetail::NonlinearVirtual, (220, 220, 210, 210), NotifyMode::Normal),
+        crossing(true, C, None, NotifyDetail::Nonlinear, (220, 220, 20, 20), NotifyMode::Normal),
+    ]);
+
+    //画面の外には出ない
+    assert_eq!(pointer.move_to(&context, (1, 1000, -5)), Some(motion(1, (639, 0, 639, 0))));
+    assert_eq!(pointer.take_events(), vec![
+        crossing(false, C, None, NotifyDetail::Ancestor, (639, 0, 439, -200), NotifyMode::Normal),
+        crossing(true, 1, Some(C), NotifyDetail::Inferior, (639, 0, 639, 0), NotifyMode::Normal),
+    ]);
+
+    //WarpPointer
+    let warp = WarpPointerRequest { src_window: None, dst_window: Some(B), src_x: 0, src_y: 0, src_width: 0, src_height: 0, dst_x: 1, dst_y: 1 };
+    assert_eq!(pointer.warp_pointer(&context, &warp).unwrap(), Some(motion(B, (16, 16, 1, 1))));
+    pointer.take_events();
+    assert_eq!(pointer.warp_pointer(&context, &WarpPointerRequest { dst_window: None, dst_x: -1, dst_y: -1, ..warp.clone() }).unwrap(), Some(motion(B, (15, 15, 0, 0))));
+    assert!(pointer.take_events().is_empty());
+    assert_eq!(pointer.warp_pointer(&context, &WarpPointerRequest { src_window: Some(C), ..warp.clone() }).unwrap(), None);
+    assert_eq!(pointer.warp_pointer(&context, &WarpPointerRequest { src_window: Some(A), src_x: 10, ..warp.clone() }).unwrap(), None);
+    assert!(matches!(pointer.warp_pointer(&context, &WarpPointerRequest { dst_window: Some(5), ..warp.clone() }), Err(Error::ProtocolError(ErrorCode::Window, 5))));
+
+    //QueryPointerとbutton
+    assert_eq!(pointer.press(&tree, 1, 10), Event::ButtonPress(DeviceEvent {
+        detail: 1,
+        sequence_number: 0,
+        time: 10,
+        root: 1,
+        event: B,
+        child: None,
+        root_x: 15,
+        root_y: 15,
+        event_x: 0,
+        event_y: 0,
+        state: HashSet::new(),
+        same_screen: true,
+    }));
+    assert_eq!(pointer.query_pointer(&tree, &QueryPointerRequest { window: A }).unwrap(), QueryPointerResponse {
+        same_screen: true,
+        sequence_number: 0,
+        root: 1,
+        child: Some(B),
+        root_x: 15,
+        root_y: 15,
+        win_x: 5,
+        win_y: 5,
+        mask: HashSet::from_iter([KeyButMask::Button1].to_vec()),
+    });
+    pointer.release(&tree, 1, 10);
+    assert!(pointer.buttons().is_empty());
+
+    //Bが表示されなくなればAに入る
+    tree.unmap_window(&UnmapWindowRequest { window: B }).unwrap();
+    let context = Context { windows: &tree, grab: None, focus: &focus, time: 10 };
+    pointer.update(&context);
+    assert_eq!(pointer.window(), A);
+    assert_eq!(pointer.take_events(), vec![
+        crossing(false, B, None, NotifyDetail::Ancestor, (15, 15, 0, 0), NotifyMode::Normal),
+        crossing(true, A, Some(B), NotifyDetail::Inferior, (15, 15, 5, 5), NotifyMode::Normal),
+    ]);
+
+    //grab中はgrabしているclientにだけ報告し、confine_toの外には出ない
+    let grab = ActiveGrab {
+        client: 2,
+        window: C,
+        owner_events: false,
+        event_mask: HashSet::from_iter([setup::Event::EnterWindow].to_vec()),
+        pointer_mode: GrabMode::Asynchronous,
+        keyboard_mode: GrabMode::Asynchronous,
+        confine_to: Some(C),
+        cursor: None,
+        time: 10,
+        detail: None,
+    };
+    let context = Context { windows: &tree, grab: Some(&grab), focus: &focus, time: 10 };
+    pointer.grab(&context, C);
+    assert_eq!(pointer.take_events(), vec![
+        (2, crossing(true, C, None, NotifyDetail::Nonlinear, (15, 15, -185, -185), NotifyMode::Grab).1),
+    ]);
+    assert_eq!(pointer.move_to(&context, (1, 0, 0)), Some(motion(C, (200, 200, 0, 0))));
+    assert_eq!(pointer.take_events(), vec![
+        (2, crossing(true, C, None, NotifyDetail::Nonlinear, (200, 200, 0, 0), NotifyMode::Normal).1),
+    ]);
+    let context = Context { windows: &tree, grab: None, focus: &focus, time: 10 };
+    pointer.ungrab(&context, C);
+    assert!(pointer.take_events().is_empty());
+
+    //PointerMotionHintなら同じwindowの中では一度だけ送る
+    let (value_mask, value) = select(&[setup::Event::PointerMotion, setup::Event::PointerMotionHint]);
+    tree.change_window_attributes(3, &ChangeWindowAttributesRequest { window: C, value_mask, value }).unwrap();
+    let context = Context { windows: &tree, grab: None, focus: &focus, time: 10 };
+    let mut grabs = GrabTable::new();
+    let hint = |position| match motion(C, position) {
+        Event::MotionNotify(event) => Event::MotionNotify(MotionNotifyEvent { detail: MotionNotifyDetail::Hint, ..event }),
+        _ => unreachable!(),
+    };
+    for x in [201, 202] {
+        grabs.process(&tree, pointer.move_to(&context, (1, x, 200)).unwrap());
+    }
+    grabs.reset_motion_hints(3);
+    grabs.process(&tree, pointer.move_to(&context, (1, 203, 200)).unwrap());
+    assert_eq!(grabs.take_events(), vec![(3, hint((201, 200, 1, 0))), (3, hint((203, 200, 3, 0)))]);
+}
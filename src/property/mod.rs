@@ -0,0 +1,137 @@
+/// windowごとのpropertyの値と、ChangeProperty、GetPropertyなどの規則
+/// atomが有効かどうかやeventの送信はWindowTreeで行う
+use std::collections::BTreeMap;
+
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::request::change_property::{ChangePropertyMode, PropertyValue};
+use crate::request::get_property::{GetPropertyRequest, GetPropertyResponse};
+
+mod test;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Property {
+    pub type_: u32,
+    pub value: PropertyValue,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PropertyStore {
+    properties: BTreeMap<u32, Property>,
+}
+
+impl PropertyStore {
+    pub fn new() -> Self {
+        PropertyStore::default()
+    }
+
+    pub fn get(&self, property: u32) -> Option<&Property> {
+        self.properties.get(&property)
+    }
+
+    /// atomの小さい順
+    pub fn list(&self) -> Vec<u32> {
+        self.properties.keys().cloned().collect()
+    }
+
+    /// PrependとAppendで既存の値とtypeかformatが違えばBadMatch
+    pub fn change(&mut self, property: u32, type_: u32, mode: ChangePropertyMode, value: PropertyValue) -> Result<()> {
+        let prepend = mode == ChangePropertyMode::Prepend;
+        let current = match (mode, self.properties.get_mut(&property)) {
+            (ChangePropertyMode::Replace, _) | (_, None) => {
+                self.properties.insert(property, Property { type_, value });
+                return Ok(());
+            }
+            (_, Some(current)) => current,
+        };
+        if current.type_ != type_ {
+            return Err(Error::ProtocolError(ErrorCode::Match, property));
+        }
+        current.value = match (&current.value, value) {
+            (PropertyValue::Format8(current), PropertyValue::Format8(value)) => PropertyValue::Format8(join(current, &value, prepend)),
+            (PropertyValue::Format16(current), PropertyValue::Format16(value)) => PropertyValue::Format16(join(current, &value, prepend)),
+            (PropertyValue::Format32(current), PropertyValue::Format32(value)) => PropertyValue::Format32(join(current, &value, prepend)),
+            _ => return Err(Error::ProtocolError(ErrorCode::Match, property)),
+        };
+        Ok(())
+    }
+
+    pub fn delete(&mut self, property: u32) -> Option<Property> {
+        self.properties.remove(&property)
+    }
+
+    /// GetPropertyの返事と、deleteで実際にpropertyを消したかどうか
+    /// long_offsetが値の長さを超えていればBadValue
+    pub fn read(&mut self, request: &GetPropertyRequest) -> Result<(GetPropertyResponse, bool)> {
+        let current = match self.properties.get(&request.property) {
+            Some(current) => current,
+            None => {
+                return Ok((GetPropertyResponse {
+                    sequence_number: 0,
+                    type_: None,
+                    bytes_after: 0,
+                    value: None,
+                }, false));
+            }
+        };
+        let length = current.value.byte_len();
+        let unit = current.value.format() as usize / 8;
+        //typeが違えば値は返さず、bytes_afterに長さを入れる
+        if request.type_.map(|type_| type_ != current.type_).unwrap_or(false) {
+            return Ok((GetPropertyResponse {
+                sequence_number: 0,
+                type_: Some(current.type_),
+                bytes_after: length as u32,
+                value: Some(current.value.slice(0..0)),
+            }, false));
+        }
+        let offset = request.long_offset as u64 * 4;
+        if offset > length as u64 {
+            return Err(Error::ProtocolError(ErrorCode::Value, request.long_offset));
+        }
+        let offset = offset as usize;
+        let value_len = (length - offset).min(request.long_length as usize * 4);
+        let bytes_after = length - offset - value_len;
+        //offsetとvalue_lenは4の倍数か値の末尾なので、formatの単位で割り切れる
+        let response = GetPropertyResponse {
+            sequence_number: 0,
+            type_: Some(current.type_),
+            bytes_after: bytes_after as u32,
+            value: Some(current.value.slice(offset / unit..(offset + value_len) / unit)),
+        };
+        let deleted = request.delete && bytes_after == 0;
+        if deleted {
+            self.properties.remove(&request.property);
+        }
+        Ok((response, deleted))
+    }
+
+    /// 名前が重複しているか、存在しないpropertyがあればBadMatch
+    /// 値が実際に入れ替わったときだけtrue
+    pub fn rotate(&mut self, properties: &[u32], delta: i16) -> Result<bool> {
+        for (i, property) in properties.iter().enumerate() {
+            if !self.properties.contains_key(property) || properties[..i].contains(property) {
+                return Err(Error::ProtocolError(ErrorCode::Match, *property));
+            }
+        }
+        let n = properties.len() as i32;
+        if n == 0 || delta as i32 % n == 0 {
+            return Ok(false);
+        }
+        let values = properties.iter().map(|property| self.properties.remove(property).unwrap()).collect::<Vec<_>>();
+        //I番目の値が(I + delta) mod N番目のpropertyの値になる
+        for (i, value) in values.into_iter().enumerate() {
+            let j = (i as i32 + delta as i32).rem_euclid(n) as usize;
+            self.properties.insert(properties[j], value);
+        }
+        Ok(true)
+    }
+}
+
+fn join<T: Clone>(current: &[T], value: &[T], prepend: bool) -> Vec<T> {
+    if prepend {
+        [value, current].concat()
+    } else {
+        [current, value].concat()
+    }
+}
@@ -0,0 +1,87 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+use crate::Error;
+use crate::error::ErrorCode;
+use crate::request::change_property::{ChangePropertyMode, PropertyValue};
+use crate::request::get_property::{GetPropertyRequest, GetPropertyResponse};
+use super::*;
+
+#[test]
+fn change_test() {
+    let mut store = PropertyStore::new();
+    store.change(39, 31, ChangePropertyMode::Append, PropertyValue::Format8(b"bc".to_vec())).unwrap();
+    store.change(39, 31, ChangePropertyMode::Prepend, PropertyValue::Format8(b"a".to_vec())).unwrap();
+    store.change(39, 31, ChangePropertyMode::Append, PropertyValue::Format8(b"d".to_vec())).unwrap();
+    assert_eq!(store.get(39), Some(&Property { type_: 31, value: PropertyValue::Format8(b"abcd".to_vec()) }));
+    assert!(matches!(store.change(39, 4, ChangePropertyMode::Append, PropertyValue::Format8(b"e".to_vec())), Err(Error::ProtocolError(ErrorCode::Match, 39))));
+    assert!(matches!(store.change(39, 31, ChangePropertyMode::Prepend, PropertyValue::Format16(vec![1])), Err(Error::ProtocolError(ErrorCode::Match, 39))));
+    store.change(39, 6, ChangePropertyMode::Replace, PropertyValue::Format32(vec![1])).unwrap();
+    store.change(39, 6, ChangePropertyMode::Prepend, PropertyValue::Format32(vec![0x12345678])).unwrap();
+    assert_eq!(store.get(39), Some(&Property { type_: 6, value: PropertyValue::Format32(vec![0x12345678, 1]) }));
+    store.change(1, 31, ChangePropertyMode::Replace, PropertyValue::Format8(Vec::new())).unwrap();
+    assert_eq!(store.list(), vec![1, 39]);
+    assert!(store.delete(1).is_some());
+    assert!(store.delete(1).is_none());
+    assert_eq!(store.list(), vec![39]);
+}
+
+#[test]
+fn read_test() {
+    let mut store = PropertyStore::new();
+    store.change(39, 31, ChangePropertyMode::Replace, PropertyValue::Format16(vec![1, 2, 3, 4, 5])).unwrap();
+    let request = GetPropertyRequest { delete: true, window: 1, property: 39, type_: Some(31), long_offset: 1, long_length: 1 };
+    assert_eq!(store.read(&request).unwrap(), (GetPropertyResponse {
+        sequence_number: 0,
+        type_: Some(31),
+        bytes_after: 2,
+        value: Some(PropertyValue::Format16(vec![3, 4])),
+    }, false));
+
+    //typeが違えば値を返さずに長さだけ返し、消さない
+    let request = GetPropertyRequest { delete: true, window: 1, property: 39, type_: Some(4), long_offset: 0, long_length: 100 };
+    assert_eq!(store.read(&request).unwrap(), (GetPropertyResponse {
+        sequence_number: 0,
+        type_: Some(31),
+        bytes_after: 10,
+        value: Some(PropertyValue::Format16(Vec::new())),
+    }, false));
+
+    let request = GetPropertyRequest { delete: false, window: 1, property: 39, type_: None, long_offset: 3, long_length: 0 };
+    assert!(matches!(store.read(&request), Err(Error::ProtocolError(ErrorCode::Value, 3))));
+
+    let request = GetPropertyRequest { delete: true, window: 1, property: 39, type_: None, long_offset: 2, long_length: 1 };
+    assert_eq!(store.read(&request).unwrap(), (GetPropertyResponse {
+        sequence_number: 0,
+        type_: Some(31),
+        bytes_after: 0,
+        value: Some(PropertyValue::Format16(vec![5])),
+    }, true));
+    assert!(store.get(39).is_none());
+
+    let request = GetPropertyRequest { delete: true, window: 1, property: 39, type_: None, long_offset: 0, long_length: 1 };
+    assert_eq!(store.read(&request).unwrap(), (GetPropertyResponse {
+        sequence_number: 0,
+        type_: None,
+        bytes_after: 0,
+        value: None,
+    }, false));
+}
+
+#[test]
+fn rotate_test() {
+    let mut store = PropertyStore::new();
+    for (property, value) in [(10, b"a"), (11, b"b"), (12, b"c")].iter() {
+        store.change(*property, 31, ChangePropertyMode::Replace, PropertyValue::Format8(value.to_vec())).unwrap();
+    }
+    assert!(store.rotate(&[10, 11, 12], 1).unwrap());
+    assert_eq!(store.get(10).unwrap().value, PropertyValue::Format8(b"c".to_vec()));
+    assert_eq!(store.get(11).unwrap().value, PropertyValue::Format8(b"a".to_vec()));
+    assert_eq!(store.get(12).unwrap().value, PropertyValue::Format8(b"b".to_vec()));
+    assert!(store.rotate(&[10, 11, 12], -4).unwrap());
+    assert_eq!(store.get(10).unwrap().value, PropertyValue::Format8(b"a".to_vec()));
+    assert!(!store.rotate(&[10, 11, 12], 3).unwrap());
+    assert!(matches!(store.rotate(&[10, 11, 10], 1), Err(Error::ProtocolError(ErrorCode::Match, 10))));
+    assert!(matches!(store.rotate(&[10, 13], 1), Err(Error::ProtocolError(ErrorCode::Match, 13))));
+    assert_eq!(store.get(10).unwrap().value, PropertyValue::Format8(b"a".to_vec()));
+}
@@ -9,7 +9,7 @@ pub(crate) fn read_specified_length(stream: &mut BufReader<impl Read>, buffer: &
     let mut read_length = 0;
     while read_length < length {
         let i = stream.read(&mut buffer[read_length..length])
-            .map_err(|e| Error::IoError(e))?;
+            .map_err(Error::IoError)?;
         if i == 0 {
             return Err(Error::UnknownError);
         }
@@ -39,6 +39,7 @@ pub trait Encoding {
     fn decode(order: &ByteOrder, data: &[u8]) -> Self;
 }
 
+#[allow(clippy::len_zero)]
 impl Encoding for bool {
     const SIZE: usize = 1;
     fn encode(&self, _order: &ByteOrder, dest: &mut [u8]) {
@@ -52,6 +53,7 @@ impl Encoding for bool {
     }
 }
 
+#[allow(clippy::len_zero)]
 impl Encoding for u8 {
     const SIZE: usize = 1;
     fn encode(&self, _order: &ByteOrder, dest: &mut [u8]) {
@@ -65,6 +67,7 @@ impl Encoding for u8 {
     }
 }
 
+#[allow(clippy::identity_op)]
 impl Encoding for u16 {
     const SIZE: usize = 2;
     fn encode(&self, order: &ByteOrder, dest: &mut [u8]) {
@@ -96,6 +99,7 @@ impl Encoding for u16 {
     }
 }
 
+#[allow(clippy::identity_op, clippy::zero_prefixed_literal)]
 impl Encoding for u32 {
     const SIZE: usize = 4;
     fn encode(&self, order: &ByteOrder, dest: &mut [u8]) {
@@ -135,6 +139,7 @@ impl Encoding for u32 {
     }
 }
 
+#[allow(clippy::len_zero)]
 impl Encoding for i8 {
     const SIZE: usize = 1;
     fn encode(&self, _order: &ByteOrder, dest: &mut [u8]) {
@@ -148,6 +153,7 @@ impl Encoding for i8 {
     }
 }
 
+#[allow(clippy::identity_op)]
 impl Encoding for i16 {
     const SIZE: usize = 2;
     fn encode(&self, order: &ByteOrder, dest: &mut [u8]) {
@@ -179,6 +185,7 @@ impl Encoding for i16 {
     }
 }
 
+#[allow(clippy::identity_op, clippy::zero_prefixed_literal)]
 impl Encoding for i32 {
     const SIZE: usize = 4;
     fn encode(&self, order: &ByteOrder, dest: &mut [u8]) {
@@ -238,7 +245,7 @@ impl<V: Sized + Encoding> Writable for V {
     fn write(stream: &mut BufWriter<impl Write>, data: V, order: &ByteOrder) -> Result<()> {
         let mut buffer = vec![0; V::SIZE];
         data.encode(order, &mut buffer[..]);
-        stream.write_all(&buffer[..]).map_err(|e| Error::IoError(e))?;
+        stream.write_all(&buffer[..]).map_err(Error::IoError)?;
         Ok(())
     }
 }
@@ -261,4 +268,119 @@ impl<S: Write> WritableWrite for BufWriter<S> {
     fn write_value<T: Writable>(&mut self, data: T, order: &ByteOrder) -> Result<()> {
         T::write(self, data, order)
     }
-}
\ No newline at end of file
+}
+
+/// &[u8]から直接読むためのcursor
+/// BufReaderを通さず、byte列や文字列は入力のbufferを借用して返す
+#[derive(Clone, Debug)]
+pub struct SliceReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> SliceReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        SliceReader { data, position: 0 }
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.data[self.position..]
+    }
+
+    pub fn read<T: Encoding>(&mut self, order: &ByteOrder) -> Result<T> {
+        let data = self.read_bytes(T::SIZE)?;
+        Ok(T::decode(order, data))
+    }
+
+    pub fn read_bytes(&mut self, length: usize) -> Result<&'a [u8]> {
+        if self.data.len() - self.position < length {
+            return Err(Error::UnknownError);
+        }
+        let data = &self.data[self.position..self.position + length];
+        self.position += length;
+        Ok(data)
+    }
+
+    pub fn read_str(&mut self, length: usize) -> Result<&'a str> {
+        std::str::from_utf8(self.read_bytes(length)?).map_err(Error::StringError)
+    }
+
+    pub fn skip(&mut self, length: usize) -> Result<()> {
+        self.read_bytes(length).map(|_| ())
+    }
+}
+
+/// 呼び出し側が用意した&mut [u8]に書き込む
+#[derive(Debug)]
+pub struct SliceWriter<'a> {
+    data: &'a mut [u8],
+    position: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    pub fn new(data: &'a mut [u8]) -> Self {
+        SliceWriter { data, position: 0 }
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn write<T: Encoding>(&mut self, data: T, order: &ByteOrder) -> Result<()> {
+        let dest = self.reserve(T::SIZE)?;
+        data.encode(order, dest);
+        Ok(())
+    }
+
+    pub fn write_bytes(&mut self, data: &[u8]) -> Result<()> {
+        self.reserve(data.len())?.copy_from_slice(data);
+        Ok(())
+    }
+
+    pub fn write_zero(&mut self, length: usize) -> Result<()> {
+        self.reserve(length)?.iter_mut().for_each(|b| *b = 0);
+        Ok(())
+    }
+
+    fn reserve(&mut self, length: usize) -> Result<&mut [u8]> {
+        if self.data.len() - self.position < length {
+            return Err(Error::IoError(std::io::ErrorKind::WriteZero.into()));
+        }
+        let dest = &mut self.data[self.position..self.position + length];
+        self.position += length;
+        Ok(dest)
+    }
+}
+
+/// SliceReaderから読めるもの
+pub trait Decode<'a>: Sized {
+    fn decode_from(reader: &mut SliceReader<'a>, order: &ByteOrder) -> Result<Self>;
+}
+
+/// SliceWriterに書けるもの
+pub trait Encode {
+    fn encoded_length(&self) -> usize;
+    fn encode_to(&self, writer: &mut SliceWriter, order: &ByteOrder) -> Result<()>;
+
+    /// Vec<u8>の末尾に書き足す
+    /// 失敗したら書き足す前の長さに戻す
+    fn encode_to_vec(&self, dest: &mut Vec<u8>, order: &ByteOrder) -> Result<()> {
+        let start = dest.len();
+        dest.resize(start + self.encoded_length(), 0);
+        let result = self.encode_to(&mut SliceWriter::new(&mut dest[start..]), order);
+        if result.is_err() {
+            dest.truncate(start);
+        }
+        result
+    }
+}
+
+impl<'a, V: Encoding> Decode<'a> for V {
+    fn decode_from(reader: &mut SliceReader<'a>, order: &ByteOrder) -> Result<Self> {
+        reader.read(order)
+    }
+}
@@ -92,4 +92,54 @@ mod collect {
         LSBFirst.encode::<i32>(8 << 24 | 7 << 16 | 6 << 8 | 5, &mut output[4..8]);
         assert_eq!(&output[0..8], &data[0..8]);
     }
-}
\ No newline at end of file
+}
+mod slice {
+    use crate::Result;
+    use crate::read_util::{ByteOrder, Decode, Encode, SliceReader, SliceWriter};
+
+    #[test]
+    fn slice_reader_test() {
+        let input = [1u8, 2, 3, 4, 5, b'a', b'b', b'c'];
+        let mut reader = SliceReader::new(&input[..]);
+        assert_eq!(reader.read::<u16>(&ByteOrder::MSBFirst).unwrap(), 0x0102);
+        assert_eq!(u16::decode_from(&mut reader, &ByteOrder::LSBFirst).unwrap(), 0x0403);
+        reader.skip(1).unwrap();
+        assert_eq!(reader.position(), 5);
+        assert_eq!(reader.remaining(), b"abc");
+        assert_eq!(reader.read_str(3).unwrap(), "abc");
+        assert!(reader.read::<u8>(&ByteOrder::MSBFirst).is_err());
+    }
+
+    #[test]
+    fn slice_writer_test() {
+        let mut buffer = [0xffu8; 8];
+        let mut writer = SliceWriter::new(&mut buffer[..]);
+        writer.write(0x0102u16, &ByteOrder::MSBFirst).unwrap();
+        writer.write(0x0304u16, &ByteOrder::LSBFirst).unwrap();
+        writer.write_zero(1).unwrap();
+        writer.write_bytes(b"abc").unwrap();
+        assert_eq!(writer.position(), 8);
+        assert!(writer.write(0u8, &ByteOrder::MSBFirst).is_err());
+        assert_eq!(buffer, [1, 2, 4, 3, 0, b'a', b'b', b'c']);
+    }
+
+    #[test]
+    fn encode_to_vec_test() {
+        //encoded_lengthより多く書こうとする
+        struct Broken;
+
+        impl Encode for Broken {
+            fn encoded_length(&self) -> usize {
+                2
+            }
+
+            fn encode_to(&self, writer: &mut SliceWriter, order: &ByteOrder) -> Result<()> {
+                writer.write(0x01020304u32, order)
+            }
+        }
+
+        let mut buffer = [1, 2].to_vec();
+        assert!(Broken.encode_to_vec(&mut buffer, &ByteOrder::MSBFirst).is_err());
+        assert_eq!(buffer, [1, 2]);
+    }
+}
@@ -1,20 +1,21 @@
 use std::io::{Read, Write};
 
+use crate::{Error, Result};
+use crate::error::ErrorCode;
 use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct AllocNamedColorRequest;
 
 impl Readable for AllocNamedColorRequest {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Err(Error::ProtocolError(ErrorCode::Implementation, 0))
     }
 }
 
 impl Writable for AllocNamedColorRequest{
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Err(Error::ProtocolError(ErrorCode::Implementation, 0))
     }
 }
 
@@ -23,12 +24,12 @@ pub struct AllocNamedColorResponse;
 
 impl Readable for AllocNamedColorResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Err(Error::ProtocolError(ErrorCode::Implementation, 0))
     }
 }
 
 impl Writable for AllocNamedColorResponse{
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Err(Error::ProtocolError(ErrorCode::Implementation, 0))
     }
 }
@@ -1,20 +1,78 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, Readable, ReadableRead, Writable, WritableWrite};
 
+mod test;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AllowEventsMode {
+    AsyncPointer,
+    SyncPointer,
+    ReplayPointer,
+    AsyncKeyboard,
+    SyncKeyboard,
+    ReplayKeyboard,
+    AsyncBoth,
+    SyncBoth,
+}
+
+impl Readable for AllowEventsMode {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::AsyncPointer),
+            1 => Ok(Self::SyncPointer),
+            2 => Ok(Self::ReplayPointer),
+            3 => Ok(Self::AsyncKeyboard),
+            4 => Ok(Self::SyncKeyboard),
+            5 => Ok(Self::ReplayKeyboard),
+            6 => Ok(Self::AsyncBoth),
+            7 => Ok(Self::SyncBoth),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for AllowEventsMode {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::AsyncPointer => 0,
+            Self::SyncPointer => 1,
+            Self::ReplayPointer => 2,
+            Self::AsyncKeyboard => 3,
+            Self::SyncKeyboard => 4,
+            Self::ReplayKeyboard => 5,
+            Self::AsyncBoth => 6,
+            Self::SyncBoth => 7,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+/// timeが0ならCurrentTime
 #[derive(Clone, Debug, PartialEq)]
-pub struct AllowEventsRequest;
+pub struct AllowEventsRequest {
+    pub mode: AllowEventsMode,
+    pub time: u32,
+}
 
 impl Readable for AllowEventsRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let mode = stream.read_value(order)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let time = stream.read_value(order)?;
+        Ok(AllowEventsRequest { mode, time })
     }
 }
 
-impl Writable for AllowEventsRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for AllowEventsRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(35, order)?;
+        stream.write_value(data.mode, order)?;
+        stream.write_value::<u16>(2, order)?;
+        stream.write_value(data.time, order)?;
+        Ok(())
     }
 }
 
@@ -23,12 +81,12 @@ pub struct AllowEventsResponse;
 
 impl Readable for AllowEventsResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for AllowEventsResponse{
+impl Writable for AllowEventsResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
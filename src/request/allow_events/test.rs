@@ -0,0 +1,37 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::Error;
+    use crate::error::ErrorCode;
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::allow_events::{AllowEventsMode, AllowEventsRequest};
+
+    #[test]
+    fn read_test() {
+        let input = [2, 0, 2, 0, 0, 0x30, 0x39];
+        let value = AllowEventsRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, AllowEventsRequest { mode: AllowEventsMode::ReplayPointer, time: 12345 });
+        let input = [7, 2, 0, 0x39, 0x30, 0, 0];
+        let value = AllowEventsRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, AllowEventsRequest { mode: AllowEventsMode::SyncBoth, time: 12345 });
+        let input = [8, 0, 2, 0, 0, 0, 0];
+        match AllowEventsRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst) {
+            Err(Error::ProtocolError(ErrorCode::Value, 8)) => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn write_test() {
+        let data = AllowEventsRequest { mode: AllowEventsMode::SyncKeyboard, time: 12345 };
+        let mut buffer = [0; 8];
+        AllowEventsRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [35, 4, 0, 2, 0, 0, 0x30, 0x39]);
+        let mut buffer = [0; 8];
+        AllowEventsRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [35, 4, 2, 0, 0x39, 0x30, 0, 0]);
+    }
+}
@@ -1,20 +1,46 @@
+use std::collections::HashSet;
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+use crate::request::grab_pointer::{read_pointer_event_mask, write_pointer_event_mask};
+use crate::setup;
 
+mod test;
+
+/// timeが0ならCurrentTime
 #[derive(Clone, Debug, PartialEq)]
-pub struct ChangeActivePointerGrabRequest;
+pub struct ChangeActivePointerGrabRequest {
+    pub cursor: Option<u32>,
+    pub time: u32,
+    pub event_mask: HashSet<setup::Event>,
+}
 
 impl Readable for ChangeActivePointerGrabRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let cursor = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let time = stream.read_value(order)?;
+        let event_mask = read_pointer_event_mask(stream, order)?;
+        read_specified_length(stream, &mut [0; 2], 2)?;
+        Ok(ChangeActivePointerGrabRequest { cursor, time, event_mask })
     }
 }
 
-impl Writable for ChangeActivePointerGrabRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for ChangeActivePointerGrabRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(30, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(4, order)?;
+        stream.write_value(data.cursor.unwrap_or(0), order)?;
+        stream.write_value(data.time, order)?;
+        write_pointer_event_mask(stream, data.event_mask, order)?;
+        stream.write_all(&[0; 2]).map_err(Error::IoError)?;
+        Ok(())
     }
 }
 
@@ -23,12 +49,12 @@ pub struct ChangeActivePointerGrabResponse;
 
 impl Readable for ChangeActivePointerGrabResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for ChangeActivePointerGrabResponse{
+impl Writable for ChangeActivePointerGrabResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
@@ -0,0 +1,34 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::collections::HashSet;
+    use std::io::{BufReader, BufWriter};
+    use std::iter::FromIterator;
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::change_active_pointer_grab::ChangeActivePointerGrabRequest;
+    use crate::setup;
+
+    #[test]
+    fn read_test() {
+        let expected = ChangeActivePointerGrabRequest { cursor: Some(0x00200002), time: 12345, event_mask: HashSet::from_iter([setup::Event::EnterWindow, setup::Event::LeaveWindow].to_vec()) };
+        let input = [0, 0, 4, 0, 0x20, 0, 2, 0, 0, 0x30, 0x39, 0, 0x30, 0, 0];
+        let value = ChangeActivePointerGrabRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [0, 4, 0, 2, 0, 0x20, 0, 0x39, 0x30, 0, 0, 0x30, 0, 0, 0];
+        let value = ChangeActivePointerGrabRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn write_test() {
+        let data = ChangeActivePointerGrabRequest { cursor: None, time: 0, event_mask: HashSet::from_iter([setup::Event::Button1Motion].to_vec()) };
+        let mut buffer = [0; 16];
+        ChangeActivePointerGrabRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [30, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0]);
+        let mut buffer = [0; 16];
+        ChangeActivePointerGrabRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [30, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0]);
+    }
+}
@@ -0,0 +1,61 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::collections::HashSet;
+    use std::io::{BufReader, BufWriter};
+    use std::iter::FromIterator;
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::change_gc::ChangeGCRequest;
+    use crate::request::create_gc::{CreateGCValue, CreateGCValueFunction, CreateGCValueLineStyle, CreateGCValueMaskValue};
+
+    #[test]
+    fn read_test() {
+        //XChangeGC(display, gc, GCFunction | GCLineWidth | GCLineStyle | GCFont, { function: GXxor, line_width: 2, line_style: LineOnOffDash, font: 0x00400003 })
+        let input = [0, 7, 0, 1, 0, 0x40, 0, 0x31, 0x40, 0, 0, 6, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 3, 0, 0x40, 0];
+        let value = ChangeGCRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, ChangeGCRequest {
+            gc: 0x00400001,
+            value_mask: HashSet::from_iter([CreateGCValueMaskValue::Function, CreateGCValueMaskValue::LineWidth, CreateGCValueMaskValue::LineStyle, CreateGCValueMaskValue::Font].to_vec()),
+            value: CreateGCValue {
+                function: CreateGCValueFunction::Xor,
+                line_width: 2,
+                line_style: CreateGCValueLineStyle::OnOffDash,
+                font: 0x00400003,
+                ..Default::default()
+            },
+        });
+        let input = [0, 0, 4, 0, 0x40, 0, 1, 0, 0, 0, 4, 0, 0, 0, 5];
+        let value = ChangeGCRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, ChangeGCRequest {
+            gc: 0x00400001,
+            value_mask: HashSet::from_iter([CreateGCValueMaskValue::Foreground].to_vec()),
+            value: CreateGCValue {
+                foreground: 5,
+                ..Default::default()
+            },
+        });
+    }
+
+    #[test]
+    fn write_test() {
+        let value = ChangeGCRequest {
+            gc: 0x00400001,
+            value_mask: HashSet::from_iter([CreateGCValueMaskValue::Function, CreateGCValueMaskValue::LineWidth, CreateGCValueMaskValue::LineStyle, CreateGCValueMaskValue::Font].to_vec()),
+            value: CreateGCValue {
+                function: CreateGCValueFunction::Xor,
+                line_width: 2,
+                line_style: CreateGCValueLineStyle::OnOffDash,
+                font: 0x00400003,
+                ..Default::default()
+            },
+        };
+        let mut buffer = [0; 28];
+        ChangeGCRequest::write(&mut BufWriter::new(&mut buffer[..]), value.clone(), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [56, 0, 7, 0, 1, 0, 0x40, 0, 0x31, 0x40, 0, 0, 6, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 3, 0, 0x40, 0]);
+        let mut buffer = [0; 28];
+        ChangeGCRequest::write(&mut BufWriter::new(&mut buffer[..]), value, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [56, 0, 0, 7, 0, 0x40, 0, 1, 0, 0, 0x40, 0x31, 0, 0, 0, 6, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0x40, 0, 3]);
+    }
+}
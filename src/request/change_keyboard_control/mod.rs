@@ -1,20 +1,193 @@
-use std::io::{Read, Write};
+use std::collections::HashSet;
+use std::io::{BufReader, BufWriter, Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+use crate::request::value_list::{ValueListReader, ValueListWriter};
+
+mod test;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChangeKeyboardControlValueLedMode {
+    Off,
+    On,
+}
+
+impl Readable for ChangeKeyboardControlValueLedMode {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::Off),
+            1 => Ok(Self::On),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for ChangeKeyboardControlValueLedMode {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::Off => 0,
+            Self::On => 1,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChangeKeyboardControlValueAutoRepeatMode {
+    Off,
+    On,
+    Default,
+}
+
+impl Readable for ChangeKeyboardControlValueAutoRepeatMode {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::Off),
+            1 => Ok(Self::On),
+            2 => Ok(Self::Default),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for ChangeKeyboardControlValueAutoRepeatMode {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::Off => 0,
+            Self::On => 1,
+            Self::Default => 2,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+/// key_click_percent、bell_percent、bell_pitch、bell_durationは-1で既定値に戻す
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangeKeyboardControlValue {
+    pub key_click_percent: i8,
+    pub bell_percent: i8,
+    pub bell_pitch: i16,
+    pub bell_duration: i16,
+    pub led: u8,
+    pub led_mode: ChangeKeyboardControlValueLedMode,
+    pub key: u8,
+    pub auto_repeat_mode: ChangeKeyboardControlValueAutoRepeatMode,
+}
+
+impl Default for ChangeKeyboardControlValue {
+    fn default() -> Self {
+        ChangeKeyboardControlValue {
+            key_click_percent: -1,
+            bell_percent: -1,
+            bell_pitch: -1,
+            bell_duration: -1,
+            led: 0,
+            led_mode: ChangeKeyboardControlValueLedMode::Off,
+            key: 0,
+            auto_repeat_mode: ChangeKeyboardControlValueAutoRepeatMode::Default,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ChangeKeyboardControlValueMaskValue {
+    KeyClickPercent,
+    BellPercent,
+    BellPitch,
+    BellDuration,
+    Led,
+    LedMode,
+    Key,
+    AutoRepeatMode,
+}
+
+impl Readable for HashSet<ChangeKeyboardControlValueMaskValue> {
+    fn read(stream: &mut BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let mut value = HashSet::new();
+        let mask: u32 = stream.read_value(order)?;
+        if mask & 0x0001 != 0 { value.insert(ChangeKeyboardControlValueMaskValue::KeyClickPercent); }
+        if mask & 0x0002 != 0 { value.insert(ChangeKeyboardControlValueMaskValue::BellPercent); }
+        if mask & 0x0004 != 0 { value.insert(ChangeKeyboardControlValueMaskValue::BellPitch); }
+        if mask & 0x0008 != 0 { value.insert(ChangeKeyboardControlValueMaskValue::BellDuration); }
+        if mask & 0x0010 != 0 { value.insert(ChangeKeyboardControlValueMaskValue::Led); }
+        if mask & 0x0020 != 0 { value.insert(ChangeKeyboardControlValueMaskValue::LedMode); }
+        if mask & 0x0040 != 0 { value.insert(ChangeKeyboardControlValueMaskValue::Key); }
+        if mask & 0x0080 != 0 { value.insert(ChangeKeyboardControlValueMaskValue::AutoRepeatMode); }
+        if mask & 0xffffff00 != 0 { return Err(Error::ProtocolError(ErrorCode::Value, mask)); }
+        Ok(value)
+    }
+}
+
+impl Writable for HashSet<ChangeKeyboardControlValueMaskValue> {
+    fn write(stream: &mut BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let mut value = 0u32;
+        for data in data {
+            let mask = match data {
+                ChangeKeyboardControlValueMaskValue::KeyClickPercent => 0x0001,
+                ChangeKeyboardControlValueMaskValue::BellPercent => 0x0002,
+                ChangeKeyboardControlValueMaskValue::BellPitch => 0x0004,
+                ChangeKeyboardControlValueMaskValue::BellDuration => 0x0008,
+                ChangeKeyboardControlValueMaskValue::Led => 0x0010,
+                ChangeKeyboardControlValueMaskValue::LedMode => 0x0020,
+                ChangeKeyboardControlValueMaskValue::Key => 0x0040,
+                ChangeKeyboardControlValueMaskValue::AutoRepeatMode => 0x0080,
+            };
+            value |= mask;
+        }
+        stream.write_value(value, order)
+    }
+}
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct ChangeKeyboardControlRequest;
+pub struct ChangeKeyboardControlRequest {
+    pub value_mask: HashSet<ChangeKeyboardControlValueMaskValue>,
+    pub value: ChangeKeyboardControlValue,
+}
 
 impl Readable for ChangeKeyboardControlRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let length = stream.read_value::<u16>(order)? as usize;
+        if length < 2 {
+            return Err(Error::ProtocolError(ErrorCode::Length, 0));
+        }
+        let value_mask: HashSet<_> = stream.read_value(order)?;
+        let mut list = ValueListReader::read(stream, order, value_mask.len(), length - 2)?;
+        let mut value = ChangeKeyboardControlValue::default();
+        if value_mask.contains(&ChangeKeyboardControlValueMaskValue::KeyClickPercent) { value.key_click_percent = list.read_value()?; }
+        if value_mask.contains(&ChangeKeyboardControlValueMaskValue::BellPercent) { value.bell_percent = list.read_value()?; }
+        if value_mask.contains(&ChangeKeyboardControlValueMaskValue::BellPitch) { value.bell_pitch = list.read_value()?; }
+        if value_mask.contains(&ChangeKeyboardControlValueMaskValue::BellDuration) { value.bell_duration = list.read_value()?; }
+        if value_mask.contains(&ChangeKeyboardControlValueMaskValue::Led) { value.led = list.read_value()?; }
+        if value_mask.contains(&ChangeKeyboardControlValueMaskValue::LedMode) { value.led_mode = list.read_value()?; }
+        if value_mask.contains(&ChangeKeyboardControlValueMaskValue::Key) { value.key = list.read_value()?; }
+        if value_mask.contains(&ChangeKeyboardControlValueMaskValue::AutoRepeatMode) { value.auto_repeat_mode = list.read_value()?; }
+        Ok(ChangeKeyboardControlRequest {
+            value_mask,
+            value,
+        })
     }
 }
 
-impl Writable for ChangeKeyboardControlRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for ChangeKeyboardControlRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value = data.value;
+        let mut list = ValueListWriter::new();
+        if data.value_mask.contains(&ChangeKeyboardControlValueMaskValue::KeyClickPercent) { list.write_value(value.key_click_percent as i32)?; }
+        if data.value_mask.contains(&ChangeKeyboardControlValueMaskValue::BellPercent) { list.write_value(value.bell_percent as i32)?; }
+        if data.value_mask.contains(&ChangeKeyboardControlValueMaskValue::BellPitch) { list.write_value(value.bell_pitch as i32)?; }
+        if data.value_mask.contains(&ChangeKeyboardControlValueMaskValue::BellDuration) { list.write_value(value.bell_duration as i32)?; }
+        if data.value_mask.contains(&ChangeKeyboardControlValueMaskValue::Led) { list.write_value(value.led)?; }
+        if data.value_mask.contains(&ChangeKeyboardControlValueMaskValue::LedMode) { list.write_value(value.led_mode)?; }
+        if data.value_mask.contains(&ChangeKeyboardControlValueMaskValue::Key) { list.write_value(value.key)?; }
+        if data.value_mask.contains(&ChangeKeyboardControlValueMaskValue::AutoRepeatMode) { list.write_value(value.auto_repeat_mode)?; }
+        stream.write_value::<u8>(102, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value((2 + list.len()) as u16, order)?;
+        stream.write_value(data.value_mask, order)?;
+        list.write(stream, order)
     }
 }
 
@@ -23,12 +196,12 @@ pub struct ChangeKeyboardControlResponse;
 
 impl Readable for ChangeKeyboardControlResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for ChangeKeyboardControlResponse{
+impl Writable for ChangeKeyboardControlResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
@@ -0,0 +1,64 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::collections::HashSet;
+    use std::io::{BufReader, BufWriter};
+    use std::iter::FromIterator;
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::change_keyboard_control::{ChangeKeyboardControlRequest, ChangeKeyboardControlValue, ChangeKeyboardControlValueAutoRepeatMode, ChangeKeyboardControlValueLedMode, ChangeKeyboardControlValueMaskValue};
+
+    #[test]
+    fn read_test() {
+        //xset b 50 440 -1 r off
+        let input = [0, 6, 0, 0x8e, 0, 0, 0, 50, 0, 0, 0, 0xb8, 1, 0, 0, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0];
+        let value = ChangeKeyboardControlRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, ChangeKeyboardControlRequest {
+            value_mask: HashSet::from_iter([ChangeKeyboardControlValueMaskValue::BellPercent,
+                ChangeKeyboardControlValueMaskValue::BellPitch,
+                ChangeKeyboardControlValueMaskValue::BellDuration,
+                ChangeKeyboardControlValueMaskValue::AutoRepeatMode].to_vec()),
+            value: ChangeKeyboardControlValue {
+                bell_percent: 50,
+                bell_pitch: 440,
+                bell_duration: -1,
+                auto_repeat_mode: ChangeKeyboardControlValueAutoRepeatMode::Off,
+                ..Default::default()
+            },
+        });
+        let input = [0, 0, 4, 0, 0, 0, 0x30, 0, 0, 0, 3, 0, 0, 0, 1];
+        let value = ChangeKeyboardControlRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, ChangeKeyboardControlRequest {
+            value_mask: HashSet::from_iter([ChangeKeyboardControlValueMaskValue::Led, ChangeKeyboardControlValueMaskValue::LedMode].to_vec()),
+            value: ChangeKeyboardControlValue {
+                led: 3,
+                led_mode: ChangeKeyboardControlValueLedMode::On,
+                ..Default::default()
+            },
+        });
+    }
+
+    #[test]
+    fn write_test() {
+        let value = ChangeKeyboardControlRequest {
+            value_mask: HashSet::from_iter([ChangeKeyboardControlValueMaskValue::BellPercent,
+                ChangeKeyboardControlValueMaskValue::BellPitch,
+                ChangeKeyboardControlValueMaskValue::BellDuration,
+                ChangeKeyboardControlValueMaskValue::AutoRepeatMode].to_vec()),
+            value: ChangeKeyboardControlValue {
+                bell_percent: 50,
+                bell_pitch: 440,
+                bell_duration: -1,
+                auto_repeat_mode: ChangeKeyboardControlValueAutoRepeatMode::Off,
+                ..Default::default()
+            },
+        };
+        let mut buffer = [0; 24];
+        ChangeKeyboardControlRequest::write(&mut BufWriter::new(&mut buffer[..]), value.clone(), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [102, 0, 6, 0, 0x8e, 0, 0, 0, 50, 0, 0, 0, 0xb8, 1, 0, 0, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0]);
+        let mut buffer = [0; 24];
+        ChangeKeyboardControlRequest::write(&mut BufWriter::new(&mut buffer[..]), value, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [102, 0, 0, 6, 0, 0, 0, 0x8e, 0, 0, 0, 50, 0, 0, 1, 0xb8, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0]);
+    }
+}
@@ -1,20 +1,296 @@
-use std::io::{Read, Write};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::ops::Range;
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, Decode, read_specified_length, Readable, ReadableRead, SliceReader, Writable, WritableWrite};
+use crate::request::{DecodeBody, ReadableBody};
 
+mod test;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChangePropertyMode {
+    Replace,
+    Prepend,
+    Append,
+}
+
+impl ChangePropertyMode {
+    fn from_u8(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(Self::Replace),
+            1 => Ok(Self::Prepend),
+            2 => Ok(Self::Append),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Readable for ChangePropertyMode {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        Self::from_u8(stream.read_value(order)?)
+    }
+}
+
+impl Writable for ChangePropertyMode {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::Replace => 0,
+            Self::Prepend => 1,
+            Self::Append => 2,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+/// formatごとの値の並び
+/// 16bitと32bitの値はconnectionのbyte orderで読み書きする
 #[derive(Clone, Debug, PartialEq)]
-pub struct ChangePropertyRequest;
+pub enum PropertyValue {
+    Format8(Vec<u8>),
+    Format16(Vec<u16>),
+    Format32(Vec<u32>),
+}
+
+impl PropertyValue {
+    pub fn format(&self) -> u8 {
+        match self {
+            PropertyValue::Format8(_) => 8,
+            PropertyValue::Format16(_) => 16,
+            PropertyValue::Format32(_) => 32,
+        }
+    }
+
+    /// formatの単位での長さ
+    pub fn len(&self) -> usize {
+        match self {
+            PropertyValue::Format8(value) => value.len(),
+            PropertyValue::Format16(value) => value.len(),
+            PropertyValue::Format32(value) => value.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// padを含まないbyte数
+    pub fn byte_len(&self) -> usize {
+        self.len() * (self.format() as usize / 8)
+    }
+
+    /// formatの単位でのrange
+    pub fn slice(&self, range: Range<usize>) -> Self {
+        match self {
+            PropertyValue::Format8(value) => PropertyValue::Format8(value[range].to_vec()),
+            PropertyValue::Format16(value) => PropertyValue::Format16(value[range].to_vec()),
+            PropertyValue::Format32(value) => PropertyValue::Format32(value[range].to_vec()),
+        }
+    }
+}
+
+/// PropertyValueの借用版
+/// 16bitと32bitの値は、connectionのbyte orderのままのbyte列
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyValueRef<'a> {
+    Format8(&'a [u8]),
+    Format16(&'a [u8]),
+    Format32(&'a [u8]),
+}
+
+impl<'a> PropertyValueRef<'a> {
+    pub fn format(&self) -> u8 {
+        match self {
+            PropertyValueRef::Format8(_) => 8,
+            PropertyValueRef::Format16(_) => 16,
+            PropertyValueRef::Format32(_) => 32,
+        }
+    }
+
+    /// padを含まないbyte列
+    pub fn bytes(&self) -> &'a [u8] {
+        match self {
+            PropertyValueRef::Format8(value) | PropertyValueRef::Format16(value) | PropertyValueRef::Format32(value) => value,
+        }
+    }
+
+    pub fn to_value(&self, order: &ByteOrder) -> PropertyValue {
+        match self {
+            PropertyValueRef::Format8(value) => PropertyValue::Format8(value.to_vec()),
+            PropertyValueRef::Format16(value) => PropertyValue::Format16(value.chunks(2).map(|c| order.decode(c)).collect()),
+            PropertyValueRef::Format32(value) => PropertyValue::Format32(value.chunks(4).map(|c| order.decode(c)).collect()),
+        }
+    }
+}
+
+/// formatの単位でlength個の値を読む
+/// padは読まない
+pub(crate) fn read_property_value(stream: &mut BufReader<impl Read>, order: &ByteOrder, format: u8, length: usize) -> Result<PropertyValue> {
+    match format {
+        8 => {
+            let mut value = vec![0; length];
+            read_specified_length(stream, &mut value[..], length)?;
+            Ok(PropertyValue::Format8(value))
+        }
+        16 => {
+            let mut value = Vec::with_capacity(length);
+            for _ in 0..length {
+                value.push(stream.read_value(order)?);
+            }
+            Ok(PropertyValue::Format16(value))
+        }
+        32 => {
+            let mut value = Vec::with_capacity(length);
+            for _ in 0..length {
+                value.push(stream.read_value(order)?);
+            }
+            Ok(PropertyValue::Format32(value))
+        }
+        other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+    }
+}
+
+/// 4byte境界までのpadも書く
+pub(crate) fn write_property_value(stream: &mut BufWriter<impl Write>, value: PropertyValue, order: &ByteOrder) -> Result<()> {
+    let byte_len = value.byte_len();
+    match value {
+        PropertyValue::Format8(value) => stream.write_all(&value[..]).map_err(Error::IoError)?,
+        PropertyValue::Format16(value) => for v in value { stream.write_value(v, order)?; },
+        PropertyValue::Format32(value) => for v in value { stream.write_value(v, order)?; },
+    }
+    stream.write_all(&[0; 4][..(!byte_len).wrapping_add(1) & 3]).map_err(Error::IoError)?;
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangePropertyRequest {
+    pub mode: ChangePropertyMode,
+    pub window: u32,
+    pub property: u32,
+    pub type_: u32,
+    pub data: PropertyValue,
+}
 
 impl Readable for ChangePropertyRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let data = stream.read_value(order)?;
+        let length = stream.read_value::<u16>(order)?;
+        Self::read_body(stream, data, length as usize, order)
     }
 }
 
-impl Writable for ChangePropertyRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl ReadableBody for ChangePropertyRequest {
+    fn read_body(stream: &mut std::io::BufReader<impl Read>, data: u8, length: usize, order: &ByteOrder) -> Result<Self> {
+        let mode = ChangePropertyMode::from_u8(data)?;
+        if length < 6 {
+            return Err(Error::ProtocolError(ErrorCode::Length, 0));
+        }
+        let window = stream.read_value(order)?;
+        let property = stream.read_value(order)?;
+        let type_ = stream.read_value(order)?;
+        let format = stream.read_value::<u8>(order)?;
+        read_specified_length(stream, &mut [0; 3], 3)?;
+        let units = stream.read_value::<u32>(order)? as usize;
+        if format != 8 && format != 16 && format != 32 {
+            return Err(Error::ProtocolError(ErrorCode::Value, format as u32));
+        }
+        let data_len = units * (format as usize / 8);
+        let buffer_len = (length - 6) << 2;
+        if data_len > buffer_len || buffer_len - data_len >= 4 {
+            return Err(Error::ProtocolError(ErrorCode::Length, 0));
+        }
+        let data = read_property_value(stream, order, format, units)?;
+        read_specified_length(stream, &mut [0; 4], buffer_len - data_len)?;
+        Ok(ChangePropertyRequest {
+            mode,
+            window,
+            property,
+            type_,
+            data,
+        })
+    }
+}
+
+impl Writable for ChangePropertyRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(18, order)?;
+        stream.write_value(data.mode, order)?;
+        stream.write_value((6 + ((data.data.byte_len() + 3) >> 2)) as u16, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_value(data.property, order)?;
+        stream.write_value(data.type_, order)?;
+        stream.write_value(data.data.format(), order)?;
+        stream.write_all(&[0; 3]).map_err(Error::IoError)?;
+        stream.write_value(data.data.len() as u32, order)?;
+        write_property_value(stream, data.data, order)
+    }
+}
+
+/// ChangePropertyRequestのdataを入力のbufferから借用する版
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangePropertyRequestRef<'a> {
+    pub mode: ChangePropertyMode,
+    pub window: u32,
+    pub property: u32,
+    pub type_: u32,
+    pub data: PropertyValueRef<'a>,
+}
+
+impl<'a> ChangePropertyRequestRef<'a> {
+    /// 16bitと32bitの値を読むためにconnectionのbyte orderを使う
+    pub fn to_request(&self, order: &ByteOrder) -> ChangePropertyRequest {
+        ChangePropertyRequest {
+            mode: self.mode.clone(),
+            window: self.window,
+            property: self.property,
+            type_: self.type_,
+            data: self.data.to_value(order),
+        }
+    }
+}
+
+impl<'a> Decode<'a> for ChangePropertyRequestRef<'a> {
+    fn decode_from(reader: &mut SliceReader<'a>, order: &ByteOrder) -> Result<Self> {
+        let data = reader.read(order)?;
+        let length = reader.read::<u16>(order)? as usize;
+        Self::decode_body(reader, data, length, order)
+    }
+}
+
+impl<'a> DecodeBody<'a> for ChangePropertyRequestRef<'a> {
+    fn decode_body(reader: &mut SliceReader<'a>, data: u8, length: usize, order: &ByteOrder) -> Result<Self> {
+        let mode = ChangePropertyMode::from_u8(data)?;
+        if length < 6 {
+            return Err(Error::ProtocolError(ErrorCode::Length, 0));
+        }
+        let window = reader.read(order)?;
+        let property = reader.read(order)?;
+        let type_ = reader.read(order)?;
+        let format = reader.read::<u8>(order)?;
+        reader.skip(3)?;
+        let units = reader.read::<u32>(order)? as usize;
+        if format != 8 && format != 16 && format != 32 {
+            return Err(Error::ProtocolError(ErrorCode::Value, format as u32));
+        }
+        let data_len = units * (format as usize / 8);
+        let buffer_len = (length - 6) << 2;
+        if data_len > buffer_len || buffer_len - data_len >= 4 {
+            return Err(Error::ProtocolError(ErrorCode::Length, 0));
+        }
+        let data = reader.read_bytes(data_len)?;
+        reader.skip(buffer_len - data_len)?;
+        let data = match format {
+            8 => PropertyValueRef::Format8(data),
+            16 => PropertyValueRef::Format16(data),
+            _ => PropertyValueRef::Format32(data),
+        };
+        Ok(ChangePropertyRequestRef {
+            mode,
+            window,
+            property,
+            type_,
+            data,
+        })
     }
 }
 
@@ -23,12 +299,12 @@ pub struct ChangePropertyResponse;
 
 impl Readable for ChangePropertyResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for ChangePropertyResponse{
+impl Writable for ChangePropertyResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
@@ -0,0 +1,78 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::Error;
+    use crate::error::ErrorCode;
+    use crate::read_util::{ByteOrder, Decode, Readable, SliceReader, Writable};
+    use crate::request::change_property::{ChangePropertyMode, ChangePropertyRequest, ChangePropertyRequestRef, PropertyValue, PropertyValueRef};
+
+    #[test]
+    fn read_test() {
+        let expected = ChangePropertyRequest { mode: ChangePropertyMode::Append, window: 1, property: 39, type_: 31, data: PropertyValue::Format8(b"xterm".to_vec()) };
+        let input = [2, 0, 8, 0, 0, 0, 1, 0, 0, 0, 39, 0, 0, 0, 31, 8, 0, 0, 0, 0, 0, 0, 5, b'x', b't', b'e', b'r', b'm', 0, 0, 0];
+        let value = ChangePropertyRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [2, 8, 0, 1, 0, 0, 0, 39, 0, 0, 0, 31, 0, 0, 0, 8, 0, 0, 0, 5, 0, 0, 0, b'x', b't', b'e', b'r', b'm', 0, 0, 0];
+        let value = ChangePropertyRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+
+        let input = [0, 0, 7, 0, 0, 0, 1, 0, 0, 0, 39, 0, 0, 0, 31, 32, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 2];
+        let value = ChangePropertyRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, ChangePropertyRequest { mode: ChangePropertyMode::Replace, window: 1, property: 39, type_: 31, data: PropertyValue::Format32(vec![2]) });
+        let input = [0, 0, 7, 0, 0, 0, 1, 0, 0, 0, 39, 0, 0, 0, 31, 24, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 2];
+        match ChangePropertyRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst) {
+            Err(Error::ProtocolError(ErrorCode::Value, 24)) => {}
+            _ => panic!(),
+        }
+        let input = [0, 0, 7, 0, 0, 0, 1, 0, 0, 0, 39, 0, 0, 0, 31, 16, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 2];
+        match ChangePropertyRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst) {
+            Err(Error::ProtocolError(ErrorCode::Length, _)) => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn write_test() {
+        let data = ChangePropertyRequest { mode: ChangePropertyMode::Prepend, window: 1, property: 39, type_: 31, data: PropertyValue::Format16(vec![1, 2, 3]) };
+        let mut buffer = [0xff; 32];
+        ChangePropertyRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [18, 1, 0, 8, 0, 0, 0, 1, 0, 0, 0, 39, 0, 0, 0, 31, 16, 0, 0, 0, 0, 0, 0, 3, 0, 1, 0, 2, 0, 3, 0, 0]);
+        let mut buffer = [0xff; 32];
+        ChangePropertyRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [18, 1, 8, 0, 1, 0, 0, 0, 39, 0, 0, 0, 31, 0, 0, 0, 16, 0, 0, 0, 3, 0, 0, 0, 1, 0, 2, 0, 3, 0, 0, 0]);
+    }
+
+    #[test]
+    fn swap_test() {
+        //LSBのclientが書いた32bitの値はMSBのclientにも同じ値として届く
+        let input = [0, 7, 0, 1, 0, 0, 0, 39, 0, 0, 0, 6, 0, 0, 0, 32, 0, 0, 0, 1, 0, 0, 0, 0x78, 0x56, 0x34, 0x12];
+        let value = ChangePropertyRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value.data, PropertyValue::Format32(vec![0x12345678]));
+        let mut buffer = [0; 28];
+        ChangePropertyRequest::write(&mut BufWriter::new(&mut buffer[..]), value, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [18, 0, 0, 7, 0, 0, 0, 1, 0, 0, 0, 39, 0, 0, 0, 6, 32, 0, 0, 0, 0, 0, 0, 1, 0x12, 0x34, 0x56, 0x78]);
+    }
+
+    #[test]
+    fn ref_test() {
+        //16bitの値はbyte列のまま借用し、複製するときにbyte orderで読む
+        let input = [1, 8, 0, 1, 0, 0, 0, 39, 0, 0, 0, 31, 0, 0, 0, 16, 0, 0, 0, 3, 0, 0, 0, 1, 0, 2, 0, 3, 0, 0, 0];
+        let value = ChangePropertyRequestRef::decode_from(&mut SliceReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value.data, PropertyValueRef::Format16(&input[23..29]));
+        assert_eq!(value.data.bytes().as_ptr(), input[23..].as_ptr());
+        assert_eq!(value.to_request(&ByteOrder::LSBFirst), ChangePropertyRequest {
+            mode: ChangePropertyMode::Prepend,
+            window: 1,
+            property: 39,
+            type_: 31,
+            data: PropertyValue::Format16(vec![1, 2, 3]),
+        });
+        let input = [0, 0, 7, 0, 0, 0, 1, 0, 0, 0, 39, 0, 0, 0, 31, 24, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 2];
+        assert!(matches!(ChangePropertyRequestRef::decode_from(&mut SliceReader::new(&input[..]), &ByteOrder::MSBFirst), Err(Error::ProtocolError(ErrorCode::Value, 24))));
+        let input = [0, 0, 7, 0, 0, 0, 1, 0, 0, 0, 39, 0, 0, 0, 31, 16, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 2];
+        assert!(matches!(ChangePropertyRequestRef::decode_from(&mut SliceReader::new(&input[..]), &ByteOrder::MSBFirst), Err(Error::ProtocolError(ErrorCode::Length, 0))));
+    }
+}
@@ -1,20 +1,59 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, Readable, ReadableRead, Writable, WritableWrite};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct ChangeSaveSetRequest;
+pub enum ChangeSaveSetMode {
+    Insert,
+    Delete,
+}
+
+impl Readable for ChangeSaveSetMode {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::Insert),
+            1 => Ok(Self::Delete),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for ChangeSaveSetMode {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::Insert => 0,
+            Self::Delete => 1,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangeSaveSetRequest {
+    pub mode: ChangeSaveSetMode,
+    pub window: u32,
+}
 
 impl Readable for ChangeSaveSetRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let mode = stream.read_value(order)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let window = stream.read_value(order)?;
+        Ok(ChangeSaveSetRequest { mode, window })
     }
 }
 
-impl Writable for ChangeSaveSetRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for ChangeSaveSetRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(6, order)?;
+        stream.write_value(data.mode, order)?;
+        stream.write_value::<u16>(2, order)?;
+        stream.write_value(data.window, order)?;
+        Ok(())
     }
 }
 
@@ -23,12 +62,12 @@ pub struct ChangeSaveSetResponse;
 
 impl Readable for ChangeSaveSetResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for ChangeSaveSetResponse{
+impl Writable for ChangeSaveSetResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
@@ -0,0 +1,29 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::change_save_set::{ChangeSaveSetMode, ChangeSaveSetRequest};
+
+    #[test]
+    fn read_test() {
+        let input = [1, 0, 2, 0, 0, 0, 3];
+        let value = ChangeSaveSetRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, ChangeSaveSetRequest { mode: ChangeSaveSetMode::Delete, window: 3 });
+        let input = [0, 2, 0, 3, 0, 0, 0];
+        let value = ChangeSaveSetRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, ChangeSaveSetRequest { mode: ChangeSaveSetMode::Insert, window: 3 });
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0; 8];
+        ChangeSaveSetRequest::write(&mut BufWriter::new(&mut buffer[..]), ChangeSaveSetRequest { mode: ChangeSaveSetMode::Delete, window: 3 }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [6, 1, 0, 2, 0, 0, 0, 3]);
+        let mut buffer = [0; 8];
+        ChangeSaveSetRequest::write(&mut BufWriter::new(&mut buffer[..]), ChangeSaveSetRequest { mode: ChangeSaveSetMode::Insert, window: 3 }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [6, 0, 2, 0, 3, 0, 0, 0]);
+    }
+}
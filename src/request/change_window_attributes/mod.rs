@@ -1,20 +1,44 @@
+use std::collections::HashSet;
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+use crate::request::create_window::{CreateWindowValue, CreateWindowValueMaskValue, read_window_value, write_window_value};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct ChangeWindowAttributesRequest;
+pub struct ChangeWindowAttributesRequest {
+    pub window: u32,
+    pub value_mask: HashSet<CreateWindowValueMaskValue>,
+    pub value: CreateWindowValue,
+}
 
 impl Readable for ChangeWindowAttributesRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let length = stream.read_value::<u16>(order)? as usize;
+        if length < 3 {
+            return Err(Error::ProtocolError(ErrorCode::Length, 0));
+        }
+        let window = stream.read_value(order)?;
+        let (value_mask, value) = read_window_value(stream, order, length - 3)?;
+        Ok(ChangeWindowAttributesRequest {
+            window,
+            value_mask,
+            value,
+        })
     }
 }
 
-impl Writable for ChangeWindowAttributesRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for ChangeWindowAttributesRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(2, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value((3 + data.value_mask.len()) as u16, order)?;
+        stream.write_value(data.window, order)?;
+        write_window_value(stream, data.value_mask, data.value, order)
     }
 }
 
@@ -23,12 +47,12 @@ pub struct ChangeWindowAttributesResponse;
 
 impl Readable for ChangeWindowAttributesResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for ChangeWindowAttributesResponse{
+impl Writable for ChangeWindowAttributesResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
@@ -0,0 +1,74 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::collections::HashSet;
+    use std::io::{BufReader, BufWriter};
+    use std::iter::FromIterator;
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::change_window_attributes::ChangeWindowAttributesRequest;
+    use crate::request::create_window::{CreateWindowValue, CreateWindowValueBackgroundPixmap, CreateWindowValueBackingStore, CreateWindowValueColormap, CreateWindowValueMaskValue};
+
+    #[test]
+    fn read_test() {
+        const MSB: [u8; 28] = [2, 0, 0, 7, 0, 0, 0, 1, 0, 0, 0x24, 0x41,
+            0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 9];
+        const LSB: [u8; 28] = [2, 0, 7, 0, 1, 0, 0, 0, 0x41, 0x24, 0, 0,
+            1, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 9, 0, 0, 0];
+
+        fn expected() -> ChangeWindowAttributesRequest {
+            ChangeWindowAttributesRequest {
+                window: 1,
+                value_mask: HashSet::from_iter([CreateWindowValueMaskValue::BackgroundPixmap,
+                    CreateWindowValueMaskValue::BackingStore,
+                    CreateWindowValueMaskValue::SaveUnder,
+                    CreateWindowValueMaskValue::Colormap].to_vec()),
+                value: CreateWindowValue {
+                    background_pixmap: CreateWindowValueBackgroundPixmap::ParentRelative,
+                    backing_store: CreateWindowValueBackingStore::Always,
+                    save_under: true,
+                    colormap: CreateWindowValueColormap::Colormap(9),
+                    ..Default::default()
+                },
+            }
+        }
+
+        let value = ChangeWindowAttributesRequest::read(&mut BufReader::new(&MSB[1..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected());
+        let value = ChangeWindowAttributesRequest::read(&mut BufReader::new(&LSB[1..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected());
+    }
+
+    #[test]
+    fn write_test() {
+        const MSB: [u8; 28] = [2, 0, 0, 7, 0, 0, 0, 1, 0, 0, 0x24, 0x41,
+            0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 9];
+        const LSB: [u8; 28] = [2, 0, 7, 0, 1, 0, 0, 0, 0x41, 0x24, 0, 0,
+            1, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 9, 0, 0, 0];
+
+        fn expected() -> ChangeWindowAttributesRequest {
+            ChangeWindowAttributesRequest {
+                window: 1,
+                value_mask: HashSet::from_iter([CreateWindowValueMaskValue::BackgroundPixmap,
+                    CreateWindowValueMaskValue::BackingStore,
+                    CreateWindowValueMaskValue::SaveUnder,
+                    CreateWindowValueMaskValue::Colormap].to_vec()),
+                value: CreateWindowValue {
+                    background_pixmap: CreateWindowValueBackgroundPixmap::ParentRelative,
+                    backing_store: CreateWindowValueBackingStore::Always,
+                    save_under: true,
+                    colormap: CreateWindowValueColormap::Colormap(9),
+                    ..Default::default()
+                },
+            }
+        }
+
+        let mut buffer = [0; 28];
+        ChangeWindowAttributesRequest::write(&mut BufWriter::new(&mut buffer[..]), expected(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, MSB);
+        let mut buffer = [0; 28];
+        ChangeWindowAttributesRequest::write(&mut BufWriter::new(&mut buffer[..]), expected(), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, LSB);
+    }
+}
@@ -1,20 +1,59 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, Readable, ReadableRead, Writable, WritableWrite};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct CirculateWindowRequest;
+pub enum CirculateWindowDirection {
+    RaiseLowest,
+    LowerHighest,
+}
+
+impl Readable for CirculateWindowDirection {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::RaiseLowest),
+            1 => Ok(Self::LowerHighest),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for CirculateWindowDirection {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::RaiseLowest => 0,
+            Self::LowerHighest => 1,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CirculateWindowRequest {
+    pub direction: CirculateWindowDirection,
+    pub window: u32,
+}
 
 impl Readable for CirculateWindowRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let direction = stream.read_value(order)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let window = stream.read_value(order)?;
+        Ok(CirculateWindowRequest { direction, window })
     }
 }
 
-impl Writable for CirculateWindowRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for CirculateWindowRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(13, order)?;
+        stream.write_value(data.direction, order)?;
+        stream.write_value::<u16>(2, order)?;
+        stream.write_value(data.window, order)?;
+        Ok(())
     }
 }
 
@@ -23,12 +62,12 @@ pub struct CirculateWindowResponse;
 
 impl Readable for CirculateWindowResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for CirculateWindowResponse{
+impl Writable for CirculateWindowResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
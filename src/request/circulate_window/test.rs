@@ -0,0 +1,29 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::circulate_window::{CirculateWindowDirection, CirculateWindowRequest};
+
+    #[test]
+    fn read_test() {
+        let input = [1, 0, 2, 0, 0, 0, 3];
+        let value = CirculateWindowRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, CirculateWindowRequest { direction: CirculateWindowDirection::LowerHighest, window: 3 });
+        let input = [0, 2, 0, 3, 0, 0, 0];
+        let value = CirculateWindowRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, CirculateWindowRequest { direction: CirculateWindowDirection::RaiseLowest, window: 3 });
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0; 8];
+        CirculateWindowRequest::write(&mut BufWriter::new(&mut buffer[..]), CirculateWindowRequest { direction: CirculateWindowDirection::LowerHighest, window: 3 }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [13, 1, 0, 2, 0, 0, 0, 3]);
+        let mut buffer = [0; 8];
+        CirculateWindowRequest::write(&mut BufWriter::new(&mut buffer[..]), CirculateWindowRequest { direction: CirculateWindowDirection::RaiseLowest, window: 3 }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [13, 0, 2, 0, 3, 0, 0, 0]);
+    }
+}
@@ -1,20 +1,31 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct CloseFontRequest;
+pub struct CloseFontRequest {
+    pub font: u32,
+}
 
 impl Readable for CloseFontRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let font = stream.read_value(order)?;
+        Ok(CloseFontRequest { font })
     }
 }
 
-impl Writable for CloseFontRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for CloseFontRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(46, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(2, order)?;
+        stream.write_value(data.font, order)?;
+        Ok(())
     }
 }
 
@@ -23,12 +34,12 @@ pub struct CloseFontResponse;
 
 impl Readable for CloseFontResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for CloseFontResponse{
+impl Writable for CloseFontResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
@@ -0,0 +1,29 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::close_font::CloseFontRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 2, 0, 0x20, 0, 1];
+        let value = CloseFontRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, CloseFontRequest { font: 0x00200001 });
+        let input = [0, 2, 0, 1, 0, 0x20, 0];
+        let value = CloseFontRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, CloseFontRequest { font: 0x00200001 });
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0xff; 8];
+        CloseFontRequest::write(&mut BufWriter::new(&mut buffer[..]), CloseFontRequest { font: 0x00200001 }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [46, 0, 0, 2, 0, 0x20, 0, 1]);
+        let mut buffer = [0xff; 8];
+        CloseFontRequest::write(&mut BufWriter::new(&mut buffer[..]), CloseFontRequest { font: 0x00200001 }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [46, 0, 2, 0, 1, 0, 0x20, 0]);
+    }
+}
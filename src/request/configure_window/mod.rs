@@ -1,20 +1,172 @@
-use std::io::{Read, Write};
+use std::collections::HashSet;
+use std::io::{BufReader, BufWriter, Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+use crate::request::value_list::{ValueListReader, ValueListWriter};
+
+mod test;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfigureWindowValueStackMode {
+    Above,
+    Below,
+    TopIf,
+    BottomIf,
+    Opposite,
+}
+
+impl Readable for ConfigureWindowValueStackMode {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::Above),
+            1 => Ok(Self::Below),
+            2 => Ok(Self::TopIf),
+            3 => Ok(Self::BottomIf),
+            4 => Ok(Self::Opposite),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for ConfigureWindowValueStackMode {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::Above => 0,
+            Self::Below => 1,
+            Self::TopIf => 2,
+            Self::BottomIf => 3,
+            Self::Opposite => 4,
+        };
+        stream.write_value(value, order)
+    }
+}
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct ConfigureWindowRequest;
+pub struct ConfigureWindowValue {
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    pub border_width: u16,
+    pub sibling: u32,
+    pub stack_mode: ConfigureWindowValueStackMode,
+}
+
+impl Default for ConfigureWindowValue {
+    fn default() -> Self {
+        ConfigureWindowValue {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+            border_width: 0,
+            sibling: 0,
+            stack_mode: ConfigureWindowValueStackMode::Above,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ConfigureWindowValueMaskValue {
+    X,
+    Y,
+    Width,
+    Height,
+    BorderWidth,
+    Sibling,
+    StackMode,
+}
+
+impl Readable for HashSet<ConfigureWindowValueMaskValue> {
+    fn read(stream: &mut BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let mut value = HashSet::new();
+        let mask: u16 = stream.read_value(order)?;
+        if mask & 0x0001 != 0 { value.insert(ConfigureWindowValueMaskValue::X); }
+        if mask & 0x0002 != 0 { value.insert(ConfigureWindowValueMaskValue::Y); }
+        if mask & 0x0004 != 0 { value.insert(ConfigureWindowValueMaskValue::Width); }
+        if mask & 0x0008 != 0 { value.insert(ConfigureWindowValueMaskValue::Height); }
+        if mask & 0x0010 != 0 { value.insert(ConfigureWindowValueMaskValue::BorderWidth); }
+        if mask & 0x0020 != 0 { value.insert(ConfigureWindowValueMaskValue::Sibling); }
+        if mask & 0x0040 != 0 { value.insert(ConfigureWindowValueMaskValue::StackMode); }
+        if mask & 0xff80 != 0 { return Err(Error::ProtocolError(ErrorCode::Value, mask as u32)); }
+        Ok(value)
+    }
+}
+
+impl Writable for HashSet<ConfigureWindowValueMaskValue> {
+    fn write(stream: &mut BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let mut value = 0u16;
+        for data in data {
+            let mask = match data {
+                ConfigureWindowValueMaskValue::X => 0x0001,
+                ConfigureWindowValueMaskValue::Y => 0x0002,
+                ConfigureWindowValueMaskValue::Width => 0x0004,
+                ConfigureWindowValueMaskValue::Height => 0x0008,
+                ConfigureWindowValueMaskValue::BorderWidth => 0x0010,
+                ConfigureWindowValueMaskValue::Sibling => 0x0020,
+                ConfigureWindowValueMaskValue::StackMode => 0x0040,
+            };
+            value |= mask;
+        }
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigureWindowRequest {
+    pub window: u32,
+    pub value_mask: HashSet<ConfigureWindowValueMaskValue>,
+    pub value: ConfigureWindowValue,
+}
 
 impl Readable for ConfigureWindowRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let length = stream.read_value::<u16>(order)? as usize;
+        if length < 3 {
+            return Err(Error::ProtocolError(ErrorCode::Length, 0));
+        }
+        let length = length - 3;
+        let window = stream.read_value(order)?;
+        let value_mask: HashSet<_> = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 2], 2)?;
+        let mut list = ValueListReader::read(stream, order, value_mask.len(), length)?;
+        let mut value = ConfigureWindowValue::default();
+        if value_mask.contains(&ConfigureWindowValueMaskValue::X) { value.x = list.read_value()?; }
+        if value_mask.contains(&ConfigureWindowValueMaskValue::Y) { value.y = list.read_value()?; }
+        if value_mask.contains(&ConfigureWindowValueMaskValue::Width) { value.width = list.read_value()?; }
+        if value_mask.contains(&ConfigureWindowValueMaskValue::Height) { value.height = list.read_value()?; }
+        if value_mask.contains(&ConfigureWindowValueMaskValue::BorderWidth) { value.border_width = list.read_value()?; }
+        if value_mask.contains(&ConfigureWindowValueMaskValue::Sibling) { value.sibling = list.read_value()?; }
+        if value_mask.contains(&ConfigureWindowValueMaskValue::StackMode) { value.stack_mode = list.read_value()?; }
+        Ok(ConfigureWindowRequest {
+            window,
+            value_mask,
+            value,
+        })
     }
 }
 
-impl Writable for ConfigureWindowRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for ConfigureWindowRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value = data.value;
+        let mut list = ValueListWriter::new();
+        if data.value_mask.contains(&ConfigureWindowValueMaskValue::X) { list.write_value(value.x as i32)?; }
+        if data.value_mask.contains(&ConfigureWindowValueMaskValue::Y) { list.write_value(value.y as i32)?; }
+        if data.value_mask.contains(&ConfigureWindowValueMaskValue::Width) { list.write_value(value.width)?; }
+        if data.value_mask.contains(&ConfigureWindowValueMaskValue::Height) { list.write_value(value.height)?; }
+        if data.value_mask.contains(&ConfigureWindowValueMaskValue::BorderWidth) { list.write_value(value.border_width)?; }
+        if data.value_mask.contains(&ConfigureWindowValueMaskValue::Sibling) { list.write_value(value.sibling)?; }
+        if data.value_mask.contains(&ConfigureWindowValueMaskValue::StackMode) { list.write_value(value.stack_mode)?; }
+        stream.write_value::<u8>(12, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value((3 + list.len()) as u16, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_value(data.value_mask, order)?;
+        stream.write_all(&[0; 2]).map_err(Error::IoError)?;
+        list.write(stream, order)
     }
 }
 
@@ -23,12 +175,12 @@ pub struct ConfigureWindowResponse;
 
 impl Readable for ConfigureWindowResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for ConfigureWindowResponse{
+impl Writable for ConfigureWindowResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
@@ -0,0 +1,73 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::collections::HashSet;
+    use std::io::{BufReader, BufWriter};
+    use std::iter::FromIterator;
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::configure_window::{ConfigureWindowRequest, ConfigureWindowValue, ConfigureWindowValueMaskValue, ConfigureWindowValueStackMode};
+
+    #[test]
+    fn read_test() {
+        const MSB: [u8; 28] = [12, 0, 0, 7, 0, 0, 0, 1, 0, 0x65, 0, 0,
+            0xff, 0xff, 0xff, 0xfc, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 1];
+        const LSB: [u8; 28] = [12, 0, 7, 0, 1, 0, 0, 0, 0x65, 0, 0, 0,
+            0xfc, 0xff, 0xff, 0xff, 2, 0, 0, 0, 3, 0, 0, 0, 1, 0, 0, 0];
+
+        fn expected() -> ConfigureWindowRequest {
+            ConfigureWindowRequest {
+                window: 1,
+                value_mask: HashSet::from_iter([ConfigureWindowValueMaskValue::X,
+                    ConfigureWindowValueMaskValue::Width,
+                    ConfigureWindowValueMaskValue::Sibling,
+                    ConfigureWindowValueMaskValue::StackMode].to_vec()),
+                value: ConfigureWindowValue {
+                    x: -4,
+                    width: 2,
+                    sibling: 3,
+                    stack_mode: ConfigureWindowValueStackMode::Below,
+                    ..Default::default()
+                },
+            }
+        }
+
+        let value = ConfigureWindowRequest::read(&mut BufReader::new(&MSB[1..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected());
+        let value = ConfigureWindowRequest::read(&mut BufReader::new(&LSB[1..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected());
+    }
+
+    #[test]
+    fn write_test() {
+        const MSB: [u8; 28] = [12, 0, 0, 7, 0, 0, 0, 1, 0, 0x65, 0, 0,
+            0xff, 0xff, 0xff, 0xfc, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 1];
+        const LSB: [u8; 28] = [12, 0, 7, 0, 1, 0, 0, 0, 0x65, 0, 0, 0,
+            0xfc, 0xff, 0xff, 0xff, 2, 0, 0, 0, 3, 0, 0, 0, 1, 0, 0, 0];
+
+        fn expected() -> ConfigureWindowRequest {
+            ConfigureWindowRequest {
+                window: 1,
+                value_mask: HashSet::from_iter([ConfigureWindowValueMaskValue::X,
+                    ConfigureWindowValueMaskValue::Width,
+                    ConfigureWindowValueMaskValue::Sibling,
+                    ConfigureWindowValueMaskValue::StackMode].to_vec()),
+                value: ConfigureWindowValue {
+                    x: -4,
+                    width: 2,
+                    sibling: 3,
+                    stack_mode: ConfigureWindowValueStackMode::Below,
+                    ..Default::default()
+                },
+            }
+        }
+
+        let mut buffer = [0; 28];
+        ConfigureWindowRequest::write(&mut BufWriter::new(&mut buffer[..]), expected(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, MSB);
+        let mut buffer = [0; 28];
+        ConfigureWindowRequest::write(&mut BufWriter::new(&mut buffer[..]), expected(), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, LSB);
+    }
+}
@@ -1,20 +1,53 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
 
+mod test;
+
+/// timeが0ならCurrentTime
 #[derive(Clone, Debug, PartialEq)]
-pub struct ConvertSelectionRequest;
+pub struct ConvertSelectionRequest {
+    pub requestor: u32,
+    pub selection: u32,
+    pub target: u32,
+    pub property: Option<u32>,
+    pub time: u32,
+}
 
 impl Readable for ConvertSelectionRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let requestor = stream.read_value(order)?;
+        let selection = stream.read_value(order)?;
+        let target = stream.read_value(order)?;
+        let property = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let time = stream.read_value(order)?;
+        Ok(ConvertSelectionRequest {
+            requestor,
+            selection,
+            target,
+            property,
+            time,
+        })
     }
 }
 
-impl Writable for ConvertSelectionRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for ConvertSelectionRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(24, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(6, order)?;
+        stream.write_value(data.requestor, order)?;
+        stream.write_value(data.selection, order)?;
+        stream.write_value(data.target, order)?;
+        stream.write_value(data.property.unwrap_or(0), order)?;
+        stream.write_value(data.time, order)?;
+        Ok(())
     }
 }
 
@@ -23,12 +56,12 @@ pub struct ConvertSelectionResponse;
 
 impl Readable for ConvertSelectionResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for ConvertSelectionResponse{
+impl Writable for ConvertSelectionResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
@@ -0,0 +1,31 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::convert_selection::ConvertSelectionRequest;
+
+    #[test]
+    fn read_test() {
+        let expected = ConvertSelectionRequest { requestor: 0x00200001, selection: 1, target: 31, property: Some(300), time: 0 };
+        let input = [0, 0, 6, 0, 0x20, 0, 1, 0, 0, 0, 1, 0, 0, 0, 31, 0, 0, 1, 0x2c, 0, 0, 0, 0];
+        let value = ConvertSelectionRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [0, 6, 0, 1, 0, 0x20, 0, 1, 0, 0, 0, 31, 0, 0, 0, 0x2c, 1, 0, 0, 0, 0, 0, 0];
+        let value = ConvertSelectionRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn write_test() {
+        let data = ConvertSelectionRequest { requestor: 0x00200001, selection: 1, target: 31, property: None, time: 12345 };
+        let mut buffer = [0; 24];
+        ConvertSelectionRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [24, 0, 0, 6, 0, 0x20, 0, 1, 0, 0, 0, 1, 0, 0, 0, 31, 0, 0, 0, 0, 0, 0, 0x30, 0x39]);
+        let mut buffer = [0; 24];
+        ConvertSelectionRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [24, 0, 6, 0, 1, 0, 0x20, 0, 1, 0, 0, 0, 31, 0, 0, 0, 0, 0, 0, 0, 0x39, 0x30, 0, 0]);
+    }
+}
@@ -2,7 +2,9 @@ use std::collections::HashSet;
 use std::io::{BufReader, BufWriter, Read, Write};
 
 use crate::{Error, Result};
+use crate::error::ErrorCode;
 use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+use crate::request::value_list::{ValueListReader, ValueListWriter};
 
 mod test;
 
@@ -45,7 +47,7 @@ impl Readable for CreateGCValueFunction {
             13 => Ok(Self::OrInverted),
             14 => Ok(Self::Nand),
             15 => Ok(Self::Set),
-            _ => Err(Error::InvalidValue("CreateGCValueFunction")),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
         }
     }
 }
@@ -87,7 +89,7 @@ impl Readable for CreateGCValueLineStyle {
             0 => Ok(Self::Solid),
             1 => Ok(Self::OnOffDash),
             2 => Ok(Self::DoubleDash),
-            _ => Err(Error::InvalidValue("CreateGCValueLineStyle")),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
         }
     }
 }
@@ -118,7 +120,7 @@ impl Readable for CreateGCValueCapStyle {
             1 => Ok(Self::Butt),
             2 => Ok(Self::Round),
             3 => Ok(Self::Projecting),
-            _ => Err(Error::InvalidValue("CreateGCValueCapStyle")),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
         }
     }
 }
@@ -148,7 +150,7 @@ impl Readable for CreateGCValueJoinStyle {
             0 => Ok(Self::Miter),
             1 => Ok(Self::Round),
             2 => Ok(Self::Bevel),
-            _ => Err(Error::InvalidValue("CreateGCValueJoinStyle")),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
         }
     }
 }
@@ -179,7 +181,7 @@ impl Readable for CreateGCValueFillStyle {
             1 => Ok(Self::Tiled),
             2 => Ok(Self::Stippled),
             3 => Ok(Self::OpaqueStippled),
-            _ => Err(Error::InvalidValue("CreateGCValueFillStyle")),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
         }
     }
 }
@@ -207,7 +209,7 @@ impl Readable for CreateGCValueFillRule {
         match stream.read_value::<u8>(order)? {
             0 => Ok(Self::EvenOdd),
             1 => Ok(Self::Winding),
-            _ => Err(Error::InvalidValue("CreateGCValueFillRule")),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
         }
     }
 }
@@ -233,7 +235,7 @@ impl Readable for CreateGCValueSubwindowMode {
         match stream.read_value::<u8>(order)? {
             0 => Ok(Self::ClipByChildren),
             1 => Ok(Self::IncludeInferiors),
-            _ => Err(Error::InvalidValue("CreateGCValueSubwindowMode")),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
         }
     }
 }
@@ -259,7 +261,7 @@ impl Readable for CreateGCValueArcMode {
         match stream.read_value::<u8>(order)? {
             0 => Ok(Self::Chord),
             1 => Ok(Self::PieSlice),
-            _ => Err(Error::InvalidValue("CreateGCValueArcMode")),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
         }
     }
 }
@@ -385,6 +387,7 @@ impl Readable for HashSet<CreateGCValueMaskValue> {
         if mask & 0x00100000 != 0 { value.insert(CreateGCValueMaskValue::DashOffset); }
         if mask & 0x00200000 != 0 { value.insert(CreateGCValueMaskValue::Dashes); }
         if mask & 0x00400000 != 0 { value.insert(CreateGCValueMaskValue::ArcMode); }
+        if mask & 0xff800000 != 0 { return Err(Error::ProtocolError(ErrorCode::Value, mask)); }
         Ok(value)
     }
 }
@@ -424,6 +427,71 @@ impl Writable for HashSet<CreateGCValueMaskValue> {
     }
 }
 
+/// ChangeGCと共通のvalue-mask以降の部分
+pub(crate) fn read_gc_value(stream: &mut BufReader<impl Read>, order: &ByteOrder, length: usize) -> Result<(HashSet<CreateGCValueMaskValue>, CreateGCValue)> {
+    let value_mask: HashSet<_> = stream.read_value(order)?;
+    let mut list = ValueListReader::read(stream, order, value_mask.len(), length)?;
+    let mut value = CreateGCValue::default();
+    if value_mask.contains(&CreateGCValueMaskValue::Function) { value.function = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::PlaneMask) { value.plane_mask = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::Foreground) { value.foreground = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::Background) { value.background = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::LineWidth) { value.line_width = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::LineStyle) { value.line_style = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::CapStyle) { value.cap_style = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::JoinStyle) { value.join_style = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::FillStyle) { value.fill_style = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::FillRule) { value.fill_rule = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::Tile) { value.tile = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::Stipple) { value.stipple = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::TileStippleXOrigin) { value.tile_stipple_x_origin = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::TileStippleYOrigin) { value.tile_stipple_y_origin = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::Font) { value.font = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::SubwindowMode) { value.subwindow_mode = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::GraphicsExposures) { value.graphics_exposures = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::ClipXOrigin) { value.clip_x_origin = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::ClipYOrigin) { value.clip_y_origin = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::ClipMask) {
+        value.clip_mask = match list.read_value()? {
+            0 => None,
+            other => Some(other),
+        };
+    }
+    if value_mask.contains(&CreateGCValueMaskValue::DashOffset) { value.dash_offset = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::Dashes) { value.dashes = list.read_value()?; }
+    if value_mask.contains(&CreateGCValueMaskValue::ArcMode) { value.arc_mode = list.read_value()?; }
+    Ok((value_mask, value))
+}
+
+pub(crate) fn write_gc_value(stream: &mut BufWriter<impl Write>, value_mask: HashSet<CreateGCValueMaskValue>, value: CreateGCValue, order: &ByteOrder) -> Result<()> {
+    let mut list = ValueListWriter::new();
+    if value_mask.contains(&CreateGCValueMaskValue::Function) { list.write_value(value.function)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::PlaneMask) { list.write_value(value.plane_mask)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::Foreground) { list.write_value(value.foreground)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::Background) { list.write_value(value.background)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::LineWidth) { list.write_value(value.line_width)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::LineStyle) { list.write_value(value.line_style)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::CapStyle) { list.write_value(value.cap_style)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::JoinStyle) { list.write_value(value.join_style)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::FillStyle) { list.write_value(value.fill_style)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::FillRule) { list.write_value(value.fill_rule)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::Tile) { list.write_value(value.tile)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::Stipple) { list.write_value(value.stipple)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::TileStippleXOrigin) { list.write_value(value.tile_stipple_x_origin as i32)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::TileStippleYOrigin) { list.write_value(value.tile_stipple_y_origin as i32)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::Font) { list.write_value(value.font)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::SubwindowMode) { list.write_value(value.subwindow_mode)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::GraphicsExposures) { list.write_value(value.graphics_exposures)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::ClipXOrigin) { list.write_value(value.clip_x_origin as i32)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::ClipYOrigin) { list.write_value(value.clip_y_origin as i32)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::ClipMask) { list.write_value(value.clip_mask.unwrap_or(0))?; }
+    if value_mask.contains(&CreateGCValueMaskValue::DashOffset) { list.write_value(value.dash_offset)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::Dashes) { list.write_value(value.dashes)?; }
+    if value_mask.contains(&CreateGCValueMaskValue::ArcMode) { list.write_value(value.arc_mode)?; }
+    stream.write_value(value_mask, order)?;
+    list.write(stream, order)
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct CreateGCRequest {
     pub cid: u32,
@@ -435,42 +503,13 @@ pub struct CreateGCRequest {
 impl Readable for CreateGCRequest {
     fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
         read_specified_length(stream, &mut [0; 1], 1)?;
-        let length = stream.read_value::<u16>(order)? as usize - 4;
+        let length = stream.read_value::<u16>(order)? as usize;
+        if length < 4 {
+            return Err(Error::ProtocolError(ErrorCode::Length, 0));
+        }
         let cid = stream.read_value(order)?;
         let drawable = stream.read_value(order)?;
-        let value_mask: HashSet<_> = stream.read_value(order)?;
-        let mut value = CreateGCValue::default();
-        let mut buffer = vec![0; length << 2];
-        read_specified_length(stream, &mut buffer[..], length << 2)?;
-        let mut buffer = BufReader::new(&buffer[..]);
-        if value_mask.contains(&CreateGCValueMaskValue::Function) { value.function = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::PlaneMask) { value.plane_mask = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::Foreground) { value.foreground = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::Background) { value.background = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::LineWidth) { value.line_width = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::LineStyle) { value.line_style = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::CapStyle) { value.cap_style = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::JoinStyle) { value.join_style = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::FillStyle) { value.fill_style = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::FillRule) { value.fill_rule = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::Tile) { value.tile = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::Stipple) { value.stipple = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::TileStippleXOrigin) { value.tile_stipple_x_origin = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::TileStippleYOrigin) { value.tile_stipple_y_origin = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::Font) { value.font = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::SubwindowMode) { value.subwindow_mode = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::GraphicsExposures) { value.graphics_exposures = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::ClipXOrigin) { value.clip_x_origin = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::ClipYOrigin) { value.clip_y_origin = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::ClipMask) {
-            value.clip_mask = match buffer.read_value(order)? {
-                0 => None,
-                other => Some(other),
-            };
-        }
-        if value_mask.contains(&CreateGCValueMaskValue::DashOffset) { value.dash_offset = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::Dashes) { value.dashes = buffer.read_value(order)?; }
-        if value_mask.contains(&CreateGCValueMaskValue::ArcMode) { value.arc_mode = buffer.read_value(order)?; }
+        let (value_mask, value) = read_gc_value(stream, order, length - 4)?;
         Ok(CreateGCRequest {
             cid,
             drawable,
@@ -480,139 +519,15 @@ impl Readable for CreateGCRequest {
     }
 }
 
+#[allow(clippy::redundant_closure)]
 impl Writable for CreateGCRequest {
     fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
-        let mut buffer = [0; 52];
-        let mut value_writer = BufWriter::new(&mut buffer[..]);
-        let default_value = CreateGCValue::default();
-        let mut written_size = 0;
-        let mut written = HashSet::new();
-        if data.value.function != default_value.function {
-            value_writer.write_value(data.value.function, order)?;
-            written.insert(CreateGCValueMaskValue::Function);
-            written_size += 1;
-        }
-        if data.value.plane_mask != default_value.plane_mask {
-            value_writer.write_value(data.value.plane_mask, order)?;
-            written.insert(CreateGCValueMaskValue::PlaneMask);
-            written_size += 4;
-        }
-        if data.value.foreground != default_value.foreground {
-            value_writer.write_value(data.value.foreground, order)?;
-            written.insert(CreateGCValueMaskValue::Foreground);
-            written_size += 4;
-        }
-        if data.value.background != default_value.background {
-            value_writer.write_value(data.value.background, order)?;
-            written.insert(CreateGCValueMaskValue::Background);
-            written_size += 4;
-        }
-        if data.value.line_width != default_value.line_width {
-            value_writer.write_value(data.value.line_width, order)?;
-            written.insert(CreateGCValueMaskValue::LineWidth);
-            written_size += 2;
-        }
-        if data.value.line_style != default_value.line_style {
-            value_writer.write_value(data.value.line_style, order)?;
-            written.insert(CreateGCValueMaskValue::LineStyle);
-            written_size += 1;
-        }
-        if data.value.cap_style != default_value.cap_style {
-            value_writer.write_value(data.value.cap_style, order)?;
-            written.insert(CreateGCValueMaskValue::CapStyle);
-            written_size += 1;
-        }
-        if data.value.join_style != default_value.join_style {
-            value_writer.write_value(data.value.join_style, order)?;
-            written.insert(CreateGCValueMaskValue::JoinStyle);
-            written_size += 1;
-        }
-        if data.value.fill_style != default_value.fill_style {
-            value_writer.write_value(data.value.fill_style, order)?;
-            written.insert(CreateGCValueMaskValue::FillStyle);
-            written_size += 1;
-        }
-        if data.value.fill_rule != default_value.fill_rule {
-            value_writer.write_value(data.value.fill_rule, order)?;
-            written.insert(CreateGCValueMaskValue::FillRule);
-            written_size += 1;
-        }
-        if data.value.tile != default_value.tile {
-            value_writer.write_value(data.value.tile, order)?;
-            written.insert(CreateGCValueMaskValue::Tile);
-            written_size += 4;
-        }
-        if data.value.stipple != default_value.stipple {
-            value_writer.write_value(data.value.stipple, order)?;
-            written.insert(CreateGCValueMaskValue::Stipple);
-            written_size += 4;
-        }
-        if data.value.tile_stipple_x_origin != default_value.tile_stipple_x_origin {
-            value_writer.write_value(data.value.tile_stipple_x_origin, order)?;
-            written.insert(CreateGCValueMaskValue::TileStippleXOrigin);
-            written_size += 2;
-        }
-        if data.value.tile_stipple_y_origin != default_value.tile_stipple_y_origin {
-            value_writer.write_value(data.value.tile_stipple_y_origin, order)?;
-            written.insert(CreateGCValueMaskValue::TileStippleYOrigin);
-            written_size += 2;
-        }
-        if data.value.font != default_value.font {
-            value_writer.write_value(data.value.font, order)?;
-            written.insert(CreateGCValueMaskValue::Font);
-            written_size += 4;
-        }
-        if data.value.subwindow_mode != default_value.subwindow_mode {
-            value_writer.write_value(data.value.subwindow_mode, order)?;
-            written.insert(CreateGCValueMaskValue::SubwindowMode);
-            written_size += 1;
-        }
-        if data.value.graphics_exposures != default_value.graphics_exposures {
-            value_writer.write_value(data.value.graphics_exposures, order)?;
-            written.insert(CreateGCValueMaskValue::GraphicsExposures);
-            written_size += 1;
-        }
-        if data.value.clip_x_origin != default_value.clip_x_origin {
-            value_writer.write_value(data.value.clip_x_origin, order)?;
-            written.insert(CreateGCValueMaskValue::ClipXOrigin);
-            written_size += 2;
-        }
-        if data.value.clip_y_origin != default_value.clip_y_origin {
-            value_writer.write_value(data.value.clip_y_origin, order)?;
-            written.insert(CreateGCValueMaskValue::ClipYOrigin);
-            written_size += 2;
-        }
-        if data.value.clip_mask != default_value.clip_mask {
-            value_writer.write_value(data.value.clip_mask.unwrap_or(0), order)?;
-            written.insert(CreateGCValueMaskValue::ClipMask);
-            written_size += 4;
-        }
-        if data.value.dash_offset != default_value.dash_offset {
-            value_writer.write_value(data.value.dash_offset, order)?;
-            written.insert(CreateGCValueMaskValue::DashOffset);
-            written_size += 2;
-        }
-        if data.value.dashes != default_value.dashes {
-            value_writer.write_value(data.value.dashes, order)?;
-            written.insert(CreateGCValueMaskValue::Dashes);
-            written_size += 1;
-        }
-        if data.value.arc_mode != default_value.arc_mode {
-            value_writer.write_value(data.value.arc_mode, order)?;
-            written.insert(CreateGCValueMaskValue::ArcMode);
-            written_size += 1;
-        }
-        value_writer.flush().map_err(|e| Error::IoError(e))?;
-        drop(value_writer);
-        let written_size = (written_size + 3) & !3;
         stream.write_value::<u8>(55, order)?;
         stream.write_all(&[0]).map_err(|e| Error::IoError(e))?;
-        stream.write_value((written_size >> 2) as u16 + 4, order)?;
+        stream.write_value((4 + data.value_mask.len()) as u16, order)?;
         stream.write_value(data.cid, order)?;
         stream.write_value(data.drawable, order)?;
-        stream.write_value(written, order)?;
-        stream.write_all(&buffer[..written_size]).map_err(|e| Error::IoError(e))?;
-        Ok(())
+        write_gc_value(stream, data.value_mask, data.value, order)
     }
 }
 
@@ -6,6 +6,8 @@ mod request {
     use std::io::{BufReader, BufWriter};
     use std::iter::FromIterator;
 
+    use crate::Error;
+    use crate::error::ErrorCode;
     use crate::read_util::{ByteOrder, Readable, Writable};
     use crate::request::create_gc::{CreateGCRequest, CreateGCValue, CreateGCValueArcMode, CreateGCValueCapStyle, CreateGCValueFillRule, CreateGCValueFillStyle, CreateGCValueFunction, CreateGCValueJoinStyle, CreateGCValueLineStyle, CreateGCValueMaskValue, CreateGCValueSubwindowMode};
 
@@ -19,9 +21,7 @@ mod request {
             value_mask: HashSet::new(),
             value: Default::default(),
         });
-        let input = [0, 0, 17, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0x7f, 0xff, 0xff,
-            3, 0, 0, 0, 4, 0, 0, 0, 5, 0, 0, 0, 6, 0, 7, 1, 1, 1, 1, 1, 0, 0, 0, 8, 0, 0, 0, 9, 0, 10, 0, 11, 0, 0, 0, 12, 1, 1, 0, 13, 0, 14, 0, 0, 0, 15, 0, 16, 17, 1, 0, 0,
-        ];
+        let input = [0, 0, 27, 0, 0, 0, 1, 0, 0, 0, 2, 0, 127, 255, 255, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0, 5, 0, 0, 0, 6, 0, 0, 0, 7, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 8, 0, 0, 0, 9, 0, 0, 0, 10, 0, 0, 0, 11, 0, 0, 0, 12, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 13, 0, 0, 0, 14, 0, 0, 0, 15, 0, 0, 0, 16, 0, 0, 0, 17, 0, 0, 0, 1];
         let value = CreateGCRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
         assert_eq!(value, CreateGCRequest {
             cid: 1,
@@ -77,6 +77,52 @@ mod request {
         });
     }
 
+    #[test]
+    fn read_xlib_test() {
+        //XCreateGC(display, window, GCForeground | GCBackground | GCGraphicsExposures, { foreground: 0, background: 0xffffff, graphics_exposures: False })
+        let input = [0, 7, 0, 1, 0, 64, 0, 33, 0, 0, 0, 12, 0, 1, 0, 0, 0, 0, 0, 255, 255, 255, 0, 0, 0, 0, 0];
+        let value = CreateGCRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, CreateGCRequest {
+            cid: 0x00400001,
+            drawable: 0x00000021,
+            value_mask: HashSet::from_iter([CreateGCValueMaskValue::Foreground, CreateGCValueMaskValue::Background, CreateGCValueMaskValue::GraphicsExposures].to_vec()),
+            value: CreateGCValue {
+                foreground: 0,
+                background: 0xffffff,
+                graphics_exposures: false,
+                ..Default::default()
+            },
+        });
+        //負の値は符号拡張されている
+        let input = [0, 6, 0, 2, 0, 64, 0, 33, 0, 0, 0, 0, 0, 6, 0, 253, 255, 255, 255, 252, 255, 255, 255];
+        let value = CreateGCRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, CreateGCRequest {
+            cid: 0x00400002,
+            drawable: 0x00000021,
+            value_mask: HashSet::from_iter([CreateGCValueMaskValue::ClipXOrigin, CreateGCValueMaskValue::ClipYOrigin].to_vec()),
+            value: CreateGCValue {
+                clip_x_origin: -3,
+                clip_y_origin: -4,
+                ..Default::default()
+            },
+        });
+    }
+
+    #[test]
+    fn read_error_test() {
+        //value-maskとlengthが合わない
+        let input = [0, 5, 0, 1, 0, 0, 0, 2, 0, 0, 0, 12, 0, 0, 0, 0, 0, 0, 0];
+        match CreateGCRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst) {
+            Err(Error::ProtocolError(ErrorCode::Length, _)) => {}
+            other => panic!("{:?}", other),
+        }
+        let input = [0, 4, 0, 1, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0x80, 0];
+        match CreateGCRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst) {
+            Err(Error::ProtocolError(ErrorCode::Value, 0x00800000)) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+
     #[test]
     fn write_test() {
         let value = CreateGCRequest {
@@ -140,9 +186,28 @@ mod request {
                 arc_mode: CreateGCValueArcMode::Chord,
             },
         };
-        let mut buffer = [255; 69];
-        CreateGCRequest::write(&mut BufWriter::new(&mut buffer[..]), value, &ByteOrder::MSBFirst).unwrap();
-        assert_eq!(buffer, [55, 0, 0, 17, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0x7f, 0xff, 0xff,
-            1, 0, 0, 0, 4, 0, 0, 0, 5, 0, 0, 0, 6, 0, 7, 1, 2, 1, 1, 1, 0, 0, 0, 8, 0, 0, 0, 9, 0, 10, 0, 11, 0, 0, 0, 12, 1, 0, 0, 13, 0, 14, 0, 0, 0, 15, 0, 16, 17, 0, 0, 0, 255]);
+        let mut buffer = [255; 109];
+        CreateGCRequest::write(&mut BufWriter::new(&mut buffer[..]), value.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer[..], [55, 0, 0, 27, 0, 0, 0, 1, 0, 0, 0, 2, 0, 127, 255, 255, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 5, 0, 0, 0, 6, 0, 0, 0, 7, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 8, 0, 0, 0, 9, 0, 0, 0, 10, 0, 0, 0, 11, 0, 0, 0, 12, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 13, 0, 0, 0, 14, 0, 0, 0, 15, 0, 0, 0, 16, 0, 0, 0, 17, 0, 0, 0, 0, 255][..]);
+        let mut buffer = [255; 109];
+        CreateGCRequest::write(&mut BufWriter::new(&mut buffer[..]), value, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer[..], [55, 0, 27, 0, 1, 0, 0, 0, 2, 0, 0, 0, 255, 255, 127, 0, 1, 0, 0, 0, 4, 0, 0, 0, 5, 0, 0, 0, 6, 0, 0, 0, 7, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 8, 0, 0, 0, 9, 0, 0, 0, 10, 0, 0, 0, 11, 0, 0, 0, 12, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 13, 0, 0, 0, 14, 0, 0, 0, 15, 0, 0, 0, 16, 0, 0, 0, 17, 0, 0, 0, 0, 0, 0, 0, 255][..]);
+    }
+
+    #[test]
+    fn write_xlib_test() {
+        let value = CreateGCRequest {
+            cid: 0x00400002,
+            drawable: 0x00000021,
+            value_mask: HashSet::from_iter([CreateGCValueMaskValue::ClipXOrigin, CreateGCValueMaskValue::ClipYOrigin].to_vec()),
+            value: CreateGCValue {
+                clip_x_origin: -3,
+                clip_y_origin: -4,
+                ..Default::default()
+            },
+        };
+        let mut buffer = [0; 24];
+        CreateGCRequest::write(&mut BufWriter::new(&mut buffer[..]), value, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [55, 0, 6, 0, 2, 0, 64, 0, 33, 0, 0, 0, 0, 0, 6, 0, 253, 255, 255, 255, 252, 255, 255, 255]);
     }
-}
\ No newline at end of file
+}
@@ -1,20 +1,77 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
 
+mod test;
+
+/// mask_fontがNoneならsourceの全てのpixelを表示する
 #[derive(Clone, Debug, PartialEq)]
-pub struct CreateGlyphCursorRequest;
+pub struct CreateGlyphCursorRequest {
+    pub cid: u32,
+    pub source_font: u32,
+    pub mask_font: Option<u32>,
+    pub source_char: u16,
+    pub mask_char: u16,
+    pub fore_red: u16,
+    pub fore_green: u16,
+    pub fore_blue: u16,
+    pub back_red: u16,
+    pub back_green: u16,
+    pub back_blue: u16,
+}
 
 impl Readable for CreateGlyphCursorRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let cid = stream.read_value(order)?;
+        let source_font = stream.read_value(order)?;
+        let mask_font = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let source_char = stream.read_value(order)?;
+        let mask_char = stream.read_value(order)?;
+        let fore_red = stream.read_value(order)?;
+        let fore_green = stream.read_value(order)?;
+        let fore_blue = stream.read_value(order)?;
+        let back_red = stream.read_value(order)?;
+        let back_green = stream.read_value(order)?;
+        let back_blue = stream.read_value(order)?;
+        Ok(CreateGlyphCursorRequest {
+            cid,
+            source_font,
+            mask_font,
+            source_char,
+            mask_char,
+            fore_red,
+            fore_green,
+            fore_blue,
+            back_red,
+            back_green,
+            back_blue,
+        })
     }
 }
 
-impl Writable for CreateGlyphCursorRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for CreateGlyphCursorRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(94, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(8, order)?;
+        stream.write_value(data.cid, order)?;
+        stream.write_value(data.source_font, order)?;
+        stream.write_value(data.mask_font.unwrap_or(0), order)?;
+        stream.write_value(data.source_char, order)?;
+        stream.write_value(data.mask_char, order)?;
+        stream.write_value(data.fore_red, order)?;
+        stream.write_value(data.fore_green, order)?;
+        stream.write_value(data.fore_blue, order)?;
+        stream.write_value(data.back_red, order)?;
+        stream.write_value(data.back_green, order)?;
+        stream.write_value(data.back_blue, order)?;
+        Ok(())
     }
 }
 
@@ -23,12 +80,12 @@ pub struct CreateGlyphCursorResponse;
 
 impl Readable for CreateGlyphCursorResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for CreateGlyphCursorResponse{
+impl Writable for CreateGlyphCursorResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
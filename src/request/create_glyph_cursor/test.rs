@@ -0,0 +1,61 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::create_glyph_cursor::CreateGlyphCursorRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [
+            0, 0, 8, 0, 0x20, 0, 3, 0, 0x20, 0, 1, 0, 0x20, 0, 1, 0, 0x44, 0, 0x45,
+            0xff, 0xff, 0, 0, 0, 0, 0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc,
+        ];
+        let value = CreateGlyphCursorRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, CreateGlyphCursorRequest {
+            cid: 0x00200003,
+            source_font: 0x00200001,
+            mask_font: Some(0x00200001),
+            source_char: 0x44,
+            mask_char: 0x45,
+            fore_red: 0xffff,
+            fore_green: 0,
+            fore_blue: 0,
+            back_red: 0x1234,
+            back_green: 0x5678,
+            back_blue: 0x9abc,
+        });
+        //mask_fontが0ならNone
+        let input = [
+            0, 8, 0, 3, 0, 0x20, 0, 1, 0, 0x20, 0, 0, 0, 0, 0, 0x44, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let value = CreateGlyphCursorRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!((value.mask_font, value.source_char, value.mask_char), (None, 0x44, 0));
+    }
+
+    #[test]
+    fn write_test() {
+        let value = CreateGlyphCursorRequest {
+            cid: 0x00200003,
+            source_font: 0x00200001,
+            mask_font: None,
+            source_char: 0x0144,
+            mask_char: 0,
+            fore_red: 0,
+            fore_green: 0,
+            fore_blue: 0,
+            back_red: 0xffff,
+            back_green: 0xffff,
+            back_blue: 0xffff,
+        };
+        let mut buffer = [0; 32];
+        CreateGlyphCursorRequest::write(&mut BufWriter::new(&mut buffer[..]), value, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [
+            94, 0, 8, 0, 3, 0, 0x20, 0, 1, 0, 0x20, 0, 0, 0, 0, 0,
+            0x44, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        ]);
+    }
+}
@@ -1,20 +1,484 @@
-use std::io::{Read, Write};
+use std::collections::HashSet;
+use std::io::{BufReader, BufWriter, Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, Readable, ReadableRead, Writable, WritableWrite};
+use crate::request::value_list::{ValueListReader, ValueListWriter};
+use crate::setup::Event;
+
+mod test;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CreateWindowClass {
+    CopyFromParent,
+    InputOutput,
+    InputOnly,
+}
+
+impl Readable for CreateWindowClass {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u16>(order)? {
+            0 => Ok(Self::CopyFromParent),
+            1 => Ok(Self::InputOutput),
+            2 => Ok(Self::InputOnly),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for CreateWindowClass {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u16 = match data {
+            Self::CopyFromParent => 0,
+            Self::InputOutput => 1,
+            Self::InputOnly => 2,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CreateWindowValueBackgroundPixmap {
+    None,
+    ParentRelative,
+    Pixmap(u32),
+}
+
+impl Readable for CreateWindowValueBackgroundPixmap {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u32>(order)? {
+            0 => Ok(Self::None),
+            1 => Ok(Self::ParentRelative),
+            other => Ok(Self::Pixmap(other)),
+        }
+    }
+}
+
+impl Writable for CreateWindowValueBackgroundPixmap {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u32 = match data {
+            Self::None => 0,
+            Self::ParentRelative => 1,
+            Self::Pixmap(pixmap) => pixmap,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CreateWindowValueBorderPixmap {
+    CopyFromParent,
+    Pixmap(u32),
+}
+
+impl Readable for CreateWindowValueBorderPixmap {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u32>(order)? {
+            0 => Ok(Self::CopyFromParent),
+            other => Ok(Self::Pixmap(other)),
+        }
+    }
+}
+
+impl Writable for CreateWindowValueBorderPixmap {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u32 = match data {
+            Self::CopyFromParent => 0,
+            Self::Pixmap(pixmap) => pixmap,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CreateWindowValueBitGravity {
+    Forget,
+    NorthWest,
+    North,
+    NorthEast,
+    West,
+    Center,
+    East,
+    SouthWest,
+    South,
+    SouthEast,
+    Static,
+}
+
+impl Readable for CreateWindowValueBitGravity {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::Forget),
+            1 => Ok(Self::NorthWest),
+            2 => Ok(Self::North),
+            3 => Ok(Self::NorthEast),
+            4 => Ok(Self::West),
+            5 => Ok(Self::Center),
+            6 => Ok(Self::East),
+            7 => Ok(Self::SouthWest),
+            8 => Ok(Self::South),
+            9 => Ok(Self::SouthEast),
+            10 => Ok(Self::Static),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for CreateWindowValueBitGravity {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::Forget => 0,
+            Self::NorthWest => 1,
+            Self::North => 2,
+            Self::NorthEast => 3,
+            Self::West => 4,
+            Self::Center => 5,
+            Self::East => 6,
+            Self::SouthWest => 7,
+            Self::South => 8,
+            Self::SouthEast => 9,
+            Self::Static => 10,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CreateWindowValueWinGravity {
+    Unmap,
+    NorthWest,
+    North,
+    NorthEast,
+    West,
+    Center,
+    East,
+    SouthWest,
+    South,
+    SouthEast,
+    Static,
+}
+
+impl Readable for CreateWindowValueWinGravity {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::Unmap),
+            1 => Ok(Self::NorthWest),
+            2 => Ok(Self::North),
+            3 => Ok(Self::NorthEast),
+            4 => Ok(Self::West),
+            5 => Ok(Self::Center),
+            6 => Ok(Self::East),
+            7 => Ok(Self::SouthWest),
+            8 => Ok(Self::South),
+            9 => Ok(Self::SouthEast),
+            10 => Ok(Self::Static),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for CreateWindowValueWinGravity {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::Unmap => 0,
+            Self::NorthWest => 1,
+            Self::North => 2,
+            Self::NorthEast => 3,
+            Self::West => 4,
+            Self::Center => 5,
+            Self::East => 6,
+            Self::SouthWest => 7,
+            Self::South => 8,
+            Self::SouthEast => 9,
+            Self::Static => 10,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CreateWindowValueBackingStore {
+    NotUseful,
+    WhenMapped,
+    Always,
+}
+
+impl Readable for CreateWindowValueBackingStore {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::NotUseful),
+            1 => Ok(Self::WhenMapped),
+            2 => Ok(Self::Always),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for CreateWindowValueBackingStore {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::NotUseful => 0,
+            Self::WhenMapped => 1,
+            Self::Always => 2,
+        };
+        stream.write_value(value, order)
+    }
+}
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct CreateWindowRequest;
+pub enum CreateWindowValueColormap {
+    CopyFromParent,
+    Colormap(u32),
+}
+
+impl Readable for CreateWindowValueColormap {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u32>(order)? {
+            0 => Ok(Self::CopyFromParent),
+            other => Ok(Self::Colormap(other)),
+        }
+    }
+}
+
+impl Writable for CreateWindowValueColormap {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u32 = match data {
+            Self::CopyFromParent => 0,
+            Self::Colormap(colormap) => colormap,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CreateWindowValue {
+    pub background_pixmap: CreateWindowValueBackgroundPixmap,
+    pub background_pixel: u32,
+    pub border_pixmap: CreateWindowValueBorderPixmap,
+    pub border_pixel: u32,
+    pub bit_gravity: CreateWindowValueBitGravity,
+    pub win_gravity: CreateWindowValueWinGravity,
+    pub backing_store: CreateWindowValueBackingStore,
+    pub backing_planes: u32,
+    pub backing_pixel: u32,
+    pub override_redirect: bool,
+    pub save_under: bool,
+    pub event_mask: HashSet<Event>,
+    pub do_not_propagate_mask: HashSet<Event>,
+    pub colormap: CreateWindowValueColormap,
+    pub cursor: Option<u32>,
+}
+
+impl Default for CreateWindowValue {
+    fn default() -> Self {
+        CreateWindowValue {
+            background_pixmap: CreateWindowValueBackgroundPixmap::None,
+            background_pixel: 0,
+            border_pixmap: CreateWindowValueBorderPixmap::CopyFromParent,
+            border_pixel: 0,
+            bit_gravity: CreateWindowValueBitGravity::Forget,
+            win_gravity: CreateWindowValueWinGravity::NorthWest,
+            backing_store: CreateWindowValueBackingStore::NotUseful,
+            backing_planes: 0xffffffff,
+            backing_pixel: 0,
+            override_redirect: false,
+            save_under: false,
+            event_mask: HashSet::new(),
+            do_not_propagate_mask: HashSet::new(),
+            colormap: CreateWindowValueColormap::CopyFromParent,
+            cursor: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CreateWindowValueMaskValue {
+    BackgroundPixmap,
+    BackgroundPixel,
+    BorderPixmap,
+    BorderPixel,
+    BitGravity,
+    WinGravity,
+    BackingStore,
+    BackingPlanes,
+    BackingPixel,
+    OverrideRedirect,
+    SaveUnder,
+    EventMask,
+    DoNotPropagateMask,
+    Colormap,
+    Cursor,
+}
+
+impl Readable for HashSet<CreateWindowValueMaskValue> {
+    fn read(stream: &mut BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let mut value = HashSet::new();
+        let mask: u32 = stream.read_value(order)?;
+        if mask & 0x00000001 != 0 { value.insert(CreateWindowValueMaskValue::BackgroundPixmap); }
+        if mask & 0x00000002 != 0 { value.insert(CreateWindowValueMaskValue::BackgroundPixel); }
+        if mask & 0x00000004 != 0 { value.insert(CreateWindowValueMaskValue::BorderPixmap); }
+        if mask & 0x00000008 != 0 { value.insert(CreateWindowValueMaskValue::BorderPixel); }
+        if mask & 0x00000010 != 0 { value.insert(CreateWindowValueMaskValue::BitGravity); }
+        if mask & 0x00000020 != 0 { value.insert(CreateWindowValueMaskValue::WinGravity); }
+        if mask & 0x00000040 != 0 { value.insert(CreateWindowValueMaskValue::BackingStore); }
+        if mask & 0x00000080 != 0 { value.insert(CreateWindowValueMaskValue::BackingPlanes); }
+        if mask & 0x00000100 != 0 { value.insert(CreateWindowValueMaskValue::BackingPixel); }
+        if mask & 0x00000200 != 0 { value.insert(CreateWindowValueMaskValue::OverrideRedirect); }
+        if mask & 0x00000400 != 0 { value.insert(CreateWindowValueMaskValue::SaveUnder); }
+        if mask & 0x00000800 != 0 { value.insert(CreateWindowValueMaskValue::EventMask); }
+        if mask & 0x00001000 != 0 { value.insert(CreateWindowValueMaskValue::DoNotPropagateMask); }
+        if mask & 0x00002000 != 0 { value.insert(CreateWindowValueMaskValue::Colormap); }
+        if mask & 0x00004000 != 0 { value.insert(CreateWindowValueMaskValue::Cursor); }
+        if mask & 0xffff8000 != 0 { return Err(Error::ProtocolError(ErrorCode::Value, mask)); }
+        Ok(value)
+    }
+}
+
+impl Writable for HashSet<CreateWindowValueMaskValue> {
+    fn write(stream: &mut BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let mut value = 0u32;
+        for data in data {
+            let mask = match data {
+                CreateWindowValueMaskValue::BackgroundPixmap => 0x00000001,
+                CreateWindowValueMaskValue::BackgroundPixel => 0x00000002,
+                CreateWindowValueMaskValue::BorderPixmap => 0x00000004,
+                CreateWindowValueMaskValue::BorderPixel => 0x00000008,
+                CreateWindowValueMaskValue::BitGravity => 0x00000010,
+                CreateWindowValueMaskValue::WinGravity => 0x00000020,
+                CreateWindowValueMaskValue::BackingStore => 0x00000040,
+                CreateWindowValueMaskValue::BackingPlanes => 0x00000080,
+                CreateWindowValueMaskValue::BackingPixel => 0x00000100,
+                CreateWindowValueMaskValue::OverrideRedirect => 0x00000200,
+                CreateWindowValueMaskValue::SaveUnder => 0x00000400,
+                CreateWindowValueMaskValue::EventMask => 0x00000800,
+                CreateWindowValueMaskValue::DoNotPropagateMask => 0x00001000,
+                CreateWindowValueMaskValue::Colormap => 0x00002000,
+                CreateWindowValueMaskValue::Cursor => 0x00004000,
+            };
+            value |= mask;
+        }
+        stream.write_value(value, order)
+    }
+}
+
+/// ChangeWindowAttributesと共通のvalue-mask以降の部分
+pub(crate) fn read_window_value(stream: &mut BufReader<impl Read>, order: &ByteOrder, length: usize) -> Result<(HashSet<CreateWindowValueMaskValue>, CreateWindowValue)> {
+    let value_mask: HashSet<_> = stream.read_value(order)?;
+    let mut list = ValueListReader::read(stream, order, value_mask.len(), length)?;
+    let mut value = CreateWindowValue::default();
+    if value_mask.contains(&CreateWindowValueMaskValue::BackgroundPixmap) { value.background_pixmap = list.read_value()?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::BackgroundPixel) { value.background_pixel = list.read_value()?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::BorderPixmap) { value.border_pixmap = list.read_value()?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::BorderPixel) { value.border_pixel = list.read_value()?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::BitGravity) { value.bit_gravity = list.read_value()?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::WinGravity) { value.win_gravity = list.read_value()?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::BackingStore) { value.backing_store = list.read_value()?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::BackingPlanes) { value.backing_planes = list.read_value()?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::BackingPixel) { value.backing_pixel = list.read_value()?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::OverrideRedirect) { value.override_redirect = list.read_value()?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::SaveUnder) { value.save_under = list.read_value()?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::EventMask) { value.event_mask = list.read_value()?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::DoNotPropagateMask) { value.do_not_propagate_mask = list.read_value()?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::Colormap) { value.colormap = list.read_value()?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::Cursor) {
+        value.cursor = match list.read_value()? {
+            0 => None,
+            other => Some(other),
+        };
+    }
+    Ok((value_mask, value))
+}
+
+pub(crate) fn write_window_value(stream: &mut BufWriter<impl Write>, value_mask: HashSet<CreateWindowValueMaskValue>, value: CreateWindowValue, order: &ByteOrder) -> Result<()> {
+    let mut list = ValueListWriter::new();
+    if value_mask.contains(&CreateWindowValueMaskValue::BackgroundPixmap) { list.write_value(value.background_pixmap)?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::BackgroundPixel) { list.write_value(value.background_pixel)?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::BorderPixmap) { list.write_value(value.border_pixmap)?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::BorderPixel) { list.write_value(value.border_pixel)?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::BitGravity) { list.write_value(value.bit_gravity)?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::WinGravity) { list.write_value(value.win_gravity)?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::BackingStore) { list.write_value(value.backing_store)?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::BackingPlanes) { list.write_value(value.backing_planes)?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::BackingPixel) { list.write_value(value.backing_pixel)?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::OverrideRedirect) { list.write_value(value.override_redirect)?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::SaveUnder) { list.write_value(value.save_under)?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::EventMask) { list.write_value(value.event_mask)?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::DoNotPropagateMask) { list.write_value(value.do_not_propagate_mask)?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::Colormap) { list.write_value(value.colormap)?; }
+    if value_mask.contains(&CreateWindowValueMaskValue::Cursor) { list.write_value(value.cursor.unwrap_or(0))?; }
+    stream.write_value(value_mask, order)?;
+    list.write(stream, order)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CreateWindowRequest {
+    pub depth: u8,
+    pub wid: u32,
+    pub parent: u32,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    pub border_width: u16,
+    pub class: CreateWindowClass,
+    pub visual: Option<u32>,
+    pub value_mask: HashSet<CreateWindowValueMaskValue>,
+    pub value: CreateWindowValue,
+}
 
 impl Readable for CreateWindowRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let depth = stream.read_value(order)?;
+        let length = stream.read_value::<u16>(order)? as usize;
+        if length < 8 {
+            return Err(Error::ProtocolError(ErrorCode::Length, 0));
+        }
+        let wid = stream.read_value(order)?;
+        let parent = stream.read_value(order)?;
+        let x = stream.read_value(order)?;
+        let y = stream.read_value(order)?;
+        let width = stream.read_value(order)?;
+        let height = stream.read_value(order)?;
+        let border_width = stream.read_value(order)?;
+        let class = stream.read_value(order)?;
+        let visual = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let (value_mask, value) = read_window_value(stream, order, length - 8)?;
+        Ok(CreateWindowRequest {
+            depth,
+            wid,
+            parent,
+            x,
+            y,
+            width,
+            height,
+            border_width,
+            class,
+            visual,
+            value_mask,
+            value,
+        })
     }
 }
 
-impl Writable for CreateWindowRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for CreateWindowRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(1, order)?;
+        stream.write_value(data.depth, order)?;
+        stream.write_value((8 + data.value_mask.len()) as u16, order)?;
+        stream.write_value(data.wid, order)?;
+        stream.write_value(data.parent, order)?;
+        stream.write_value(data.x, order)?;
+        stream.write_value(data.y, order)?;
+        stream.write_value(data.width, order)?;
+        stream.write_value(data.height, order)?;
+        stream.write_value(data.border_width, order)?;
+        stream.write_value(data.class, order)?;
+        stream.write_value(data.visual.unwrap_or(0), order)?;
+        write_window_value(stream, data.value_mask, data.value, order)
     }
 }
 
@@ -23,12 +487,12 @@ pub struct CreateWindowResponse;
 
 impl Readable for CreateWindowResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for CreateWindowResponse{
+impl Writable for CreateWindowResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
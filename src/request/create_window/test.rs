@@ -0,0 +1,112 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::collections::HashSet;
+    use std::io::{BufReader, BufWriter};
+    use std::iter::FromIterator;
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::create_window::{CreateWindowClass, CreateWindowRequest, CreateWindowValue, CreateWindowValueBitGravity, CreateWindowValueMaskValue};
+    use crate::setup::Event;
+
+    #[test]
+    fn read_test() {
+        const MSB: [u8; 52] = [1, 24, 0, 13, 0, 0, 0, 1, 0, 0, 0, 2, 0xff, 0xfe, 0, 3, 0, 4, 0, 5, 0, 6, 0, 1, 0, 0, 0, 0, 0, 0, 0x4a, 0x12,
+            0, 0, 0, 7, 0, 0, 0, 5, 0, 0, 0, 1, 0, 0, 0x80, 1, 0, 0, 0, 8];
+        const LSB: [u8; 52] = [1, 24, 13, 0, 1, 0, 0, 0, 2, 0, 0, 0, 0xfe, 0xff, 3, 0, 4, 0, 5, 0, 6, 0, 1, 0, 0, 0, 0, 0, 0x12, 0x4a, 0, 0,
+            7, 0, 0, 0, 5, 0, 0, 0, 1, 0, 0, 0, 1, 0x80, 0, 0, 8, 0, 0, 0];
+
+        fn expected() -> CreateWindowRequest {
+            CreateWindowRequest {
+                depth: 24,
+                wid: 1,
+                parent: 2,
+                x: -2,
+                y: 3,
+                width: 4,
+                height: 5,
+                border_width: 6,
+                class: CreateWindowClass::InputOutput,
+                visual: None,
+                value_mask: HashSet::from_iter([CreateWindowValueMaskValue::BackgroundPixel,
+                    CreateWindowValueMaskValue::BitGravity,
+                    CreateWindowValueMaskValue::OverrideRedirect,
+                    CreateWindowValueMaskValue::EventMask,
+                    CreateWindowValueMaskValue::Cursor].to_vec()),
+                value: CreateWindowValue {
+                    background_pixel: 7,
+                    bit_gravity: CreateWindowValueBitGravity::Center,
+                    override_redirect: true,
+                    event_mask: HashSet::from_iter([Event::KeyPress, Event::Exposure].to_vec()),
+                    cursor: Some(8),
+                    ..Default::default()
+                },
+            }
+        }
+
+        let value = CreateWindowRequest::read(&mut BufReader::new(&MSB[1..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected());
+        let value = CreateWindowRequest::read(&mut BufReader::new(&LSB[1..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected());
+        let input = [0, 0, 8, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0];
+        let value = CreateWindowRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, CreateWindowRequest {
+            depth: 0,
+            wid: 1,
+            parent: 2,
+            x: 0,
+            y: 0,
+            width: 1,
+            height: 1,
+            border_width: 0,
+            class: CreateWindowClass::InputOnly,
+            visual: Some(3),
+            value_mask: HashSet::new(),
+            value: Default::default(),
+        });
+    }
+
+    #[test]
+    fn write_test() {
+        const MSB: [u8; 52] = [1, 24, 0, 13, 0, 0, 0, 1, 0, 0, 0, 2, 0xff, 0xfe, 0, 3, 0, 4, 0, 5, 0, 6, 0, 1, 0, 0, 0, 0, 0, 0, 0x4a, 0x12,
+            0, 0, 0, 7, 0, 0, 0, 5, 0, 0, 0, 1, 0, 0, 0x80, 1, 0, 0, 0, 8];
+        const LSB: [u8; 52] = [1, 24, 13, 0, 1, 0, 0, 0, 2, 0, 0, 0, 0xfe, 0xff, 3, 0, 4, 0, 5, 0, 6, 0, 1, 0, 0, 0, 0, 0, 0x12, 0x4a, 0, 0,
+            7, 0, 0, 0, 5, 0, 0, 0, 1, 0, 0, 0, 1, 0x80, 0, 0, 8, 0, 0, 0];
+
+        fn expected() -> CreateWindowRequest {
+            CreateWindowRequest {
+                depth: 24,
+                wid: 1,
+                parent: 2,
+                x: -2,
+                y: 3,
+                width: 4,
+                height: 5,
+                border_width: 6,
+                class: CreateWindowClass::InputOutput,
+                visual: None,
+                value_mask: HashSet::from_iter([CreateWindowValueMaskValue::BackgroundPixel,
+                    CreateWindowValueMaskValue::BitGravity,
+                    CreateWindowValueMaskValue::OverrideRedirect,
+                    CreateWindowValueMaskValue::EventMask,
+                    CreateWindowValueMaskValue::Cursor].to_vec()),
+                value: CreateWindowValue {
+                    background_pixel: 7,
+                    bit_gravity: CreateWindowValueBitGravity::Center,
+                    override_redirect: true,
+                    event_mask: HashSet::from_iter([Event::KeyPress, Event::Exposure].to_vec()),
+                    cursor: Some(8),
+                    ..Default::default()
+                },
+            }
+        }
+
+        let mut buffer = [0; 52];
+        CreateWindowRequest::write(&mut BufWriter::new(&mut buffer[..]), expected(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(&buffer[..], &MSB[..]);
+        let mut buffer = [0; 52];
+        CreateWindowRequest::write(&mut BufWriter::new(&mut buffer[..]), expected(), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(&buffer[..], &LSB[..]);
+    }
+}
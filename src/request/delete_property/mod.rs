@@ -1,20 +1,34 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct DeletePropertyRequest;
+pub struct DeletePropertyRequest {
+    pub window: u32,
+    pub property: u32,
+}
 
 impl Readable for DeletePropertyRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let window = stream.read_value(order)?;
+        let property = stream.read_value(order)?;
+        Ok(DeletePropertyRequest { window, property })
     }
 }
 
-impl Writable for DeletePropertyRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for DeletePropertyRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(19, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(3, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_value(data.property, order)?;
+        Ok(())
     }
 }
 
@@ -23,12 +37,12 @@ pub struct DeletePropertyResponse;
 
 impl Readable for DeletePropertyResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for DeletePropertyResponse{
+impl Writable for DeletePropertyResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
@@ -0,0 +1,29 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::delete_property::DeletePropertyRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 3, 0, 0, 0, 1, 0, 0, 0, 39];
+        let value = DeletePropertyRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, DeletePropertyRequest { window: 1, property: 39 });
+        let input = [0, 3, 0, 1, 0, 0, 0, 39, 0, 0, 0];
+        let value = DeletePropertyRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, DeletePropertyRequest { window: 1, property: 39 });
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0; 12];
+        DeletePropertyRequest::write(&mut BufWriter::new(&mut buffer[..]), DeletePropertyRequest { window: 1, property: 39 }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [19, 0, 0, 3, 0, 0, 0, 1, 0, 0, 0, 39]);
+        let mut buffer = [0; 12];
+        DeletePropertyRequest::write(&mut BufWriter::new(&mut buffer[..]), DeletePropertyRequest { window: 1, property: 39 }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [19, 0, 3, 0, 1, 0, 0, 0, 39, 0, 0, 0]);
+    }
+}
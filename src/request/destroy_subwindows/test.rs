@@ -0,0 +1,29 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::destroy_subwindows::DestroySubwindowsRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 2, 0, 0, 0, 1];
+        let value = DestroySubwindowsRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, DestroySubwindowsRequest { window: 1 });
+        let input = [0, 2, 0, 1, 0, 0, 0];
+        let value = DestroySubwindowsRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, DestroySubwindowsRequest { window: 1 });
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0; 8];
+        DestroySubwindowsRequest::write(&mut BufWriter::new(&mut buffer[..]), DestroySubwindowsRequest { window: 1 }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [5, 0, 0, 2, 0, 0, 0, 1]);
+        let mut buffer = [0; 8];
+        DestroySubwindowsRequest::write(&mut BufWriter::new(&mut buffer[..]), DestroySubwindowsRequest { window: 1 }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [5, 0, 2, 0, 1, 0, 0, 0]);
+    }
+}
@@ -1,34 +1,71 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct GetAtomNameRequest;
+pub struct GetAtomNameRequest {
+    pub atom: u32,
+}
 
 impl Readable for GetAtomNameRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let atom = stream.read_value(order)?;
+        Ok(GetAtomNameRequest { atom })
     }
 }
 
-impl Writable for GetAtomNameRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GetAtomNameRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(17, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(2, order)?;
+        stream.write_value(data.atom, order)?;
+        Ok(())
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct GetAtomNameResponse;
+pub struct GetAtomNameResponse {
+    pub sequence_number: u16,
+    pub name: String,
+}
 
 impl Readable for GetAtomNameResponse {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 2], 2)?;
+        let sequence_number = stream.read_value(order)?;
+        let length = stream.read_value::<u32>(order)? as usize;
+        let name_len = stream.read_value::<u16>(order)? as usize;
+        read_specified_length(stream, &mut [0; 22], 22)?;
+        let mut buffer = vec![0; length << 2];
+        read_specified_length(stream, &mut buffer[..], length << 2)?;
+        if name_len > buffer.len() {
+            return Err(Error::InvalidValue("GetAtomNameResponse"));
+        }
+        let name = std::str::from_utf8(&buffer[..name_len]).map_err(Error::StringError)?;
+        Ok(GetAtomNameResponse {
+            sequence_number,
+            name: name.to_string(),
+        })
     }
 }
 
-impl Writable for GetAtomNameResponse{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GetAtomNameResponse {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let name_len = data.name.len();
+        stream.write_value::<u8>(1, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(((name_len + 3) >> 2) as u32, order)?;
+        stream.write_value(name_len as u16, order)?;
+        stream.write_all(&[0; 22]).map_err(Error::IoError)?;
+        stream.write_all(data.name.as_bytes()).map_err(Error::IoError)?;
+        stream.write_all(&[0; 4][..(!name_len).wrapping_add(1) & 3]).map_err(Error::IoError)?;
+        Ok(())
     }
 }
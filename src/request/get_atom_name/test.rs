@@ -0,0 +1,68 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::get_atom_name::GetAtomNameRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 2, 0, 0, 1, 0x2c];
+        let value = GetAtomNameRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, GetAtomNameRequest { atom: 300 });
+        let input = [0, 2, 0, 0x2c, 1, 0, 0];
+        let value = GetAtomNameRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, GetAtomNameRequest { atom: 300 });
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0; 8];
+        GetAtomNameRequest::write(&mut BufWriter::new(&mut buffer[..]), GetAtomNameRequest { atom: 300 }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [17, 0, 0, 2, 0, 0, 1, 0x2c]);
+        let mut buffer = [0; 8];
+        GetAtomNameRequest::write(&mut BufWriter::new(&mut buffer[..]), GetAtomNameRequest { atom: 300 }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [17, 0, 2, 0, 0x2c, 1, 0, 0]);
+    }
+}
+
+mod response {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::get_atom_name::GetAtomNameResponse;
+
+    #[test]
+    fn read_test() {
+        let expected = GetAtomNameResponse { sequence_number: 2, name: "STRING".to_string() };
+        let mut input = [0; 40];
+        input[..10].copy_from_slice(&[1, 0, 0, 2, 0, 0, 0, 2, 0, 6]);
+        input[32..38].copy_from_slice(b"STRING");
+        let value = GetAtomNameResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let mut input = [0; 40];
+        input[..10].copy_from_slice(&[1, 0, 2, 0, 2, 0, 0, 0, 6, 0]);
+        input[32..38].copy_from_slice(b"STRING");
+        let value = GetAtomNameResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn write_test() {
+        let data = GetAtomNameResponse { sequence_number: 2, name: "STRING".to_string() };
+        let mut expected = [0; 40];
+        expected[..10].copy_from_slice(&[1, 0, 0, 2, 0, 0, 0, 2, 0, 6]);
+        expected[32..38].copy_from_slice(b"STRING");
+        let mut buffer = [0xff; 40];
+        GetAtomNameResponse::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+        let mut expected = [0; 40];
+        expected[..10].copy_from_slice(&[1, 0, 2, 0, 2, 0, 0, 0, 6, 0]);
+        expected[32..38].copy_from_slice(b"STRING");
+        let mut buffer = [0xff; 40];
+        GetAtomNameResponse::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+    }
+}
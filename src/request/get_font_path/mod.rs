@@ -1,34 +1,61 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+use crate::request::list_fonts::{read_str_list, str_list_len, write_str_list};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct GetFontPathRequest;
 
 impl Readable for GetFontPathRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        Ok(Self)
     }
 }
 
-impl Writable for GetFontPathRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GetFontPathRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, _data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(52, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(1, order)?;
+        Ok(())
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct GetFontPathResponse;
+pub struct GetFontPathResponse {
+    pub sequence_number: u16,
+    pub path: Vec<String>,
+}
 
 impl Readable for GetFontPathResponse {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 2], 2)?;
+        let sequence_number = stream.read_value(order)?;
+        let length = stream.read_value::<u32>(order)? as usize;
+        let number_of_strs = stream.read_value::<u16>(order)? as usize;
+        read_specified_length(stream, &mut [0; 22], 22)?;
+        let path = read_str_list(stream, number_of_strs, length << 2)?;
+        Ok(GetFontPathResponse {
+            sequence_number,
+            path,
+        })
     }
 }
 
-impl Writable for GetFontPathResponse{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GetFontPathResponse {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let length = str_list_len(&data.path);
+        stream.write_value::<u8>(1, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(((length + 3) >> 2) as u32, order)?;
+        stream.write_value(data.path.len() as u16, order)?;
+        stream.write_all(&[0; 22]).map_err(Error::IoError)?;
+        write_str_list(stream, data.path)
     }
 }
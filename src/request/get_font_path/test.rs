@@ -0,0 +1,68 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::get_font_path::GetFontPathRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 1];
+        let value = GetFontPathRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, GetFontPathRequest);
+        let input = [0, 1, 0];
+        let value = GetFontPathRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, GetFontPathRequest);
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0xff; 4];
+        GetFontPathRequest::write(&mut BufWriter::new(&mut buffer[..]), GetFontPathRequest, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [52, 0, 0, 1]);
+        let mut buffer = [0xff; 4];
+        GetFontPathRequest::write(&mut BufWriter::new(&mut buffer[..]), GetFontPathRequest, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [52, 0, 1, 0]);
+    }
+}
+
+mod response {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::get_font_path::GetFontPathResponse;
+
+    #[test]
+    fn read_test() {
+        let expected = GetFontPathResponse { sequence_number: 4, path: vec!["misc/".to_string(), "75dpi".to_string()] };
+        let input = [
+            &[1, 0, 0, 4, 0, 0, 0, 3, 0, 2][..],
+            &[0; 22],
+            &[5, b'm', b'i', b's', b'c', b'/', 5, b'7', b'5', b'd', b'p', b'i'],
+        ].concat();
+        let value = GetFontPathResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [
+            &[1, 0, 4, 0, 3, 0, 0, 0, 2, 0][..],
+            &[0; 22],
+            &[5, b'm', b'i', b's', b'c', b'/', 5, b'7', b'5', b'd', b'p', b'i'],
+        ].concat();
+        let value = GetFontPathResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn write_test() {
+        let data = GetFontPathResponse { sequence_number: 4, path: vec!["misc".to_string()] };
+        let expected = [&[1, 0, 0, 4, 0, 0, 0, 2, 0, 1][..], &[0; 22], &[4, b'm', b'i', b's', b'c', 0, 0, 0]].concat();
+        let mut buffer = [0xff; 40];
+        GetFontPathResponse::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer[..], expected[..]);
+        let expected = [&[1, 0, 4, 0, 2, 0, 0, 0, 1, 0][..], &[0; 22], &[4, b'm', b'i', b's', b'c', 0, 0, 0]].concat();
+        let mut buffer = [0xff; 40];
+        GetFontPathResponse::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer[..], expected[..]);
+    }
+}
@@ -1,34 +1,85 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct GetGeometryRequest;
+pub struct GetGeometryRequest {
+    pub drawable: u32,
+}
 
 impl Readable for GetGeometryRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let drawable = stream.read_value(order)?;
+        Ok(GetGeometryRequest { drawable })
     }
 }
 
-impl Writable for GetGeometryRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GetGeometryRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(14, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(2, order)?;
+        stream.write_value(data.drawable, order)?;
+        Ok(())
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct GetGeometryResponse;
+pub struct GetGeometryResponse {
+    pub depth: u8,
+    pub sequence_number: u16,
+    pub root: u32,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    pub border_width: u16,
+}
 
 impl Readable for GetGeometryResponse {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let depth = stream.read_value(order)?;
+        let sequence_number = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 4], 4)?;
+        let root = stream.read_value(order)?;
+        let x = stream.read_value(order)?;
+        let y = stream.read_value(order)?;
+        let width = stream.read_value(order)?;
+        let height = stream.read_value(order)?;
+        let border_width = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 10], 10)?;
+        Ok(GetGeometryResponse {
+            depth,
+            sequence_number,
+            root,
+            x,
+            y,
+            width,
+            height,
+            border_width,
+        })
     }
 }
 
-impl Writable for GetGeometryResponse{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GetGeometryResponse {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(1, order)?;
+        stream.write_value(data.depth, order)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value::<u32>(0, order)?;
+        stream.write_value(data.root, order)?;
+        stream.write_value(data.x, order)?;
+        stream.write_value(data.y, order)?;
+        stream.write_value(data.width, order)?;
+        stream.write_value(data.height, order)?;
+        stream.write_value(data.border_width, order)?;
+        stream.write_all(&[0; 10]).map_err(Error::IoError)?;
+        Ok(())
     }
 }
@@ -0,0 +1,86 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::get_geometry::GetGeometryRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 2, 0, 0, 0, 1];
+        let value = GetGeometryRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, GetGeometryRequest { drawable: 1 });
+        let input = [0, 2, 0, 1, 0, 0, 0];
+        let value = GetGeometryRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, GetGeometryRequest { drawable: 1 });
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0; 8];
+        GetGeometryRequest::write(&mut BufWriter::new(&mut buffer[..]), GetGeometryRequest { drawable: 1 }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [14, 0, 0, 2, 0, 0, 0, 1]);
+        let mut buffer = [0; 8];
+        GetGeometryRequest::write(&mut BufWriter::new(&mut buffer[..]), GetGeometryRequest { drawable: 1 }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [14, 0, 2, 0, 1, 0, 0, 0]);
+    }
+}
+
+mod response {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::get_geometry::GetGeometryResponse;
+
+    #[test]
+    fn read_test() {
+        const MSB: [u8; 32] = [1, 24, 0, 2, 0, 0, 0, 0, 0, 0, 0, 3, 0xff, 0xfc, 0, 5, 0, 6, 0, 7, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        const LSB: [u8; 32] = [1, 24, 2, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0xfc, 0xff, 5, 0, 6, 0, 7, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+        fn expected() -> GetGeometryResponse {
+            GetGeometryResponse {
+                depth: 24,
+                sequence_number: 2,
+                root: 3,
+                x: -4,
+                y: 5,
+                width: 6,
+                height: 7,
+                border_width: 8,
+            }
+        }
+
+        let value = GetGeometryResponse::read(&mut BufReader::new(&MSB[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected());
+        let value = GetGeometryResponse::read(&mut BufReader::new(&LSB[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected());
+    }
+
+    #[test]
+    fn write_test() {
+        const MSB: [u8; 32] = [1, 24, 0, 2, 0, 0, 0, 0, 0, 0, 0, 3, 0xff, 0xfc, 0, 5, 0, 6, 0, 7, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        const LSB: [u8; 32] = [1, 24, 2, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0xfc, 0xff, 5, 0, 6, 0, 7, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+        fn expected() -> GetGeometryResponse {
+            GetGeometryResponse {
+                depth: 24,
+                sequence_number: 2,
+                root: 3,
+                x: -4,
+                y: 5,
+                width: 6,
+                height: 7,
+                border_width: 8,
+            }
+        }
+
+        let mut buffer = [0; 32];
+        GetGeometryResponse::write(&mut BufWriter::new(&mut buffer[..]), expected(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, MSB);
+        let mut buffer = [0; 32];
+        GetGeometryResponse::write(&mut BufWriter::new(&mut buffer[..]), expected(), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, LSB);
+    }
+}
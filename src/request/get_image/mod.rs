@@ -1,34 +1,200 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, Decode, Encode, read_specified_length, Readable, ReadableRead, SliceReader, SliceWriter, Writable, WritableWrite};
+
+mod test;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GetImageFormat {
+    XYPixmap,
+    ZPixmap,
+}
+
+impl GetImageFormat {
+    fn from_u8(value: u8) -> Result<Self> {
+        match value {
+            1 => Ok(Self::XYPixmap),
+            2 => Ok(Self::ZPixmap),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+
+    fn to_u8(&self) -> u8 {
+        match self {
+            Self::XYPixmap => 1,
+            Self::ZPixmap => 2,
+        }
+    }
+}
+
+impl Readable for GetImageFormat {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        Self::from_u8(stream.read_value(order)?)
+    }
+}
+
+impl Writable for GetImageFormat {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value(data.to_u8(), order)
+    }
+}
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct GetImageRequest;
+pub struct GetImageRequest {
+    pub format: GetImageFormat,
+    pub drawable: u32,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    pub plane_mask: u32,
+}
 
 impl Readable for GetImageRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let format = stream.read_value(order)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let drawable = stream.read_value(order)?;
+        let x = stream.read_value(order)?;
+        let y = stream.read_value(order)?;
+        let width = stream.read_value(order)?;
+        let height = stream.read_value(order)?;
+        let plane_mask = stream.read_value(order)?;
+        Ok(GetImageRequest {
+            format,
+            drawable,
+            x,
+            y,
+            width,
+            height,
+            plane_mask,
+        })
     }
 }
 
-impl Writable for GetImageRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GetImageRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(73, order)?;
+        stream.write_value(data.format, order)?;
+        stream.write_value::<u16>(5, order)?;
+        stream.write_value(data.drawable, order)?;
+        stream.write_value(data.x, order)?;
+        stream.write_value(data.y, order)?;
+        stream.write_value(data.width, order)?;
+        stream.write_value(data.height, order)?;
+        stream.write_value(data.plane_mask, order)?;
+        Ok(())
     }
 }
 
+/// dataは画像の後ろのpaddingも含む
+/// windowでなければvisualはNone
 #[derive(Clone, Debug, PartialEq)]
-pub struct GetImageResponse;
+pub struct GetImageResponse {
+    pub depth: u8,
+    pub sequence_number: u16,
+    pub visual: Option<u32>,
+    pub data: Vec<u8>,
+}
 
 impl Readable for GetImageResponse {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0], 1)?;
+        let depth = stream.read_value(order)?;
+        let sequence_number = stream.read_value(order)?;
+        let len = (stream.read_value::<u32>(order)? as usize) << 2;
+        let visual = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        read_specified_length(stream, &mut [0; 20], 20)?;
+        //届いた分だけ確保する
+        let mut data = Vec::new();
+        stream.by_ref().take(len as u64).read_to_end(&mut data).map_err(Error::IoError)?;
+        if data.len() < len {
+            return Err(Error::UnknownError);
+        }
+        Ok(GetImageResponse {
+            depth,
+            sequence_number,
+            visual,
+            data,
+        })
+    }
+}
+
+impl Writable for GetImageResponse {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let pad = (4 - (data.data.len() & 3)) & 3;
+        stream.write_value::<u8>(1, order)?;
+        stream.write_value(data.depth, order)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(((data.data.len() + pad) >> 2) as u32, order)?;
+        stream.write_value(data.visual.unwrap_or(0), order)?;
+        stream.write_all(&[0; 20]).map_err(Error::IoError)?;
+        stream.write_all(&data.data[..]).map_err(Error::IoError)?;
+        stream.write_all(&[0; 3][..pad]).map_err(Error::IoError)?;
+        Ok(())
     }
 }
 
-impl Writable for GetImageResponse{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+/// GetImageResponseのdataを入力のbufferから借用する版
+#[derive(Clone, Debug, PartialEq)]
+pub struct GetImageResponseRef<'a> {
+    pub depth: u8,
+    pub sequence_number: u16,
+    pub visual: Option<u32>,
+    pub data: &'a [u8],
+}
+
+impl<'a> GetImageResponseRef<'a> {
+    pub fn to_response(&self) -> GetImageResponse {
+        GetImageResponse {
+            depth: self.depth,
+            sequence_number: self.sequence_number,
+            visual: self.visual,
+            data: self.data.to_vec(),
+        }
+    }
+}
+
+impl<'a> Decode<'a> for GetImageResponseRef<'a> {
+    fn decode_from(reader: &mut SliceReader<'a>, order: &ByteOrder) -> Result<Self> {
+        reader.skip(1)?;
+        let depth = reader.read(order)?;
+        let sequence_number = reader.read(order)?;
+        let len = (reader.read::<u32>(order)? as usize) << 2;
+        let visual = match reader.read(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        reader.skip(20)?;
+        let data = reader.read_bytes(len)?;
+        Ok(GetImageResponseRef {
+            depth,
+            sequence_number,
+            visual,
+            data,
+        })
+    }
+}
+
+impl<'a> Encode for GetImageResponseRef<'a> {
+    fn encoded_length(&self) -> usize {
+        32 + ((self.data.len() + 3) & !3)
+    }
+
+    fn encode_to(&self, writer: &mut SliceWriter, order: &ByteOrder) -> Result<()> {
+        let pad = (4 - (self.data.len() & 3)) & 3;
+        writer.write::<u8>(1, order)?;
+        writer.write(self.depth, order)?;
+        writer.write(self.sequence_number, order)?;
+        writer.write(((self.data.len() + pad) >> 2) as u32, order)?;
+        writer.write(self.visual.unwrap_or(0), order)?;
+        writer.write_zero(20)?;
+        writer.write_bytes(self.data)?;
+        writer.write_zero(pad)
     }
 }
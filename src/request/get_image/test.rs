@@ -0,0 +1,108 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::Error;
+    use crate::error::ErrorCode;
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::get_image::{GetImageFormat, GetImageRequest};
+
+    #[test]
+    fn read_test() {
+        let input = [2, 0, 5, 0, 0, 0, 1, 0xff, 0xfe, 0, 3, 0, 2, 0, 1, 0xff, 0xff, 0xff, 0xff];
+        let value = GetImageRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, GetImageRequest {
+            format: GetImageFormat::ZPixmap,
+            drawable: 1,
+            x: -2,
+            y: 3,
+            width: 2,
+            height: 1,
+            plane_mask: 0xffffffff,
+        });
+        let input = [0, 5, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2, 0, 1, 0, 1, 0, 0, 0];
+        match GetImageRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst) {
+            Err(Error::ProtocolError(ErrorCode::Value, 0)) => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn write_test() {
+        let value = GetImageRequest {
+            format: GetImageFormat::XYPixmap,
+            drawable: 1,
+            x: -2,
+            y: 3,
+            width: 2,
+            height: 1,
+            plane_mask: 1,
+        };
+        let mut buffer = [0xff; 20];
+        GetImageRequest::write(&mut BufWriter::new(&mut buffer[..]), value, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [73, 1, 5, 0, 1, 0, 0, 0, 0xfe, 0xff, 3, 0, 2, 0, 1, 0, 1, 0, 0, 0]);
+    }
+}
+
+mod response {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Decode, Encode, Readable, SliceReader, SliceWriter, Writable};
+    use crate::request::get_image::{GetImageResponse, GetImageResponseRef};
+
+    #[test]
+    fn read_test() {
+        let input = [
+            1, 24, 0, 7, 0, 0, 0, 2, 0, 0, 0, 0x21,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            1, 2, 3, 4, 5, 6, 0, 0,
+        ];
+        let expected = GetImageResponse {
+            depth: 24,
+            sequence_number: 7,
+            visual: Some(0x21),
+            data: [1, 2, 3, 4, 5, 6, 0, 0].to_vec(),
+        };
+        let value = GetImageResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let value = GetImageResponseRef::decode_from(&mut SliceReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value.data.as_ptr(), input[32..].as_ptr());
+        assert_eq!(value.to_response(), expected);
+        //dataが足りない
+        assert!(GetImageResponse::read(&mut BufReader::new(&input[..39]), &ByteOrder::MSBFirst).is_err());
+        assert!(GetImageResponseRef::decode_from(&mut SliceReader::new(&input[..39]), &ByteOrder::MSBFirst).is_err());
+    }
+
+    #[test]
+    fn write_test() {
+        let value = GetImageResponse {
+            depth: 1,
+            sequence_number: 7,
+            visual: None,
+            data: [0x81, 0x42].to_vec(),
+        };
+        let expected = [
+            1, 1, 7, 0, 1, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0x81, 0x42, 0, 0,
+        ];
+        let mut buffer = [0xff; 36];
+        GetImageResponse::write(&mut BufWriter::new(&mut buffer[..]), value.clone(), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+        let value = GetImageResponseRef {
+            depth: value.depth,
+            sequence_number: value.sequence_number,
+            visual: value.visual,
+            data: &value.data[..],
+        };
+        assert_eq!(value.encoded_length(), 36);
+        let mut buffer = [0xff; 36];
+        value.encode_to(&mut SliceWriter::new(&mut buffer[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+        let mut buffer = Vec::new();
+        value.encode_to_vec(&mut buffer, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, expected.to_vec());
+    }
+}
@@ -1,34 +1,58 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+use crate::request::set_input_focus::{FocusRevertTo, InputFocus};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct GetInputFocusRequest;
 
 impl Readable for GetInputFocusRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        Ok(Self)
     }
 }
 
-impl Writable for GetInputFocusRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GetInputFocusRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, _data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(43, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(1, order)?;
+        Ok(())
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct GetInputFocusResponse;
+pub struct GetInputFocusResponse {
+    pub revert_to: FocusRevertTo,
+    pub sequence_number: u16,
+    pub focus: InputFocus,
+}
 
 impl Readable for GetInputFocusResponse {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let revert_to = stream.read_value(order)?;
+        let sequence_number = stream.read_value(order)?;
+        let _ = stream.read_value::<u32>(order)?;
+        let focus = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 20], 20)?;
+        Ok(GetInputFocusResponse { revert_to, sequence_number, focus })
     }
 }
 
-impl Writable for GetInputFocusResponse{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GetInputFocusResponse {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(1, order)?;
+        stream.write_value(data.revert_to, order)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value::<u32>(0, order)?;
+        stream.write_value(data.focus, order)?;
+        stream.write_all(&[0; 20]).map_err(Error::IoError)?;
+        Ok(())
     }
 }
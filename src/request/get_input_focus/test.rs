@@ -0,0 +1,65 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::get_input_focus::GetInputFocusRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 1];
+        let value = GetInputFocusRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, GetInputFocusRequest);
+        let input = [0, 1, 0];
+        let value = GetInputFocusRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, GetInputFocusRequest);
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0xff; 4];
+        GetInputFocusRequest::write(&mut BufWriter::new(&mut buffer[..]), GetInputFocusRequest, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [43, 0, 0, 1]);
+        let mut buffer = [0xff; 4];
+        GetInputFocusRequest::write(&mut BufWriter::new(&mut buffer[..]), GetInputFocusRequest, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [43, 0, 1, 0]);
+    }
+}
+
+mod response {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::get_input_focus::GetInputFocusResponse;
+    use crate::request::set_input_focus::{FocusRevertTo, InputFocus};
+
+    #[test]
+    fn read_test() {
+        let expected = GetInputFocusResponse { revert_to: FocusRevertTo::Parent, sequence_number: 2, focus: InputFocus::Window(0x00200001) };
+        let mut input = [0; 32];
+        input[..12].copy_from_slice(&[1, 2, 0, 2, 0, 0, 0, 0, 0, 0x20, 0, 1]);
+        let value = GetInputFocusResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let mut input = [0; 32];
+        input[..12].copy_from_slice(&[1, 2, 2, 0, 0, 0, 0, 0, 1, 0, 0x20, 0]);
+        let value = GetInputFocusResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn write_test() {
+        let data = GetInputFocusResponse { revert_to: FocusRevertTo::None, sequence_number: 2, focus: InputFocus::PointerRoot };
+        let mut expected = [0; 32];
+        expected[..12].copy_from_slice(&[1, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 1]);
+        let mut buffer = [0xff; 32];
+        GetInputFocusResponse::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+        let mut expected = [0; 32];
+        expected[..12].copy_from_slice(&[1, 0, 2, 0, 0, 0, 0, 0, 1, 0, 0, 0]);
+        let mut buffer = [0xff; 32];
+        GetInputFocusResponse::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+    }
+}
@@ -1,34 +1,102 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
 
+mod test;
+
+/// startとstopが0ならCurrentTime
 #[derive(Clone, Debug, PartialEq)]
-pub struct GetMotionEventsRequest;
+pub struct GetMotionEventsRequest {
+    pub window: u32,
+    pub start: u32,
+    pub stop: u32,
+}
 
 impl Readable for GetMotionEventsRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let window = stream.read_value(order)?;
+        let start = stream.read_value(order)?;
+        let stop = stream.read_value(order)?;
+        Ok(GetMotionEventsRequest { window, start, stop })
+    }
+}
+
+impl Writable for GetMotionEventsRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(39, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(4, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_value(data.start, order)?;
+        stream.write_value(data.stop, order)?;
+        Ok(())
+    }
+}
+
+/// x、yはwindowからの相対座標
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimeCoord {
+    pub time: u32,
+    pub x: i16,
+    pub y: i16,
+}
+
+impl Readable for TimeCoord {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let time = stream.read_value(order)?;
+        let x = stream.read_value(order)?;
+        let y = stream.read_value(order)?;
+        Ok(TimeCoord { time, x, y })
     }
 }
 
-impl Writable for GetMotionEventsRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for TimeCoord {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value(data.time, order)?;
+        stream.write_value(data.x, order)?;
+        stream.write_value(data.y, order)?;
+        Ok(())
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct GetMotionEventsResponse;
+pub struct GetMotionEventsResponse {
+    pub sequence_number: u16,
+    pub events: Vec<TimeCoord>,
+}
 
 impl Readable for GetMotionEventsResponse {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 2], 2)?;
+        let sequence_number = stream.read_value(order)?;
+        let length = stream.read_value::<u32>(order)? as usize;
+        let n = stream.read_value::<u32>(order)? as usize;
+        read_specified_length(stream, &mut [0; 20], 20)?;
+        if length != n * 2 {
+            return Err(Error::InvalidValue("GetMotionEventsResponse"));
+        }
+        let mut events = Vec::with_capacity(n);
+        for _ in 0..n {
+            events.push(stream.read_value(order)?);
+        }
+        Ok(GetMotionEventsResponse { sequence_number, events })
     }
 }
 
-impl Writable for GetMotionEventsResponse{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GetMotionEventsResponse {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(1, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value((data.events.len() * 2) as u32, order)?;
+        stream.write_value(data.events.len() as u32, order)?;
+        stream.write_all(&[0; 20]).map_err(Error::IoError)?;
+        for event in data.events {
+            stream.write_value(event, order)?;
+        }
+        Ok(())
     }
 }
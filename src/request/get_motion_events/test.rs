@@ -0,0 +1,70 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::get_motion_events::GetMotionEventsRequest;
+
+    #[test]
+    fn read_test() {
+        let expected = GetMotionEventsRequest { window: 0x00200001, start: 100, stop: 0 };
+        let input = [0, 0, 4, 0, 0x20, 0, 1, 0, 0, 0, 100, 0, 0, 0, 0];
+        let value = GetMotionEventsRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [0, 4, 0, 1, 0, 0x20, 0, 100, 0, 0, 0, 0, 0, 0, 0];
+        let value = GetMotionEventsRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn write_test() {
+        let data = GetMotionEventsRequest { window: 0x00200001, start: 100, stop: 12345 };
+        let mut buffer = [0; 16];
+        GetMotionEventsRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [39, 0, 0, 4, 0, 0x20, 0, 1, 0, 0, 0, 100, 0, 0, 0x30, 0x39]);
+        let mut buffer = [0; 16];
+        GetMotionEventsRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [39, 0, 4, 0, 1, 0, 0x20, 0, 100, 0, 0, 0, 0x39, 0x30, 0, 0]);
+    }
+}
+
+mod response {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::get_motion_events::{GetMotionEventsResponse, TimeCoord};
+
+    #[test]
+    fn read_test() {
+        let expected = GetMotionEventsResponse { sequence_number: 2, events: vec![TimeCoord { time: 100, x: 1, y: -1 }, TimeCoord { time: 101, x: 2, y: 3 }] };
+        let mut input = [0; 48];
+        input[..12].copy_from_slice(&[1, 0, 0, 2, 0, 0, 0, 4, 0, 0, 0, 2]);
+        input[32..].copy_from_slice(&[0, 0, 0, 100, 0, 1, 0xff, 0xff, 0, 0, 0, 101, 0, 2, 0, 3]);
+        let value = GetMotionEventsResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let mut input = [0; 48];
+        input[..12].copy_from_slice(&[1, 0, 2, 0, 4, 0, 0, 0, 2, 0, 0, 0]);
+        input[32..].copy_from_slice(&[100, 0, 0, 0, 1, 0, 0xff, 0xff, 101, 0, 0, 0, 2, 0, 3, 0]);
+        let value = GetMotionEventsResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn write_test() {
+        let data = GetMotionEventsResponse { sequence_number: 2, events: vec![TimeCoord { time: 100, x: 1, y: -1 }] };
+        let mut expected = [0; 40];
+        expected[..12].copy_from_slice(&[1, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0, 1]);
+        expected[32..].copy_from_slice(&[0, 0, 0, 100, 0, 1, 0xff, 0xff]);
+        let mut buffer = [0xff; 40];
+        GetMotionEventsResponse::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+        let mut expected = [0; 40];
+        expected[..12].copy_from_slice(&[1, 0, 2, 0, 2, 0, 0, 0, 1, 0, 0, 0]);
+        expected[32..].copy_from_slice(&[100, 0, 0, 0, 1, 0, 0xff, 0xff]);
+        let mut buffer = [0xff; 40];
+        GetMotionEventsResponse::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+    }
+}
@@ -2,6 +2,7 @@ use std::io::{Read, Write};
 
 use crate::{Error, Result};
 use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+use crate::request::change_property::{PropertyValue, read_property_value, write_property_value};
 
 mod test;
 
@@ -52,20 +53,20 @@ impl Writable for GetPropertyRequest {
     }
 }
 
+/// propertyが存在しなければvalueはNone
+/// typeが違えばvalueは値と同じformatの空の並び
 #[derive(Clone, Debug, PartialEq)]
 pub struct GetPropertyResponse {
-    pub format: u8,
     pub sequence_number: u16,
     pub type_: Option<u32>,
     pub bytes_after: u32,
-    pub length_of_value_in_format_units: u32,
-    pub value: Vec<u8>,
+    pub value: Option<PropertyValue>,
 }
 
 impl Readable for GetPropertyResponse {
     fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
         read_specified_length(stream, &mut [0], 1)?;
-        let format = stream.read_value(order)?;
+        let format = stream.read_value::<u8>(order)?;
         let sequence_number = stream.read_value(order)?;
         let len = (stream.read_value::<u32>(order)? as usize) << 2;
         let type_ = match stream.read_value(order)? {
@@ -73,44 +74,45 @@ impl Readable for GetPropertyResponse {
             other => Some(other),
         };
         let bytes_after = stream.read_value(order)?;
-        let length_of_value_in_format_units = stream.read_value(order)?;
+        let units = stream.read_value::<u32>(order)? as usize;
         read_specified_length(stream, &mut [0; 12], 12)?;
-        let value_len =
-            if (length_of_value_in_format_units << 2) as usize <= len {
-                (length_of_value_in_format_units << 2) as usize
-            } else if (length_of_value_in_format_units << 1) as usize <= len {
-                (length_of_value_in_format_units << 1) as usize
-            } else {
-                length_of_value_in_format_units as usize
-            };
-        let mut value = vec![0; len];
-        read_specified_length(stream, &mut value, len)?;
-        for _ in value_len..len {
-            value.pop();
-        }
+        let (value, value_len) = match format {
+            0 => (None, 0),
+            8 | 16 | 32 => {
+                let value_len = units * (format as usize / 8);
+                if value_len > len {
+                    return Err(Error::InvalidValue("GetPropertyResponse"));
+                }
+                (Some(read_property_value(stream, order, format, units)?), value_len)
+            }
+            _ => return Err(Error::InvalidValue("GetPropertyResponse")),
+        };
+        let mut pad = vec![0; len - value_len];
+        read_specified_length(stream, &mut pad, len - value_len)?;
         Ok(GetPropertyResponse {
-            format,
             sequence_number,
             type_,
             bytes_after,
-            length_of_value_in_format_units,
             value,
         })
     }
 }
 
+#[allow(clippy::redundant_closure)]
 impl Writable for GetPropertyResponse {
     fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let byte_len = data.value.as_ref().map(|value| value.byte_len()).unwrap_or(0);
         stream.write_value::<u8>(1, order)?;
-        stream.write_value(data.format, order)?;
+        stream.write_value(data.value.as_ref().map(|value| value.format()).unwrap_or(0), order)?;
         stream.write_value(data.sequence_number, order)?;
-        stream.write_value::<u32>((((data.value.len() + 3) & !3) >> 2) as u32, order)?;
+        stream.write_value(((byte_len + 3) >> 2) as u32, order)?;
         stream.write_value(data.type_.unwrap_or(0), order)?;
         stream.write_value(data.bytes_after, order)?;
-        stream.write_value(data.length_of_value_in_format_units, order)?;
+        stream.write_value(data.value.as_ref().map(|value| value.len()).unwrap_or(0) as u32, order)?;
         stream.write_all(&[0; 12]).map_err(|e| Error::IoError(e))?;
-        stream.write_all(&data.value[..]).map_err(|e| Error::IoError(e))?;
-        stream.write_all(&[0; 4][..(!data.value.len()).wrapping_add(1) & 3]).map_err(|e| Error::IoError(e))?;
+        if let Some(value) = data.value {
+            write_property_value(stream, value, order)?;
+        }
         Ok(())
     }
 }
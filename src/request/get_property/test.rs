@@ -41,34 +41,56 @@ mod response {
     use std::io::{BufReader, BufWriter};
 
     use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::change_property::PropertyValue;
     use crate::request::get_property::GetPropertyResponse;
 
     #[test]
     fn read_test() {
-        let input = [1, 1, 0, 2, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4, 5, 6, 7, 8, 9, 0, 0];
+        let input = [1, 16, 0, 2, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4, 0, 5, 0, 6, 0, 0];
         let value = GetPropertyResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
         assert_eq!(value, GetPropertyResponse {
-            format: 1,
             sequence_number: 2,
             type_: Some(3),
             bytes_after: 4,
-            length_of_value_in_format_units: 3,
-            value: vec![4, 5, 6, 7, 8, 9],
+            value: Some(PropertyValue::Format16(vec![4, 5, 6])),
+        });
+        let input = [1, 32, 2, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x78, 0x56, 0x34, 0x12, 1, 0, 0, 0];
+        let value = GetPropertyResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, GetPropertyResponse {
+            sequence_number: 2,
+            type_: Some(3),
+            bytes_after: 0,
+            value: Some(PropertyValue::Format32(vec![0x12345678, 1])),
+        });
+        let input = [1, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let value = GetPropertyResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, GetPropertyResponse {
+            sequence_number: 2,
+            type_: None,
+            bytes_after: 0,
+            value: None,
         });
     }
 
     #[test]
     fn write_test() {
         let value = GetPropertyResponse {
-            format: 1,
             sequence_number: 2,
             type_: Some(3),
             bytes_after: 4,
-            length_of_value_in_format_units: 3,
-            value: vec![4, 5, 6, 7, 8, 9],
+            value: Some(PropertyValue::Format8(vec![4, 5, 6, 7, 8, 9])),
         };
         let mut buffer = [0; 40];
         GetPropertyResponse::write(&mut BufWriter::new(&mut buffer[..]), value, &ByteOrder::MSBFirst).unwrap();
-        assert_eq!(buffer, [1, 1, 0, 2, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4, 5, 6, 7, 8, 9, 0, 0]);
+        assert_eq!(buffer, [1, 8, 0, 2, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4, 5, 6, 7, 8, 9, 0, 0]);
+        let value = GetPropertyResponse {
+            sequence_number: 2,
+            type_: Some(3),
+            bytes_after: 0,
+            value: Some(PropertyValue::Format16(vec![0x0102, 0x0304, 0x0506])),
+        };
+        let mut buffer = [0; 40];
+        GetPropertyResponse::write(&mut BufWriter::new(&mut buffer[..]), value, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [1, 16, 2, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 1, 4, 3, 6, 5, 0, 0]);
     }
-}
\ No newline at end of file
+}
@@ -1,34 +1,62 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct GetSelectionOwnerRequest;
+pub struct GetSelectionOwnerRequest {
+    pub selection: u32,
+}
 
 impl Readable for GetSelectionOwnerRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let selection = stream.read_value(order)?;
+        Ok(GetSelectionOwnerRequest { selection })
     }
 }
 
-impl Writable for GetSelectionOwnerRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GetSelectionOwnerRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(23, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(2, order)?;
+        stream.write_value(data.selection, order)?;
+        Ok(())
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct GetSelectionOwnerResponse;
+pub struct GetSelectionOwnerResponse {
+    pub sequence_number: u16,
+    pub owner: Option<u32>,
+}
 
 impl Readable for GetSelectionOwnerResponse {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 2], 2)?;
+        let sequence_number = stream.read_value(order)?;
+        let _ = stream.read_value::<u32>(order)?;
+        let owner = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        read_specified_length(stream, &mut [0; 20], 20)?;
+        Ok(GetSelectionOwnerResponse { sequence_number, owner })
     }
 }
 
-impl Writable for GetSelectionOwnerResponse{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GetSelectionOwnerResponse {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(1, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value::<u32>(0, order)?;
+        stream.write_value(data.owner.unwrap_or(0), order)?;
+        stream.write_all(&[0; 20]).map_err(Error::IoError)?;
+        Ok(())
     }
 }
@@ -0,0 +1,63 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::get_selection_owner::GetSelectionOwnerRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 2, 0, 0, 1, 0x2c];
+        let value = GetSelectionOwnerRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, GetSelectionOwnerRequest { selection: 300 });
+        let input = [0, 2, 0, 0x2c, 1, 0, 0];
+        let value = GetSelectionOwnerRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, GetSelectionOwnerRequest { selection: 300 });
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0; 8];
+        GetSelectionOwnerRequest::write(&mut BufWriter::new(&mut buffer[..]), GetSelectionOwnerRequest { selection: 300 }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [23, 0, 0, 2, 0, 0, 1, 0x2c]);
+        let mut buffer = [0; 8];
+        GetSelectionOwnerRequest::write(&mut BufWriter::new(&mut buffer[..]), GetSelectionOwnerRequest { selection: 300 }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [23, 0, 2, 0, 0x2c, 1, 0, 0]);
+    }
+}
+
+mod response {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::get_selection_owner::GetSelectionOwnerResponse;
+
+    #[test]
+    fn read_test() {
+        let mut input = [0; 32];
+        input[..12].copy_from_slice(&[1, 0, 0, 2, 0, 0, 0, 0, 0, 0x20, 0, 1]);
+        let value = GetSelectionOwnerResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, GetSelectionOwnerResponse { sequence_number: 2, owner: Some(0x00200001) });
+        let mut input = [0; 32];
+        input[..4].copy_from_slice(&[1, 0, 2, 0]);
+        let value = GetSelectionOwnerResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, GetSelectionOwnerResponse { sequence_number: 2, owner: None });
+    }
+
+    #[test]
+    fn write_test() {
+        let data = GetSelectionOwnerResponse { sequence_number: 2, owner: Some(0x00200001) };
+        let mut expected = [0; 32];
+        expected[..12].copy_from_slice(&[1, 0, 0, 2, 0, 0, 0, 0, 0, 0x20, 0, 1]);
+        let mut buffer = [0xff; 32];
+        GetSelectionOwnerResponse::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+        let mut expected = [0; 32];
+        expected[..12].copy_from_slice(&[1, 0, 2, 0, 0, 0, 0, 0, 1, 0, 0x20, 0]);
+        let mut buffer = [0xff; 32];
+        GetSelectionOwnerResponse::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+    }
+}
@@ -1,34 +1,160 @@
-use std::io::{Read, Write};
+use std::collections::HashSet;
+use std::io::{BufReader, Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+use crate::request::create_window::{CreateWindowClass, CreateWindowValueBackingStore, CreateWindowValueBitGravity, CreateWindowValueWinGravity};
+use crate::setup::Event;
+
+mod test;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GetWindowAttributesMapState {
+    Unmapped,
+    Unviewable,
+    Viewable,
+}
+
+impl Readable for GetWindowAttributesMapState {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::Unmapped),
+            1 => Ok(Self::Unviewable),
+            2 => Ok(Self::Viewable),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for GetWindowAttributesMapState {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::Unmapped => 0,
+            Self::Unviewable => 1,
+            Self::Viewable => 2,
+        };
+        stream.write_value(value, order)
+    }
+}
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct GetWindowAttributesRequest;
+pub struct GetWindowAttributesRequest {
+    pub window: u32,
+}
 
 impl Readable for GetWindowAttributesRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let window = stream.read_value(order)?;
+        Ok(GetWindowAttributesRequest { window })
     }
 }
 
-impl Writable for GetWindowAttributesRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GetWindowAttributesRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(3, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(2, order)?;
+        stream.write_value(data.window, order)?;
+        Ok(())
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct GetWindowAttributesResponse;
+pub struct GetWindowAttributesResponse {
+    pub backing_store: CreateWindowValueBackingStore,
+    pub sequence_number: u16,
+    pub visual: u32,
+    pub class: CreateWindowClass,
+    pub bit_gravity: CreateWindowValueBitGravity,
+    pub win_gravity: CreateWindowValueWinGravity,
+    pub backing_planes: u32,
+    pub backing_pixel: u32,
+    pub save_under: bool,
+    pub map_is_installed: bool,
+    pub map_state: GetWindowAttributesMapState,
+    pub override_redirect: bool,
+    pub colormap: Option<u32>,
+    pub all_event_masks: HashSet<Event>,
+    pub your_event_mask: HashSet<Event>,
+    pub do_not_propagate_mask: HashSet<Event>,
+}
 
 impl Readable for GetWindowAttributesResponse {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let backing_store = stream.read_value(order)?;
+        let sequence_number = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 4], 4)?;
+        let visual = stream.read_value(order)?;
+        let class = stream.read_value(order)?;
+        let bit_gravity = stream.read_value(order)?;
+        let win_gravity = stream.read_value(order)?;
+        let backing_planes = stream.read_value(order)?;
+        let backing_pixel = stream.read_value(order)?;
+        let save_under = stream.read_value(order)?;
+        let map_is_installed = stream.read_value(order)?;
+        let map_state = stream.read_value(order)?;
+        let override_redirect = stream.read_value(order)?;
+        let colormap = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let all_event_masks = stream.read_value(order)?;
+        let your_event_mask = stream.read_value(order)?;
+        let mut buffer = [0; 4];
+        order.encode(stream.read_value::<u16>(order)? as u32, &mut buffer);
+        let do_not_propagate_mask = BufReader::new(&buffer[..]).read_value(order)?;
+        read_specified_length(stream, &mut [0; 2], 2)?;
+        Ok(GetWindowAttributesResponse {
+            backing_store,
+            sequence_number,
+            visual,
+            class,
+            bit_gravity,
+            win_gravity,
+            backing_planes,
+            backing_pixel,
+            save_under,
+            map_is_installed,
+            map_state,
+            override_redirect,
+            colormap,
+            all_event_masks,
+            your_event_mask,
+            do_not_propagate_mask,
+        })
     }
 }
 
-impl Writable for GetWindowAttributesResponse{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GetWindowAttributesResponse {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(1, order)?;
+        stream.write_value(data.backing_store, order)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value::<u32>(3, order)?;
+        stream.write_value(data.visual, order)?;
+        stream.write_value(data.class, order)?;
+        stream.write_value(data.bit_gravity, order)?;
+        stream.write_value(data.win_gravity, order)?;
+        stream.write_value(data.backing_planes, order)?;
+        stream.write_value(data.backing_pixel, order)?;
+        stream.write_value(data.save_under, order)?;
+        stream.write_value(data.map_is_installed, order)?;
+        stream.write_value(data.map_state, order)?;
+        stream.write_value(data.override_redirect, order)?;
+        stream.write_value(data.colormap.unwrap_or(0), order)?;
+        stream.write_value(data.all_event_masks, order)?;
+        stream.write_value(data.your_event_mask, order)?;
+        let mut buffer = [0; 4];
+        let mut writer = std::io::BufWriter::new(&mut buffer[..]);
+        writer.write_value(data.do_not_propagate_mask, order)?;
+        writer.flush().map_err(Error::IoError)?;
+        drop(writer);
+        stream.write_value(order.decode::<u32>(&buffer) as u16, order)?;
+        stream.write_all(&[0; 2]).map_err(Error::IoError)?;
+        Ok(())
     }
 }
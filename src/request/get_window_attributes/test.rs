@@ -0,0 +1,110 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::get_window_attributes::GetWindowAttributesRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 2, 0, 0, 0, 1];
+        let value = GetWindowAttributesRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, GetWindowAttributesRequest { window: 1 });
+        let input = [0, 2, 0, 1, 0, 0, 0];
+        let value = GetWindowAttributesRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, GetWindowAttributesRequest { window: 1 });
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0; 8];
+        GetWindowAttributesRequest::write(&mut BufWriter::new(&mut buffer[..]), GetWindowAttributesRequest { window: 1 }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [3, 0, 0, 2, 0, 0, 0, 1]);
+        let mut buffer = [0; 8];
+        GetWindowAttributesRequest::write(&mut BufWriter::new(&mut buffer[..]), GetWindowAttributesRequest { window: 1 }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [3, 0, 2, 0, 1, 0, 0, 0]);
+    }
+}
+
+mod response {
+    use std::collections::HashSet;
+    use std::io::{BufReader, BufWriter};
+    use std::iter::FromIterator;
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::create_window::{CreateWindowClass, CreateWindowValueBackingStore, CreateWindowValueBitGravity, CreateWindowValueWinGravity};
+    use crate::request::get_window_attributes::{GetWindowAttributesMapState, GetWindowAttributesResponse};
+    use crate::setup::Event;
+
+    #[test]
+    fn read_test() {
+        const MSB: [u8; 44] = [1, 1, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 1, 5, 10, 0, 0, 0, 6, 0, 0, 0, 7, 1, 0, 2, 1,
+            0, 0, 0, 8, 0, 0, 0x80, 1, 0, 0, 0, 3, 0, 4, 0, 0];
+        const LSB: [u8; 44] = [1, 1, 2, 0, 3, 0, 0, 0, 4, 0, 0, 0, 1, 0, 5, 10, 6, 0, 0, 0, 7, 0, 0, 0, 1, 0, 2, 1,
+            8, 0, 0, 0, 1, 0x80, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0];
+
+        fn expected() -> GetWindowAttributesResponse {
+            GetWindowAttributesResponse {
+                backing_store: CreateWindowValueBackingStore::WhenMapped,
+                sequence_number: 2,
+                visual: 4,
+                class: CreateWindowClass::InputOutput,
+                bit_gravity: CreateWindowValueBitGravity::Center,
+                win_gravity: CreateWindowValueWinGravity::Static,
+                backing_planes: 6,
+                backing_pixel: 7,
+                save_under: true,
+                map_is_installed: false,
+                map_state: GetWindowAttributesMapState::Viewable,
+                override_redirect: true,
+                colormap: Some(8),
+                all_event_masks: HashSet::from_iter([Event::KeyPress, Event::Exposure].to_vec()),
+                your_event_mask: HashSet::from_iter([Event::KeyPress, Event::KeyRelease].to_vec()),
+                do_not_propagate_mask: HashSet::from_iter([Event::ButtonPress].to_vec()),
+            }
+        }
+
+        let value = GetWindowAttributesResponse::read(&mut BufReader::new(&MSB[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected());
+        let value = GetWindowAttributesResponse::read(&mut BufReader::new(&LSB[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected());
+    }
+
+    #[test]
+    fn write_test() {
+        const MSB: [u8; 44] = [1, 1, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 1, 5, 10, 0, 0, 0, 6, 0, 0, 0, 7, 1, 0, 2, 1,
+            0, 0, 0, 8, 0, 0, 0x80, 1, 0, 0, 0, 3, 0, 4, 0, 0];
+        const LSB: [u8; 44] = [1, 1, 2, 0, 3, 0, 0, 0, 4, 0, 0, 0, 1, 0, 5, 10, 6, 0, 0, 0, 7, 0, 0, 0, 1, 0, 2, 1,
+            8, 0, 0, 0, 1, 0x80, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0];
+
+        fn expected() -> GetWindowAttributesResponse {
+            GetWindowAttributesResponse {
+                backing_store: CreateWindowValueBackingStore::WhenMapped,
+                sequence_number: 2,
+                visual: 4,
+                class: CreateWindowClass::InputOutput,
+                bit_gravity: CreateWindowValueBitGravity::Center,
+                win_gravity: CreateWindowValueWinGravity::Static,
+                backing_planes: 6,
+                backing_pixel: 7,
+                save_under: true,
+                map_is_installed: false,
+                map_state: GetWindowAttributesMapState::Viewable,
+                override_redirect: true,
+                colormap: Some(8),
+                all_event_masks: HashSet::from_iter([Event::KeyPress, Event::Exposure].to_vec()),
+                your_event_mask: HashSet::from_iter([Event::KeyPress, Event::KeyRelease].to_vec()),
+                do_not_propagate_mask: HashSet::from_iter([Event::ButtonPress].to_vec()),
+            }
+        }
+
+        let mut buffer = [0; 44];
+        GetWindowAttributesResponse::write(&mut BufWriter::new(&mut buffer[..]), expected(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(&buffer[..], &MSB[..]);
+        let mut buffer = [0; 44];
+        GetWindowAttributesResponse::write(&mut BufWriter::new(&mut buffer[..]), expected(), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(&buffer[..], &LSB[..]);
+    }
+}
@@ -1,20 +1,129 @@
+use std::collections::HashSet;
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::event::KeyButMask;
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+use crate::request::grab_pointer::{GrabMode, read_pointer_event_mask, write_pointer_event_mask};
+use crate::setup;
 
+mod test;
+
+/// SETofKEYMASKの下位bitからの順
+const KEY_MASKS: [KeyButMask; 8] = [
+    KeyButMask::Shift,
+    KeyButMask::Lock,
+    KeyButMask::Control,
+    KeyButMask::Mod1,
+    KeyButMask::Mod2,
+    KeyButMask::Mod3,
+    KeyButMask::Mod4,
+    KeyButMask::Mod5,
+];
+
+/// AnyModifierはすべての組み合わせを表す
+/// Modifiersにはbuttonを含めない
+#[derive(Clone, Debug, PartialEq)]
+pub enum GrabModifiers {
+    AnyModifier,
+    Modifiers(HashSet<KeyButMask>),
+}
+
+impl Readable for GrabModifiers {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u16>(order)? {
+            0x8000 => Ok(Self::AnyModifier),
+            value if value & 0xff00 != 0 => Err(Error::ProtocolError(ErrorCode::Value, value as u32)),
+            value => Ok(Self::Modifiers(KEY_MASKS.iter().enumerate().filter(|(i, _)| (value >> i) & 1 == 1).map(|(_, mask)| mask.clone()).collect())),
+        }
+    }
+}
+
+impl Writable for GrabModifiers {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value = match data {
+            Self::AnyModifier => 0x8000,
+            Self::Modifiers(modifiers) => {
+                let mut value = 0u16;
+                for modifier in modifiers {
+                    match KEY_MASKS.iter().position(|mask| *mask == modifier) {
+                        Some(i) => value |= 1 << i,
+                        None => return Err(Error::InvalidValue("GrabModifiers")),
+                    }
+                }
+                value
+            }
+        };
+        stream.write_value::<u16>(value, order)
+    }
+}
+
+/// buttonがNoneならAnyButton
 #[derive(Clone, Debug, PartialEq)]
-pub struct GrabButtonRequest;
+pub struct GrabButtonRequest {
+    pub owner_events: bool,
+    pub grab_window: u32,
+    pub event_mask: HashSet<setup::Event>,
+    pub pointer_mode: GrabMode,
+    pub keyboard_mode: GrabMode,
+    pub confine_to: Option<u32>,
+    pub cursor: Option<u32>,
+    pub button: Option<u8>,
+    pub modifiers: GrabModifiers,
+}
 
 impl Readable for GrabButtonRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let owner_events = stream.read_value(order)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let grab_window = stream.read_value(order)?;
+        let event_mask = read_pointer_event_mask(stream, order)?;
+        let pointer_mode = stream.read_value(order)?;
+        let keyboard_mode = stream.read_value(order)?;
+        let confine_to = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let cursor = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let button = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let modifiers = stream.read_value(order)?;
+        Ok(GrabButtonRequest {
+            owner_events,
+            grab_window,
+            event_mask,
+            pointer_mode,
+            keyboard_mode,
+            confine_to,
+            cursor,
+            button,
+            modifiers,
+        })
     }
 }
 
-impl Writable for GrabButtonRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GrabButtonRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(28, order)?;
+        stream.write_value(data.owner_events, order)?;
+        stream.write_value::<u16>(6, order)?;
+        stream.write_value(data.grab_window, order)?;
+        write_pointer_event_mask(stream, data.event_mask, order)?;
+        stream.write_value(data.pointer_mode, order)?;
+        stream.write_value(data.keyboard_mode, order)?;
+        stream.write_value(data.confine_to.unwrap_or(0), order)?;
+        stream.write_value(data.cursor.unwrap_or(0), order)?;
+        stream.write_value(data.button.unwrap_or(0), order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value(data.modifiers, order)?;
+        Ok(())
     }
 }
 
@@ -23,12 +132,12 @@ pub struct GrabButtonResponse;
 
 impl Readable for GrabButtonResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for GrabButtonResponse{
+impl Writable for GrabButtonResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
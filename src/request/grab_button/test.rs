@@ -0,0 +1,70 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::collections::HashSet;
+    use std::io::{BufReader, BufWriter};
+    use std::iter::FromIterator;
+
+    use crate::Error;
+    use crate::error::ErrorCode;
+    use crate::event::KeyButMask;
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::grab_button::{GrabButtonRequest, GrabModifiers};
+    use crate::request::grab_pointer::GrabMode;
+    use crate::setup;
+
+    #[test]
+    fn read_test() {
+        let expected = GrabButtonRequest {
+            owner_events: false,
+            grab_window: 0x00200001,
+            event_mask: HashSet::from_iter([setup::Event::ButtonPress, setup::Event::ButtonRelease].to_vec()),
+            pointer_mode: GrabMode::Synchronous,
+            keyboard_mode: GrabMode::Asynchronous,
+            confine_to: Some(1),
+            cursor: None,
+            button: Some(3),
+            modifiers: GrabModifiers::Modifiers(HashSet::from_iter([KeyButMask::Shift, KeyButMask::Mod4].to_vec())),
+        };
+        let input = [0, 0, 6, 0, 0x20, 0, 1, 0, 0x0c, 0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 3, 0, 0, 0x41];
+        let value = GrabButtonRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [0, 6, 0, 1, 0, 0x20, 0, 0x0c, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0x41, 0];
+        let value = GrabButtonRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [0, 0, 6, 0, 0x20, 0, 1, 0, 0x0c, 0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0x80, 0];
+        let value = GrabButtonRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, GrabButtonRequest { button: None, modifiers: GrabModifiers::AnyModifier, ..expected });
+        //Button1Maskはmodifierではない
+        let input = [0, 0, 6, 0, 0x20, 0, 1, 0, 0x0c, 0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0x01, 0];
+        match GrabButtonRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst) {
+            Err(Error::ProtocolError(ErrorCode::Value, 0x100)) => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn write_test() {
+        let data = GrabButtonRequest {
+            owner_events: true,
+            grab_window: 0x00200001,
+            event_mask: HashSet::from_iter([setup::Event::ButtonMotion].to_vec()),
+            pointer_mode: GrabMode::Asynchronous,
+            keyboard_mode: GrabMode::Asynchronous,
+            confine_to: None,
+            cursor: Some(0x00200002),
+            button: None,
+            modifiers: GrabModifiers::AnyModifier,
+        };
+        let mut buffer = [0; 24];
+        GrabButtonRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [28, 1, 0, 6, 0, 0x20, 0, 1, 0x20, 0, 1, 1, 0, 0, 0, 0, 0, 0x20, 0, 2, 0, 0, 0x80, 0]);
+        let mut buffer = [0; 24];
+        GrabButtonRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [28, 1, 6, 0, 1, 0, 0x20, 0, 0, 0x20, 1, 1, 0, 0, 0, 0, 2, 0, 0x20, 0, 0, 0, 0, 0x80]);
+        let data = GrabButtonRequest { modifiers: GrabModifiers::Modifiers(HashSet::from_iter([KeyButMask::Button1].to_vec())), ..data };
+        let mut buffer = [0; 24];
+        assert!(matches!(GrabButtonRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::MSBFirst), Err(Error::InvalidValue(_))));
+    }
+}
@@ -1,20 +1,59 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+use crate::request::grab_button::GrabModifiers;
+use crate::request::grab_pointer::GrabMode;
 
+mod test;
+
+/// keyがNoneならAnyKey
 #[derive(Clone, Debug, PartialEq)]
-pub struct GrabKeyRequest;
+pub struct GrabKeyRequest {
+    pub owner_events: bool,
+    pub grab_window: u32,
+    pub modifiers: GrabModifiers,
+    pub key: Option<u8>,
+    pub pointer_mode: GrabMode,
+    pub keyboard_mode: GrabMode,
+}
 
 impl Readable for GrabKeyRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let owner_events = stream.read_value(order)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let grab_window = stream.read_value(order)?;
+        let modifiers = stream.read_value(order)?;
+        let key = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let pointer_mode = stream.read_value(order)?;
+        let keyboard_mode = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 3], 3)?;
+        Ok(GrabKeyRequest {
+            owner_events,
+            grab_window,
+            modifiers,
+            key,
+            pointer_mode,
+            keyboard_mode,
+        })
     }
 }
 
-impl Writable for GrabKeyRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GrabKeyRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(33, order)?;
+        stream.write_value(data.owner_events, order)?;
+        stream.write_value::<u16>(4, order)?;
+        stream.write_value(data.grab_window, order)?;
+        stream.write_value(data.modifiers, order)?;
+        stream.write_value(data.key.unwrap_or(0), order)?;
+        stream.write_value(data.pointer_mode, order)?;
+        stream.write_value(data.keyboard_mode, order)?;
+        stream.write_all(&[0; 3]).map_err(Error::IoError)?;
+        Ok(())
     }
 }
 
@@ -23,12 +62,12 @@ pub struct GrabKeyResponse;
 
 impl Readable for GrabKeyResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for GrabKeyResponse{
+impl Writable for GrabKeyResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
@@ -0,0 +1,50 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::collections::HashSet;
+    use std::io::{BufReader, BufWriter};
+    use std::iter::FromIterator;
+
+    use crate::event::KeyButMask;
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::grab_button::GrabModifiers;
+    use crate::request::grab_key::GrabKeyRequest;
+    use crate::request::grab_pointer::GrabMode;
+
+    #[test]
+    fn read_test() {
+        let expected = GrabKeyRequest {
+            owner_events: true,
+            grab_window: 0x00200001,
+            modifiers: GrabModifiers::Modifiers(HashSet::from_iter([KeyButMask::Control, KeyButMask::Mod1].to_vec())),
+            key: Some(38),
+            pointer_mode: GrabMode::Asynchronous,
+            keyboard_mode: GrabMode::Synchronous,
+        };
+        let input = [1, 0, 4, 0, 0x20, 0, 1, 0, 0x0c, 38, 1, 0, 0, 0, 0];
+        let value = GrabKeyRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [1, 4, 0, 1, 0, 0x20, 0, 0x0c, 0, 38, 1, 0, 0, 0, 0];
+        let value = GrabKeyRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn write_test() {
+        let data = GrabKeyRequest {
+            owner_events: false,
+            grab_window: 0x00200001,
+            modifiers: GrabModifiers::AnyModifier,
+            key: None,
+            pointer_mode: GrabMode::Synchronous,
+            keyboard_mode: GrabMode::Asynchronous,
+        };
+        let mut buffer = [0; 16];
+        GrabKeyRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [33, 0, 0, 4, 0, 0x20, 0, 1, 0x80, 0, 0, 0, 1, 0, 0, 0]);
+        let mut buffer = [0; 16];
+        GrabKeyRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [33, 0, 4, 0, 1, 0, 0x20, 0, 0, 0x80, 0, 0, 1, 0, 0, 0]);
+    }
+}
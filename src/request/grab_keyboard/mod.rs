@@ -1,34 +1,78 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+use crate::request::grab_pointer::{GrabMode, GrabStatus};
 
+mod test;
+
+/// timeが0ならCurrentTime
 #[derive(Clone, Debug, PartialEq)]
-pub struct GrabKeyboardRequest;
+pub struct GrabKeyboardRequest {
+    pub owner_events: bool,
+    pub grab_window: u32,
+    pub time: u32,
+    pub pointer_mode: GrabMode,
+    pub keyboard_mode: GrabMode,
+}
 
 impl Readable for GrabKeyboardRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let owner_events = stream.read_value(order)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let grab_window = stream.read_value(order)?;
+        let time = stream.read_value(order)?;
+        let pointer_mode = stream.read_value(order)?;
+        let keyboard_mode = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 2], 2)?;
+        Ok(GrabKeyboardRequest {
+            owner_events,
+            grab_window,
+            time,
+            pointer_mode,
+            keyboard_mode,
+        })
     }
 }
 
-impl Writable for GrabKeyboardRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GrabKeyboardRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(31, order)?;
+        stream.write_value(data.owner_events, order)?;
+        stream.write_value::<u16>(4, order)?;
+        stream.write_value(data.grab_window, order)?;
+        stream.write_value(data.time, order)?;
+        stream.write_value(data.pointer_mode, order)?;
+        stream.write_value(data.keyboard_mode, order)?;
+        stream.write_all(&[0; 2]).map_err(Error::IoError)?;
+        Ok(())
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct GrabKeyboardResponse;
+pub struct GrabKeyboardResponse {
+    pub status: GrabStatus,
+    pub sequence_number: u16,
+}
 
 impl Readable for GrabKeyboardResponse {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let status = stream.read_value(order)?;
+        let sequence_number = stream.read_value(order)?;
+        let _ = stream.read_value::<u32>(order)?;
+        read_specified_length(stream, &mut [0; 24], 24)?;
+        Ok(GrabKeyboardResponse { status, sequence_number })
     }
 }
 
-impl Writable for GrabKeyboardResponse{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GrabKeyboardResponse {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(1, order)?;
+        stream.write_value(data.status, order)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value::<u32>(0, order)?;
+        stream.write_all(&[0; 24]).map_err(Error::IoError)?;
+        Ok(())
     }
 }
@@ -0,0 +1,74 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::Error;
+    use crate::error::ErrorCode;
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::grab_keyboard::GrabKeyboardRequest;
+    use crate::request::grab_pointer::GrabMode;
+
+    #[test]
+    fn read_test() {
+        let expected = GrabKeyboardRequest { owner_events: true, grab_window: 0x00200001, time: 12345, pointer_mode: GrabMode::Asynchronous, keyboard_mode: GrabMode::Synchronous };
+        let input = [1, 0, 4, 0, 0x20, 0, 1, 0, 0, 0x30, 0x39, 1, 0, 0, 0];
+        let value = GrabKeyboardRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [1, 4, 0, 1, 0, 0x20, 0, 0x39, 0x30, 0, 0, 1, 0, 0, 0];
+        let value = GrabKeyboardRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [1, 0, 4, 0, 0x20, 0, 1, 0, 0, 0x30, 0x39, 1, 2, 0, 0];
+        match GrabKeyboardRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst) {
+            Err(Error::ProtocolError(ErrorCode::Value, 2)) => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn write_test() {
+        let data = GrabKeyboardRequest { owner_events: false, grab_window: 0x00200001, time: 0, pointer_mode: GrabMode::Synchronous, keyboard_mode: GrabMode::Asynchronous };
+        let mut buffer = [0; 16];
+        GrabKeyboardRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [31, 0, 0, 4, 0, 0x20, 0, 1, 0, 0, 0, 0, 0, 1, 0, 0]);
+        let mut buffer = [0; 16];
+        GrabKeyboardRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [31, 0, 4, 0, 1, 0, 0x20, 0, 0, 0, 0, 0, 0, 1, 0, 0]);
+    }
+}
+
+mod response {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::grab_keyboard::GrabKeyboardResponse;
+    use crate::request::grab_pointer::GrabStatus;
+
+    #[test]
+    fn read_test() {
+        let mut input = [0; 32];
+        input[..4].copy_from_slice(&[1, 2, 0, 2]);
+        let value = GrabKeyboardResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, GrabKeyboardResponse { status: GrabStatus::InvalidTime, sequence_number: 2 });
+        let mut input = [0; 32];
+        input[..4].copy_from_slice(&[1, 0, 2, 0]);
+        let value = GrabKeyboardResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, GrabKeyboardResponse { status: GrabStatus::Success, sequence_number: 2 });
+    }
+
+    #[test]
+    fn write_test() {
+        let data = GrabKeyboardResponse { status: GrabStatus::Frozen, sequence_number: 0x102 };
+        let mut expected = [0; 32];
+        expected[..4].copy_from_slice(&[1, 4, 1, 2]);
+        let mut buffer = [0xff; 32];
+        GrabKeyboardResponse::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+        let mut expected = [0; 32];
+        expected[..4].copy_from_slice(&[1, 4, 2, 1]);
+        let mut buffer = [0xff; 32];
+        GrabKeyboardResponse::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+    }
+}
@@ -1,34 +1,195 @@
-use std::io::{Read, Write};
+use std::collections::HashSet;
+use std::io::{BufReader, BufWriter, Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+use crate::setup;
 
+mod test;
+
+/// SETofPOINTEREVENTの下位2bitより上の順
+const POINTER_EVENTS: [setup::Event; 13] = [
+    setup::Event::ButtonPress,
+    setup::Event::ButtonRelease,
+    setup::Event::EnterWindow,
+    setup::Event::LeaveWindow,
+    setup::Event::PointerMotion,
+    setup::Event::PointerMotionHint,
+    setup::Event::Button1Motion,
+    setup::Event::Button2Motion,
+    setup::Event::Button3Motion,
+    setup::Event::Button4Motion,
+    setup::Event::Button5Motion,
+    setup::Event::ButtonMotion,
+    setup::Event::KeymapState,
+];
+
+/// SETofPOINTEREVENTを16bitで読む
+pub(crate) fn read_pointer_event_mask(stream: &mut BufReader<impl Read>, order: &ByteOrder) -> Result<HashSet<setup::Event>> {
+    let value = stream.read_value::<u16>(order)?;
+    if value & 0x8003 != 0 {
+        return Err(Error::ProtocolError(ErrorCode::Value, value as u32));
+    }
+    Ok(POINTER_EVENTS.iter().enumerate().filter(|(i, _)| (value >> (i + 2)) & 1 == 1).map(|(_, event)| event.clone()).collect())
+}
+
+/// pointerのevent以外が含まれていればInvalidValue
+pub(crate) fn write_pointer_event_mask(stream: &mut BufWriter<impl Write>, data: HashSet<setup::Event>, order: &ByteOrder) -> Result<()> {
+    let mut value = 0u16;
+    for event in data {
+        match POINTER_EVENTS.iter().position(|e| *e == event) {
+            Some(i) => value |= 1 << (i + 2),
+            None => return Err(Error::InvalidValue("pointer event mask")),
+        }
+    }
+    stream.write_value(value, order)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GrabMode {
+    Synchronous,
+    Asynchronous,
+}
+
+impl Readable for GrabMode {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::Synchronous),
+            1 => Ok(Self::Asynchronous),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for GrabMode {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::Synchronous => 0,
+            Self::Asynchronous => 1,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GrabStatus {
+    Success,
+    AlreadyGrabbed,
+    InvalidTime,
+    NotViewable,
+    Frozen,
+}
+
+impl Readable for GrabStatus {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::Success),
+            1 => Ok(Self::AlreadyGrabbed),
+            2 => Ok(Self::InvalidTime),
+            3 => Ok(Self::NotViewable),
+            4 => Ok(Self::Frozen),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for GrabStatus {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::Success => 0,
+            Self::AlreadyGrabbed => 1,
+            Self::InvalidTime => 2,
+            Self::NotViewable => 3,
+            Self::Frozen => 4,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+/// timeが0ならCurrentTime
 #[derive(Clone, Debug, PartialEq)]
-pub struct GrabPointerRequest;
+pub struct GrabPointerRequest {
+    pub owner_events: bool,
+    pub grab_window: u32,
+    pub event_mask: HashSet<setup::Event>,
+    pub pointer_mode: GrabMode,
+    pub keyboard_mode: GrabMode,
+    pub confine_to: Option<u32>,
+    pub cursor: Option<u32>,
+    pub time: u32,
+}
 
 impl Readable for GrabPointerRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let owner_events = stream.read_value(order)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let grab_window = stream.read_value(order)?;
+        let event_mask = read_pointer_event_mask(stream, order)?;
+        let pointer_mode = stream.read_value(order)?;
+        let keyboard_mode = stream.read_value(order)?;
+        let confine_to = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let cursor = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let time = stream.read_value(order)?;
+        Ok(GrabPointerRequest {
+            owner_events,
+            grab_window,
+            event_mask,
+            pointer_mode,
+            keyboard_mode,
+            confine_to,
+            cursor,
+            time,
+        })
     }
 }
 
-impl Writable for GrabPointerRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GrabPointerRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(26, order)?;
+        stream.write_value(data.owner_events, order)?;
+        stream.write_value::<u16>(6, order)?;
+        stream.write_value(data.grab_window, order)?;
+        write_pointer_event_mask(stream, data.event_mask, order)?;
+        stream.write_value(data.pointer_mode, order)?;
+        stream.write_value(data.keyboard_mode, order)?;
+        stream.write_value(data.confine_to.unwrap_or(0), order)?;
+        stream.write_value(data.cursor.unwrap_or(0), order)?;
+        stream.write_value(data.time, order)?;
+        Ok(())
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct GrabPointerResponse;
+pub struct GrabPointerResponse {
+    pub status: GrabStatus,
+    pub sequence_number: u16,
+}
 
 impl Readable for GrabPointerResponse {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let status = stream.read_value(order)?;
+        let sequence_number = stream.read_value(order)?;
+        let _ = stream.read_value::<u32>(order)?;
+        read_specified_length(stream, &mut [0; 24], 24)?;
+        Ok(GrabPointerResponse { status, sequence_number })
     }
 }
 
-impl Writable for GrabPointerResponse{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for GrabPointerResponse {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(1, order)?;
+        stream.write_value(data.status, order)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value::<u32>(0, order)?;
+        stream.write_all(&[0; 24]).map_err(Error::IoError)?;
+        Ok(())
     }
 }
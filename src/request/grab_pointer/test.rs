@@ -0,0 +1,97 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::collections::HashSet;
+    use std::io::{BufReader, BufWriter};
+    use std::iter::FromIterator;
+
+    use crate::Error;
+    use crate::error::ErrorCode;
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::grab_pointer::{GrabMode, GrabPointerRequest};
+    use crate::setup;
+
+    #[test]
+    fn read_test() {
+        let expected = GrabPointerRequest {
+            owner_events: true,
+            grab_window: 0x00200001,
+            event_mask: HashSet::from_iter([setup::Event::ButtonPress, setup::Event::ButtonRelease, setup::Event::KeymapState].to_vec()),
+            pointer_mode: GrabMode::Asynchronous,
+            keyboard_mode: GrabMode::Synchronous,
+            confine_to: None,
+            cursor: Some(0x00200002),
+            time: 12345,
+        };
+        let input = [1, 0, 6, 0, 0x20, 0, 1, 0x40, 0x0c, 1, 0, 0, 0, 0, 0, 0, 0x20, 0, 2, 0, 0, 0x30, 0x39];
+        let value = GrabPointerRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [1, 6, 0, 1, 0, 0x20, 0, 0x0c, 0x40, 1, 0, 0, 0, 0, 0, 2, 0, 0x20, 0, 0x39, 0x30, 0, 0];
+        let value = GrabPointerRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+        //KeyPressはpointerのeventではない
+        let input = [1, 0, 6, 0, 0x20, 0, 1, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0x20, 0, 2, 0, 0, 0x30, 0x39];
+        match GrabPointerRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst) {
+            Err(Error::ProtocolError(ErrorCode::Value, 1)) => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn write_test() {
+        let data = GrabPointerRequest {
+            owner_events: false,
+            grab_window: 0x00200001,
+            event_mask: HashSet::from_iter([setup::Event::PointerMotion].to_vec()),
+            pointer_mode: GrabMode::Synchronous,
+            keyboard_mode: GrabMode::Asynchronous,
+            confine_to: Some(1),
+            cursor: None,
+            time: 0,
+        };
+        let mut buffer = [0; 24];
+        GrabPointerRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [26, 0, 0, 6, 0, 0x20, 0, 1, 0, 0x40, 0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let mut buffer = [0; 24];
+        GrabPointerRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [26, 0, 6, 0, 1, 0, 0x20, 0, 0x40, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let data = GrabPointerRequest { event_mask: HashSet::from_iter([setup::Event::KeyPress].to_vec()), ..data };
+        let mut buffer = [0; 24];
+        assert!(matches!(GrabPointerRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::MSBFirst), Err(Error::InvalidValue(_))));
+    }
+}
+
+mod response {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::grab_pointer::{GrabPointerResponse, GrabStatus};
+
+    #[test]
+    fn read_test() {
+        let mut input = [0; 32];
+        input[..4].copy_from_slice(&[1, 3, 0, 2]);
+        let value = GrabPointerResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, GrabPointerResponse { status: GrabStatus::NotViewable, sequence_number: 2 });
+        let mut input = [0; 32];
+        input[..4].copy_from_slice(&[1, 4, 2, 0]);
+        let value = GrabPointerResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, GrabPointerResponse { status: GrabStatus::Frozen, sequence_number: 2 });
+    }
+
+    #[test]
+    fn write_test() {
+        let data = GrabPointerResponse { status: GrabStatus::AlreadyGrabbed, sequence_number: 2 };
+        let mut expected = [0; 32];
+        expected[..4].copy_from_slice(&[1, 1, 0, 2]);
+        let mut buffer = [0xff; 32];
+        GrabPointerResponse::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+        let mut expected = [0; 32];
+        expected[..4].copy_from_slice(&[1, 1, 2, 0]);
+        let mut buffer = [0xff; 32];
+        GrabPointerResponse::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+    }
+}
@@ -0,0 +1,29 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::grab_server::GrabServerRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 1];
+        let value = GrabServerRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, GrabServerRequest);
+        let input = [0, 1, 0];
+        let value = GrabServerRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, GrabServerRequest);
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0xff; 4];
+        GrabServerRequest::write(&mut BufWriter::new(&mut buffer[..]), GrabServerRequest, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [36, 0, 0, 1]);
+        let mut buffer = [0xff; 4];
+        GrabServerRequest::write(&mut BufWriter::new(&mut buffer[..]), GrabServerRequest, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [36, 0, 1, 0]);
+    }
+}
@@ -1,16 +1,19 @@
 use std::io::{Read, Write};
 
 use crate::{Error, Result};
-use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, Decode, read_specified_length, Readable, ReadableRead, SliceReader, Writable, WritableWrite};
+use crate::request::DecodeBody;
 
 mod test;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct InternAtomRequest {
-    only_if_exists: bool,
-    name: String,
+    pub only_if_exists: bool,
+    pub name: String,
 }
 
+#[allow(clippy::redundant_closure)]
 impl Readable for InternAtomRequest {
     fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
         let only_if_exists = stream.read_value(order)?;
@@ -28,6 +31,7 @@ impl Readable for InternAtomRequest {
     }
 }
 
+#[allow(clippy::needless_as_bytes, clippy::redundant_closure)]
 impl Writable for InternAtomRequest {
     fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
         stream.write_value::<u8>(16, order)?;
@@ -37,15 +41,53 @@ impl Writable for InternAtomRequest {
         stream.write_value(name_len as u16, order)?;
         stream.write_all(&[0; 2]).map_err(|e| Error::IoError(e))?;
         stream.write_all(data.name.as_bytes()).map_err(|e| Error::IoError(e))?;
-        stream.write_all(&[0; 4][..(!name_len).wrapping_add(1) & !3]).map_err(|e| Error::IoError(e))?;
+        stream.write_all(&[0; 4][..(!name_len).wrapping_add(1) & 3]).map_err(|e| Error::IoError(e))?;
         Ok(())
     }
 }
 
+/// InternAtomRequestのnameを入力のbufferから借用する版
+#[derive(Clone, Debug, PartialEq)]
+pub struct InternAtomRequestRef<'a> {
+    pub only_if_exists: bool,
+    pub name: &'a str,
+}
+
+impl<'a> InternAtomRequestRef<'a> {
+    pub fn to_request(&self) -> InternAtomRequest {
+        InternAtomRequest {
+            only_if_exists: self.only_if_exists,
+            name: self.name.to_string(),
+        }
+    }
+}
+
+impl<'a> Decode<'a> for InternAtomRequestRef<'a> {
+    fn decode_from(reader: &mut SliceReader<'a>, order: &ByteOrder) -> Result<Self> {
+        let data = reader.read(order)?;
+        let length = reader.read::<u16>(order)? as usize;
+        Self::decode_body(reader, data, length, order)
+    }
+}
+
+impl<'a> DecodeBody<'a> for InternAtomRequestRef<'a> {
+    fn decode_body(reader: &mut SliceReader<'a>, data: u8, length: usize, order: &ByteOrder) -> Result<Self> {
+        let total_length = length.checked_sub(2).ok_or(Error::ProtocolError(ErrorCode::Length, 0))? << 2;
+        let name_len = reader.read::<u16>(order)? as usize;
+        reader.skip(2)?;
+        let buffer = reader.read_bytes(total_length)?;
+        let name = buffer.get(..name_len).ok_or(Error::ProtocolError(ErrorCode::Length, 0))?;
+        Ok(InternAtomRequestRef {
+            only_if_exists: data != 0,
+            name: std::str::from_utf8(name).map_err(Error::StringError)?,
+        })
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct InternAtomResponse {
-    sequence_number: u16,
-    atom: Option<u32>,
+    pub sequence_number: u16,
+    pub atom: Option<u32>,
 }
 
 impl Readable for InternAtomResponse {
@@ -65,11 +107,12 @@ impl Readable for InternAtomResponse {
     }
 }
 
+#[allow(clippy::redundant_closure)]
 impl Writable for InternAtomResponse {
     fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
         stream.write_value::<u8>(1, order)?;
         stream.write_all(&[0]).map_err(|e| Error::IoError(e))?;
-        stream.write_value(data.sequence_number)?;
+        stream.write_value(data.sequence_number, order)?;
         stream.write_all(&[0; 4]).map_err(|e| Error::IoError(e))?;
         stream.write_value(data.atom.unwrap_or(0), order)?;
         stream.write_all(&[0; 20]).map_err(|e| Error::IoError(e))?;
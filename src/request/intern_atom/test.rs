@@ -0,0 +1,78 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::Error;
+    use crate::error::ErrorCode;
+    use crate::read_util::{ByteOrder, Decode, Readable, SliceReader, Writable};
+    use crate::request::intern_atom::{InternAtomRequest, InternAtomRequestRef};
+
+    #[test]
+    fn read_test() {
+        let expected = InternAtomRequest { only_if_exists: true, name: "WM_STATE".to_string() };
+        let input = [1, 0, 4, 0, 8, 0, 0, b'W', b'M', b'_', b'S', b'T', b'A', b'T', b'E'];
+        let value = InternAtomRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [1, 4, 0, 8, 0, 0, 0, b'W', b'M', b'_', b'S', b'T', b'A', b'T', b'E'];
+        let value = InternAtomRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn write_test() {
+        let data = InternAtomRequest { only_if_exists: false, name: "UTF8".to_string() + "_STRING" };
+        let mut buffer = [0xff; 20];
+        InternAtomRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [16, 0, 0, 5, 0, 11, 0, 0, b'U', b'T', b'F', b'8', b'_', b'S', b'T', b'R', b'I', b'N', b'G', 0]);
+        let mut buffer = [0xff; 20];
+        InternAtomRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [16, 0, 5, 0, 11, 0, 0, 0, b'U', b'T', b'F', b'8', b'_', b'S', b'T', b'R', b'I', b'N', b'G', 0]);
+    }
+
+    #[test]
+    fn ref_test() {
+        let input = [1, 0, 4, 0, 8, 0, 0, b'W', b'M', b'_', b'S', b'T', b'A', b'T', b'E'];
+        let value = InternAtomRequestRef::decode_from(&mut SliceReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, InternAtomRequestRef { only_if_exists: true, name: "WM_STATE" });
+        assert_eq!(value.name.as_ptr(), input[7..].as_ptr());
+        assert_eq!(value.to_request(), InternAtomRequest { only_if_exists: true, name: "WM_STATE".to_string() });
+        //name_lenがrequestより長ければBadLength
+        let input = [0, 0, 3, 0, 5, 0, 0, b'W', b'M', b'_', b'S'];
+        assert!(matches!(InternAtomRequestRef::decode_from(&mut SliceReader::new(&input[..]), &ByteOrder::MSBFirst), Err(Error::ProtocolError(ErrorCode::Length, 0))));
+    }
+}
+
+mod response {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::intern_atom::InternAtomResponse;
+
+    #[test]
+    fn read_test() {
+        let mut input = [0; 32];
+        input[..12].copy_from_slice(&[1, 0, 0, 2, 0, 0, 0, 0, 0, 0, 1, 0x2c]);
+        let value = InternAtomResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, InternAtomResponse { sequence_number: 2, atom: Some(300) });
+        let mut input = [0; 32];
+        input[..12].copy_from_slice(&[1, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let value = InternAtomResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, InternAtomResponse { sequence_number: 2, atom: None });
+    }
+
+    #[test]
+    fn write_test() {
+        let mut expected = [0; 32];
+        expected[..12].copy_from_slice(&[1, 0, 0, 2, 0, 0, 0, 0, 0, 0, 1, 0x2c]);
+        let mut buffer = [0xff; 32];
+        InternAtomResponse::write(&mut BufWriter::new(&mut buffer[..]), InternAtomResponse { sequence_number: 2, atom: Some(300) }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+        let mut expected = [0; 32];
+        expected[..12].copy_from_slice(&[1, 0, 2, 0, 0, 0, 0, 0, 0x2c, 1, 0, 0]);
+        let mut buffer = [0xff; 32];
+        InternAtomResponse::write(&mut BufWriter::new(&mut buffer[..]), InternAtomResponse { sequence_number: 2, atom: Some(300) }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+    }
+}
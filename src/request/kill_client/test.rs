@@ -0,0 +1,29 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::kill_client::{KillClientRequest, KillClientResource};
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 2, 0, 0x40, 0, 1];
+        let value = KillClientRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, KillClientRequest { resource: KillClientResource::Resource(0x00400001) });
+        let input = [0, 2, 0, 0, 0, 0, 0];
+        let value = KillClientRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, KillClientRequest { resource: KillClientResource::AllTemporary });
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0; 8];
+        KillClientRequest::write(&mut BufWriter::new(&mut buffer[..]), KillClientRequest { resource: KillClientResource::Resource(0x00400001) }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [113, 0, 0, 2, 0, 0x40, 0, 1]);
+        let mut buffer = [0; 8];
+        KillClientRequest::write(&mut BufWriter::new(&mut buffer[..]), KillClientRequest { resource: KillClientResource::AllTemporary }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [113, 0, 2, 0, 0, 0, 0, 0]);
+    }
+}
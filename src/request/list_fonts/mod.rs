@@ -1,34 +1,174 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, Decode, read_specified_length, Readable, ReadableRead, SliceReader, Writable, WritableWrite};
+use crate::request::DecodeBody;
+
+mod test;
+
+/// LISTofSTRのpaddingを除いたbyte数
+pub(crate) fn str_list_len(strings: &[String]) -> usize {
+    strings.iter().map(|s| 1 + s.len()).sum()
+}
+
+/// paddingを含めてlength byteを読み、count個のSTRを取り出す
+pub(crate) fn read_str_list(stream: &mut std::io::BufReader<impl Read>, count: usize, length: usize) -> Result<Vec<String>> {
+    let mut buffer = vec![0; length];
+    read_specified_length(stream, &mut buffer[..], length)?;
+    let mut strings = Vec::with_capacity(count);
+    let mut rest = &buffer[..];
+    for _ in 0..count {
+        let (len, tail) = rest.split_first().ok_or(Error::ProtocolError(ErrorCode::Length, 0))?;
+        let len = *len as usize;
+        if len > tail.len() {
+            return Err(Error::ProtocolError(ErrorCode::Length, 0));
+        }
+        let s = std::str::from_utf8(&tail[..len]).map_err(Error::StringError)?;
+        strings.push(s.to_string());
+        rest = &tail[len..];
+    }
+    Ok(strings)
+}
+
+/// paddingまで書く
+/// 255byteより長い文字列があればInvalidValue
+pub(crate) fn write_str_list(stream: &mut std::io::BufWriter<impl Write>, strings: Vec<String>) -> Result<()> {
+    let length = str_list_len(&strings);
+    for s in strings {
+        if s.len() > 255 {
+            return Err(Error::InvalidValue("LISTofSTR"));
+        }
+        stream.write_all(&[s.len() as u8]).map_err(Error::IoError)?;
+        stream.write_all(s.as_bytes()).map_err(Error::IoError)?;
+    }
+    stream.write_all(&[0; 4][..(!length).wrapping_add(1) & 3]).map_err(Error::IoError)?;
+    Ok(())
+}
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct ListFontsRequest;
+pub struct ListFontsRequest {
+    pub max_names: u16,
+    /// XLFDのpatternで、*と?を使える
+    pub pattern: String,
+}
+
+/// ListFontsとListFontsWithInfoで共通のrequestの本体を読む
+pub(crate) fn read_pattern(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<ListFontsRequest> {
+    read_specified_length(stream, &mut [0; 1], 1)?;
+    let length = stream.read_value::<u16>(order)? as usize;
+    let max_names = stream.read_value(order)?;
+    let pattern_len = stream.read_value::<u16>(order)? as usize;
+    let total_length = length.checked_sub(2).ok_or(Error::ProtocolError(ErrorCode::Length, 0))? << 2;
+    if pattern_len > total_length {
+        return Err(Error::ProtocolError(ErrorCode::Length, 0));
+    }
+    let mut buffer = vec![0; total_length];
+    read_specified_length(stream, &mut buffer[..], total_length)?;
+    let pattern = std::str::from_utf8(&buffer[..pattern_len]).map_err(Error::StringError)?;
+    Ok(ListFontsRequest {
+        max_names,
+        pattern: pattern.to_string(),
+    })
+}
+
+pub(crate) fn write_pattern(stream: &mut std::io::BufWriter<impl Write>, opcode: u8, max_names: u16, pattern: String, order: &ByteOrder) -> Result<()> {
+    let pattern_len = pattern.len();
+    stream.write_value(opcode, order)?;
+    stream.write_all(&[0]).map_err(Error::IoError)?;
+    stream.write_value((2 + ((pattern_len + 3) >> 2)) as u16, order)?;
+    stream.write_value(max_names, order)?;
+    stream.write_value(pattern_len as u16, order)?;
+    stream.write_all(pattern.as_bytes()).map_err(Error::IoError)?;
+    stream.write_all(&[0; 4][..(!pattern_len).wrapping_add(1) & 3]).map_err(Error::IoError)?;
+    Ok(())
+}
 
 impl Readable for ListFontsRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_pattern(stream, order)
     }
 }
 
-impl Writable for ListFontsRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for ListFontsRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        write_pattern(stream, 49, data.max_names, data.pattern, order)
     }
 }
 
+/// ListFontsRequestのpatternを入力のbufferから借用する版
 #[derive(Clone, Debug, PartialEq)]
-pub struct ListFontsResponse;
+pub struct ListFontsRequestRef<'a> {
+    pub max_names: u16,
+    pub pattern: &'a str,
+}
+
+impl<'a> ListFontsRequestRef<'a> {
+    pub fn to_request(&self) -> ListFontsRequest {
+        ListFontsRequest {
+            max_names: self.max_names,
+            pattern: self.pattern.to_string(),
+        }
+    }
+}
+
+/// read_patternのSliceReader版
+pub(crate) fn decode_pattern<'a>(reader: &mut SliceReader<'a>, length: usize, order: &ByteOrder) -> Result<ListFontsRequestRef<'a>> {
+    let total_length = length.checked_sub(2).ok_or(Error::ProtocolError(ErrorCode::Length, 0))? << 2;
+    let max_names = reader.read(order)?;
+    let pattern_len = reader.read::<u16>(order)? as usize;
+    let buffer = reader.read_bytes(total_length)?;
+    let pattern = buffer.get(..pattern_len).ok_or(Error::ProtocolError(ErrorCode::Length, 0))?;
+    Ok(ListFontsRequestRef {
+        max_names,
+        pattern: std::str::from_utf8(pattern).map_err(Error::StringError)?,
+    })
+}
+
+impl<'a> Decode<'a> for ListFontsRequestRef<'a> {
+    fn decode_from(reader: &mut SliceReader<'a>, order: &ByteOrder) -> Result<Self> {
+        let data = reader.read(order)?;
+        let length = reader.read::<u16>(order)? as usize;
+        Self::decode_body(reader, data, length, order)
+    }
+}
+
+impl<'a> DecodeBody<'a> for ListFontsRequestRef<'a> {
+    fn decode_body(reader: &mut SliceReader<'a>, _data: u8, length: usize, order: &ByteOrder) -> Result<Self> {
+        decode_pattern(reader, length, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListFontsResponse {
+    pub sequence_number: u16,
+    pub names: Vec<String>,
+}
 
 impl Readable for ListFontsResponse {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 2], 2)?;
+        let sequence_number = stream.read_value(order)?;
+        let length = stream.read_value::<u32>(order)? as usize;
+        let number_of_names = stream.read_value::<u16>(order)? as usize;
+        read_specified_length(stream, &mut [0; 22], 22)?;
+        let names = read_str_list(stream, number_of_names, length << 2)?;
+        Ok(ListFontsResponse {
+            sequence_number,
+            names,
+        })
     }
 }
 
-impl Writable for ListFontsResponse{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for ListFontsResponse {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let length = str_list_len(&data.names);
+        stream.write_value::<u8>(1, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(((length + 3) >> 2) as u32, order)?;
+        stream.write_value(data.names.len() as u16, order)?;
+        stream.write_all(&[0; 22]).map_err(Error::IoError)?;
+        write_str_list(stream, data.names)
     }
 }
@@ -0,0 +1,91 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::Error;
+    use crate::error::ErrorCode;
+    use crate::read_util::{ByteOrder, Decode, Readable, SliceReader, Writable};
+    use crate::request::list_fonts::{ListFontsRequest, ListFontsRequestRef};
+
+    #[test]
+    fn read_test() {
+        let expected = ListFontsRequest { max_names: 100, pattern: "*-r-*".to_string() };
+        let input = [0, 0, 4, 0, 100, 0, 5, b'*', b'-', b'r', b'-', b'*', 0, 0, 0];
+        let value = ListFontsRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [0, 4, 0, 100, 0, 5, 0, b'*', b'-', b'r', b'-', b'*', 0, 0, 0];
+        let value = ListFontsRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+        //lengthが足りなければBadLength
+        let input = [0, 0, 1, 0, 100, 0, 0];
+        assert!(matches!(ListFontsRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst), Err(Error::ProtocolError(ErrorCode::Length, 0))));
+        let input = [0, 0, 3, 0, 100, 0, 5, b'*', b'-', b'r', b'-'];
+        assert!(matches!(ListFontsRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst), Err(Error::ProtocolError(ErrorCode::Length, 0))));
+    }
+
+    #[test]
+    fn write_test() {
+        let data = ListFontsRequest { max_names: 0xffff, pattern: "fixed".to_string() };
+        let mut buffer = [0xff; 16];
+        ListFontsRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [49, 0, 0, 4, 0xff, 0xff, 0, 5, b'f', b'i', b'x', b'e', b'd', 0, 0, 0]);
+        let mut buffer = [0xff; 16];
+        ListFontsRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [49, 0, 4, 0, 0xff, 0xff, 5, 0, b'f', b'i', b'x', b'e', b'd', 0, 0, 0]);
+    }
+
+    #[test]
+    fn ref_test() {
+        let input = [0, 4, 0, 100, 0, 5, 0, b'*', b'-', b'r', b'-', b'*', 0, 0, 0];
+        let value = ListFontsRequestRef::decode_from(&mut SliceReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, ListFontsRequestRef { max_names: 100, pattern: "*-r-*" });
+        assert_eq!(value.pattern.as_ptr(), input[7..].as_ptr());
+        assert_eq!(value.to_request(), ListFontsRequest { max_names: 100, pattern: "*-r-*".to_string() });
+        let input = [0, 0, 3, 0, 100, 0, 5, b'*', b'-', b'r', b'-'];
+        assert!(matches!(ListFontsRequestRef::decode_from(&mut SliceReader::new(&input[..]), &ByteOrder::MSBFirst), Err(Error::ProtocolError(ErrorCode::Length, 0))));
+    }
+}
+
+mod response {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::Error;
+    use crate::error::ErrorCode;
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::list_fonts::ListFontsResponse;
+
+    #[test]
+    fn read_test() {
+        let expected = ListFontsResponse { sequence_number: 5, names: vec!["fixed".to_string(), "6x13".to_string()] };
+        let mut input = [0; 44];
+        input[..8].copy_from_slice(&[1, 0, 0, 5, 0, 0, 0, 3]);
+        input[8..10].copy_from_slice(&[0, 2]);
+        input[32..43].copy_from_slice(&[5, b'f', b'i', b'x', b'e', b'd', 4, b'6', b'x', b'1', b'3']);
+        let value = ListFontsResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        input[..10].copy_from_slice(&[1, 0, 5, 0, 3, 0, 0, 0, 2, 0]);
+        let value = ListFontsResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+        //STRが返事の長さを超える
+        input[..10].copy_from_slice(&[1, 0, 5, 0, 2, 0, 0, 0, 2, 0]);
+        assert!(matches!(ListFontsResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst), Err(Error::ProtocolError(ErrorCode::Length, 0))));
+    }
+
+    #[test]
+    fn write_test() {
+        let data = ListFontsResponse { sequence_number: 5, names: vec!["fixed".to_string(), "6x13".to_string()] };
+        let mut expected = [0; 44];
+        expected[..8].copy_from_slice(&[1, 0, 0, 5, 0, 0, 0, 3]);
+        expected[8..10].copy_from_slice(&[0, 2]);
+        expected[32..43].copy_from_slice(&[5, b'f', b'i', b'x', b'e', b'd', 4, b'6', b'x', b'1', b'3']);
+        let mut buffer = [0xff; 44];
+        ListFontsResponse::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+        expected[..10].copy_from_slice(&[1, 0, 5, 0, 3, 0, 0, 0, 2, 0]);
+        let mut buffer = [0xff; 44];
+        ListFontsResponse::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+    }
+}
@@ -1,34 +1,188 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, Decode, read_specified_length, Readable, ReadableRead, SliceReader, Writable, WritableWrite};
+use crate::request::DecodeBody;
+use crate::request::list_fonts::{decode_pattern, read_pattern, write_pattern};
+use crate::request::query_font::{FontInfo, read_font_info, write_font_info};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct ListFontsWithInfoRequest;
+pub struct ListFontsWithInfoRequest {
+    pub max_names: u16,
+    /// XLFDのpatternで、*と?を使える
+    pub pattern: String,
+}
 
 impl Readable for ListFontsWithInfoRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let request = read_pattern(stream, order)?;
+        Ok(ListFontsWithInfoRequest {
+            max_names: request.max_names,
+            pattern: request.pattern,
+        })
+    }
+}
+
+impl Writable for ListFontsWithInfoRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        write_pattern(stream, 50, data.max_names, data.pattern, order)
+    }
+}
+
+/// ListFontsWithInfoRequestのpatternを入力のbufferから借用する版
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListFontsWithInfoRequestRef<'a> {
+    pub max_names: u16,
+    pub pattern: &'a str,
+}
+
+impl<'a> ListFontsWithInfoRequestRef<'a> {
+    pub fn to_request(&self) -> ListFontsWithInfoRequest {
+        ListFontsWithInfoRequest {
+            max_names: self.max_names,
+            pattern: self.pattern.to_string(),
+        }
+    }
+}
+
+impl<'a> Decode<'a> for ListFontsWithInfoRequestRef<'a> {
+    fn decode_from(reader: &mut SliceReader<'a>, order: &ByteOrder) -> Result<Self> {
+        let data = reader.read(order)?;
+        let length = reader.read::<u16>(order)? as usize;
+        Self::decode_body(reader, data, length, order)
     }
 }
 
-impl Writable for ListFontsWithInfoRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl<'a> DecodeBody<'a> for ListFontsWithInfoRequestRef<'a> {
+    fn decode_body(reader: &mut SliceReader<'a>, _data: u8, length: usize, order: &ByteOrder) -> Result<Self> {
+        let request = decode_pattern(reader, length, order)?;
+        Ok(ListFontsWithInfoRequestRef {
+            max_names: request.max_names,
+            pattern: request.pattern,
+        })
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct ListFontsWithInfoResponse;
+pub struct FontWithInfo {
+    pub name: String,
+    pub info: FontInfo,
+    /// このあとに続く返事の数の目安
+    pub replies_hint: u32,
+}
+
+/// ListFontsWithInfoの返事1つ分
+/// fontがNoneなら最後の返事(名前の長さが0)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListFontsWithInfoReply {
+    pub sequence_number: u16,
+    pub font: Option<FontWithInfo>,
+}
+
+impl Readable for ListFontsWithInfoReply {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let name_len = stream.read_value::<u8>(order)? as usize;
+        let sequence_number = stream.read_value(order)?;
+        let length = stream.read_value::<u32>(order)? as usize;
+        if name_len == 0 {
+            if length != 7 {
+                return Err(Error::InvalidValue("ListFontsWithInfoReply"));
+            }
+            read_specified_length(stream, &mut [0; 52], 52)?;
+            return Ok(ListFontsWithInfoReply {
+                sequence_number,
+                font: None,
+            });
+        }
+        let (mut info, number_of_properties) = read_font_info(stream, order)?;
+        let replies_hint = stream.read_value(order)?;
+        if length != 7 + 2 * number_of_properties + ((name_len + 3) >> 2) {
+            return Err(Error::InvalidValue("ListFontsWithInfoReply"));
+        }
+        for _ in 0..number_of_properties {
+            info.properties.push(stream.read_value(order)?);
+        }
+        let name_length = (name_len + 3) & !3;
+        let mut buffer = vec![0; name_length];
+        read_specified_length(stream, &mut buffer[..], name_length)?;
+        let name = std::str::from_utf8(&buffer[..name_len]).map_err(Error::StringError)?;
+        Ok(ListFontsWithInfoReply {
+            sequence_number,
+            font: Some(FontWithInfo {
+                name: name.to_string(),
+                info,
+                replies_hint,
+            }),
+        })
+    }
+}
+
+impl Writable for ListFontsWithInfoReply {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let font = match data.font {
+            Some(font) => font,
+            None => {
+                stream.write_value::<u8>(1, order)?;
+                stream.write_all(&[0]).map_err(Error::IoError)?;
+                stream.write_value(data.sequence_number, order)?;
+                stream.write_value::<u32>(7, order)?;
+                stream.write_all(&[0; 52]).map_err(Error::IoError)?;
+                return Ok(());
+            }
+        };
+        let name_len = font.name.len();
+        //名前の長さが0の返事は最後の返事になってしまう
+        if name_len == 0 || name_len > 255 {
+            return Err(Error::InvalidValue("ListFontsWithInfoReply"));
+        }
+        stream.write_value::<u8>(1, order)?;
+        stream.write_value(name_len as u8, order)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value((7 + 2 * font.info.properties.len() + ((name_len + 3) >> 2)) as u32, order)?;
+        write_font_info(stream, &font.info, order)?;
+        stream.write_value(font.replies_hint, order)?;
+        for property in font.info.properties {
+            stream.write_value(property, order)?;
+        }
+        stream.write_all(font.name.as_bytes()).map_err(Error::IoError)?;
+        stream.write_all(&[0; 4][..(!name_len).wrapping_add(1) & 3]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+/// 最後の返事までのListFontsWithInfoReplyをまとめたもの
+/// sequence_numberは全ての返事で同じ
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListFontsWithInfoResponse {
+    pub sequence_number: u16,
+    pub fonts: Vec<FontWithInfo>,
+}
 
 impl Readable for ListFontsWithInfoResponse {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let mut fonts = Vec::new();
+        loop {
+            let reply = ListFontsWithInfoReply::read(stream, order)?;
+            match reply.font {
+                Some(font) => fonts.push(font),
+                None => return Ok(ListFontsWithInfoResponse {
+                    sequence_number: reply.sequence_number,
+                    fonts,
+                }),
+            }
+        }
     }
 }
 
-impl Writable for ListFontsWithInfoResponse{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for ListFontsWithInfoResponse {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let sequence_number = data.sequence_number;
+        for font in data.fonts {
+            stream.write_value(ListFontsWithInfoReply { sequence_number, font: Some(font) }, order)?;
+        }
+        stream.write_value(ListFontsWithInfoReply { sequence_number, font: None }, order)
     }
 }
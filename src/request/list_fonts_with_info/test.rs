@@ -0,0 +1,184 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Decode, Readable, SliceReader, Writable};
+    use crate::request::list_fonts_with_info::{ListFontsWithInfoRequest, ListFontsWithInfoRequestRef};
+
+    #[test]
+    fn read_test() {
+        let expected = ListFontsWithInfoRequest { max_names: 100, pattern: "*-r-*".to_string() };
+        let input = [0, 0, 4, 0, 100, 0, 5, b'*', b'-', b'r', b'-', b'*', 0, 0, 0];
+        let value = ListFontsWithInfoRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [0, 4, 0, 100, 0, 5, 0, b'*', b'-', b'r', b'-', b'*', 0, 0, 0];
+        let value = ListFontsWithInfoRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn write_test() {
+        let data = ListFontsWithInfoRequest { max_names: 1, pattern: "6x13".to_string() };
+        let mut buffer = [0xff; 12];
+        ListFontsWithInfoRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [50, 0, 0, 3, 0, 1, 0, 4, b'6', b'x', b'1', b'3']);
+        let mut buffer = [0xff; 12];
+        ListFontsWithInfoRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [50, 0, 3, 0, 1, 0, 4, 0, b'6', b'x', b'1', b'3']);
+    }
+
+    #[test]
+    fn ref_test() {
+        let input = [0, 0, 3, 0, 1, 0, 4, b'6', b'x', b'1', b'3'];
+        let value = ListFontsWithInfoRequestRef::decode_from(&mut SliceReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, ListFontsWithInfoRequestRef { max_names: 1, pattern: "6x13" });
+        assert_eq!(value.to_request(), ListFontsWithInfoRequest { max_names: 1, pattern: "6x13".to_string() });
+    }
+}
+
+mod response {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::Error;
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::list_fonts_with_info::{FontWithInfo, ListFontsWithInfoReply, ListFontsWithInfoResponse};
+    use crate::request::query_font::{CharInfo, DrawDirection, FontInfo, FontProp};
+
+    #[test]
+    fn read_test() {
+        let bounds = CharInfo { left_side_bearing: 0, right_side_bearing: 6, character_width: 6, ascent: 10, descent: 3, attributes: 0 };
+        let expected = ListFontsWithInfoResponse {
+            sequence_number: 9,
+            fonts: vec![FontWithInfo {
+                name: "fixed".to_string(),
+                info: FontInfo {
+                    min_bounds: bounds,
+                    max_bounds: bounds,
+                    min_char_or_byte2: 0,
+                    max_char_or_byte2: 0xff,
+                    default_char: 0,
+                    draw_direction: DrawDirection::LeftToRight,
+                    min_byte1: 0,
+                    max_byte1: 0,
+                    all_chars_exist: true,
+                    font_ascent: 10,
+                    font_descent: 3,
+                    properties: vec![FontProp { name: 300, value: 13 }],
+                },
+                replies_hint: 0,
+            }],
+        };
+        //長さは7 + 2 * 1 + 2 = 11、最後の返事は名前の長さが0
+        let input = [
+            &[1, 5, 0, 9, 0, 0, 0, 11][..],
+            &[0, 0, 0, 6, 0, 6, 0, 10, 0, 3, 0, 0, 0, 0, 0, 0],
+            &[0, 0, 0, 6, 0, 6, 0, 10, 0, 3, 0, 0, 0, 0, 0, 0],
+            &[0, 0, 0, 0xff, 0, 0, 0, 1, 0, 0, 0, 1, 0, 10, 0, 3],
+            &[0, 0, 0, 0],
+            &[0, 0, 1, 0x2c, 0, 0, 0, 13],
+            &[b'f', b'i', b'x', b'e', b'd', 0, 0, 0],
+            &[1, 0, 0, 9, 0, 0, 0, 7],
+            &[0; 52],
+        ].concat();
+        let value = ListFontsWithInfoResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [
+            &[1, 5, 9, 0, 11, 0, 0, 0][..],
+            &[0, 0, 6, 0, 6, 0, 10, 0, 3, 0, 0, 0, 0, 0, 0, 0],
+            &[0, 0, 6, 0, 6, 0, 10, 0, 3, 0, 0, 0, 0, 0, 0, 0],
+            &[0, 0, 0xff, 0, 0, 0, 1, 0, 0, 0, 0, 1, 10, 0, 3, 0],
+            &[0, 0, 0, 0],
+            &[0x2c, 1, 0, 0, 13, 0, 0, 0],
+            &[b'f', b'i', b'x', b'e', b'd', 0, 0, 0],
+            &[1, 0, 9, 0, 7, 0, 0, 0],
+            &[0; 52],
+        ].concat();
+        let value = ListFontsWithInfoResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+        //一致するfontが無ければ最後の返事だけ
+        let input = [&[1, 0, 0, 9, 0, 0, 0, 7][..], &[0; 52]].concat();
+        let value = ListFontsWithInfoResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, ListFontsWithInfoResponse { sequence_number: 9, fonts: vec![] });
+    }
+
+    #[test]
+    fn reply_test() {
+        //返事は1つずつ読め、次の返事には触らない
+        let input = [
+            &[1, 4, 0, 9, 0, 0, 0, 8][..],
+            &[0; 48],
+            &[0, 0, 0, 2],
+            b"6x13",
+            &[1, 0, 0, 9, 0, 0, 0, 7],
+            &[0; 52],
+        ].concat();
+        let mut stream = BufReader::new(&input[..]);
+        let value = ListFontsWithInfoReply::read(&mut stream, &ByteOrder::MSBFirst).unwrap();
+        let font = value.font.unwrap();
+        assert_eq!((value.sequence_number, font.name.as_str(), font.replies_hint), (9, "6x13", 2));
+        let value = ListFontsWithInfoReply::read(&mut stream, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, ListFontsWithInfoReply { sequence_number: 9, font: None });
+
+        let mut buffer = [0xff; 60];
+        ListFontsWithInfoReply::write(&mut BufWriter::new(&mut buffer[..]), ListFontsWithInfoReply { sequence_number: 9, font: None }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer[..], [&[1, 0, 9, 0, 7, 0, 0, 0][..], &[0; 52]].concat()[..]);
+        //最後の返事の長さは7
+        let input = [&[1, 0, 0, 9, 0, 0, 0, 8][..], &[0; 56]].concat();
+        assert!(matches!(ListFontsWithInfoReply::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst), Err(Error::InvalidValue(_))));
+    }
+
+    #[test]
+    fn write_test() {
+        let bounds = CharInfo { left_side_bearing: 0, right_side_bearing: 6, character_width: 6, ascent: 10, descent: 3, attributes: 0 };
+        let info = FontInfo {
+            min_bounds: bounds,
+            max_bounds: bounds,
+            min_char_or_byte2: 0,
+            max_char_or_byte2: 0xff,
+            default_char: 0,
+            draw_direction: DrawDirection::LeftToRight,
+            min_byte1: 0,
+            max_byte1: 0,
+            all_chars_exist: true,
+            font_ascent: 10,
+            font_descent: 3,
+            properties: vec![],
+        };
+        let data = ListFontsWithInfoResponse {
+            sequence_number: 9,
+            fonts: vec![FontWithInfo { name: "6x13".to_string(), info: info.clone(), replies_hint: 1 }],
+        };
+        let expected = [
+            &[1, 4, 0, 9, 0, 0, 0, 8][..],
+            &[0, 0, 0, 6, 0, 6, 0, 10, 0, 3, 0, 0, 0, 0, 0, 0],
+            &[0, 0, 0, 6, 0, 6, 0, 10, 0, 3, 0, 0, 0, 0, 0, 0],
+            &[0, 0, 0, 0xff, 0, 0, 0, 0, 0, 0, 0, 1, 0, 10, 0, 3],
+            &[0, 0, 0, 1],
+            b"6x13",
+            &[1, 0, 0, 9, 0, 0, 0, 7],
+            &[0; 52],
+        ].concat();
+        let mut buffer = [0xff; 124];
+        ListFontsWithInfoResponse::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer[..], expected[..]);
+        let expected = [
+            &[1, 4, 9, 0, 8, 0, 0, 0][..],
+            &[0, 0, 6, 0, 6, 0, 10, 0, 3, 0, 0, 0, 0, 0, 0, 0],
+            &[0, 0, 6, 0, 6, 0, 10, 0, 3, 0, 0, 0, 0, 0, 0, 0],
+            &[0, 0, 0xff, 0, 0, 0, 0, 0, 0, 0, 0, 1, 10, 0, 3, 0],
+            &[1, 0, 0, 0],
+            b"6x13",
+            &[1, 0, 9, 0, 7, 0, 0, 0],
+            &[0; 52],
+        ].concat();
+        let mut buffer = [0xff; 124];
+        ListFontsWithInfoResponse::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer[..], expected[..]);
+
+        let empty = ListFontsWithInfoResponse { sequence_number: 9, fonts: vec![FontWithInfo { name: String::new(), info, replies_hint: 0 }] };
+        let mut buffer = [0xff; 124];
+        assert!(matches!(ListFontsWithInfoResponse::write(&mut BufWriter::new(&mut buffer[..]), empty, &ByteOrder::MSBFirst), Err(Error::InvalidValue(_))));
+    }
+}
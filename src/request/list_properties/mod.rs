@@ -1,34 +1,72 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct ListPropertiesRequest;
+pub struct ListPropertiesRequest {
+    pub window: u32,
+}
 
 impl Readable for ListPropertiesRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let window = stream.read_value(order)?;
+        Ok(ListPropertiesRequest { window })
     }
 }
 
-impl Writable for ListPropertiesRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for ListPropertiesRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(21, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(2, order)?;
+        stream.write_value(data.window, order)?;
+        Ok(())
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct ListPropertiesResponse;
+pub struct ListPropertiesResponse {
+    pub sequence_number: u16,
+    pub atoms: Vec<u32>,
+}
 
 impl Readable for ListPropertiesResponse {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 2], 2)?;
+        let sequence_number = stream.read_value(order)?;
+        let length = stream.read_value::<u32>(order)? as usize;
+        let number_of_atoms = stream.read_value::<u16>(order)? as usize;
+        read_specified_length(stream, &mut [0; 22], 22)?;
+        if number_of_atoms != length {
+            return Err(Error::InvalidValue("ListPropertiesResponse"));
+        }
+        let mut atoms = Vec::with_capacity(number_of_atoms);
+        for _ in 0..number_of_atoms {
+            atoms.push(stream.read_value(order)?);
+        }
+        Ok(ListPropertiesResponse {
+            sequence_number,
+            atoms,
+        })
     }
 }
 
-impl Writable for ListPropertiesResponse{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for ListPropertiesResponse {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(1, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.atoms.len() as u32, order)?;
+        stream.write_value(data.atoms.len() as u16, order)?;
+        stream.write_all(&[0; 22]).map_err(Error::IoError)?;
+        for atom in data.atoms {
+            stream.write_value(atom, order)?;
+        }
+        Ok(())
     }
 }
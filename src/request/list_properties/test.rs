@@ -0,0 +1,68 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::list_properties::ListPropertiesRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 2, 0, 0, 0, 1];
+        let value = ListPropertiesRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, ListPropertiesRequest { window: 1 });
+        let input = [0, 2, 0, 1, 0, 0, 0];
+        let value = ListPropertiesRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, ListPropertiesRequest { window: 1 });
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0; 8];
+        ListPropertiesRequest::write(&mut BufWriter::new(&mut buffer[..]), ListPropertiesRequest { window: 1 }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [21, 0, 0, 2, 0, 0, 0, 1]);
+        let mut buffer = [0; 8];
+        ListPropertiesRequest::write(&mut BufWriter::new(&mut buffer[..]), ListPropertiesRequest { window: 1 }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [21, 0, 2, 0, 1, 0, 0, 0]);
+    }
+}
+
+mod response {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::list_properties::ListPropertiesResponse;
+
+    #[test]
+    fn read_test() {
+        let expected = ListPropertiesResponse { sequence_number: 2, atoms: vec![39, 300] };
+        let mut input = [0; 40];
+        input[..10].copy_from_slice(&[1, 0, 0, 2, 0, 0, 0, 2, 0, 2]);
+        input[32..].copy_from_slice(&[0, 0, 0, 39, 0, 0, 1, 0x2c]);
+        let value = ListPropertiesResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let mut input = [0; 40];
+        input[..10].copy_from_slice(&[1, 0, 2, 0, 2, 0, 0, 0, 2, 0]);
+        input[32..].copy_from_slice(&[39, 0, 0, 0, 0x2c, 1, 0, 0]);
+        let value = ListPropertiesResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn write_test() {
+        let data = ListPropertiesResponse { sequence_number: 2, atoms: vec![39, 300] };
+        let mut expected = [0; 40];
+        expected[..10].copy_from_slice(&[1, 0, 0, 2, 0, 0, 0, 2, 0, 2]);
+        expected[32..].copy_from_slice(&[0, 0, 0, 39, 0, 0, 1, 0x2c]);
+        let mut buffer = [0xff; 40];
+        ListPropertiesResponse::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+        let mut expected = [0; 40];
+        expected[..10].copy_from_slice(&[1, 0, 2, 0, 2, 0, 0, 0, 2, 0]);
+        expected[32..].copy_from_slice(&[39, 0, 0, 0, 0x2c, 1, 0, 0]);
+        let mut buffer = [0xff; 40];
+        ListPropertiesResponse::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+    }
+}
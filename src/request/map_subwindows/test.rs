@@ -0,0 +1,29 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::map_subwindows::MapSubwindowsRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 2, 0, 0, 0, 1];
+        let value = MapSubwindowsRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, MapSubwindowsRequest { window: 1 });
+        let input = [0, 2, 0, 1, 0, 0, 0];
+        let value = MapSubwindowsRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, MapSubwindowsRequest { window: 1 });
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0; 8];
+        MapSubwindowsRequest::write(&mut BufWriter::new(&mut buffer[..]), MapSubwindowsRequest { window: 1 }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [9, 0, 0, 2, 0, 0, 0, 1]);
+        let mut buffer = [0; 8];
+        MapSubwindowsRequest::write(&mut BufWriter::new(&mut buffer[..]), MapSubwindowsRequest { window: 1 }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [9, 0, 2, 0, 1, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,29 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::map_window::MapWindowRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 2, 0, 0, 0, 1];
+        let value = MapWindowRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, MapWindowRequest { window: 1 });
+        let input = [0, 2, 0, 1, 0, 0, 0];
+        let value = MapWindowRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, MapWindowRequest { window: 1 });
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0; 8];
+        MapWindowRequest::write(&mut BufWriter::new(&mut buffer[..]), MapWindowRequest { window: 1 }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [8, 0, 0, 2, 0, 0, 0, 1]);
+        let mut buffer = [0; 8];
+        MapWindowRequest::write(&mut BufWriter::new(&mut buffer[..]), MapWindowRequest { window: 1 }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [8, 0, 2, 0, 1, 0, 0, 0]);
+    }
+}
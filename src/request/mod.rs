@@ -1,7 +1,8 @@
-use std::io::{BufRead, BufReader, Read, Write};
+use std::io::{Read, Write};
 
 use crate::Error;
-use crate::read_util::{ByteOrder, Encoding, Readable, ReadableRead, Writable, WritableWrite};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, SliceReader, Writable, WritableWrite};
 use crate::Result;
 
 pub mod create_window;
@@ -124,6 +125,9 @@ pub mod get_pointer_mapping;
 pub mod set_modifier_mapping;
 pub mod get_modifier_mapping;
 pub mod no_operation;
+pub mod value_list;
+
+mod test;
 
 
 pub enum Request {
@@ -249,9 +253,10 @@ pub enum Request {
     NoOperation(no_operation::NoOperationRequest),
 }
 
-impl Readable for Request {
-    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
-        match stream.read_value::<u8>(order)? {
+impl Request {
+    /// opcodeを読んだ後の部分を読む
+    pub(crate) fn read_body(opcode: u8, stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match opcode {
             1 => Ok(Request::CreateWindow(stream.read_value(order)?)),
             2 => Ok(Request::ChangeWindowAttributes(stream.read_value(order)?)),
             3 => Ok(Request::GetWindowAttributes(stream.read_value(order)?)),
@@ -372,9 +377,203 @@ impl Readable for Request {
             118 => Ok(Request::SetModifierMapping(stream.read_value(order)?)),
             119 => Ok(Request::GetModifierMapping(stream.read_value(order)?)),
             127 => Ok(Request::NoOperation(stream.read_value(order)?)),
-            _ => Err(Error::InvalidValue("opcode")),
+            other => Err(Error::ProtocolError(ErrorCode::Request, other as u32)),
+        }
+    }
+
+    /// headerを読んだ後のbodyを読む
+    /// lengthは4byteのheaderを含めた長さに直したもので、BIG-REQUESTSではu16に収まらないことがある
+    pub(crate) fn read_body_with_length(opcode: u8, data: u8, length: usize, stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match opcode {
+            18 => Ok(Request::ChangeProperty(ReadableBody::read_body(stream, data, length, order)?)),
+            48 => Ok(Request::QueryTextExtents(ReadableBody::read_body(stream, data, length, order)?)),
+            51 => Ok(Request::SetFontPath(ReadableBody::read_body(stream, data, length, order)?)),
+            72 => Ok(Request::PutImage(ReadableBody::read_body(stream, data, length, order)?)),
+            127 => Ok(Request::NoOperation(ReadableBody::read_body(stream, data, length, order)?)),
+            //他のrequestはu16に収まらない長さにならない
+            _ if length > u16::MAX as usize => Err(Error::ProtocolError(ErrorCode::Length, 0)),
+            _ => {
+                let mut header = [data, 0, 0];
+                order.encode(length as u16, &mut header[1..3]);
+                Request::read_body(opcode, &mut std::io::BufReader::new((&header[..]).chain(stream)), order)
+            }
+        }
+    }
+}
+
+/// data byteとlengthを読んだ後の部分を読む
+/// BIG-REQUESTSで長くなりうるrequestはこれを実装して、u16に収まらないlengthを受け取る
+pub(crate) trait ReadableBody: Sized {
+    fn read_body(stream: &mut std::io::BufReader<impl Read>, data: u8, length: usize, order: &ByteOrder) -> Result<Self>;
+}
+
+/// ReadableBodyのSliceReader版
+/// 文字列やdataを入力のbufferから借用するrequestが実装する
+pub(crate) trait DecodeBody<'a>: Sized {
+    fn decode_body(reader: &mut SliceReader<'a>, data: u8, length: usize, order: &ByteOrder) -> Result<Self>;
+}
+
+/// 文字列やdataを入力のbufferから借用して読んだrequest
+/// 借用する版の無いrequestはOtherにRequestとして入る
+pub enum RequestRef<'a> {
+    InternAtom(intern_atom::InternAtomRequestRef<'a>),
+    ChangeProperty(change_property::ChangePropertyRequestRef<'a>),
+    OpenFont(open_font::OpenFontRequestRef<'a>),
+    ListFonts(list_fonts::ListFontsRequestRef<'a>),
+    ListFontsWithInfo(list_fonts_with_info::ListFontsWithInfoRequestRef<'a>),
+    PutImage(put_image::PutImageRequestRef<'a>),
+    PolyText8(poly_text8::PolyText8RequestRef<'a>),
+    PolyText16(poly_text16::PolyText16RequestRef<'a>),
+    QueryExtension(query_extension::QueryExtensionRequestRef<'a>),
+    Other(Request),
+}
+
+impl<'a> RequestRef<'a> {
+    /// headerを読んだ後のbodyを読む
+    /// lengthはRequest::read_body_with_lengthと同じく、4byteのheaderを含めた長さに直したもの
+    pub(crate) fn decode_body_with_length(opcode: u8, data: u8, length: usize, body: &'a [u8], order: &ByteOrder) -> Result<Self> {
+        let reader = &mut SliceReader::new(body);
+        match opcode {
+            18 => Ok(RequestRef::ChangeProperty(DecodeBody::decode_body(reader, data, length, order)?)),
+            72 => Ok(RequestRef::PutImage(DecodeBody::decode_body(reader, data, length, order)?)),
+            _ if length > u16::MAX as usize => Err(Error::ProtocolError(ErrorCode::Length, 0)),
+            16 => Ok(RequestRef::InternAtom(DecodeBody::decode_body(reader, data, length, order)?)),
+            45 => Ok(RequestRef::OpenFont(DecodeBody::decode_body(reader, data, length, order)?)),
+            49 => Ok(RequestRef::ListFonts(DecodeBody::decode_body(reader, data, length, order)?)),
+            50 => Ok(RequestRef::ListFontsWithInfo(DecodeBody::decode_body(reader, data, length, order)?)),
+            74 => Ok(RequestRef::PolyText8(DecodeBody::decode_body(reader, data, length, order)?)),
+            75 => Ok(RequestRef::PolyText16(DecodeBody::decode_body(reader, data, length, order)?)),
+            98 => Ok(RequestRef::QueryExtension(DecodeBody::decode_body(reader, data, length, order)?)),
+            _ => Request::read_body_with_length(opcode, data, length, &mut std::io::BufReader::new(body), order).map(RequestRef::Other),
+        }
+    }
+
+    /// 借用している文字列やdataを複製する
+    /// ChangePropertyの16bitと32bitの値を読むためにconnectionのbyte orderを使う
+    pub fn into_request(self, order: &ByteOrder) -> Request {
+        match self {
+            RequestRef::InternAtom(request) => Request::InternAtom(request.to_request()),
+            RequestRef::ChangeProperty(request) => Request::ChangeProperty(request.to_request(order)),
+            RequestRef::OpenFont(request) => Request::OpenFont(request.to_request()),
+            RequestRef::ListFonts(request) => Request::ListFonts(request.to_request()),
+            RequestRef::ListFontsWithInfo(request) => Request::ListFontsWithInfo(request.to_request()),
+            RequestRef::PutImage(request) => Request::PutImage(request.to_request()),
+            RequestRef::PolyText8(request) => Request::PolyText8(request.to_request()),
+            RequestRef::PolyText16(request) => Request::PolyText16(request.to_request()),
+            RequestRef::QueryExtension(request) => Request::QueryExtension(request.to_request()),
+            RequestRef::Other(request) => request,
+        }
+    }
+}
+
+/// BIG-REQUESTSで受け付けるrequestの最大の長さ
+/// Xorgと同じく4byte単位で16MiB未満
+pub const MAXIMUM_BIG_REQUEST_LENGTH: u32 = 4194303;
+
+impl Readable for Request {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let mut reader = RequestReader::new(u16::MAX);
+        reader.enable_big_requests(MAXIMUM_BIG_REQUEST_LENGTH);
+        reader.read(stream, order)?.1
+    }
+}
+
+/// lengthは4byte単位で、headerを含むrequest全体の長さ
+#[derive(Clone, Debug, PartialEq)]
+pub struct RequestHeader {
+    pub opcode: u8,
+    pub data: u8,
+    pub length: u32,
+}
+
+/// headerを読んでrequest1つ分だけを切り出してから各requestのdecoderに渡す
+/// decodeに失敗しても次のrequestの先頭まで読み進めるので、接続を切らずにエラーを返せる
+#[derive(Clone, Debug, PartialEq)]
+pub struct RequestReader {
+    pub maximum_request_length: u32,
+    pub big_requests: bool,
+}
+
+impl RequestReader {
+    pub fn new(maximum_request_length: u16) -> Self {
+        RequestReader {
+            maximum_request_length: maximum_request_length as u32,
+            big_requests: false,
         }
     }
+
+    /// BIG-REQUESTSを有効にしたときに呼ぶ
+    pub fn enable_big_requests(&mut self, maximum_request_length: u32) {
+        self.maximum_request_length = maximum_request_length;
+        self.big_requests = true;
+    }
+
+    /// 外側のErrは接続自体の問題で、内側のErrはクライアントにエラーとして返すもの
+    pub fn read(&self, stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<(RequestHeader, Result<Request>)> {
+        let mut header = [0; 4];
+        read_specified_length(stream, &mut header, 4)?;
+        let opcode = header[0];
+        let data = header[1];
+        let mut length = order.decode::<u16>(&header[2..4]) as u32;
+        let mut header_length = 4;
+        if length == 0 && self.big_requests {
+            length = stream.read_value(order)?;
+            header_length = 8;
+        }
+        let header = RequestHeader { opcode, data, length };
+        //長さ0はheaderだけ読み飛ばす
+        if (length as usize) << 2 < header_length {
+            return Ok((header, Err(Error::ProtocolError(ErrorCode::Length, 0))));
+        }
+        let body_length = ((length as usize) << 2) - header_length;
+        if length > self.maximum_request_length {
+            let skipped = std::io::copy(&mut stream.by_ref().take(body_length as u64), &mut std::io::sink())
+                .map_err(Error::IoError)?;
+            if skipped < body_length as u64 {
+                return Err(Error::UnknownError);
+            }
+            return Ok((header, Err(Error::ProtocolError(ErrorCode::Length, 0))));
+        }
+        //届いた分だけ確保する
+        let mut buffer = Vec::new();
+        stream.by_ref().take(body_length as u64).read_to_end(&mut buffer).map_err(Error::IoError)?;
+        if buffer.len() < body_length {
+            return Err(Error::UnknownError);
+        }
+        let request = decode_body(opcode, data, &buffer, order).map(|request| request.into_request(order));
+        Ok((header, request))
+    }
+
+    /// request1つ分以上が入ったbufferから、先頭のrequestを読む
+    /// 文字列やdataはbufferから借用するので、bodyを複製しない
+    /// 読んだ長さはheaderのlengthの4倍で、長さ0のときはheaderの長さ
+    /// 外側のErrはbufferが足りないときで、内側のErrはクライアントにエラーとして返すもの
+    pub fn read_slice<'a>(&self, buffer: &'a [u8], order: &ByteOrder) -> Result<(RequestHeader, Result<RequestRef<'a>>)> {
+        let reader = &mut SliceReader::new(buffer);
+        let opcode = reader.read(order)?;
+        let data = reader.read(order)?;
+        let mut length = reader.read::<u16>(order)? as u32;
+        if length == 0 && self.big_requests {
+            length = reader.read(order)?;
+        }
+        let header = RequestHeader { opcode, data, length };
+        if (length as usize) << 2 < reader.position() || length > self.maximum_request_length {
+            return Ok((header, Err(Error::ProtocolError(ErrorCode::Length, 0))));
+        }
+        let body = reader.read_bytes(((length as usize) << 2) - reader.position())?;
+        Ok((header, decode_body(opcode, data, body, order)))
+    }
+}
+
+/// 各decoderには4byteのheaderの形式に直したlengthを渡す
+/// bodyが途中で終わったものはBadLength
+fn decode_body<'a>(opcode: u8, data: u8, body: &'a [u8], order: &ByteOrder) -> Result<RequestRef<'a>> {
+    let normal_length = (body.len() + 4) >> 2;
+    RequestRef::decode_body_with_length(opcode, data, normal_length, body, order)
+        .map_err(|e| match e {
+            Error::UnknownError | Error::IoError(_) => Error::ProtocolError(ErrorCode::Length, 0),
+            e => e,
+        })
 }
 
 impl Writable for Request {
@@ -504,19 +703,96 @@ impl Writable for Request {
     }
 }
 
-struct Response {
-    pub sequence_number: u16,
-    pub data: Vec<u8>,
+/// replyが返ってくるrequestと、そのreplyの型の対応
+pub trait ReplyRequest {
+    type Reply: Readable;
 }
 
-impl Readable for Response {
-    fn read(stream: &mut BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
-        while stream.fill_buf().map_err(|e| Error::IoError(e))?.len() < 32 {}
-        let sequence_number = u16::decode(order, &stream.buffer()[2..4]);
-        let additional_length = u16::decode(order, &stream.buffer()[4..6]) as usize;
-        while stream.fill_buf().map_err(|e| Error::IoError(e))?.len() < 32 + (additional_length << 2) {}
-        let data = Vec::from(&stream.buffer()[..32 + (additional_length << 2)]);
-        stream.consume(32 + (additional_length << 2));
-        Ok(Response { sequence_number, data })
-    }
-}
\ No newline at end of file
+impl ReplyRequest for get_window_attributes::GetWindowAttributesRequest {
+    type Reply = get_window_attributes::GetWindowAttributesResponse;
+}
+
+impl ReplyRequest for get_geometry::GetGeometryRequest {
+    type Reply = get_geometry::GetGeometryResponse;
+}
+
+impl ReplyRequest for query_tree::QueryTreeRequest {
+    type Reply = query_tree::QueryTreeResponse;
+}
+
+impl ReplyRequest for translate_coordinates::TranslateCoordinatesRequest {
+    type Reply = translate_coordinates::TranslateCoordinatesResponse;
+}
+
+impl ReplyRequest for grab_pointer::GrabPointerRequest {
+    type Reply = grab_pointer::GrabPointerResponse;
+}
+
+impl ReplyRequest for grab_keyboard::GrabKeyboardRequest {
+    type Reply = grab_keyboard::GrabKeyboardResponse;
+}
+
+impl ReplyRequest for query_pointer::QueryPointerRequest {
+    type Reply = query_pointer::QueryPointerResponse;
+}
+
+impl ReplyRequest for get_motion_events::GetMotionEventsRequest {
+    type Reply = get_motion_events::GetMotionEventsResponse;
+}
+
+impl ReplyRequest for get_input_focus::GetInputFocusRequest {
+    type Reply = get_input_focus::GetInputFocusResponse;
+}
+
+impl ReplyRequest for query_keymap::QueryKeymapRequest {
+    type Reply = query_keymap::QueryKeymapResponse;
+}
+
+impl ReplyRequest for query_font::QueryFontRequest {
+    type Reply = query_font::QueryFontResponse;
+}
+
+impl ReplyRequest for query_text_extents::QueryTextExtentsRequest {
+    type Reply = query_text_extents::QueryTextExtentsResponse;
+}
+
+impl ReplyRequest for list_fonts::ListFontsRequest {
+    type Reply = list_fonts::ListFontsResponse;
+}
+
+/// replyはfontごとに1つずつで、fontがNoneのものが最後
+impl ReplyRequest for list_fonts_with_info::ListFontsWithInfoRequest {
+    type Reply = list_fonts_with_info::ListFontsWithInfoReply;
+}
+
+impl ReplyRequest for get_font_path::GetFontPathRequest {
+    type Reply = get_font_path::GetFontPathResponse;
+}
+
+impl ReplyRequest for get_image::GetImageRequest {
+    type Reply = get_image::GetImageResponse;
+}
+
+impl ReplyRequest for intern_atom::InternAtomRequest {
+    type Reply = intern_atom::InternAtomResponse;
+}
+
+impl ReplyRequest for get_atom_name::GetAtomNameRequest {
+    type Reply = get_atom_name::GetAtomNameResponse;
+}
+
+impl ReplyRequest for get_property::GetPropertyRequest {
+    type Reply = get_property::GetPropertyResponse;
+}
+
+impl ReplyRequest for list_properties::ListPropertiesRequest {
+    type Reply = list_properties::ListPropertiesResponse;
+}
+
+impl ReplyRequest for get_selection_owner::GetSelectionOwnerRequest {
+    type Reply = get_selection_owner::GetSelectionOwnerResponse;
+}
+
+impl ReplyRequest for query_extension::QueryExtensionRequest {
+    type Reply = query_extension::QueryExtensionResponse;
+}
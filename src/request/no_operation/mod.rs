@@ -1,20 +1,42 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, Readable, ReadableRead, Writable, WritableWrite};
+use crate::request::ReadableBody;
 
+mod test;
+
+/// lengthは1より長くてもよく、残りは読み捨てる
 #[derive(Clone, Debug, PartialEq)]
 pub struct NoOperationRequest;
 
 impl Readable for NoOperationRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let data = stream.read_value(order)?;
+        let length = stream.read_value::<u16>(order)?;
+        Self::read_body(stream, data, length as usize, order)
     }
 }
 
-impl Writable for NoOperationRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl ReadableBody for NoOperationRequest {
+    fn read_body(stream: &mut std::io::BufReader<impl Read>, _data: u8, length: usize, _order: &ByteOrder) -> Result<Self> {
+        let rest = length.checked_sub(1).ok_or(Error::ProtocolError(ErrorCode::Length, 0))? << 2;
+        let skipped = std::io::copy(&mut stream.by_ref().take(rest as u64), &mut std::io::sink())
+            .map_err(Error::IoError)?;
+        if skipped < rest as u64 {
+            return Err(Error::UnknownError);
+        }
+        Ok(Self)
+    }
+}
+
+impl Writable for NoOperationRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, _data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(127, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(1, order)?;
+        Ok(())
     }
 }
 
@@ -23,12 +45,12 @@ pub struct NoOperationResponse;
 
 impl Readable for NoOperationResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for NoOperationResponse{
+impl Writable for NoOperationResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
@@ -0,0 +1,34 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter, Read};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::no_operation::NoOperationRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 1];
+        let value = NoOperationRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, NoOperationRequest);
+        //余分なbodyは読み捨てる
+        let input = [0, 3, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let mut stream = BufReader::new(&input[..]);
+        let value = NoOperationRequest::read(&mut stream, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, NoOperationRequest);
+        let mut rest = Vec::new();
+        stream.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, [9]);
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0xff; 4];
+        NoOperationRequest::write(&mut BufWriter::new(&mut buffer[..]), NoOperationRequest, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [127, 0, 0, 1]);
+        let mut buffer = [0xff; 4];
+        NoOperationRequest::write(&mut BufWriter::new(&mut buffer[..]), NoOperationRequest, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [127, 0, 1, 0]);
+    }
+}
@@ -1,20 +1,90 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, Decode, read_specified_length, Readable, ReadableRead, SliceReader, Writable, WritableWrite};
+use crate::request::DecodeBody;
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct OpenFontRequest;
+pub struct OpenFontRequest {
+    pub fid: u32,
+    pub name: String,
+}
 
 impl Readable for OpenFontRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let length = stream.read_value::<u16>(order)? as usize;
+        let fid = stream.read_value(order)?;
+        let name_len = stream.read_value::<u16>(order)? as usize;
+        read_specified_length(stream, &mut [0; 2], 2)?;
+        let total_length = length.checked_sub(3).ok_or(Error::ProtocolError(ErrorCode::Length, 0))? << 2;
+        if name_len > total_length {
+            return Err(Error::ProtocolError(ErrorCode::Length, 0));
+        }
+        let mut buffer = vec![0; total_length];
+        read_specified_length(stream, &mut buffer[..], total_length)?;
+        let name = std::str::from_utf8(&buffer[..name_len]).map_err(Error::StringError)?;
+        Ok(OpenFontRequest {
+            fid,
+            name: name.to_string(),
+        })
     }
 }
 
-impl Writable for OpenFontRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for OpenFontRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let name_len = data.name.len();
+        stream.write_value::<u8>(45, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value((3 + ((name_len + 3) >> 2)) as u16, order)?;
+        stream.write_value(data.fid, order)?;
+        stream.write_value(name_len as u16, order)?;
+        stream.write_all(&[0; 2]).map_err(Error::IoError)?;
+        stream.write_all(data.name.as_bytes()).map_err(Error::IoError)?;
+        stream.write_all(&[0; 4][..(!name_len).wrapping_add(1) & 3]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+/// OpenFontRequestのnameを入力のbufferから借用する版
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpenFontRequestRef<'a> {
+    pub fid: u32,
+    pub name: &'a str,
+}
+
+impl<'a> OpenFontRequestRef<'a> {
+    pub fn to_request(&self) -> OpenFontRequest {
+        OpenFontRequest {
+            fid: self.fid,
+            name: self.name.to_string(),
+        }
+    }
+}
+
+impl<'a> Decode<'a> for OpenFontRequestRef<'a> {
+    fn decode_from(reader: &mut SliceReader<'a>, order: &ByteOrder) -> Result<Self> {
+        let data = reader.read(order)?;
+        let length = reader.read::<u16>(order)? as usize;
+        Self::decode_body(reader, data, length, order)
+    }
+}
+
+impl<'a> DecodeBody<'a> for OpenFontRequestRef<'a> {
+    fn decode_body(reader: &mut SliceReader<'a>, _data: u8, length: usize, order: &ByteOrder) -> Result<Self> {
+        let total_length = length.checked_sub(3).ok_or(Error::ProtocolError(ErrorCode::Length, 0))? << 2;
+        let fid = reader.read(order)?;
+        let name_len = reader.read::<u16>(order)? as usize;
+        reader.skip(2)?;
+        let buffer = reader.read_bytes(total_length)?;
+        let name = buffer.get(..name_len).ok_or(Error::ProtocolError(ErrorCode::Length, 0))?;
+        Ok(OpenFontRequestRef {
+            fid,
+            name: std::str::from_utf8(name).map_err(Error::StringError)?,
+        })
     }
 }
 
@@ -23,12 +93,12 @@ pub struct OpenFontResponse;
 
 impl Readable for OpenFontResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for OpenFontResponse{
+impl Writable for OpenFontResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
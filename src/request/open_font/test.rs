@@ -0,0 +1,49 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::Error;
+    use crate::error::ErrorCode;
+    use crate::read_util::{ByteOrder, Decode, Readable, SliceReader, Writable};
+    use crate::request::open_font::{OpenFontRequest, OpenFontRequestRef};
+
+    #[test]
+    fn read_test() {
+        let expected = OpenFontRequest { fid: 0x00200001, name: "fixed".to_string() };
+        let input = [0, 0, 5, 0, 0x20, 0, 1, 0, 5, 0, 0, b'f', b'i', b'x', b'e', b'd', 0, 0, 0];
+        let value = OpenFontRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [0, 5, 0, 1, 0, 0x20, 0, 5, 0, 0, 0, b'f', b'i', b'x', b'e', b'd', 0, 0, 0];
+        let value = OpenFontRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+        //lengthが足りなければBadLength
+        let input = [0, 0, 2, 0, 0x20, 0, 1, 0, 0, 0, 0];
+        assert!(matches!(OpenFontRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst), Err(Error::ProtocolError(ErrorCode::Length, 0))));
+        let input = [0, 0, 4, 0, 0x20, 0, 1, 0, 5, 0, 0, b'f', b'i', b'x', b'e'];
+        assert!(matches!(OpenFontRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst), Err(Error::ProtocolError(ErrorCode::Length, 0))));
+    }
+
+    #[test]
+    fn write_test() {
+        let data = OpenFontRequest { fid: 0x00200001, name: "cursor".to_string() };
+        let mut buffer = [0xff; 20];
+        OpenFontRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [45, 0, 0, 5, 0, 0x20, 0, 1, 0, 6, 0, 0, b'c', b'u', b'r', b's', b'o', b'r', 0, 0]);
+        let mut buffer = [0xff; 20];
+        OpenFontRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [45, 0, 5, 0, 1, 0, 0x20, 0, 6, 0, 0, 0, b'c', b'u', b'r', b's', b'o', b'r', 0, 0]);
+    }
+
+    #[test]
+    fn ref_test() {
+        let input = [0, 5, 0, 1, 0, 0x20, 0, 5, 0, 0, 0, b'f', b'i', b'x', b'e', b'd', 0, 0, 0];
+        let value = OpenFontRequestRef::decode_from(&mut SliceReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, OpenFontRequestRef { fid: 0x00200001, name: "fixed" });
+        assert_eq!(value.name.as_ptr(), input[11..].as_ptr());
+        assert_eq!(value.to_request(), OpenFontRequest { fid: 0x00200001, name: "fixed".to_string() });
+        let input = [0, 0, 4, 0, 0x20, 0, 1, 0, 5, 0, 0, b'f', b'i', b'x', b'e'];
+        assert!(matches!(OpenFontRequestRef::decode_from(&mut SliceReader::new(&input[..]), &ByteOrder::MSBFirst), Err(Error::ProtocolError(ErrorCode::Length, 0))));
+    }
+}
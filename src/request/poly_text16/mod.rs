@@ -1,20 +1,129 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, Decode, read_specified_length, Readable, ReadableRead, SliceReader, Writable, WritableWrite};
+use crate::request::DecodeBody;
+use crate::request::poly_text8::{decode_text_items, TextItemRef, write_font_shift};
 
+mod test;
+
+/// TEXTITEM16
 #[derive(Clone, Debug, PartialEq)]
-pub struct PolyText16Request;
+pub enum TextItem16 {
+    /// deltaだけxをずらしてからstringを書く
+    /// stringはCHAR2Bの列で、byte1が上位
+    Text { delta: i8, string: Vec<u16> },
+    /// 以降の文字をこのfontで書く
+    Font(u32),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PolyText16Request {
+    pub drawable: u32,
+    pub gc: u32,
+    pub x: i16,
+    pub y: i16,
+    pub items: Vec<TextItem16>,
+}
 
 impl Readable for PolyText16Request {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let length = stream.read_value::<u16>(order)? as usize;
+        let body_length = length.checked_sub(1).ok_or(Error::ProtocolError(ErrorCode::Length, 0))? << 2;
+        let mut buffer = vec![0; body_length];
+        read_specified_length(stream, &mut buffer[..], body_length)?;
+        Ok(PolyText16RequestRef::decode_body(&mut SliceReader::new(&buffer[..]), 0, length, order)?.to_request())
     }
 }
 
-impl Writable for PolyText16Request{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for PolyText16Request {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let items_len: usize = data.items.iter().map(|item| match item {
+            TextItem16::Text { string, .. } => 2 + (string.len() << 1),
+            TextItem16::Font(_) => 5,
+        }).sum();
+        stream.write_value::<u8>(75, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value((4 + ((items_len + 3) >> 2)) as u16, order)?;
+        stream.write_value(data.drawable, order)?;
+        stream.write_value(data.gc, order)?;
+        stream.write_value(data.x, order)?;
+        stream.write_value(data.y, order)?;
+        for item in data.items {
+            match item {
+                TextItem16::Text { delta, string } => {
+                    //255はfontの切り替えを表す
+                    if string.len() > 254 {
+                        return Err(Error::InvalidValue("TEXTELT16"));
+                    }
+                    stream.write_value(string.len() as u8, order)?;
+                    stream.write_value(delta, order)?;
+                    for c in string {
+                        stream.write_all(&c.to_be_bytes()).map_err(Error::IoError)?;
+                    }
+                }
+                TextItem16::Font(font) => write_font_shift(stream, font)?,
+            }
+        }
+        stream.write_all(&[0; 4][..(!items_len).wrapping_add(1) & 3]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+/// PolyText16Requestのstringを入力のbufferから借用する版
+/// stringはCHAR2Bのbyte列のまま
+#[derive(Clone, Debug, PartialEq)]
+pub struct PolyText16RequestRef<'a> {
+    pub drawable: u32,
+    pub gc: u32,
+    pub x: i16,
+    pub y: i16,
+    pub items: Vec<TextItemRef<'a>>,
+}
+
+impl<'a> PolyText16RequestRef<'a> {
+    pub fn to_request(&self) -> PolyText16Request {
+        PolyText16Request {
+            drawable: self.drawable,
+            gc: self.gc,
+            x: self.x,
+            y: self.y,
+            items: self.items.iter().map(|item| match item {
+                TextItemRef::Text { delta, string } => TextItem16::Text {
+                    delta: *delta,
+                    string: string.chunks(2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect(),
+                },
+                TextItemRef::Font(font) => TextItem16::Font(*font),
+            }).collect(),
+        }
+    }
+}
+
+impl<'a> Decode<'a> for PolyText16RequestRef<'a> {
+    fn decode_from(reader: &mut SliceReader<'a>, order: &ByteOrder) -> Result<Self> {
+        let data = reader.read(order)?;
+        let length = reader.read::<u16>(order)? as usize;
+        Self::decode_body(reader, data, length, order)
+    }
+}
+
+impl<'a> DecodeBody<'a> for PolyText16RequestRef<'a> {
+    fn decode_body(reader: &mut SliceReader<'a>, _data: u8, length: usize, order: &ByteOrder) -> Result<Self> {
+        let items_length = length.checked_sub(4).ok_or(Error::ProtocolError(ErrorCode::Length, 0))? << 2;
+        let drawable = reader.read(order)?;
+        let gc = reader.read(order)?;
+        let x = reader.read(order)?;
+        let y = reader.read(order)?;
+        let items = decode_text_items(&mut SliceReader::new(reader.read_bytes(items_length)?), 2, order)?;
+        Ok(PolyText16RequestRef {
+            drawable,
+            gc,
+            x,
+            y,
+            items,
+        })
     }
 }
 
@@ -23,12 +132,12 @@ pub struct PolyText16Response;
 
 impl Readable for PolyText16Response {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for PolyText16Response{
+impl Writable for PolyText16Response {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
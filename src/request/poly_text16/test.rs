@@ -0,0 +1,38 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Decode, Readable, SliceReader, Writable};
+    use crate::request::poly_text8::TextItemRef;
+    use crate::request::poly_text16::{PolyText16Request, PolyText16RequestRef, TextItem16};
+
+    #[test]
+    fn read_test() {
+        let expected = PolyText16Request {
+            drawable: 1,
+            gc: 2,
+            x: 3,
+            y: 4,
+            items: vec![
+                TextItem16::Text { delta: 0, string: vec![0x0041, 0x2422] },
+                TextItem16::Font(0x00200001),
+            ],
+        };
+        //CHAR2Bはbyte orderによらずbyte1、byte2の順
+        let input = [
+            0, 7, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 4, 0,
+            2, 0, 0, 0x41, 0x24, 0x22, 255, 0, 0x20, 0, 1, 0,
+        ];
+        let value = PolyText16Request::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let value = PolyText16RequestRef::decode_from(&mut SliceReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value.items, vec![TextItemRef::Text { delta: 0, string: &input[17..21] }, TextItemRef::Font(0x00200001)]);
+        assert_eq!(value.to_request(), expected);
+
+        let mut buffer = [0xff; 28];
+        PolyText16Request::write(&mut BufWriter::new(&mut buffer[..]), expected, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(&buffer[..], &[&[75][..], &input[..]].concat()[..]);
+    }
+}
@@ -1,20 +1,162 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, Decode, read_specified_length, Readable, ReadableRead, SliceReader, Writable, WritableWrite};
+use crate::request::DecodeBody;
 
+mod test;
+
+/// TEXTITEM8
 #[derive(Clone, Debug, PartialEq)]
-pub struct PolyText8Request;
+pub enum TextItem8 {
+    /// deltaだけxをずらしてからstringを書く
+    Text { delta: i8, string: Vec<u8> },
+    /// 以降の文字をこのfontで書く
+    Font(u32),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PolyText8Request {
+    pub drawable: u32,
+    pub gc: u32,
+    pub x: i16,
+    pub y: i16,
+    pub items: Vec<TextItem8>,
+}
 
 impl Readable for PolyText8Request {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let length = stream.read_value::<u16>(order)? as usize;
+        let body_length = length.checked_sub(1).ok_or(Error::ProtocolError(ErrorCode::Length, 0))? << 2;
+        let mut buffer = vec![0; body_length];
+        read_specified_length(stream, &mut buffer[..], body_length)?;
+        Ok(PolyText8RequestRef::decode_body(&mut SliceReader::new(&buffer[..]), 0, length, order)?.to_request())
     }
 }
 
-impl Writable for PolyText8Request{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for PolyText8Request {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let items_len: usize = data.items.iter().map(|item| match item {
+            TextItem8::Text { string, .. } => 2 + string.len(),
+            TextItem8::Font(_) => 5,
+        }).sum();
+        stream.write_value::<u8>(74, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value((4 + ((items_len + 3) >> 2)) as u16, order)?;
+        stream.write_value(data.drawable, order)?;
+        stream.write_value(data.gc, order)?;
+        stream.write_value(data.x, order)?;
+        stream.write_value(data.y, order)?;
+        for item in data.items {
+            match item {
+                TextItem8::Text { delta, string } => {
+                    //255はfontの切り替えを表す
+                    if string.len() > 254 {
+                        return Err(Error::InvalidValue("TEXTELT8"));
+                    }
+                    stream.write_value(string.len() as u8, order)?;
+                    stream.write_value(delta, order)?;
+                    stream.write_all(&string[..]).map_err(Error::IoError)?;
+                }
+                TextItem8::Font(font) => write_font_shift(stream, font)?,
+            }
+        }
+        stream.write_all(&[0; 4][..(!items_len).wrapping_add(1) & 3]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+/// TEXTITEM8とTEXTITEM16で共通の借用版
+/// TEXTITEM16のstringはCHAR2Bのbyte列
+#[derive(Clone, Debug, PartialEq)]
+pub enum TextItemRef<'a> {
+    Text { delta: i8, string: &'a [u8] },
+    Font(u32),
+}
+
+/// fontはbyte orderによらず上位byteから送る
+pub(crate) fn write_font_shift(stream: &mut std::io::BufWriter<impl Write>, font: u32) -> Result<()> {
+    stream.write_all(&[255]).map_err(Error::IoError)?;
+    stream.write_all(&font.to_be_bytes()).map_err(Error::IoError)
+}
+
+/// 1文字がchar_size byteのTEXTITEMを、readerの終わりまで読む
+/// 最後の2byte以下はpadding
+pub(crate) fn decode_text_items<'a>(reader: &mut SliceReader<'a>, char_size: usize, order: &ByteOrder) -> Result<Vec<TextItemRef<'a>>> {
+    let mut items = Vec::new();
+    while reader.remaining().len() > 2 {
+        let length = reader.read::<u8>(order)? as usize;
+        if length == 255 {
+            if reader.remaining().len() < 4 {
+                return Err(Error::ProtocolError(ErrorCode::Length, 0));
+            }
+            let font = reader.read_bytes(4)?;
+            items.push(TextItemRef::Font(u32::from_be_bytes([font[0], font[1], font[2], font[3]])));
+            continue;
+        }
+        let delta = reader.read(order)?;
+        if reader.remaining().len() < length * char_size {
+            return Err(Error::ProtocolError(ErrorCode::Length, 0));
+        }
+        let string = reader.read_bytes(length * char_size)?;
+        //長さ0でずらしもしないものは、paddingと区別がつかず何も書かないので捨てる
+        if length != 0 || delta != 0 {
+            items.push(TextItemRef::Text { delta, string });
+        }
+    }
+    Ok(items)
+}
+
+/// PolyText8Requestのstringを入力のbufferから借用する版
+#[derive(Clone, Debug, PartialEq)]
+pub struct PolyText8RequestRef<'a> {
+    pub drawable: u32,
+    pub gc: u32,
+    pub x: i16,
+    pub y: i16,
+    pub items: Vec<TextItemRef<'a>>,
+}
+
+impl<'a> PolyText8RequestRef<'a> {
+    pub fn to_request(&self) -> PolyText8Request {
+        PolyText8Request {
+            drawable: self.drawable,
+            gc: self.gc,
+            x: self.x,
+            y: self.y,
+            items: self.items.iter().map(|item| match item {
+                TextItemRef::Text { delta, string } => TextItem8::Text { delta: *delta, string: string.to_vec() },
+                TextItemRef::Font(font) => TextItem8::Font(*font),
+            }).collect(),
+        }
+    }
+}
+
+impl<'a> Decode<'a> for PolyText8RequestRef<'a> {
+    fn decode_from(reader: &mut SliceReader<'a>, order: &ByteOrder) -> Result<Self> {
+        let data = reader.read(order)?;
+        let length = reader.read::<u16>(order)? as usize;
+        Self::decode_body(reader, data, length, order)
+    }
+}
+
+impl<'a> DecodeBody<'a> for PolyText8RequestRef<'a> {
+    fn decode_body(reader: &mut SliceReader<'a>, _data: u8, length: usize, order: &ByteOrder) -> Result<Self> {
+        let items_length = length.checked_sub(4).ok_or(Error::ProtocolError(ErrorCode::Length, 0))? << 2;
+        let drawable = reader.read(order)?;
+        let gc = reader.read(order)?;
+        let x = reader.read(order)?;
+        let y = reader.read(order)?;
+        let items = decode_text_items(&mut SliceReader::new(reader.read_bytes(items_length)?), 1, order)?;
+        Ok(PolyText8RequestRef {
+            drawable,
+            gc,
+            x,
+            y,
+            items,
+        })
     }
 }
 
@@ -23,12 +165,12 @@ pub struct PolyText8Response;
 
 impl Readable for PolyText8Response {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for PolyText8Response{
+impl Writable for PolyText8Response {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
@@ -0,0 +1,93 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::Error;
+    use crate::error::ErrorCode;
+    use crate::read_util::{ByteOrder, Decode, Readable, SliceReader, Writable};
+    use crate::request::poly_text8::{PolyText8Request, PolyText8RequestRef, TextItem8, TextItemRef};
+
+    #[test]
+    fn read_test() {
+        let expected = PolyText8Request {
+            drawable: 1,
+            gc: 2,
+            x: 10,
+            y: -20,
+            items: vec![
+                TextItem8::Text { delta: 2, string: b"ab".to_vec() },
+                TextItem8::Font(0x01020304),
+                TextItem8::Text { delta: -1, string: b"c".to_vec() },
+            ],
+        };
+        let input = [
+            0, 0, 7, 0, 0, 0, 1, 0, 0, 0, 2, 0, 10, 0xff, 0xec,
+            2, 2, b'a', b'b', 255, 1, 2, 3, 4, 1, 0xff, b'c',
+        ];
+        let value = PolyText8Request::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let value = PolyText8RequestRef::decode_from(&mut SliceReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert!(matches!(value.items[0], TextItemRef::Text { delta: 2, string } if string.as_ptr() == input[17..].as_ptr()));
+        assert_eq!(value.to_request(), expected);
+        //fontはLSBFirstでも上位byteから
+        let input = [
+            0, 7, 0, 1, 0, 0, 0, 2, 0, 0, 0, 10, 0, 0xec, 0xff,
+            2, 2, b'a', b'b', 255, 1, 2, 3, 4, 1, 0xff, b'c',
+        ];
+        let value = PolyText8Request::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn padding_test() {
+        //3byteのpaddingは長さ0のTEXTELT8に見えるが、何も書かないので捨てる
+        let input = [0, 0, 6, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 0, 255, 0, 0, 0, 3, 0, 0, 0];
+        let value = PolyText8RequestRef::decode_from(&mut SliceReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value.items, vec![TextItemRef::Font(3)]);
+        //長さ0でもずらすものは残す
+        let input = [0, 0, 5, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 0, 0, 5, 0, 0];
+        let value = PolyText8RequestRef::decode_from(&mut SliceReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value.items, vec![TextItemRef::Text { delta: 5, string: &[] }]);
+        //stringがrequestの外まで続けばBadLength
+        let input = [0, 0, 5, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 0, 3, 0, b'a', b'b', b'c'];
+        assert!(matches!(PolyText8RequestRef::decode_from(&mut SliceReader::new(&input[..]), &ByteOrder::MSBFirst), Err(Error::ProtocolError(ErrorCode::Length, 0))));
+        assert!(matches!(PolyText8Request::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst), Err(Error::ProtocolError(ErrorCode::Length, 0))));
+    }
+
+    #[test]
+    fn write_test() {
+        let expected = PolyText8Request {
+            drawable: 1,
+            gc: 2,
+            x: 10,
+            y: -20,
+            items: vec![
+                TextItem8::Text { delta: 2, string: b"ab".to_vec() },
+                TextItem8::Font(0x01020304),
+                TextItem8::Text { delta: -1, string: b"c".to_vec() },
+            ],
+        };
+        let mut buffer = [0xff; 28];
+        PolyText8Request::write(&mut BufWriter::new(&mut buffer[..]), expected.clone(), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [
+            74, 0, 7, 0, 1, 0, 0, 0, 2, 0, 0, 0, 10, 0, 0xec, 0xff,
+            2, 2, b'a', b'b', 255, 1, 2, 3, 4, 1, 0xff, b'c',
+        ]);
+        let data = PolyText8Request {
+            items: vec![TextItem8::Text { delta: 0, string: b"abc".to_vec() }],
+            ..expected.clone()
+        };
+        let mut buffer = [0xff; 24];
+        PolyText8Request::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [74, 0, 0, 6, 0, 0, 0, 1, 0, 0, 0, 2, 0, 10, 0xff, 0xec, 3, 0, b'a', b'b', b'c', 0, 0, 0]);
+        //255文字以上は1つのTEXTELT8に入らない
+        let data = PolyText8Request {
+            items: vec![TextItem8::Text { delta: 0, string: vec![b'a'; 255] }],
+            ..expected
+        };
+        let mut buffer = [0; 276];
+        assert!(matches!(PolyText8Request::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::MSBFirst), Err(Error::InvalidValue(_))));
+    }
+}
@@ -1,20 +1,253 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, Decode, Encode, read_specified_length, Readable, ReadableRead, SliceReader, SliceWriter, Writable, WritableWrite};
+use crate::request::{DecodeBody, ReadableBody};
 
+mod test;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PutImageFormat {
+    Bitmap,
+    XYPixmap,
+    ZPixmap,
+}
+
+impl PutImageFormat {
+    fn from_u8(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(Self::Bitmap),
+            1 => Ok(Self::XYPixmap),
+            2 => Ok(Self::ZPixmap),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+
+    fn to_u8(&self) -> u8 {
+        match self {
+            Self::Bitmap => 0,
+            Self::XYPixmap => 1,
+            Self::ZPixmap => 2,
+        }
+    }
+}
+
+impl Readable for PutImageFormat {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        Self::from_u8(stream.read_value(order)?)
+    }
+}
+
+impl Writable for PutImageFormat {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value(data.to_u8(), order)
+    }
+}
+
+/// dataはwidth、height、formatから決まる画像の後ろのpaddingも含む
 #[derive(Clone, Debug, PartialEq)]
-pub struct PutImageRequest;
+pub struct PutImageRequest {
+    pub format: PutImageFormat,
+    pub drawable: u32,
+    pub gc: u32,
+    pub width: u16,
+    pub height: u16,
+    pub dst_x: i16,
+    pub dst_y: i16,
+    pub left_pad: u8,
+    pub depth: u8,
+    pub data: Vec<u8>,
+}
+
+/// BIG-REQUESTSの形式ではlengthが0で、その後にu32のlengthが続く
+/// 返す長さは4byteのheaderの形式に直したもの
+fn read_length(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<usize> {
+    match stream.read_value::<u16>(order)? {
+        0 => Ok((stream.read_value::<u32>(order)? as usize).saturating_sub(1)),
+        length => Ok(length as usize),
+    }
+}
+
+/// headerを含む4byte単位の長さ
+/// u16に収まらなければBIG-REQUESTSの形式で書く
+fn request_length(data_len: usize) -> usize {
+    let length = 6 + ((data_len + 3) >> 2);
+    if length > u16::MAX as usize {
+        length + 1
+    } else {
+        length
+    }
+}
 
 impl Readable for PutImageRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let data = stream.read_value(order)?;
+        let length = read_length(stream, order)?;
+        Self::read_body(stream, data, length, order)
     }
 }
 
-impl Writable for PutImageRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl ReadableBody for PutImageRequest {
+    fn read_body(stream: &mut std::io::BufReader<impl Read>, data: u8, length: usize, order: &ByteOrder) -> Result<Self> {
+        let format = PutImageFormat::from_u8(data)?;
+        if length < 6 {
+            return Err(Error::ProtocolError(ErrorCode::Length, 0));
+        }
+        let drawable = stream.read_value(order)?;
+        let gc = stream.read_value(order)?;
+        let width = stream.read_value(order)?;
+        let height = stream.read_value(order)?;
+        let dst_x = stream.read_value(order)?;
+        let dst_y = stream.read_value(order)?;
+        let left_pad = stream.read_value(order)?;
+        let depth = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 2], 2)?;
+        let mut data = vec![0; (length - 6) << 2];
+        read_specified_length(stream, &mut data[..], (length - 6) << 2)?;
+        Ok(PutImageRequest {
+            format,
+            drawable,
+            gc,
+            width,
+            height,
+            dst_x,
+            dst_y,
+            left_pad,
+            depth,
+            data,
+        })
+    }
+}
+
+impl Writable for PutImageRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let pad = (4 - (data.data.len() & 3)) & 3;
+        let length = request_length(data.data.len());
+        stream.write_value::<u8>(72, order)?;
+        stream.write_value(data.format, order)?;
+        if length > u16::MAX as usize {
+            stream.write_value::<u16>(0, order)?;
+            stream.write_value(length as u32, order)?;
+        } else {
+            stream.write_value(length as u16, order)?;
+        }
+        stream.write_value(data.drawable, order)?;
+        stream.write_value(data.gc, order)?;
+        stream.write_value(data.width, order)?;
+        stream.write_value(data.height, order)?;
+        stream.write_value(data.dst_x, order)?;
+        stream.write_value(data.dst_y, order)?;
+        stream.write_value(data.left_pad, order)?;
+        stream.write_value(data.depth, order)?;
+        stream.write_all(&[0; 2]).map_err(Error::IoError)?;
+        stream.write_all(&data.data[..]).map_err(Error::IoError)?;
+        stream.write_all(&[0; 3][..pad]).map_err(Error::IoError)?;
+        Ok(())
+    }
+}
+
+/// PutImageRequestのdataを入力のbufferから借用する版
+#[derive(Clone, Debug, PartialEq)]
+pub struct PutImageRequestRef<'a> {
+    pub format: PutImageFormat,
+    pub drawable: u32,
+    pub gc: u32,
+    pub width: u16,
+    pub height: u16,
+    pub dst_x: i16,
+    pub dst_y: i16,
+    pub left_pad: u8,
+    pub depth: u8,
+    pub data: &'a [u8],
+}
+
+impl<'a> PutImageRequestRef<'a> {
+    pub fn to_request(&self) -> PutImageRequest {
+        PutImageRequest {
+            format: self.format.clone(),
+            drawable: self.drawable,
+            gc: self.gc,
+            width: self.width,
+            height: self.height,
+            dst_x: self.dst_x,
+            dst_y: self.dst_y,
+            left_pad: self.left_pad,
+            depth: self.depth,
+            data: self.data.to_vec(),
+        }
+    }
+}
+
+impl<'a> Decode<'a> for PutImageRequestRef<'a> {
+    fn decode_from(reader: &mut SliceReader<'a>, order: &ByteOrder) -> Result<Self> {
+        let data = reader.read(order)?;
+        let length = match reader.read::<u16>(order)? {
+            0 => (reader.read::<u32>(order)? as usize).saturating_sub(1),
+            length => length as usize,
+        };
+        Self::decode_body(reader, data, length, order)
+    }
+}
+
+impl<'a> DecodeBody<'a> for PutImageRequestRef<'a> {
+    fn decode_body(reader: &mut SliceReader<'a>, data: u8, length: usize, order: &ByteOrder) -> Result<Self> {
+        let format = PutImageFormat::from_u8(data)?;
+        if length < 6 {
+            return Err(Error::ProtocolError(ErrorCode::Length, 0));
+        }
+        let drawable = reader.read(order)?;
+        let gc = reader.read(order)?;
+        let width = reader.read(order)?;
+        let height = reader.read(order)?;
+        let dst_x = reader.read(order)?;
+        let dst_y = reader.read(order)?;
+        let left_pad = reader.read(order)?;
+        let depth = reader.read(order)?;
+        reader.skip(2)?;
+        let data = reader.read_bytes((length - 6) << 2)?;
+        Ok(PutImageRequestRef {
+            format,
+            drawable,
+            gc,
+            width,
+            height,
+            dst_x,
+            dst_y,
+            left_pad,
+            depth,
+            data,
+        })
+    }
+}
+
+impl<'a> Encode for PutImageRequestRef<'a> {
+    fn encoded_length(&self) -> usize {
+        request_length(self.data.len()) << 2
+    }
+
+    fn encode_to(&self, writer: &mut SliceWriter, order: &ByteOrder) -> Result<()> {
+        let pad = (4 - (self.data.len() & 3)) & 3;
+        let length = request_length(self.data.len());
+        writer.write::<u8>(72, order)?;
+        writer.write(self.format.to_u8(), order)?;
+        if length > u16::MAX as usize {
+            writer.write::<u16>(0, order)?;
+            writer.write(length as u32, order)?;
+        } else {
+            writer.write(length as u16, order)?;
+        }
+        writer.write(self.drawable, order)?;
+        writer.write(self.gc, order)?;
+        writer.write(self.width, order)?;
+        writer.write(self.height, order)?;
+        writer.write(self.dst_x, order)?;
+        writer.write(self.dst_y, order)?;
+        writer.write(self.left_pad, order)?;
+        writer.write(self.depth, order)?;
+        writer.write_zero(2)?;
+        writer.write_bytes(self.data)?;
+        writer.write_zero(pad)
     }
 }
 
@@ -23,12 +256,12 @@ pub struct PutImageResponse;
 
 impl Readable for PutImageResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for PutImageResponse{
+impl Writable for PutImageResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
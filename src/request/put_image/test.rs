@@ -0,0 +1,118 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Decode, Encode, Readable, SliceReader, SliceWriter, Writable};
+    use crate::request::put_image::{PutImageFormat, PutImageRequest, PutImageRequestRef};
+
+    #[test]
+    fn read_test() {
+        let input = [2, 0, 8, 0, 0, 0, 1, 0, 0, 0, 2, 0, 2, 0, 1, 0xff, 0xfe, 0, 3, 0, 24, 0, 0, 1, 2, 3, 4, 5, 6, 0, 0];
+        let expected = PutImageRequest {
+            format: PutImageFormat::ZPixmap,
+            drawable: 1,
+            gc: 2,
+            width: 2,
+            height: 1,
+            dst_x: -2,
+            dst_y: 3,
+            left_pad: 0,
+            depth: 24,
+            data: [1, 2, 3, 4, 5, 6, 0, 0].to_vec(),
+        };
+        let value = PutImageRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let value = PutImageRequestRef::decode_from(&mut SliceReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value.data.as_ptr(), input[23..].as_ptr());
+        assert_eq!(value.to_request(), expected);
+        let input = [0, 7, 0, 1, 0, 0, 0, 2, 0, 0, 0, 8, 0, 1, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0x81, 0, 0, 0];
+        let expected = PutImageRequest {
+            format: PutImageFormat::Bitmap,
+            drawable: 1,
+            gc: 2,
+            width: 8,
+            height: 1,
+            dst_x: 0,
+            dst_y: 0,
+            left_pad: 0,
+            depth: 1,
+            data: [0x81, 0, 0, 0].to_vec(),
+        };
+        let value = PutImageRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let value = PutImageRequestRef::decode_from(&mut SliceReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value.to_request(), expected);
+    }
+
+    #[test]
+    fn write_test() {
+        let value = PutImageRequest {
+            format: PutImageFormat::ZPixmap,
+            drawable: 1,
+            gc: 2,
+            width: 2,
+            height: 1,
+            dst_x: -2,
+            dst_y: 3,
+            left_pad: 0,
+            depth: 24,
+            data: [1, 2, 3, 4, 5, 6].to_vec(),
+        };
+        let expected = [72, 2, 0, 8, 0, 0, 0, 1, 0, 0, 0, 2, 0, 2, 0, 1, 0xff, 0xfe, 0, 3, 0, 24, 0, 0, 1, 2, 3, 4, 5, 6, 0, 0];
+        let mut buffer = [0xff; 32];
+        PutImageRequest::write(&mut BufWriter::new(&mut buffer[..]), value.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+        let value = PutImageRequestRef {
+            format: value.format,
+            drawable: value.drawable,
+            gc: value.gc,
+            width: value.width,
+            height: value.height,
+            dst_x: value.dst_x,
+            dst_y: value.dst_y,
+            left_pad: value.left_pad,
+            depth: value.depth,
+            data: &value.data[..],
+        };
+        assert_eq!(value.encoded_length(), 32);
+        let mut buffer = [0xff; 32];
+        value.encode_to(&mut SliceWriter::new(&mut buffer[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+        let mut buffer = [1, 2].to_vec();
+        value.encode_to_vec(&mut buffer, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [1, 2, 72, 2, 8, 0, 1, 0, 0, 0, 2, 0, 0, 0, 2, 0, 1, 0, 0xfe, 0xff, 3, 0, 0, 24, 0, 0, 1, 2, 3, 4, 5, 6, 0, 0].to_vec());
+        assert!(value.encode_to(&mut SliceWriter::new(&mut [0; 31][..]), &ByteOrder::MSBFirst).is_err());
+    }
+
+    #[test]
+    fn big_request_test() {
+        //262140byteを超えるとu16のlengthに収まらない
+        let value = PutImageRequest {
+            format: PutImageFormat::ZPixmap,
+            drawable: 1,
+            gc: 2,
+            width: 256,
+            height: 256,
+            dst_x: 0,
+            dst_y: 0,
+            left_pad: 0,
+            depth: 32,
+            data: (0..0x40000).map(|i| i as u8).collect(),
+        };
+        let length = 6 + 0x10000 + 1;
+        let mut buffer = vec![0xff; length << 2];
+        PutImageRequest::write(&mut BufWriter::new(&mut buffer[..]), value.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer[..12], [72, 2, 0, 0, 0, 1, 0, 7, 0, 0, 0, 1]);
+        assert_eq!(buffer[28..], value.data[..]);
+        let read = PutImageRequest::read(&mut BufReader::new(&buffer[1..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(read, value);
+        let decoded = PutImageRequestRef::decode_from(&mut SliceReader::new(&buffer[1..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(decoded.to_request(), value);
+        assert_eq!(decoded.encoded_length(), length << 2);
+        let mut encoded = Vec::new();
+        decoded.encode_to_vec(&mut encoded, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(encoded, buffer);
+    }
+}
@@ -1,7 +1,9 @@
 use std::io::{Read, Write};
 
 use crate::{Error, Result};
-use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, Decode, read_specified_length, Readable, ReadableRead, SliceReader, Writable, WritableWrite};
+use crate::request::DecodeBody;
 
 mod test;
 
@@ -10,6 +12,7 @@ pub struct QueryExtensionRequest {
     pub name: String,
 }
 
+#[allow(clippy::redundant_closure)]
 impl Readable for QueryExtensionRequest {
     fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
         read_specified_length(stream, &mut [0; 1], 1)?;
@@ -23,6 +26,7 @@ impl Readable for QueryExtensionRequest {
     }
 }
 
+#[allow(clippy::needless_as_bytes, clippy::redundant_closure)]
 impl Writable for QueryExtensionRequest {
     fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
         stream.write_value::<u8>(98, order)?;
@@ -38,6 +42,37 @@ impl Writable for QueryExtensionRequest {
     }
 }
 
+/// QueryExtensionRequestのnameを入力のbufferから借用する版
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryExtensionRequestRef<'a> {
+    pub name: &'a str,
+}
+
+impl<'a> QueryExtensionRequestRef<'a> {
+    pub fn to_request(&self) -> QueryExtensionRequest {
+        QueryExtensionRequest { name: self.name.to_string() }
+    }
+}
+
+impl<'a> Decode<'a> for QueryExtensionRequestRef<'a> {
+    fn decode_from(reader: &mut SliceReader<'a>, order: &ByteOrder) -> Result<Self> {
+        let data = reader.read(order)?;
+        let length = reader.read::<u16>(order)? as usize;
+        Self::decode_body(reader, data, length, order)
+    }
+}
+
+impl<'a> DecodeBody<'a> for QueryExtensionRequestRef<'a> {
+    fn decode_body(reader: &mut SliceReader<'a>, _data: u8, length: usize, order: &ByteOrder) -> Result<Self> {
+        let total_length = length.checked_sub(2).ok_or(Error::ProtocolError(ErrorCode::Length, 0))? << 2;
+        let name_len = reader.read::<u16>(order)? as usize;
+        reader.skip(2)?;
+        let buffer = reader.read_bytes(total_length)?;
+        let name = buffer.get(..name_len).ok_or(Error::ProtocolError(ErrorCode::Length, 0))?;
+        Ok(QueryExtensionRequestRef { name: std::str::from_utf8(name).map_err(Error::StringError)? })
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct QueryExtensionResponse {
     pub sequence_number: u16,
@@ -67,6 +102,7 @@ impl Readable for QueryExtensionResponse {
     }
 }
 
+#[allow(clippy::redundant_closure)]
 impl Writable for QueryExtensionResponse {
     fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
         stream.write_value::<u8>(1, order)?;
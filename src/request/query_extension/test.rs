@@ -4,8 +4,8 @@
 mod request {
     use std::io::{BufReader, BufWriter};
 
-    use crate::read_util::{ByteOrder, Readable, Writable};
-    use crate::request::query_extension::QueryExtensionRequest;
+    use crate::read_util::{ByteOrder, Decode, Readable, SliceReader, Writable};
+    use crate::request::query_extension::{QueryExtensionRequest, QueryExtensionRequestRef};
 
     #[test]
     fn read_test() {
@@ -25,6 +25,15 @@ mod request {
         QueryExtensionRequest::write(&mut BufWriter::new(&mut buffer[..]), value, &ByteOrder::MSBFirst).unwrap();
         assert_eq!(buffer, [98, 0, 0, 4, 0, 5, 0, 0, b't', b'e', b's', b't', b'A', 0, 0, 0]);
     }
+
+    #[test]
+    fn ref_test() {
+        let input = [0, 0, 4, 0, 5, 0, 0, b't', b'e', b's', b't', b'A', 0, 0, 0];
+        let value = QueryExtensionRequestRef::decode_from(&mut SliceReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, QueryExtensionRequestRef { name: "testA" });
+        assert_eq!(value.name.as_ptr(), input[7..].as_ptr());
+        assert_eq!(value.to_request(), QueryExtensionRequest { name: String::from("testA") });
+    }
 }
 
 mod response {
@@ -1,34 +1,238 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct QueryFontRequest;
+pub struct QueryFontRequest {
+    /// FONTかGCONTEXT
+    pub font: u32,
+}
 
 impl Readable for QueryFontRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let font = stream.read_value(order)?;
+        Ok(QueryFontRequest { font })
+    }
+}
+
+impl Writable for QueryFontRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(47, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(2, order)?;
+        stream.write_value(data.font, order)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CharInfo {
+    pub left_side_bearing: i16,
+    pub right_side_bearing: i16,
+    pub character_width: i16,
+    pub ascent: i16,
+    pub descent: i16,
+    pub attributes: u16,
+}
+
+impl Readable for CharInfo {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        Ok(CharInfo {
+            left_side_bearing: stream.read_value(order)?,
+            right_side_bearing: stream.read_value(order)?,
+            character_width: stream.read_value(order)?,
+            ascent: stream.read_value(order)?,
+            descent: stream.read_value(order)?,
+            attributes: stream.read_value(order)?,
+        })
+    }
+}
+
+impl Writable for CharInfo {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value(data.left_side_bearing, order)?;
+        stream.write_value(data.right_side_bearing, order)?;
+        stream.write_value(data.character_width, order)?;
+        stream.write_value(data.ascent, order)?;
+        stream.write_value(data.descent, order)?;
+        stream.write_value(data.attributes, order)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FontProp {
+    pub name: u32,
+    /// 型はnameのatomで決まる
+    pub value: u32,
+}
+
+impl Readable for FontProp {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        Ok(FontProp {
+            name: stream.read_value(order)?,
+            value: stream.read_value(order)?,
+        })
+    }
+}
+
+impl Writable for FontProp {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value(data.name, order)?;
+        stream.write_value(data.value, order)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DrawDirection {
+    LeftToRight,
+    RightToLeft,
+}
+
+impl Readable for DrawDirection {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::LeftToRight),
+            1 => Ok(Self::RightToLeft),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
     }
 }
 
-impl Writable for QueryFontRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for DrawDirection {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::LeftToRight => 0,
+            Self::RightToLeft => 1,
+        };
+        stream.write_value(value, order)
     }
 }
 
+/// QueryFontとListFontsWithInfoの返事で共通の、fontの情報
 #[derive(Clone, Debug, PartialEq)]
-pub struct QueryFontResponse;
+pub struct FontInfo {
+    pub min_bounds: CharInfo,
+    pub max_bounds: CharInfo,
+    pub min_char_or_byte2: u16,
+    pub max_char_or_byte2: u16,
+    pub default_char: u16,
+    pub draw_direction: DrawDirection,
+    pub min_byte1: u8,
+    pub max_byte1: u8,
+    pub all_chars_exist: bool,
+    pub font_ascent: i16,
+    pub font_descent: i16,
+    pub properties: Vec<FontProp>,
+}
+
+/// min_boundsからfont_descentまでの48byteを読み、FONTPROPの数と一緒に返す
+/// propertiesは空なので、呼び出し側で読む
+pub(crate) fn read_font_info(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<(FontInfo, usize)> {
+    let min_bounds = stream.read_value(order)?;
+    read_specified_length(stream, &mut [0; 4], 4)?;
+    let max_bounds = stream.read_value(order)?;
+    read_specified_length(stream, &mut [0; 4], 4)?;
+    let min_char_or_byte2 = stream.read_value(order)?;
+    let max_char_or_byte2 = stream.read_value(order)?;
+    let default_char = stream.read_value(order)?;
+    let number_of_properties = stream.read_value::<u16>(order)? as usize;
+    let draw_direction = stream.read_value(order)?;
+    let min_byte1 = stream.read_value(order)?;
+    let max_byte1 = stream.read_value(order)?;
+    let all_chars_exist = stream.read_value(order)?;
+    let font_ascent = stream.read_value(order)?;
+    let font_descent = stream.read_value(order)?;
+    Ok((FontInfo {
+        min_bounds,
+        max_bounds,
+        min_char_or_byte2,
+        max_char_or_byte2,
+        default_char,
+        draw_direction,
+        min_byte1,
+        max_byte1,
+        all_chars_exist,
+        font_ascent,
+        font_descent,
+        properties: Vec::new(),
+    }, number_of_properties))
+}
+
+/// read_font_infoと同じ48byteを書く
+pub(crate) fn write_font_info(stream: &mut std::io::BufWriter<impl Write>, info: &FontInfo, order: &ByteOrder) -> Result<()> {
+    stream.write_value(info.min_bounds, order)?;
+    stream.write_all(&[0; 4]).map_err(Error::IoError)?;
+    stream.write_value(info.max_bounds, order)?;
+    stream.write_all(&[0; 4]).map_err(Error::IoError)?;
+    stream.write_value(info.min_char_or_byte2, order)?;
+    stream.write_value(info.max_char_or_byte2, order)?;
+    stream.write_value(info.default_char, order)?;
+    stream.write_value(info.properties.len() as u16, order)?;
+    stream.write_value(info.draw_direction.clone(), order)?;
+    stream.write_value(info.min_byte1, order)?;
+    stream.write_value(info.max_byte1, order)?;
+    stream.write_value(info.all_chars_exist, order)?;
+    stream.write_value(info.font_ascent, order)?;
+    stream.write_value(info.font_descent, order)?;
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryFontResponse {
+    pub sequence_number: u16,
+    pub info: FontInfo,
+    /// min_byte1からmax_byte1、min_char_or_byte2からmax_char_or_byte2の順
+    /// 全ての文字が同じ大きさなら空でもよい
+    pub char_infos: Vec<CharInfo>,
+}
 
 impl Readable for QueryFontResponse {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 2], 2)?;
+        let sequence_number = stream.read_value(order)?;
+        let length = stream.read_value::<u32>(order)? as usize;
+        let (mut info, number_of_properties) = read_font_info(stream, order)?;
+        let number_of_char_infos = stream.read_value::<u32>(order)? as usize;
+        if length != 7 + 2 * number_of_properties + 3 * number_of_char_infos {
+            return Err(Error::InvalidValue("QueryFontResponse"));
+        }
+        for _ in 0..number_of_properties {
+            info.properties.push(stream.read_value(order)?);
+        }
+        let mut char_infos = Vec::with_capacity(number_of_char_infos);
+        for _ in 0..number_of_char_infos {
+            char_infos.push(stream.read_value(order)?);
+        }
+        Ok(QueryFontResponse {
+            sequence_number,
+            info,
+            char_infos,
+        })
     }
 }
 
-impl Writable for QueryFontResponse{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for QueryFontResponse {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(1, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value((7 + 2 * data.info.properties.len() + 3 * data.char_infos.len()) as u32, order)?;
+        write_font_info(stream, &data.info, order)?;
+        stream.write_value(data.char_infos.len() as u32, order)?;
+        for property in data.info.properties {
+            stream.write_value(property, order)?;
+        }
+        for char_info in data.char_infos {
+            stream.write_value(char_info, order)?;
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,156 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::query_font::QueryFontRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 2, 0, 0x20, 0, 1];
+        let value = QueryFontRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, QueryFontRequest { font: 0x00200001 });
+        let input = [0, 2, 0, 1, 0, 0x20, 0];
+        let value = QueryFontRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, QueryFontRequest { font: 0x00200001 });
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0xff; 8];
+        QueryFontRequest::write(&mut BufWriter::new(&mut buffer[..]), QueryFontRequest { font: 0x00200001 }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [47, 0, 0, 2, 0, 0x20, 0, 1]);
+        let mut buffer = [0xff; 8];
+        QueryFontRequest::write(&mut BufWriter::new(&mut buffer[..]), QueryFontRequest { font: 0x00200001 }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [47, 0, 2, 0, 1, 0, 0x20, 0]);
+    }
+}
+
+mod response {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::query_font::{CharInfo, DrawDirection, FontInfo, FontProp, QueryFontResponse};
+
+    #[test]
+    fn read_test() {
+        fn expected() -> QueryFontResponse {
+            let narrow = CharInfo { left_side_bearing: 0, right_side_bearing: 6, character_width: 6, ascent: 10, descent: 3, attributes: 0 };
+            let wide = CharInfo { left_side_bearing: -1, right_side_bearing: 7, character_width: 6, ascent: 11, descent: 3, attributes: 0 };
+            QueryFontResponse {
+                sequence_number: 7,
+                info: FontInfo {
+                    min_bounds: narrow,
+                    max_bounds: wide,
+                    min_char_or_byte2: 0x20,
+                    max_char_or_byte2: 0x21,
+                    default_char: 0x20,
+                    draw_direction: DrawDirection::LeftToRight,
+                    min_byte1: 0,
+                    max_byte1: 0,
+                    all_chars_exist: true,
+                    font_ascent: 11,
+                    font_descent: 3,
+                    properties: vec![FontProp { name: 300, value: 10 }],
+                },
+                char_infos: vec![narrow, wide],
+            }
+        }
+
+        /// 長さは7 + 2 * 1 + 3 * 2 = 15
+        fn msb() -> Vec<u8> {
+            [
+                &[1, 0, 0, 7, 0, 0, 0, 15][..],
+                &[0, 0, 0, 6, 0, 6, 0, 10, 0, 3, 0, 0, 0, 0, 0, 0],
+                &[0xff, 0xff, 0, 7, 0, 6, 0, 11, 0, 3, 0, 0, 0, 0, 0, 0],
+                &[0, 0x20, 0, 0x21, 0, 0x20, 0, 1, 0, 0, 0, 1, 0, 11, 0, 3],
+                &[0, 0, 0, 2],
+                &[0, 0, 1, 0x2c, 0, 0, 0, 10],
+                &[0, 0, 0, 6, 0, 6, 0, 10, 0, 3, 0, 0],
+                &[0xff, 0xff, 0, 7, 0, 6, 0, 11, 0, 3, 0, 0],
+            ].concat()
+        }
+
+        fn lsb() -> Vec<u8> {
+            [
+                &[1, 0, 7, 0, 15, 0, 0, 0][..],
+                &[0, 0, 6, 0, 6, 0, 10, 0, 3, 0, 0, 0, 0, 0, 0, 0],
+                &[0xff, 0xff, 7, 0, 6, 0, 11, 0, 3, 0, 0, 0, 0, 0, 0, 0],
+                &[0x20, 0, 0x21, 0, 0x20, 0, 1, 0, 0, 0, 0, 1, 11, 0, 3, 0],
+                &[2, 0, 0, 0],
+                &[0x2c, 1, 0, 0, 10, 0, 0, 0],
+                &[0, 0, 6, 0, 6, 0, 10, 0, 3, 0, 0, 0],
+                &[0xff, 0xff, 7, 0, 6, 0, 11, 0, 3, 0, 0, 0],
+            ].concat()
+        }
+
+        let input = msb();
+        let value = QueryFontResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected());
+        let input = lsb();
+        let value = QueryFontResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected());
+    }
+
+    #[test]
+    fn write_test() {
+        fn expected() -> QueryFontResponse {
+            let narrow = CharInfo { left_side_bearing: 0, right_side_bearing: 6, character_width: 6, ascent: 10, descent: 3, attributes: 0 };
+            let wide = CharInfo { left_side_bearing: -1, right_side_bearing: 7, character_width: 6, ascent: 11, descent: 3, attributes: 0 };
+            QueryFontResponse {
+                sequence_number: 7,
+                info: FontInfo {
+                    min_bounds: narrow,
+                    max_bounds: wide,
+                    min_char_or_byte2: 0x20,
+                    max_char_or_byte2: 0x21,
+                    default_char: 0x20,
+                    draw_direction: DrawDirection::LeftToRight,
+                    min_byte1: 0,
+                    max_byte1: 0,
+                    all_chars_exist: true,
+                    font_ascent: 11,
+                    font_descent: 3,
+                    properties: vec![FontProp { name: 300, value: 10 }],
+                },
+                char_infos: vec![narrow, wide],
+            }
+        }
+
+        /// 長さは7 + 2 * 1 + 3 * 2 = 15
+        fn msb() -> Vec<u8> {
+            [
+                &[1, 0, 0, 7, 0, 0, 0, 15][..],
+                &[0, 0, 0, 6, 0, 6, 0, 10, 0, 3, 0, 0, 0, 0, 0, 0],
+                &[0xff, 0xff, 0, 7, 0, 6, 0, 11, 0, 3, 0, 0, 0, 0, 0, 0],
+                &[0, 0x20, 0, 0x21, 0, 0x20, 0, 1, 0, 0, 0, 1, 0, 11, 0, 3],
+                &[0, 0, 0, 2],
+                &[0, 0, 1, 0x2c, 0, 0, 0, 10],
+                &[0, 0, 0, 6, 0, 6, 0, 10, 0, 3, 0, 0],
+                &[0xff, 0xff, 0, 7, 0, 6, 0, 11, 0, 3, 0, 0],
+            ].concat()
+        }
+
+        fn lsb() -> Vec<u8> {
+            [
+                &[1, 0, 7, 0, 15, 0, 0, 0][..],
+                &[0, 0, 6, 0, 6, 0, 10, 0, 3, 0, 0, 0, 0, 0, 0, 0],
+                &[0xff, 0xff, 7, 0, 6, 0, 11, 0, 3, 0, 0, 0, 0, 0, 0, 0],
+                &[0x20, 0, 0x21, 0, 0x20, 0, 1, 0, 0, 0, 0, 1, 11, 0, 3, 0],
+                &[2, 0, 0, 0],
+                &[0x2c, 1, 0, 0, 10, 0, 0, 0],
+                &[0, 0, 6, 0, 6, 0, 10, 0, 3, 0, 0, 0],
+                &[0xff, 0xff, 7, 0, 6, 0, 11, 0, 3, 0, 0, 0],
+            ].concat()
+        }
+
+        let mut buffer = [0xff; 92];
+        QueryFontResponse::write(&mut BufWriter::new(&mut buffer[..]), expected(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer[..], msb()[..]);
+        let mut buffer = [0xff; 92];
+        QueryFontResponse::write(&mut BufWriter::new(&mut buffer[..]), expected(), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer[..], lsb()[..]);
+    }
+}
@@ -1,34 +1,55 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct QueryKeymapRequest;
 
 impl Readable for QueryKeymapRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        Ok(Self)
     }
 }
 
-impl Writable for QueryKeymapRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for QueryKeymapRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, _data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(44, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(1, order)?;
+        Ok(())
     }
 }
 
+/// keysはkeycodeごとに1bitで、押されていれば1
 #[derive(Clone, Debug, PartialEq)]
-pub struct QueryKeymapResponse;
+pub struct QueryKeymapResponse {
+    pub sequence_number: u16,
+    pub keys: [u8; 32],
+}
 
 impl Readable for QueryKeymapResponse {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 2], 2)?;
+        let sequence_number = stream.read_value(order)?;
+        let _ = stream.read_value::<u32>(order)?;
+        let mut keys = [0; 32];
+        read_specified_length(stream, &mut keys, 32)?;
+        Ok(QueryKeymapResponse { sequence_number, keys })
     }
 }
 
-impl Writable for QueryKeymapResponse{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for QueryKeymapResponse {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(1, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value::<u32>(2, order)?;
+        stream.write_all(&data.keys).map_err(Error::IoError)?;
+        Ok(())
     }
 }
@@ -0,0 +1,67 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::query_keymap::QueryKeymapRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 1];
+        let value = QueryKeymapRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, QueryKeymapRequest);
+        let input = [0, 1, 0];
+        let value = QueryKeymapRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, QueryKeymapRequest);
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0xff; 4];
+        QueryKeymapRequest::write(&mut BufWriter::new(&mut buffer[..]), QueryKeymapRequest, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [44, 0, 0, 1]);
+        let mut buffer = [0xff; 4];
+        QueryKeymapRequest::write(&mut BufWriter::new(&mut buffer[..]), QueryKeymapRequest, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [44, 0, 1, 0]);
+    }
+}
+
+mod response {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::query_keymap::QueryKeymapResponse;
+
+    #[test]
+    fn read_test() {
+        let mut keys = [0; 32];
+        keys[4] = 0x40;
+        keys[31] = 0x80;
+        let mut input = [0; 40];
+        input[..8].copy_from_slice(&[1, 0, 0, 2, 0, 0, 0, 2]);
+        input[8..].copy_from_slice(&keys);
+        let value = QueryKeymapResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, QueryKeymapResponse { sequence_number: 2, keys });
+        input[..8].copy_from_slice(&[1, 0, 2, 0, 2, 0, 0, 0]);
+        let value = QueryKeymapResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, QueryKeymapResponse { sequence_number: 2, keys });
+    }
+
+    #[test]
+    fn write_test() {
+        let mut keys = [0; 32];
+        keys[4] = 0x40;
+        let mut expected = [0; 40];
+        expected[..8].copy_from_slice(&[1, 0, 0, 2, 0, 0, 0, 2]);
+        expected[8..].copy_from_slice(&keys);
+        let mut buffer = [0xff; 40];
+        QueryKeymapResponse::write(&mut BufWriter::new(&mut buffer[..]), QueryKeymapResponse { sequence_number: 2, keys }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+        expected[..8].copy_from_slice(&[1, 0, 2, 0, 2, 0, 0, 0]);
+        let mut buffer = [0xff; 40];
+        QueryKeymapResponse::write(&mut BufWriter::new(&mut buffer[..]), QueryKeymapResponse { sequence_number: 2, keys }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+    }
+}
@@ -1,34 +1,95 @@
+use std::collections::HashSet;
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::event::KeyButMask;
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct QueryPointerRequest;
+pub struct QueryPointerRequest {
+    pub window: u32,
+}
 
 impl Readable for QueryPointerRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let window = stream.read_value(order)?;
+        Ok(QueryPointerRequest { window })
     }
 }
 
-impl Writable for QueryPointerRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for QueryPointerRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(38, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(2, order)?;
+        stream.write_value(data.window, order)?;
+        Ok(())
     }
 }
 
+/// same_screenがfalseならchildはNoneで、win_xとwin_yは0
 #[derive(Clone, Debug, PartialEq)]
-pub struct QueryPointerResponse;
+pub struct QueryPointerResponse {
+    pub same_screen: bool,
+    pub sequence_number: u16,
+    pub root: u32,
+    pub child: Option<u32>,
+    pub root_x: i16,
+    pub root_y: i16,
+    pub win_x: i16,
+    pub win_y: i16,
+    pub mask: HashSet<KeyButMask>,
+}
 
 impl Readable for QueryPointerResponse {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let same_screen = stream.read_value(order)?;
+        let sequence_number = stream.read_value(order)?;
+        let _ = stream.read_value::<u32>(order)?;
+        let root = stream.read_value(order)?;
+        let child = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let root_x = stream.read_value(order)?;
+        let root_y = stream.read_value(order)?;
+        let win_x = stream.read_value(order)?;
+        let win_y = stream.read_value(order)?;
+        let mask = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 6], 6)?;
+        Ok(QueryPointerResponse {
+            same_screen,
+            sequence_number,
+            root,
+            child,
+            root_x,
+            root_y,
+            win_x,
+            win_y,
+            mask,
+        })
     }
 }
 
-impl Writable for QueryPointerResponse{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for QueryPointerResponse {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(1, order)?;
+        stream.write_value(data.same_screen, order)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value::<u32>(0, order)?;
+        stream.write_value(data.root, order)?;
+        stream.write_value(data.child.unwrap_or(0), order)?;
+        stream.write_value(data.root_x, order)?;
+        stream.write_value(data.root_y, order)?;
+        stream.write_value(data.win_x, order)?;
+        stream.write_value(data.win_y, order)?;
+        stream.write_value(data.mask, order)?;
+        stream.write_all(&[0; 6]).map_err(Error::IoError)?;
+        Ok(())
     }
 }
@@ -0,0 +1,87 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::query_pointer::QueryPointerRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 2, 0, 0x20, 0, 1];
+        let value = QueryPointerRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, QueryPointerRequest { window: 0x00200001 });
+        let input = [0, 2, 0, 1, 0, 0x20, 0];
+        let value = QueryPointerRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, QueryPointerRequest { window: 0x00200001 });
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0; 8];
+        QueryPointerRequest::write(&mut BufWriter::new(&mut buffer[..]), QueryPointerRequest { window: 0x00200001 }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [38, 0, 0, 2, 0, 0x20, 0, 1]);
+        let mut buffer = [0; 8];
+        QueryPointerRequest::write(&mut BufWriter::new(&mut buffer[..]), QueryPointerRequest { window: 0x00200001 }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [38, 0, 2, 0, 1, 0, 0x20, 0]);
+    }
+}
+
+mod response {
+    use std::collections::HashSet;
+    use std::io::{BufReader, BufWriter};
+    use std::iter::FromIterator;
+
+    use crate::event::KeyButMask;
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::query_pointer::QueryPointerResponse;
+
+    #[test]
+    fn read_test() {
+        let expected = QueryPointerResponse {
+            same_screen: true,
+            sequence_number: 2,
+            root: 1,
+            child: Some(0x00200001),
+            root_x: 100,
+            root_y: -2,
+            win_x: 90,
+            win_y: 300,
+            mask: HashSet::from_iter([KeyButMask::Shift, KeyButMask::Button1].to_vec()),
+        };
+        let mut input = [0; 32];
+        input[..26].copy_from_slice(&[1, 1, 0, 2, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0x20, 0, 1, 0, 100, 0xff, 0xfe, 0, 90, 1, 0x2c, 1, 1]);
+        let value = QueryPointerResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let mut input = [0; 32];
+        input[..26].copy_from_slice(&[1, 1, 2, 0, 0, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0x20, 0, 100, 0, 0xfe, 0xff, 90, 0, 0x2c, 1, 1, 1]);
+        let value = QueryPointerResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn write_test() {
+        let data = QueryPointerResponse {
+            same_screen: false,
+            sequence_number: 2,
+            root: 1,
+            child: None,
+            root_x: 100,
+            root_y: 200,
+            win_x: 0,
+            win_y: 0,
+            mask: HashSet::from_iter([KeyButMask::Control].to_vec()),
+        };
+        let mut expected = [0; 32];
+        expected[..26].copy_from_slice(&[1, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 100, 0, 200, 0, 0, 0, 0, 0, 4]);
+        let mut buffer = [0xff; 32];
+        QueryPointerResponse::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+        let mut expected = [0; 32];
+        expected[..26].copy_from_slice(&[1, 0, 2, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 100, 0, 200, 0, 0, 0, 0, 0, 4, 0]);
+        let mut buffer = [0xff; 32];
+        QueryPointerResponse::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+    }
+}
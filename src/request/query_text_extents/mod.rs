@@ -1,34 +1,114 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+use crate::request::query_font::DrawDirection;
+use crate::request::ReadableBody;
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct QueryTextExtentsRequest;
+pub struct QueryTextExtentsRequest {
+    /// FONTかGCONTEXT
+    pub font: u32,
+    /// CHAR2Bの列で、byte1が上位
+    /// byte orderによらずbyte1、byte2の順に送る
+    pub string: Vec<u16>,
+}
 
 impl Readable for QueryTextExtentsRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let data = stream.read_value(order)?;
+        let length = stream.read_value::<u16>(order)?;
+        Self::read_body(stream, data, length as usize, order)
+    }
+}
+
+impl ReadableBody for QueryTextExtentsRequest {
+    fn read_body(stream: &mut std::io::BufReader<impl Read>, data: u8, length: usize, order: &ByteOrder) -> Result<Self> {
+        let odd_length = data != 0;
+        let font = stream.read_value(order)?;
+        let total_length = length.checked_sub(2).ok_or(Error::ProtocolError(ErrorCode::Length, 0))? << 2;
+        let mut buffer = vec![0; total_length];
+        read_specified_length(stream, &mut buffer[..], total_length)?;
+        //奇数個なら最後の2byteはpadding
+        let string_len = (total_length >> 1).checked_sub(odd_length as usize).ok_or(Error::ProtocolError(ErrorCode::Length, 0))?;
+        let string = buffer.chunks(2).take(string_len).map(|c| u16::from_be_bytes([c[0], c[1]])).collect();
+        Ok(QueryTextExtentsRequest { font, string })
     }
 }
 
-impl Writable for QueryTextExtentsRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for QueryTextExtentsRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let string_len = data.string.len() << 1;
+        stream.write_value::<u8>(48, order)?;
+        stream.write_value(data.string.len() % 2 == 1, order)?;
+        stream.write_value((2 + ((string_len + 3) >> 2)) as u16, order)?;
+        stream.write_value(data.font, order)?;
+        for c in data.string {
+            stream.write_all(&c.to_be_bytes()).map_err(Error::IoError)?;
+        }
+        stream.write_all(&[0; 4][..(!string_len).wrapping_add(1) & 3]).map_err(Error::IoError)?;
+        Ok(())
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct QueryTextExtentsResponse;
+pub struct QueryTextExtentsResponse {
+    pub draw_direction: DrawDirection,
+    pub sequence_number: u16,
+    pub font_ascent: i16,
+    pub font_descent: i16,
+    pub overall_ascent: i16,
+    pub overall_descent: i16,
+    pub overall_width: i32,
+    pub overall_left: i32,
+    pub overall_right: i32,
+}
 
 impl Readable for QueryTextExtentsResponse {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let draw_direction = stream.read_value(order)?;
+        let sequence_number = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 4], 4)?;
+        let font_ascent = stream.read_value(order)?;
+        let font_descent = stream.read_value(order)?;
+        let overall_ascent = stream.read_value(order)?;
+        let overall_descent = stream.read_value(order)?;
+        let overall_width = stream.read_value(order)?;
+        let overall_left = stream.read_value(order)?;
+        let overall_right = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 4], 4)?;
+        Ok(QueryTextExtentsResponse {
+            draw_direction,
+            sequence_number,
+            font_ascent,
+            font_descent,
+            overall_ascent,
+            overall_descent,
+            overall_width,
+            overall_left,
+            overall_right,
+        })
     }
 }
 
-impl Writable for QueryTextExtentsResponse{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for QueryTextExtentsResponse {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(1, order)?;
+        stream.write_value(data.draw_direction, order)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_all(&[0; 4]).map_err(Error::IoError)?;
+        stream.write_value(data.font_ascent, order)?;
+        stream.write_value(data.font_descent, order)?;
+        stream.write_value(data.overall_ascent, order)?;
+        stream.write_value(data.overall_descent, order)?;
+        stream.write_value(data.overall_width, order)?;
+        stream.write_value(data.overall_left, order)?;
+        stream.write_value(data.overall_right, order)?;
+        stream.write_all(&[0; 4]).map_err(Error::IoError)?;
+        Ok(())
     }
 }
@@ -0,0 +1,97 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::Error;
+    use crate::error::ErrorCode;
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::query_text_extents::QueryTextExtentsRequest;
+
+    #[test]
+    fn read_test() {
+        //奇数個なら最後の2byteはpadding
+        let expected = QueryTextExtentsRequest { font: 0x00200001, string: vec![0x0041, 0x0142, 0x0043] };
+        let input = [1, 0, 4, 0, 0x20, 0, 1, 0, 0x41, 1, 0x42, 0, 0x43, 0, 0];
+        let value = QueryTextExtentsRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [1, 4, 0, 1, 0, 0x20, 0, 0, 0x41, 1, 0x42, 0, 0x43, 0, 0];
+        let value = QueryTextExtentsRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let expected = QueryTextExtentsRequest { font: 0x00200001, string: vec![0x0041, 0x0142] };
+        let input = [0, 0, 3, 0, 0x20, 0, 1, 0, 0x41, 1, 0x42];
+        let value = QueryTextExtentsRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        //空の文字列で奇数個、lengthが足りないものはBadLength
+        let input = [1, 0, 2, 0, 0x20, 0, 1];
+        assert!(matches!(QueryTextExtentsRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst), Err(Error::ProtocolError(ErrorCode::Length, 0))));
+        let input = [0, 0, 1, 0, 0x20, 0, 1];
+        assert!(matches!(QueryTextExtentsRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst), Err(Error::ProtocolError(ErrorCode::Length, 0))));
+    }
+
+    #[test]
+    fn write_test() {
+        let data = QueryTextExtentsRequest { font: 0x00200001, string: vec![0x0041, 0x0142, 0x0043] };
+        let mut buffer = [0xff; 16];
+        QueryTextExtentsRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [48, 1, 0, 4, 0, 0x20, 0, 1, 0, 0x41, 1, 0x42, 0, 0x43, 0, 0]);
+        let mut buffer = [0xff; 16];
+        QueryTextExtentsRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [48, 1, 4, 0, 1, 0, 0x20, 0, 0, 0x41, 1, 0x42, 0, 0x43, 0, 0]);
+        let data = QueryTextExtentsRequest { font: 0x00200001, string: vec![0x0041, 0x0142] };
+        let mut buffer = [0xff; 12];
+        QueryTextExtentsRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [48, 0, 0, 3, 0, 0x20, 0, 1, 0, 0x41, 1, 0x42]);
+    }
+}
+
+mod response {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::query_font::DrawDirection;
+    use crate::request::query_text_extents::QueryTextExtentsResponse;
+
+    #[test]
+    fn read_test() {
+        let expected = QueryTextExtentsResponse {
+            draw_direction: DrawDirection::RightToLeft,
+            sequence_number: 3,
+            font_ascent: 11,
+            font_descent: 3,
+            overall_ascent: 10,
+            overall_descent: 2,
+            overall_width: 18,
+            overall_left: -1,
+            overall_right: 19,
+        };
+        let input = [1, 1, 0, 3, 0, 0, 0, 0, 0, 11, 0, 3, 0, 10, 0, 2, 0, 0, 0, 18, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 19, 0, 0, 0, 0];
+        let value = QueryTextExtentsResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [1, 1, 3, 0, 0, 0, 0, 0, 11, 0, 3, 0, 10, 0, 2, 0, 18, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 19, 0, 0, 0, 0, 0, 0, 0];
+        let value = QueryTextExtentsResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn write_test() {
+        let data = QueryTextExtentsResponse {
+            draw_direction: DrawDirection::LeftToRight,
+            sequence_number: 3,
+            font_ascent: 11,
+            font_descent: 3,
+            overall_ascent: 10,
+            overall_descent: 2,
+            overall_width: 18,
+            overall_left: 0,
+            overall_right: 18,
+        };
+        let mut buffer = [0xff; 32];
+        QueryTextExtentsResponse::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [1, 0, 0, 3, 0, 0, 0, 0, 0, 11, 0, 3, 0, 10, 0, 2, 0, 0, 0, 18, 0, 0, 0, 0, 0, 0, 0, 18, 0, 0, 0, 0]);
+        let mut buffer = [0xff; 32];
+        QueryTextExtentsResponse::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [1, 0, 3, 0, 0, 0, 0, 0, 11, 0, 3, 0, 10, 0, 2, 0, 18, 0, 0, 0, 0, 0, 0, 0, 18, 0, 0, 0, 0, 0, 0, 0]);
+    }
+}
@@ -1,34 +1,84 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct QueryTreeRequest;
+pub struct QueryTreeRequest {
+    pub window: u32,
+}
 
 impl Readable for QueryTreeRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let window = stream.read_value(order)?;
+        Ok(QueryTreeRequest { window })
     }
 }
 
-impl Writable for QueryTreeRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for QueryTreeRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(15, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(2, order)?;
+        stream.write_value(data.window, order)?;
+        Ok(())
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct QueryTreeResponse;
+pub struct QueryTreeResponse {
+    pub sequence_number: u16,
+    pub root: u32,
+    pub parent: Option<u32>,
+    pub children: Vec<u32>,
+}
 
 impl Readable for QueryTreeResponse {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 2], 2)?;
+        let sequence_number = stream.read_value(order)?;
+        let length = stream.read_value::<u32>(order)? as usize;
+        let root = stream.read_value(order)?;
+        let parent = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let n = stream.read_value::<u16>(order)? as usize;
+        if n != length {
+            return Err(Error::InvalidValue("QueryTreeResponse length"));
+        }
+        read_specified_length(stream, &mut [0; 14], 14)?;
+        let mut children = Vec::with_capacity(n);
+        for _ in 0..n {
+            children.push(stream.read_value(order)?);
+        }
+        Ok(QueryTreeResponse {
+            sequence_number,
+            root,
+            parent,
+            children,
+        })
     }
 }
 
-impl Writable for QueryTreeResponse{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for QueryTreeResponse {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        assert!(data.children.len() <= u16::MAX as usize);
+        stream.write_value::<u8>(1, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value(data.children.len() as u32, order)?;
+        stream.write_value(data.root, order)?;
+        stream.write_value(data.parent.unwrap_or(0), order)?;
+        stream.write_value(data.children.len() as u16, order)?;
+        stream.write_all(&[0; 14]).map_err(Error::IoError)?;
+        for child in data.children {
+            stream.write_value(child, order)?;
+        }
+        Ok(())
     }
 }
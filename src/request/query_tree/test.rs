@@ -0,0 +1,82 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::query_tree::QueryTreeRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 2, 0, 0, 0, 1];
+        let value = QueryTreeRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, QueryTreeRequest { window: 1 });
+        let input = [0, 2, 0, 1, 0, 0, 0];
+        let value = QueryTreeRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, QueryTreeRequest { window: 1 });
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0; 8];
+        QueryTreeRequest::write(&mut BufWriter::new(&mut buffer[..]), QueryTreeRequest { window: 1 }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [15, 0, 0, 2, 0, 0, 0, 1]);
+        let mut buffer = [0; 8];
+        QueryTreeRequest::write(&mut BufWriter::new(&mut buffer[..]), QueryTreeRequest { window: 1 }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [15, 0, 2, 0, 1, 0, 0, 0]);
+    }
+}
+
+mod response {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::query_tree::QueryTreeResponse;
+
+    #[test]
+    fn read_test() {
+        const MSB: [u8; 40] = [1, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 4, 0, 0, 0, 5];
+        const LSB: [u8; 40] = [1, 0, 2, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            4, 0, 0, 0, 5, 0, 0, 0];
+
+        fn expected() -> QueryTreeResponse {
+            QueryTreeResponse {
+                sequence_number: 2,
+                root: 3,
+                parent: None,
+                children: vec![4, 5],
+            }
+        }
+
+        let value = QueryTreeResponse::read(&mut BufReader::new(&MSB[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected());
+        let value = QueryTreeResponse::read(&mut BufReader::new(&LSB[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected());
+    }
+
+    #[test]
+    fn write_test() {
+        const MSB: [u8; 40] = [1, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 4, 0, 0, 0, 5];
+        const LSB: [u8; 40] = [1, 0, 2, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            4, 0, 0, 0, 5, 0, 0, 0];
+
+        fn expected() -> QueryTreeResponse {
+            QueryTreeResponse {
+                sequence_number: 2,
+                root: 3,
+                parent: None,
+                children: vec![4, 5],
+            }
+        }
+
+        let mut buffer = [0; 40];
+        QueryTreeResponse::write(&mut BufWriter::new(&mut buffer[..]), expected(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(&buffer[..], &MSB[..]);
+        let mut buffer = [0; 40];
+        QueryTreeResponse::write(&mut BufWriter::new(&mut buffer[..]), expected(), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(&buffer[..], &LSB[..]);
+    }
+}
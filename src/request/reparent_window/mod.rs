@@ -1,20 +1,45 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct ReparentWindowRequest;
+pub struct ReparentWindowRequest {
+    pub window: u32,
+    pub parent: u32,
+    pub x: i16,
+    pub y: i16,
+}
 
 impl Readable for ReparentWindowRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let window = stream.read_value(order)?;
+        let parent = stream.read_value(order)?;
+        let x = stream.read_value(order)?;
+        let y = stream.read_value(order)?;
+        Ok(ReparentWindowRequest {
+            window,
+            parent,
+            x,
+            y,
+        })
     }
 }
 
-impl Writable for ReparentWindowRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for ReparentWindowRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(7, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(4, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_value(data.parent, order)?;
+        stream.write_value(data.x, order)?;
+        stream.write_value(data.y, order)?;
+        Ok(())
     }
 }
 
@@ -23,12 +48,12 @@ pub struct ReparentWindowResponse;
 
 impl Readable for ReparentWindowResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for ReparentWindowResponse{
+impl Writable for ReparentWindowResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
@@ -0,0 +1,30 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::reparent_window::ReparentWindowRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 4, 0, 0, 0, 1, 0, 0, 0, 2, 0, 3, 0xff, 0xfc];
+        let value = ReparentWindowRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, ReparentWindowRequest { window: 1, parent: 2, x: 3, y: -4 });
+        let input = [0, 4, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0xfc, 0xff];
+        let value = ReparentWindowRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, ReparentWindowRequest { window: 1, parent: 2, x: 3, y: -4 });
+    }
+
+    #[test]
+    fn write_test() {
+        let value = ReparentWindowRequest { window: 1, parent: 2, x: 3, y: -4 };
+        let mut buffer = [0; 16];
+        ReparentWindowRequest::write(&mut BufWriter::new(&mut buffer[..]), value.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [7, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0, 2, 0, 3, 0xff, 0xfc]);
+        let mut buffer = [0; 16];
+        ReparentWindowRequest::write(&mut BufWriter::new(&mut buffer[..]), value, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [7, 0, 4, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0xfc, 0xff]);
+    }
+}
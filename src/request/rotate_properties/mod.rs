@@ -1,20 +1,52 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct RotatePropertiesRequest;
+pub struct RotatePropertiesRequest {
+    pub window: u32,
+    pub delta: i16,
+    pub properties: Vec<u32>,
+}
 
 impl Readable for RotatePropertiesRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let length = stream.read_value::<u16>(order)? as usize;
+        let window = stream.read_value(order)?;
+        let number_of_properties = stream.read_value::<u16>(order)? as usize;
+        let delta = stream.read_value(order)?;
+        if length != 3 + number_of_properties {
+            return Err(Error::ProtocolError(ErrorCode::Length, 0));
+        }
+        let mut properties = Vec::with_capacity(number_of_properties);
+        for _ in 0..number_of_properties {
+            properties.push(stream.read_value(order)?);
+        }
+        Ok(RotatePropertiesRequest {
+            window,
+            delta,
+            properties,
+        })
     }
 }
 
-impl Writable for RotatePropertiesRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for RotatePropertiesRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(114, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value((3 + data.properties.len()) as u16, order)?;
+        stream.write_value(data.window, order)?;
+        stream.write_value(data.properties.len() as u16, order)?;
+        stream.write_value(data.delta, order)?;
+        for property in data.properties {
+            stream.write_value(property, order)?;
+        }
+        Ok(())
     }
 }
 
@@ -23,12 +55,12 @@ pub struct RotatePropertiesResponse;
 
 impl Readable for RotatePropertiesResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for RotatePropertiesResponse{
+impl Writable for RotatePropertiesResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
@@ -0,0 +1,38 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::Error;
+    use crate::error::ErrorCode;
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::rotate_properties::RotatePropertiesRequest;
+
+    #[test]
+    fn read_test() {
+        let expected = RotatePropertiesRequest { window: 1, delta: -1, properties: vec![9, 10] };
+        let input = [0, 0, 5, 0, 0, 0, 1, 0, 2, 0xff, 0xff, 0, 0, 0, 9, 0, 0, 0, 10];
+        let value = RotatePropertiesRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [0, 5, 0, 1, 0, 0, 0, 2, 0, 0xff, 0xff, 9, 0, 0, 0, 10, 0, 0, 0];
+        let value = RotatePropertiesRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [0, 0, 4, 0, 0, 0, 1, 0, 2, 0xff, 0xff, 0, 0, 0, 9, 0, 0, 0, 10];
+        match RotatePropertiesRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst) {
+            Err(Error::ProtocolError(ErrorCode::Length, _)) => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn write_test() {
+        let data = RotatePropertiesRequest { window: 1, delta: -1, properties: vec![9, 10] };
+        let mut buffer = [0; 20];
+        RotatePropertiesRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [114, 0, 0, 5, 0, 0, 0, 1, 0, 2, 0xff, 0xff, 0, 0, 0, 9, 0, 0, 0, 10]);
+        let mut buffer = [0; 20];
+        RotatePropertiesRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [114, 0, 5, 0, 1, 0, 0, 0, 2, 0, 0xff, 0xff, 9, 0, 0, 0, 10, 0, 0, 0]);
+    }
+}
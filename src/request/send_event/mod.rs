@@ -1,20 +1,74 @@
+use std::collections::HashSet;
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
 use crate::Result;
+use crate::event::Event;
+use crate::read_util::{ByteOrder, Readable, ReadableRead, Writable, WritableWrite};
+use crate::setup;
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct SendEventRequest;
+pub enum SendEventDestination {
+    PointerWindow,
+    InputFocus,
+    Window(u32),
+}
+
+impl Readable for SendEventDestination {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u32>(order)? {
+            0 => Ok(Self::PointerWindow),
+            1 => Ok(Self::InputFocus),
+            other => Ok(Self::Window(other)),
+        }
+    }
+}
+
+impl Writable for SendEventDestination {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u32 = match data {
+            Self::PointerWindow => 0,
+            Self::InputFocus => 1,
+            Self::Window(window) => window,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SendEventRequest {
+    pub propagate: bool,
+    pub destination: SendEventDestination,
+    pub event_mask: HashSet<setup::Event>,
+    pub event: Event,
+}
 
 impl Readable for SendEventRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let propagate = stream.read_value(order)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let destination = stream.read_value(order)?;
+        let event_mask = stream.read_value(order)?;
+        let event = stream.read_value(order)?;
+        Ok(SendEventRequest {
+            propagate,
+            destination,
+            event_mask,
+            event,
+        })
     }
 }
 
-impl Writable for SendEventRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for SendEventRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(25, order)?;
+        stream.write_value(data.propagate, order)?;
+        stream.write_value::<u16>(11, order)?;
+        stream.write_value(data.destination, order)?;
+        stream.write_value(data.event_mask, order)?;
+        stream.write_value(data.event, order)?;
+        Ok(())
     }
 }
 
@@ -23,12 +77,12 @@ pub struct SendEventResponse;
 
 impl Readable for SendEventResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for SendEventResponse{
+impl Writable for SendEventResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
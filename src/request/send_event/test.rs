@@ -0,0 +1,42 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::collections::HashSet;
+    use std::io::{BufReader, BufWriter};
+    use std::iter::FromIterator;
+
+    use crate::event::{ClientMessageData, ClientMessageEvent, Event};
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::send_event::{SendEventDestination, SendEventRequest};
+    use crate::setup;
+
+    #[test]
+    fn read_write_test() {
+        let msb = [25, 1, 0, 11, 0, 0, 0, 1, 0, 0, 0x80, 0,
+            33, 32, 0, 0, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0, 5, 0, 0, 0, 6, 0, 0, 0, 7, 0, 0, 0, 8];
+        let lsb = [25, 1, 11, 0, 1, 0, 0, 0, 0, 0x80, 0, 0,
+            33, 32, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0, 5, 0, 0, 0, 6, 0, 0, 0, 7, 0, 0, 0, 8, 0, 0, 0];
+        let expected = SendEventRequest {
+            propagate: true,
+            destination: SendEventDestination::InputFocus,
+            event_mask: HashSet::from_iter([setup::Event::Exposure].to_vec()),
+            event: Event::ClientMessage(ClientMessageEvent {
+                sequence_number: 0,
+                window: 2,
+                type_: 3,
+                data: ClientMessageData::Format32([4, 5, 6, 7, 8]),
+            }),
+        };
+        let value = SendEventRequest::read(&mut BufReader::new(&msb[1..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let value = SendEventRequest::read(&mut BufReader::new(&lsb[1..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let mut buffer = [0; 44];
+        SendEventRequest::write(&mut BufWriter::new(&mut buffer[..]), expected.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(&buffer[..], &msb[..]);
+        let mut buffer = [0; 44];
+        SendEventRequest::write(&mut BufWriter::new(&mut buffer[..]), expected, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(&buffer[..], &lsb[..]);
+    }
+}
@@ -1,20 +1,59 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, Readable, ReadableRead, Writable, WritableWrite};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct SetCloseDownModeRequest;
+pub enum SetCloseDownModeMode {
+    Destroy,
+    RetainPermanent,
+    RetainTemporary,
+}
+
+impl Readable for SetCloseDownModeMode {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::Destroy),
+            1 => Ok(Self::RetainPermanent),
+            2 => Ok(Self::RetainTemporary),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for SetCloseDownModeMode {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::Destroy => 0,
+            Self::RetainPermanent => 1,
+            Self::RetainTemporary => 2,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetCloseDownModeRequest {
+    pub mode: SetCloseDownModeMode,
+}
 
 impl Readable for SetCloseDownModeRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let mode = stream.read_value(order)?;
+        let _ = stream.read_value::<u16>(order)?;
+        Ok(SetCloseDownModeRequest { mode })
     }
 }
 
-impl Writable for SetCloseDownModeRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for SetCloseDownModeRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(112, order)?;
+        stream.write_value(data.mode, order)?;
+        stream.write_value::<u16>(1, order)?;
+        Ok(())
     }
 }
 
@@ -23,12 +62,12 @@ pub struct SetCloseDownModeResponse;
 
 impl Readable for SetCloseDownModeResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for SetCloseDownModeResponse{
+impl Writable for SetCloseDownModeResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
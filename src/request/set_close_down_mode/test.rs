@@ -0,0 +1,31 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::set_close_down_mode::{SetCloseDownModeMode, SetCloseDownModeRequest};
+
+    #[test]
+    fn read_test() {
+        let input = [1, 0, 1];
+        let value = SetCloseDownModeRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, SetCloseDownModeRequest { mode: SetCloseDownModeMode::RetainPermanent });
+        let input = [2, 1, 0];
+        let value = SetCloseDownModeRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, SetCloseDownModeRequest { mode: SetCloseDownModeMode::RetainTemporary });
+        let input = [3, 1, 0];
+        assert!(SetCloseDownModeRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).is_err());
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0; 4];
+        SetCloseDownModeRequest::write(&mut BufWriter::new(&mut buffer[..]), SetCloseDownModeRequest { mode: SetCloseDownModeMode::RetainPermanent }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [112, 1, 0, 1]);
+        let mut buffer = [0; 4];
+        SetCloseDownModeRequest::write(&mut BufWriter::new(&mut buffer[..]), SetCloseDownModeRequest { mode: SetCloseDownModeMode::Destroy }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [112, 0, 1, 0]);
+    }
+}
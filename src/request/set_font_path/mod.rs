@@ -1,20 +1,46 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+use crate::request::list_fonts::{read_str_list, str_list_len, write_str_list};
+use crate::request::ReadableBody;
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct SetFontPathRequest;
+pub struct SetFontPathRequest {
+    /// 空ならserverの既定のpathに戻す
+    pub path: Vec<String>,
+}
 
 impl Readable for SetFontPathRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let length = stream.read_value::<u16>(order)?;
+        Self::read_body(stream, 0, length as usize, order)
     }
 }
 
-impl Writable for SetFontPathRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl ReadableBody for SetFontPathRequest {
+    fn read_body(stream: &mut std::io::BufReader<impl Read>, _data: u8, length: usize, order: &ByteOrder) -> Result<Self> {
+        let number_of_strs = stream.read_value::<u16>(order)? as usize;
+        read_specified_length(stream, &mut [0; 2], 2)?;
+        let total_length = length.checked_sub(2).ok_or(Error::ProtocolError(ErrorCode::Length, 0))? << 2;
+        let path = read_str_list(stream, number_of_strs, total_length)?;
+        Ok(SetFontPathRequest { path })
+    }
+}
+
+impl Writable for SetFontPathRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let length = str_list_len(&data.path);
+        stream.write_value::<u8>(51, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value((2 + ((length + 3) >> 2)) as u16, order)?;
+        stream.write_value(data.path.len() as u16, order)?;
+        stream.write_all(&[0; 2]).map_err(Error::IoError)?;
+        write_str_list(stream, data.path)
     }
 }
 
@@ -23,12 +49,12 @@ pub struct SetFontPathResponse;
 
 impl Readable for SetFontPathResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for SetFontPathResponse{
+impl Writable for SetFontPathResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
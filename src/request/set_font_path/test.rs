@@ -0,0 +1,45 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::Error;
+    use crate::error::ErrorCode;
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::set_font_path::SetFontPathRequest;
+
+    #[test]
+    fn read_test() {
+        let expected = SetFontPathRequest { path: vec!["misc/".to_string(), "75dpi".to_string()] };
+        let input = [0, 0, 5, 0, 2, 0, 0, 5, b'm', b'i', b's', b'c', b'/', 5, b'7', b'5', b'd', b'p', b'i', 0];
+        let value = SetFontPathRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [0, 5, 0, 2, 0, 0, 0, 5, b'm', b'i', b's', b'c', b'/', 5, b'7', b'5', b'd', b'p', b'i', 0];
+        let value = SetFontPathRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [0, 0, 2, 0, 0, 0, 0];
+        let value = SetFontPathRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, SetFontPathRequest { path: vec![] });
+        //STRの長さが残りのbyte数を超える
+        let input = [0, 0, 3, 0, 1, 0, 0, 9, b'm', b'i', b's', b'c'];
+        assert!(matches!(SetFontPathRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst), Err(Error::ProtocolError(ErrorCode::Length, 0))));
+        let input = [0, 0, 1, 0, 0, 0, 0];
+        assert!(matches!(SetFontPathRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst), Err(Error::ProtocolError(ErrorCode::Length, 0))));
+    }
+
+    #[test]
+    fn write_test() {
+        let data = SetFontPathRequest { path: vec!["misc/".to_string(), "75dpi".to_string()] };
+        let mut buffer = [0xff; 20];
+        SetFontPathRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [51, 0, 0, 5, 0, 2, 0, 0, 5, b'm', b'i', b's', b'c', b'/', 5, b'7', b'5', b'd', b'p', b'i']);
+        let mut buffer = [0xff; 20];
+        SetFontPathRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [51, 0, 5, 0, 2, 0, 0, 0, 5, b'm', b'i', b's', b'c', b'/', 5, b'7', b'5', b'd', b'p', b'i']);
+        let data = SetFontPathRequest { path: vec!["a".to_string()] };
+        let mut buffer = [0xff; 12];
+        SetFontPathRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [51, 0, 0, 3, 0, 1, 0, 0, 1, b'a', 0, 0]);
+    }
+}
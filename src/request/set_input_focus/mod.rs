@@ -1,20 +1,94 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, Readable, ReadableRead, Writable, WritableWrite};
 
+mod test;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FocusRevertTo {
+    None,
+    PointerRoot,
+    Parent,
+}
+
+impl Readable for FocusRevertTo {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u8>(order)? {
+            0 => Ok(Self::None),
+            1 => Ok(Self::PointerRoot),
+            2 => Ok(Self::Parent),
+            other => Err(Error::ProtocolError(ErrorCode::Value, other as u32)),
+        }
+    }
+}
+
+impl Writable for FocusRevertTo {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u8 = match data {
+            Self::None => 0,
+            Self::PointerRoot => 1,
+            Self::Parent => 2,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum InputFocus {
+    None,
+    PointerRoot,
+    Window(u32),
+}
+
+impl Readable for InputFocus {
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        match stream.read_value::<u32>(order)? {
+            0 => Ok(Self::None),
+            1 => Ok(Self::PointerRoot),
+            other => Ok(Self::Window(other)),
+        }
+    }
+}
+
+impl Writable for InputFocus {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        let value: u32 = match data {
+            Self::None => 0,
+            Self::PointerRoot => 1,
+            Self::Window(window) => window,
+        };
+        stream.write_value(value, order)
+    }
+}
+
+/// timeが0ならCurrentTime
 #[derive(Clone, Debug, PartialEq)]
-pub struct SetInputFocusRequest;
+pub struct SetInputFocusRequest {
+    pub revert_to: FocusRevertTo,
+    pub focus: InputFocus,
+    pub time: u32,
+}
 
 impl Readable for SetInputFocusRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let revert_to = stream.read_value(order)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let focus = stream.read_value(order)?;
+        let time = stream.read_value(order)?;
+        Ok(SetInputFocusRequest { revert_to, focus, time })
     }
 }
 
-impl Writable for SetInputFocusRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for SetInputFocusRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(42, order)?;
+        stream.write_value(data.revert_to, order)?;
+        stream.write_value::<u16>(3, order)?;
+        stream.write_value(data.focus, order)?;
+        stream.write_value(data.time, order)?;
+        Ok(())
     }
 }
 
@@ -23,12 +97,12 @@ pub struct SetInputFocusResponse;
 
 impl Readable for SetInputFocusResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for SetInputFocusResponse{
+impl Writable for SetInputFocusResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
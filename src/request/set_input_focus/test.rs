@@ -0,0 +1,41 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::Error;
+    use crate::error::ErrorCode;
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::set_input_focus::{FocusRevertTo, InputFocus, SetInputFocusRequest};
+
+    #[test]
+    fn read_test() {
+        let expected = SetInputFocusRequest { revert_to: FocusRevertTo::Parent, focus: InputFocus::Window(0x00200001), time: 12345 };
+        let input = [2, 0, 3, 0, 0x20, 0, 1, 0, 0, 0x30, 0x39];
+        let value = SetInputFocusRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [2, 3, 0, 1, 0, 0x20, 0, 0x39, 0x30, 0, 0];
+        let value = SetInputFocusRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [0, 0, 3, 0, 0, 0, 1, 0, 0, 0, 0];
+        let value = SetInputFocusRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, SetInputFocusRequest { revert_to: FocusRevertTo::None, focus: InputFocus::PointerRoot, time: 0 });
+        let input = [3, 0, 3, 0, 0, 0, 1, 0, 0, 0, 0];
+        match SetInputFocusRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst) {
+            Err(Error::ProtocolError(ErrorCode::Value, 3)) => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn write_test() {
+        let data = SetInputFocusRequest { revert_to: FocusRevertTo::PointerRoot, focus: InputFocus::None, time: 12345 };
+        let mut buffer = [0; 12];
+        SetInputFocusRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [42, 1, 0, 3, 0, 0, 0, 0, 0, 0, 0x30, 0x39]);
+        let mut buffer = [0; 12];
+        SetInputFocusRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [42, 1, 3, 0, 0, 0, 0, 0, 0x39, 0x30, 0, 0]);
+    }
+}
@@ -1,20 +1,41 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
 
+mod test;
+
+/// timeが0ならCurrentTime
 #[derive(Clone, Debug, PartialEq)]
-pub struct SetSelectionOwnerRequest;
+pub struct SetSelectionOwnerRequest {
+    pub owner: Option<u32>,
+    pub selection: u32,
+    pub time: u32,
+}
 
 impl Readable for SetSelectionOwnerRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let owner = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let selection = stream.read_value(order)?;
+        let time = stream.read_value(order)?;
+        Ok(SetSelectionOwnerRequest { owner, selection, time })
     }
 }
 
-impl Writable for SetSelectionOwnerRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for SetSelectionOwnerRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(22, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(4, order)?;
+        stream.write_value(data.owner.unwrap_or(0), order)?;
+        stream.write_value(data.selection, order)?;
+        stream.write_value(data.time, order)?;
+        Ok(())
     }
 }
 
@@ -23,12 +44,12 @@ pub struct SetSelectionOwnerResponse;
 
 impl Readable for SetSelectionOwnerResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for SetSelectionOwnerResponse{
+impl Writable for SetSelectionOwnerResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
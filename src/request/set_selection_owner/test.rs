@@ -0,0 +1,30 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::set_selection_owner::SetSelectionOwnerRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 4, 0, 0x20, 0, 1, 0, 0, 0, 1, 0, 0, 0x30, 0x39];
+        let value = SetSelectionOwnerRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, SetSelectionOwnerRequest { owner: Some(0x00200001), selection: 1, time: 12345 });
+        let input = [0, 4, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0];
+        let value = SetSelectionOwnerRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, SetSelectionOwnerRequest { owner: None, selection: 1, time: 0 });
+    }
+
+    #[test]
+    fn write_test() {
+        let data = SetSelectionOwnerRequest { owner: Some(0x00200001), selection: 1, time: 12345 };
+        let mut buffer = [0; 16];
+        SetSelectionOwnerRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [22, 0, 0, 4, 0, 0x20, 0, 1, 0, 0, 0, 1, 0, 0, 0x30, 0x39]);
+        let mut buffer = [0; 16];
+        SetSelectionOwnerRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [22, 0, 4, 0, 1, 0, 0x20, 0, 1, 0, 0, 0, 0x39, 0x30, 0, 0]);
+    }
+}
@@ -0,0 +1,258 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request_reader {
+    use std::io::BufReader;
+
+    use crate::Error;
+    use crate::error::ErrorCode;
+    use crate::read_util::ByteOrder;
+    use crate::request::{Request, RequestHeader, RequestReader, RequestRef};
+
+    #[test]
+    fn read_test() {
+        let input = [8, 0, 0, 2, 0, 0, 0, 1, 10, 0, 0, 2, 0, 0, 0, 2];
+        let reader = RequestReader::new(0xffff);
+        let mut stream = BufReader::new(&input[..]);
+        let (header, request) = reader.read(&mut stream, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(header, RequestHeader { opcode: 8, data: 0, length: 2 });
+        match request {
+            Ok(Request::MapWindow(request)) => assert_eq!(request.window, 1),
+            _ => panic!(),
+        }
+        let (header, request) = reader.read(&mut stream, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(header, RequestHeader { opcode: 10, data: 0, length: 2 });
+        match request {
+            Ok(Request::UnmapWindow(request)) => assert_eq!(request.window, 2),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn unknown_opcode_test() {
+        let input = [
+            120, 0, 3, 0, 1, 2, 3, 4, 5, 6, 7, 8,
+            135, 4, 2, 0, 1, 2, 3, 4,
+            8, 0, 2, 0, 1, 0, 0, 0,
+        ];
+        let reader = RequestReader::new(0xffff);
+        let mut stream = BufReader::new(&input[..]);
+        let (header, request) = reader.read(&mut stream, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(header, RequestHeader { opcode: 120, data: 0, length: 3 });
+        match request {
+            Err(Error::ProtocolError(ErrorCode::Request, _)) => {}
+            _ => panic!(),
+        }
+        let (header, request) = reader.read(&mut stream, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(header, RequestHeader { opcode: 135, data: 4, length: 2 });
+        match request {
+            Err(Error::ProtocolError(ErrorCode::Request, _)) => {}
+            _ => panic!(),
+        }
+        let (_, request) = reader.read(&mut stream, &ByteOrder::LSBFirst).unwrap();
+        match request {
+            Ok(Request::MapWindow(request)) => assert_eq!(request.window, 1),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn length_test() {
+        let input = [
+            //maximum_request_lengthを超える
+            8, 0, 0, 5, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            //bodyが足りない
+            14, 0, 0, 1,
+            //長さ0
+            8, 0, 0, 0,
+            8, 0, 0, 2, 0, 0, 0, 3,
+        ];
+        let reader = RequestReader::new(4);
+        let mut stream = BufReader::new(&input[..]);
+        for _ in 0..3 {
+            match reader.read(&mut stream, &ByteOrder::MSBFirst).unwrap().1 {
+                Err(Error::ProtocolError(ErrorCode::Length, _)) => {}
+                _ => panic!(),
+            }
+        }
+        match reader.read(&mut stream, &ByteOrder::MSBFirst).unwrap().1 {
+            Ok(Request::MapWindow(request)) => assert_eq!(request.window, 3),
+            _ => panic!(),
+        }
+        assert!(reader.read(&mut stream, &ByteOrder::MSBFirst).is_err());
+    }
+
+    #[test]
+    fn big_requests_test() {
+        let input = [
+            8, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 1,
+            8, 0, 0, 0, 0, 0, 0, 9, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            10, 0, 0, 2, 0, 0, 0, 2,
+        ];
+        let mut reader = RequestReader::new(4);
+        reader.enable_big_requests(8);
+        let mut stream = BufReader::new(&input[..]);
+        let (header, request) = reader.read(&mut stream, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(header, RequestHeader { opcode: 8, data: 0, length: 3 });
+        match request {
+            Ok(Request::MapWindow(request)) => assert_eq!(request.window, 1),
+            _ => panic!(),
+        }
+        let (header, request) = reader.read(&mut stream, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(header, RequestHeader { opcode: 8, data: 0, length: 9 });
+        match request {
+            Err(Error::ProtocolError(ErrorCode::Length, _)) => {}
+            _ => panic!(),
+        }
+        match reader.read(&mut stream, &ByteOrder::MSBFirst).unwrap().1 {
+            Ok(Request::UnmapWindow(request)) => assert_eq!(request.window, 2),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn unimplemented_request_test() {
+        let input = [
+            //Bell
+            104, 0, 1, 0,
+            //CopyArea
+            62, 0, 7, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            127, 0, 2, 0, 0, 0, 0, 0,
+            8, 0, 2, 0, 1, 0, 0, 0,
+        ];
+        let reader = RequestReader::new(0xffff);
+        let mut stream = BufReader::new(&input[..]);
+        for opcode in [104, 62] {
+            let (header, request) = reader.read(&mut stream, &ByteOrder::LSBFirst).unwrap();
+            assert_eq!(header.opcode, opcode);
+            match request {
+                Err(Error::ProtocolError(ErrorCode::Implementation, 0)) => {}
+                _ => panic!(),
+            }
+        }
+        match reader.read(&mut stream, &ByteOrder::LSBFirst).unwrap().1 {
+            Ok(Request::NoOperation(_)) => {}
+            _ => panic!(),
+        }
+        match reader.read(&mut stream, &ByteOrder::LSBFirst).unwrap().1 {
+            Ok(Request::MapWindow(request)) => assert_eq!(request.window, 1),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn big_length_test() {
+        //u16に収まらない長さのChangeProperty
+        let units = 0x10000 * 4;
+        let mut input = vec![18, 0, 0, 0];
+        input.extend_from_slice(&(7 + 0x10000u32).to_le_bytes());
+        input.extend_from_slice(&[1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 8, 0, 0, 0]);
+        input.extend_from_slice(&(units as u32).to_le_bytes());
+        input.extend(std::iter::repeat_n(7, units));
+        //同じ長さでも長くならないrequestはBadLength
+        input.extend_from_slice(&[8, 0, 0, 0]);
+        input.extend_from_slice(&(1 + 0x10000u32).to_le_bytes());
+        input.extend(std::iter::repeat_n(0, 0x10000 * 4 - 4));
+        let mut reader = RequestReader::new(0xffff);
+        reader.enable_big_requests(0x20000);
+        let mut stream = BufReader::new(&input[..]);
+        match reader.read(&mut stream, &ByteOrder::LSBFirst).unwrap().1 {
+            Ok(Request::ChangeProperty(request)) => {
+                assert_eq!(request.window, 1);
+                assert_eq!(request.data.len(), units);
+            }
+            _ => panic!(),
+        }
+        match reader.read(&mut stream, &ByteOrder::LSBFirst).unwrap().1 {
+            Err(Error::ProtocolError(ErrorCode::Length, 0)) => {}
+            _ => panic!(),
+        }
+        assert_eq!(stream.buffer().len(), 0);
+    }
+
+    #[test]
+    fn readable_length_test() {
+        use crate::read_util::Readable;
+        use crate::request::MAXIMUM_BIG_REQUEST_LENGTH;
+
+        //上限を超えるrequestはbodyを読み捨ててBadLength
+        let mut input = vec![8, 0, 0, 0];
+        input.extend_from_slice(&(MAXIMUM_BIG_REQUEST_LENGTH + 1).to_le_bytes());
+        input.resize(((MAXIMUM_BIG_REQUEST_LENGTH as usize + 1) << 2) + 8, 0);
+        input[((MAXIMUM_BIG_REQUEST_LENGTH as usize + 1) << 2)..].copy_from_slice(&[8, 0, 2, 0, 1, 0, 0, 0]);
+        let mut stream = BufReader::new(&input[..]);
+        match Request::read(&mut stream, &ByteOrder::LSBFirst) {
+            Err(Error::ProtocolError(ErrorCode::Length, 0)) => {}
+            _ => panic!(),
+        }
+        match Request::read(&mut stream, &ByteOrder::LSBFirst) {
+            Ok(Request::MapWindow(request)) => assert_eq!(request.window, 1),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn read_slice_test() {
+        let input = [
+            //InternAtom "WM_NAME"
+            16, 0, 4, 0, 7, 0, 0, 0, b'W', b'M', b'_', b'N', b'A', b'M', b'E', 0,
+            8, 0, 2, 0, 1, 0, 0, 0,
+            //maximum_request_lengthを超える
+            8, 0, 5, 0, 0, 0, 0, 0,
+        ];
+        let reader = RequestReader::new(4);
+        let (header, request) = reader.read_slice(&input[..], &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(header, RequestHeader { opcode: 16, data: 0, length: 4 });
+        match request {
+            Ok(RequestRef::InternAtom(request)) => {
+                assert_eq!(request.name, "WM_NAME");
+                assert_eq!(request.name.as_ptr(), input[8..].as_ptr());
+            }
+            _ => panic!(),
+        }
+        //借用する版の無いrequestはそのまま読む
+        match reader.read_slice(&input[16..], &ByteOrder::LSBFirst).unwrap().1 {
+            Ok(RequestRef::Other(Request::MapWindow(request))) => assert_eq!(request.window, 1),
+            _ => panic!(),
+        }
+        let (header, request) = reader.read_slice(&input[24..], &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(header.length, 5);
+        assert!(matches!(request, Err(Error::ProtocolError(ErrorCode::Length, 0))));
+        //bufferが足りない
+        assert!(reader.read_slice(&input[..12], &ByteOrder::LSBFirst).is_err());
+        //readと同じものになる
+        let mut stream = BufReader::new(&input[..]);
+        match reader.read(&mut stream, &ByteOrder::LSBFirst).unwrap().1 {
+            Ok(Request::InternAtom(request)) => assert_eq!(request.name, "WM_NAME"),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn read_slice_big_requests_test() {
+        //u16に収まらない長さのChangeProperty
+        let units = 0x10000 * 4;
+        let mut input = vec![18, 0, 0, 0];
+        input.extend_from_slice(&(7 + 0x10000u32).to_le_bytes());
+        input.extend_from_slice(&[1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 8, 0, 0, 0]);
+        input.extend_from_slice(&(units as u32).to_le_bytes());
+        input.extend(std::iter::repeat_n(7, units));
+        let mut reader = RequestReader::new(0xffff);
+        reader.enable_big_requests(0x20000);
+        let (header, request) = reader.read_slice(&input[..], &ByteOrder::LSBFirst).unwrap();
+        assert_eq!((header.length as usize) << 2, input.len());
+        match request {
+            Ok(RequestRef::ChangeProperty(request)) => {
+                assert_eq!(request.window, 1);
+                assert_eq!(request.data.bytes().as_ptr(), input[28..].as_ptr());
+                assert_eq!(request.data.bytes().len(), units);
+            }
+            _ => panic!(),
+        }
+        //長くならないrequestはBadLength
+        let mut input = vec![16, 0, 0, 0];
+        input.extend_from_slice(&(1 + 0x10000u32).to_le_bytes());
+        input.resize(0x10000 * 4 + 4, 0);
+        assert!(matches!(reader.read_slice(&input[..], &ByteOrder::LSBFirst).unwrap().1, Err(Error::ProtocolError(ErrorCode::Length, 0))));
+    }
+}
@@ -1,34 +1,90 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+
+mod test;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct TranslateCoordinatesRequest;
+pub struct TranslateCoordinatesRequest {
+    pub src_window: u32,
+    pub dst_window: u32,
+    pub src_x: i16,
+    pub src_y: i16,
+}
 
 impl Readable for TranslateCoordinatesRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let src_window = stream.read_value(order)?;
+        let dst_window = stream.read_value(order)?;
+        let src_x = stream.read_value(order)?;
+        let src_y = stream.read_value(order)?;
+        Ok(TranslateCoordinatesRequest {
+            src_window,
+            dst_window,
+            src_x,
+            src_y,
+        })
     }
 }
 
-impl Writable for TranslateCoordinatesRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for TranslateCoordinatesRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(40, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(4, order)?;
+        stream.write_value(data.src_window, order)?;
+        stream.write_value(data.dst_window, order)?;
+        stream.write_value(data.src_x, order)?;
+        stream.write_value(data.src_y, order)?;
+        Ok(())
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct TranslateCoordinatesResponse;
+pub struct TranslateCoordinatesResponse {
+    pub same_screen: bool,
+    pub sequence_number: u16,
+    pub child: Option<u32>,
+    pub dst_x: i16,
+    pub dst_y: i16,
+}
 
 impl Readable for TranslateCoordinatesResponse {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let same_screen = stream.read_value(order)?;
+        let sequence_number = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 4], 4)?;
+        let child = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let dst_x = stream.read_value(order)?;
+        let dst_y = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 16], 16)?;
+        Ok(TranslateCoordinatesResponse {
+            same_screen,
+            sequence_number,
+            child,
+            dst_x,
+            dst_y,
+        })
     }
 }
 
-impl Writable for TranslateCoordinatesResponse{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for TranslateCoordinatesResponse {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(1, order)?;
+        stream.write_value(data.same_screen, order)?;
+        stream.write_value(data.sequence_number, order)?;
+        stream.write_value::<u32>(0, order)?;
+        stream.write_value(data.child.unwrap_or(0), order)?;
+        stream.write_value(data.dst_x, order)?;
+        stream.write_value(data.dst_y, order)?;
+        stream.write_all(&[0; 16]).map_err(Error::IoError)?;
+        Ok(())
     }
 }
@@ -0,0 +1,66 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::translate_coordinates::TranslateCoordinatesRequest;
+
+    #[test]
+    fn read_test() {
+        let expected = TranslateCoordinatesRequest { src_window: 1, dst_window: 2, src_x: -3, src_y: 4 };
+        let input = [0, 0, 4, 0, 0, 0, 1, 0, 0, 0, 2, 0xff, 0xfd, 0, 4];
+        let value = TranslateCoordinatesRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [0, 4, 0, 1, 0, 0, 0, 2, 0, 0, 0, 0xfd, 0xff, 4, 0];
+        let value = TranslateCoordinatesRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn write_test() {
+        let data = TranslateCoordinatesRequest { src_window: 1, dst_window: 2, src_x: -3, src_y: 4 };
+        let mut buffer = [0; 16];
+        TranslateCoordinatesRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [40, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0, 2, 0xff, 0xfd, 0, 4]);
+        let mut buffer = [0; 16];
+        TranslateCoordinatesRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [40, 0, 4, 0, 1, 0, 0, 0, 2, 0, 0, 0, 0xfd, 0xff, 4, 0]);
+    }
+}
+
+mod response {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::translate_coordinates::TranslateCoordinatesResponse;
+
+    #[test]
+    fn read_test() {
+        let expected = TranslateCoordinatesResponse { same_screen: true, sequence_number: 2, child: Some(3), dst_x: -4, dst_y: 5 };
+        let mut input = [0; 32];
+        input[..16].copy_from_slice(&[1, 1, 0, 2, 0, 0, 0, 0, 0, 0, 0, 3, 0xff, 0xfc, 0, 5]);
+        let value = TranslateCoordinatesResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let mut input = [0; 32];
+        input[..16].copy_from_slice(&[1, 1, 2, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0xfc, 0xff, 5, 0]);
+        let value = TranslateCoordinatesResponse::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn write_test() {
+        let data = TranslateCoordinatesResponse { same_screen: false, sequence_number: 2, child: None, dst_x: -4, dst_y: 5 };
+        let mut expected = [0; 32];
+        expected[..16].copy_from_slice(&[1, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xfc, 0, 5]);
+        let mut buffer = [0; 32];
+        TranslateCoordinatesResponse::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+        let mut expected = [0; 32];
+        expected[..16].copy_from_slice(&[1, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xfc, 0xff, 5, 0]);
+        let mut buffer = [0; 32];
+        TranslateCoordinatesResponse::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, expected);
+    }
+}
@@ -0,0 +1,35 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::collections::HashSet;
+    use std::io::{BufReader, BufWriter};
+    use std::iter::FromIterator;
+
+    use crate::event::KeyButMask;
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::grab_button::GrabModifiers;
+    use crate::request::ungrab_button::UngrabButtonRequest;
+
+    #[test]
+    fn read_test() {
+        let expected = UngrabButtonRequest { button: Some(1), grab_window: 0x00200001, modifiers: GrabModifiers::Modifiers(HashSet::from_iter([KeyButMask::Control].to_vec())) };
+        let input = [1, 0, 3, 0, 0x20, 0, 1, 0, 4, 0, 0];
+        let value = UngrabButtonRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [1, 3, 0, 1, 0, 0x20, 0, 4, 0, 0, 0];
+        let value = UngrabButtonRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn write_test() {
+        let data = UngrabButtonRequest { button: None, grab_window: 0x00200001, modifiers: GrabModifiers::AnyModifier };
+        let mut buffer = [0; 12];
+        UngrabButtonRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [29, 0, 0, 3, 0, 0x20, 0, 1, 0x80, 0, 0, 0]);
+        let mut buffer = [0; 12];
+        UngrabButtonRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [29, 0, 3, 0, 1, 0, 0x20, 0, 0, 0x80, 0, 0]);
+    }
+}
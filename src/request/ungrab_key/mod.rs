@@ -1,20 +1,42 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
+use crate::request::grab_button::GrabModifiers;
 
+mod test;
+
+/// keyがNoneならAnyKey
 #[derive(Clone, Debug, PartialEq)]
-pub struct UngrabKeyRequest;
+pub struct UngrabKeyRequest {
+    pub key: Option<u8>,
+    pub grab_window: u32,
+    pub modifiers: GrabModifiers,
+}
 
 impl Readable for UngrabKeyRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let key = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let _ = stream.read_value::<u16>(order)?;
+        let grab_window = stream.read_value(order)?;
+        let modifiers = stream.read_value(order)?;
+        read_specified_length(stream, &mut [0; 2], 2)?;
+        Ok(UngrabKeyRequest { key, grab_window, modifiers })
     }
 }
 
-impl Writable for UngrabKeyRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for UngrabKeyRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(34, order)?;
+        stream.write_value(data.key.unwrap_or(0), order)?;
+        stream.write_value::<u16>(3, order)?;
+        stream.write_value(data.grab_window, order)?;
+        stream.write_value(data.modifiers, order)?;
+        stream.write_all(&[0; 2]).map_err(Error::IoError)?;
+        Ok(())
     }
 }
 
@@ -23,12 +45,12 @@ pub struct UngrabKeyResponse;
 
 impl Readable for UngrabKeyResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for UngrabKeyResponse{
+impl Writable for UngrabKeyResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
@@ -0,0 +1,35 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::collections::HashSet;
+    use std::io::{BufReader, BufWriter};
+    use std::iter::FromIterator;
+
+    use crate::event::KeyButMask;
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::grab_button::GrabModifiers;
+    use crate::request::ungrab_key::UngrabKeyRequest;
+
+    #[test]
+    fn read_test() {
+        let expected = UngrabKeyRequest { key: Some(38), grab_window: 0x00200001, modifiers: GrabModifiers::Modifiers(HashSet::from_iter([KeyButMask::Lock].to_vec())) };
+        let input = [38, 0, 3, 0, 0x20, 0, 1, 0, 2, 0, 0];
+        let value = UngrabKeyRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [38, 3, 0, 1, 0, 0x20, 0, 2, 0, 0, 0];
+        let value = UngrabKeyRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn write_test() {
+        let data = UngrabKeyRequest { key: None, grab_window: 0x00200001, modifiers: GrabModifiers::AnyModifier };
+        let mut buffer = [0; 12];
+        UngrabKeyRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [34, 0, 0, 3, 0, 0x20, 0, 1, 0x80, 0, 0, 0]);
+        let mut buffer = [0; 12];
+        UngrabKeyRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [34, 0, 3, 0, 1, 0, 0x20, 0, 0, 0x80, 0, 0]);
+    }
+}
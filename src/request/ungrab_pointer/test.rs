@@ -0,0 +1,29 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::ungrab_pointer::UngrabPointerRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 2, 0, 0, 0x30, 0x39];
+        let value = UngrabPointerRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, UngrabPointerRequest { time: 12345 });
+        let input = [0, 2, 0, 0x39, 0x30, 0, 0];
+        let value = UngrabPointerRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, UngrabPointerRequest { time: 12345 });
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0; 8];
+        UngrabPointerRequest::write(&mut BufWriter::new(&mut buffer[..]), UngrabPointerRequest { time: 12345 }, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [27, 0, 0, 2, 0, 0, 0x30, 0x39]);
+        let mut buffer = [0; 8];
+        UngrabPointerRequest::write(&mut BufWriter::new(&mut buffer[..]), UngrabPointerRequest { time: 0 }, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [27, 0, 2, 0, 0, 0, 0, 0]);
+    }
+}
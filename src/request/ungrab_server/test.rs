@@ -0,0 +1,29 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::ungrab_server::UngrabServerRequest;
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 1];
+        let value = UngrabServerRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, UngrabServerRequest);
+        let input = [0, 1, 0];
+        let value = UngrabServerRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, UngrabServerRequest);
+    }
+
+    #[test]
+    fn write_test() {
+        let mut buffer = [0xff; 4];
+        UngrabServerRequest::write(&mut BufWriter::new(&mut buffer[..]), UngrabServerRequest, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [37, 0, 0, 1]);
+        let mut buffer = [0xff; 4];
+        UngrabServerRequest::write(&mut BufWriter::new(&mut buffer[..]), UngrabServerRequest, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [37, 0, 1, 0]);
+    }
+}
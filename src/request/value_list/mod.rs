@@ -0,0 +1,72 @@
+/// LISTofVALUE
+/// value-maskの立っているbitの順に値が並び、各値は4byteで下位byteに詰められている
+use std::io::{BufReader, BufWriter, Read, Write};
+
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::read_util::{ByteOrder, Readable, ReadableRead, Writable, WritableWrite};
+
+mod test;
+
+#[derive(Clone, Debug)]
+pub struct ValueListReader {
+    values: std::vec::IntoIter<u32>,
+}
+
+impl ValueListReader {
+    /// countはvalue-maskの立っているbitの数、lengthはrequestの残りの長さ(4byte単位)
+    pub fn read(stream: &mut BufReader<impl Read>, order: &ByteOrder, count: usize, length: usize) -> Result<Self> {
+        if count != length {
+            return Err(Error::ProtocolError(ErrorCode::Length, 0));
+        }
+        let mut values = Vec::with_capacity(count);
+        for _ in 0..count {
+            values.push(stream.read_value::<u32>(order)?);
+        }
+        Ok(ValueListReader { values: values.into_iter() })
+    }
+
+    pub fn read_value<T: Readable>(&mut self) -> Result<T> {
+        let value = self.values.next().ok_or(Error::ProtocolError(ErrorCode::Length, 0))?;
+        let mut buffer = [0; 4];
+        ByteOrder::LSBFirst.encode(value, &mut buffer);
+        T::read(&mut BufReader::new(&buffer[..]), &ByteOrder::LSBFirst)
+    }
+}
+
+/// 符号付きの値はi32にしてから渡すと符号拡張される
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ValueListWriter {
+    values: Vec<u32>,
+}
+
+impl ValueListWriter {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn write_value<T: Writable>(&mut self, data: T) -> Result<()> {
+        let mut buffer = [0; 4];
+        let mut writer = BufWriter::new(&mut buffer[..]);
+        writer.write_value(data, &ByteOrder::LSBFirst)?;
+        writer.flush().map_err(Error::IoError)?;
+        drop(writer);
+        self.values.push(ByteOrder::LSBFirst.decode(&buffer));
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn write(self, stream: &mut BufWriter<impl Write>, order: &ByteOrder) -> Result<()> {
+        for value in self.values {
+            stream.write_value(value, order)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod value_list {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::Error;
+    use crate::error::ErrorCode;
+    use crate::read_util::ByteOrder;
+    use crate::request::value_list::{ValueListReader, ValueListWriter};
+
+    #[test]
+    fn read_test() {
+        let input = [0, 0, 0, 3, 0, 0, 1, 0, 0xff, 0xff, 0xff, 0xfe, 0, 0, 0, 1];
+        let mut reader = ValueListReader::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst, 4, 4).unwrap();
+        assert_eq!(reader.read_value::<u8>().unwrap(), 3);
+        assert_eq!(reader.read_value::<u16>().unwrap(), 256);
+        assert_eq!(reader.read_value::<i16>().unwrap(), -2);
+        assert!(reader.read_value::<bool>().unwrap());
+        assert!(reader.read_value::<u32>().is_err());
+        let input = [3, 0, 0, 0, 0, 1, 0, 0, 0xfe, 0xff, 0xff, 0xff, 1, 0, 0, 0];
+        let mut reader = ValueListReader::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst, 4, 4).unwrap();
+        assert_eq!(reader.read_value::<u8>().unwrap(), 3);
+        assert_eq!(reader.read_value::<u16>().unwrap(), 256);
+        assert_eq!(reader.read_value::<i16>().unwrap(), -2);
+        assert!(reader.read_value::<bool>().unwrap());
+    }
+
+    #[test]
+    fn length_test() {
+        let input = [0, 0, 0, 3, 0, 0, 1, 0];
+        match ValueListReader::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst, 1, 2) {
+            Err(Error::ProtocolError(ErrorCode::Length, _)) => {}
+            other => panic!("{:?}", other),
+        }
+        match ValueListReader::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst, 3, 2) {
+            Err(Error::ProtocolError(ErrorCode::Length, _)) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn write_test() {
+        let mut writer = ValueListWriter::new();
+        writer.write_value(3u8).unwrap();
+        writer.write_value(256u16).unwrap();
+        writer.write_value(-2i16 as i32).unwrap();
+        writer.write_value(true).unwrap();
+        assert_eq!(writer.len(), 4);
+        let mut buffer = [0; 16];
+        writer.clone().write(&mut BufWriter::new(&mut buffer[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [0, 0, 0, 3, 0, 0, 1, 0, 0xff, 0xff, 0xff, 0xfe, 0, 0, 0, 1]);
+        let mut buffer = [0; 16];
+        writer.write(&mut BufWriter::new(&mut buffer[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [3, 0, 0, 0, 0, 1, 0, 0, 0xfe, 0xff, 0xff, 0xff, 1, 0, 0, 0]);
+    }
+}
@@ -1,20 +1,68 @@
 use std::io::{Read, Write};
 
-use crate::read_util::{ByteOrder, Readable, Writable};
-use crate::Result;
+use crate::{Error, Result};
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
 
+mod test;
+
+/// dst_windowがNoneなら今の位置からdst_x、dst_yだけ動かす
 #[derive(Clone, Debug, PartialEq)]
-pub struct WarpPointerRequest;
+pub struct WarpPointerRequest {
+    pub src_window: Option<u32>,
+    pub dst_window: Option<u32>,
+    pub src_x: i16,
+    pub src_y: i16,
+    pub src_width: u16,
+    pub src_height: u16,
+    pub dst_x: i16,
+    pub dst_y: i16,
+}
 
 impl Readable for WarpPointerRequest {
-    fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+    fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        read_specified_length(stream, &mut [0; 1], 1)?;
+        let _ = stream.read_value::<u16>(order)?;
+        let src_window = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let dst_window = match stream.read_value(order)? {
+            0 => None,
+            other => Some(other),
+        };
+        let src_x = stream.read_value(order)?;
+        let src_y = stream.read_value(order)?;
+        let src_width = stream.read_value(order)?;
+        let src_height = stream.read_value(order)?;
+        let dst_x = stream.read_value(order)?;
+        let dst_y = stream.read_value(order)?;
+        Ok(WarpPointerRequest {
+            src_window,
+            dst_window,
+            src_x,
+            src_y,
+            src_width,
+            src_height,
+            dst_x,
+            dst_y,
+        })
     }
 }
 
-impl Writable for WarpPointerRequest{
-    fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+impl Writable for WarpPointerRequest {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        stream.write_value::<u8>(41, order)?;
+        stream.write_all(&[0]).map_err(Error::IoError)?;
+        stream.write_value::<u16>(6, order)?;
+        stream.write_value(data.src_window.unwrap_or(0), order)?;
+        stream.write_value(data.dst_window.unwrap_or(0), order)?;
+        stream.write_value(data.src_x, order)?;
+        stream.write_value(data.src_y, order)?;
+        stream.write_value(data.src_width, order)?;
+        stream.write_value(data.src_height, order)?;
+        stream.write_value(data.dst_x, order)?;
+        stream.write_value(data.dst_y, order)?;
+        Ok(())
     }
 }
 
@@ -23,12 +71,12 @@ pub struct WarpPointerResponse;
 
 impl Readable for WarpPointerResponse {
     fn read(_stream: &mut std::io::BufReader<impl Read>, _order: &ByteOrder) -> Result<Self> {
-        unimplemented!()
+        Ok(Self)
     }
 }
 
-impl Writable for WarpPointerResponse{
+impl Writable for WarpPointerResponse {
     fn write(_stream: &mut std::io::BufWriter<impl Write>, _data: Self, _order: &ByteOrder) -> Result<()> {
-        unimplemented!()
+        Ok(())
     }
 }
@@ -0,0 +1,31 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod request {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::request::warp_pointer::WarpPointerRequest;
+
+    #[test]
+    fn read_test() {
+        let expected = WarpPointerRequest { src_window: None, dst_window: Some(0x00200001), src_x: 0, src_y: 0, src_width: 0, src_height: 0, dst_x: 10, dst_y: -20 };
+        let input = [0, 0, 6, 0, 0, 0, 0, 0, 0x20, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 10, 0xff, 0xec];
+        let value = WarpPointerRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, expected);
+        let input = [0, 6, 0, 0, 0, 0, 0, 1, 0, 0x20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 10, 0, 0xec, 0xff];
+        let value = WarpPointerRequest::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn write_test() {
+        let data = WarpPointerRequest { src_window: Some(0x00200001), dst_window: None, src_x: 1, src_y: 2, src_width: 300, src_height: 4, dst_x: -1, dst_y: 5 };
+        let mut buffer = [0; 24];
+        WarpPointerRequest::write(&mut BufWriter::new(&mut buffer[..]), data.clone(), &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(buffer, [41, 0, 0, 6, 0, 0x20, 0, 1, 0, 0, 0, 0, 0, 1, 0, 2, 1, 0x2c, 0, 4, 0xff, 0xff, 0, 5]);
+        let mut buffer = [0; 24];
+        WarpPointerRequest::write(&mut BufWriter::new(&mut buffer[..]), data, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [41, 0, 6, 0, 1, 0, 0x20, 0, 0, 0, 0, 0, 1, 0, 2, 0, 0x2c, 1, 4, 0, 0xff, 0xff, 5, 0]);
+    }
+}
@@ -0,0 +1,251 @@
+/// resource idの割り当てと、どのclientがどの種類のresourceを持っているかの管理
+/// resource_id_maskは下位の連続したbitである前提
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{Error, Result};
+use crate::error::ErrorCode;
+use crate::request::kill_client::KillClientResource;
+use crate::request::set_close_down_mode::SetCloseDownModeMode;
+use crate::setup::ConnectionSetupSuccess;
+
+mod test;
+
+/// 0はserver自身で、rootのwindowやdefaultのcolormapを持つ
+pub type ClientId = u32;
+
+pub const SERVER_CLIENT: ClientId = 0;
+
+/// resource idの上位3bitは常に0
+const RESOURCE_ID_BITS: u32 = 0x1fffffff;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClientIdRange {
+    pub base: u32,
+    pub mask: u32,
+}
+
+impl ClientIdRange {
+    pub fn contains(&self, id: u32) -> bool {
+        id & !self.mask == self.base
+    }
+}
+
+impl From<&ConnectionSetupSuccess> for ClientIdRange {
+    fn from(setup: &ConnectionSetupSuccess) -> Self {
+        ClientIdRange {
+            base: setup.resource_id_base,
+            mask: setup.resource_id_mask,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ResourceType {
+    Window,
+    Pixmap,
+    GContext,
+    Font,
+    Cursor,
+    Colormap,
+}
+
+impl ResourceType {
+    /// 見つからなかったときや種類が違ったときのエラー
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            ResourceType::Window => ErrorCode::Window,
+            ResourceType::Pixmap => ErrorCode::Pixmap,
+            ResourceType::GContext => ErrorCode::GContext,
+            ResourceType::Font => ErrorCode::Font,
+            ResourceType::Cursor => ErrorCode::Cursor,
+            ResourceType::Colormap => ErrorCode::Colormap,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Resource {
+    pub id: u32,
+    pub type_: ResourceType,
+    pub client: ClientId,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Client {
+    range: ClientIdRange,
+    close_down_mode: SetCloseDownModeMode,
+    connected: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct ResourceTable {
+    mask: u32,
+    clients: HashMap<ClientId, Client>,
+    resources: BTreeMap<u32, Resource>,
+}
+
+impl ResourceTable {
+    pub fn new(resource_id_mask: u32) -> Self {
+        let mut clients = HashMap::new();
+        clients.insert(SERVER_CLIENT, Client {
+            range: ClientIdRange { base: 0, mask: resource_id_mask },
+            close_down_mode: SetCloseDownModeMode::RetainPermanent,
+            connected: true,
+        });
+        ResourceTable {
+            mask: resource_id_mask,
+            clients,
+            resources: BTreeMap::new(),
+        }
+    }
+
+    pub fn max_clients(&self) -> u32 {
+        (RESOURCE_ID_BITS >> self.mask.count_ones()) + 1
+    }
+
+    /// 新しく接続したclientにidの範囲を割り当てる
+    /// RetainPermanentなどで残っているclientの範囲は使わない
+    pub fn add_client(&mut self) -> Option<(ClientId, ClientIdRange)> {
+        let client = (1..self.max_clients()).find(|client| !self.clients.contains_key(client))?;
+        let range = ClientIdRange {
+            base: client << self.mask.count_ones(),
+            mask: self.mask,
+        };
+        self.clients.insert(client, Client {
+            range: range.clone(),
+            close_down_mode: SetCloseDownModeMode::Destroy,
+            connected: true,
+        });
+        Some((client, range))
+    }
+
+    pub fn client_range(&self, client: ClientId) -> Option<&ClientIdRange> {
+        self.clients.get(&client).map(|c| &c.range)
+    }
+
+    /// idの範囲から持ち主のclientを求める
+    pub fn client_of(&self, id: u32) -> Option<ClientId> {
+        let client = (id & RESOURCE_ID_BITS) >> self.mask.count_ones();
+        if self.clients.contains_key(&client) { Some(client) } else { None }
+    }
+
+    /// clientに割り当てた範囲の外か、既に使われているidならBadIDChoice
+    pub fn add(&mut self, client: ClientId, id: u32, type_: ResourceType) -> Result<()> {
+        let in_range = self.clients.get(&client).map(|c| c.range.contains(id)).unwrap_or(false);
+        if id == 0 || !in_range || self.resources.contains_key(&id) {
+            return Err(Error::ProtocolError(ErrorCode::IDChoice, id));
+        }
+        self.resources.insert(id, Resource { id, type_, client });
+        Ok(())
+    }
+
+    pub fn remove(&mut self, id: u32) -> Option<Resource> {
+        self.resources.remove(&id)
+    }
+
+    pub fn get(&self, id: u32) -> Option<&Resource> {
+        self.resources.get(&id)
+    }
+
+    /// 無いか種類が違えば、その種類に対応するエラー
+    pub fn lookup(&self, id: u32, type_: ResourceType) -> Result<&Resource> {
+        match self.resources.get(&id) {
+            Some(resource) if resource.type_ == type_ => Ok(resource),
+            _ => Err(Error::ProtocolError(type_.error_code(), id)),
+        }
+    }
+
+    /// WindowかPixmapでなければBadDrawable
+    pub fn lookup_drawable(&self, id: u32) -> Result<&Resource> {
+        match self.resources.get(&id) {
+            Some(resource) if resource.type_ == ResourceType::Window || resource.type_ == ResourceType::Pixmap => Ok(resource),
+            _ => Err(Error::ProtocolError(ErrorCode::Drawable, id)),
+        }
+    }
+
+    pub fn resources_of(&self, client: ClientId) -> impl Iterator<Item=&Resource> {
+        self.resources.values().filter(move |resource| resource.client == client)
+    }
+
+    pub fn set_close_down_mode(&mut self, client: ClientId, mode: SetCloseDownModeMode) {
+        if let Some(client) = self.clients.get_mut(&client) {
+            client.close_down_mode = mode;
+        }
+    }
+
+    /// clientの接続が切れたときに呼ぶ
+    /// 破棄するべきresourceを返すので、呼び出し側で各resourceの後始末をする
+    pub fn disconnect_client(&mut self, client: ClientId) -> Vec<Resource> {
+        let mode = match self.clients.get_mut(&client) {
+            Some(c) => {
+                c.connected = false;
+                c.close_down_mode.clone()
+            }
+            None => return Vec::new(),
+        };
+        match mode {
+            SetCloseDownModeMode::Destroy => self.remove_client(client),
+            SetCloseDownModeMode::RetainPermanent | SetCloseDownModeMode::RetainTemporary => Vec::new(),
+        }
+    }
+
+    /// KillClientの処理
+    /// 対象が接続中のclientなら、接続を切るべきclientとしても返す
+    pub fn kill_client(&mut self, resource: KillClientResource) -> Result<(Option<ClientId>, Vec<Resource>)> {
+        match resource {
+            KillClientResource::AllTemporary => {
+                let clients = self.clients.iter()
+                    .filter(|(_, c)| !c.connected && c.close_down_mode == SetCloseDownModeMode::RetainTemporary)
+                    .map(|(client, _)| *client)
+                    .collect::<Vec<_>>();
+                let mut resources = Vec::new();
+                for client in clients {
+                    resources.append(&mut self.remove_client(client));
+                }
+                Ok((None, resources))
+            }
+            KillClientResource::Resource(id) => {
+                let client = self.resources.get(&id)
+                    .map(|resource| resource.client)
+                    .filter(|client| *client != SERVER_CLIENT)
+                    .ok_or(Error::ProtocolError(ErrorCode::Value, id))?;
+                if self.clients[&client].connected {
+                    //接続を切った後のdisconnect_clientで後始末する
+                    self.set_close_down_mode(client, SetCloseDownModeMode::Destroy);
+                    Ok((Some(client), Vec::new()))
+                } else {
+                    Ok((None, self.remove_client(client)))
+                }
+            }
+        }
+    }
+
+    fn remove_client(&mut self, client: ClientId) -> Vec<Resource> {
+        if client != SERVER_CLIENT {
+            self.clients.remove(&client);
+        }
+        let ids = self.resources_of(client).map(|resource| resource.id).collect::<Vec<_>>();
+        ids.into_iter().filter_map(|id| self.resources.remove(&id)).collect()
+    }
+
+    /// XC-MISCのGetXIDRange
+    /// clientの範囲で使われていない最も長い連続したidの範囲を(start_id, count)で返す
+    pub fn get_xid_range(&self, client: ClientId) -> (u32, u32) {
+        let range = match self.clients.get(&client) {
+            Some(c) => c.range.clone(),
+            None => return (0, 0),
+        };
+        //idの0は使えない
+        let first = range.base.max(1) as u64;
+        let last = (range.base | range.mask) as u64;
+        let mut best = (0, 0);
+        let mut start = first;
+        for id in self.resources.range(range.base..=range.base | range.mask).map(|(id, _)| *id as u64).chain(std::iter::once(last + 1)) {
+            if id > start && id - start > best.1 {
+                best = (start, id - start);
+            }
+            start = start.max(id + 1);
+        }
+        (best.0 as u32, best.1 as u32)
+    }
+}
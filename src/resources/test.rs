@@ -0,0 +1,98 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+use crate::Error;
+use crate::error::ErrorCode;
+use crate::request::kill_client::KillClientResource;
+use crate::request::set_close_down_mode::SetCloseDownModeMode;
+use super::*;
+
+#[test]
+fn add_client_test() {
+    let mut table = ResourceTable::new(0x001fffff);
+    assert_eq!(table.max_clients(), 256);
+    let (client, range) = table.add_client().unwrap();
+    assert_eq!(client, 1);
+    assert_eq!(range, ClientIdRange { base: 0x00200000, mask: 0x001fffff });
+    assert!(range.contains(0x00200001));
+    assert!(!range.contains(0x00400001));
+    let (client, range) = table.add_client().unwrap();
+    assert_eq!(client, 2);
+    assert_eq!(range.base, 0x00400000);
+    assert_eq!(table.client_of(0x00400005), Some(2));
+    assert_eq!(table.client_of(0x00600005), None);
+}
+
+#[test]
+fn add_test() {
+    let mut table = ResourceTable::new(0x001fffff);
+    let (client, _) = table.add_client().unwrap();
+    table.add(client, 0x00200001, ResourceType::Window).unwrap();
+    assert!(matches!(table.add(client, 0x00200001, ResourceType::Pixmap), Err(Error::ProtocolError(ErrorCode::IDChoice, 0x00200001))));
+    assert!(matches!(table.add(client, 0x00400001, ResourceType::Pixmap), Err(Error::ProtocolError(ErrorCode::IDChoice, 0x00400001))));
+    table.add(client, 0x00200002, ResourceType::GContext).unwrap();
+
+    assert_eq!(table.lookup(0x00200001, ResourceType::Window).unwrap().client, client);
+    assert!(matches!(table.lookup(0x00200001, ResourceType::Pixmap), Err(Error::ProtocolError(ErrorCode::Pixmap, 0x00200001))));
+    assert!(matches!(table.lookup(0x00200003, ResourceType::Font), Err(Error::ProtocolError(ErrorCode::Font, 0x00200003))));
+    assert!(table.lookup_drawable(0x00200001).is_ok());
+    assert!(matches!(table.lookup_drawable(0x00200002), Err(Error::ProtocolError(ErrorCode::Drawable, 0x00200002))));
+
+    assert_eq!(table.remove(0x00200001).map(|r| r.type_), Some(ResourceType::Window));
+    table.add(client, 0x00200001, ResourceType::Pixmap).unwrap();
+}
+
+#[test]
+fn disconnect_client_test() {
+    let mut table = ResourceTable::new(0x001fffff);
+    table.add(SERVER_CLIENT, 0x00000001, ResourceType::Window).unwrap();
+    let (destroy, _) = table.add_client().unwrap();
+    let (retain, _) = table.add_client().unwrap();
+    table.add(destroy, 0x00200001, ResourceType::Window).unwrap();
+    table.add(destroy, 0x00200002, ResourceType::Cursor).unwrap();
+    table.add(retain, 0x00400001, ResourceType::Window).unwrap();
+    table.set_close_down_mode(retain, SetCloseDownModeMode::RetainTemporary);
+
+    let destroyed = table.disconnect_client(destroy);
+    assert_eq!(destroyed.iter().map(|r| r.id).collect::<Vec<_>>(), vec![0x00200001, 0x00200002]);
+    assert!(table.disconnect_client(retain).is_empty());
+    assert!(table.get(0x00400001).is_some());
+
+    //切断されたclientの範囲は再利用され、retainしたclientの範囲は使われない
+    assert_eq!(table.add_client().unwrap().0, destroy);
+    assert_eq!(table.add_client().unwrap().0, 3);
+
+    let (killed, destroyed) = table.kill_client(KillClientResource::AllTemporary).unwrap();
+    assert_eq!(killed, None);
+    assert_eq!(destroyed.len(), 1);
+    assert!(table.get(0x00400001).is_none());
+    assert!(table.get(0x00000001).is_some());
+}
+
+#[test]
+fn kill_client_test() {
+    let mut table = ResourceTable::new(0x001fffff);
+    table.add(SERVER_CLIENT, 0x00000001, ResourceType::Window).unwrap();
+    let (client, _) = table.add_client().unwrap();
+    table.add(client, 0x00200001, ResourceType::Font).unwrap();
+    table.set_close_down_mode(client, SetCloseDownModeMode::RetainPermanent);
+
+    assert!(matches!(table.kill_client(KillClientResource::Resource(0x00000001)), Err(Error::ProtocolError(ErrorCode::Value, 0x00000001))));
+    assert_eq!(table.kill_client(KillClientResource::Resource(0x00200001)).unwrap(), (Some(client), Vec::new()));
+    assert_eq!(table.disconnect_client(client).len(), 1);
+    assert!(table.client_range(client).is_none());
+}
+
+#[test]
+fn get_xid_range_test() {
+    let mut table = ResourceTable::new(0x000000ff);
+    let (client, _) = table.add_client().unwrap();
+    assert_eq!(table.get_xid_range(client), (0x00000100, 0x100));
+    table.add(client, 0x00000110, ResourceType::Window).unwrap();
+    table.add(client, 0x00000180, ResourceType::Window).unwrap();
+    assert_eq!(table.get_xid_range(client), (0x00000181, 0x7f));
+    table.add(client, 0x000001a0, ResourceType::Window).unwrap();
+    assert_eq!(table.get_xid_range(client), (0x00000111, 0x6f));
+    assert_eq!(table.get_xid_range(SERVER_CLIENT), (0x00000001, 0xff));
+    assert_eq!(table.get_xid_range(100), (0, 0));
+}
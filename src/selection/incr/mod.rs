@@ -0,0 +1,204 @@
+/// ICCCMのINCRによる大きなselectionの受け渡し
+/// 所有者側はPropertyNotifyのDeletedごとに次の塊を書き、要求側はNewValueごとに読んで消す
+/// どちらもrequestを組み立てるだけなので、送信やeventの受信は呼び出し側で行う
+use crate::{Error, Result};
+use crate::event::{Event, PropertyNotifyState};
+use crate::request::change_property::{ChangePropertyMode, ChangePropertyRequest, PropertyValue};
+use crate::request::get_property::{GetPropertyRequest, GetPropertyResponse};
+
+mod test;
+
+/// ChangePropertyのheaderの長さ
+const CHANGE_PROPERTY_HEADER: usize = 24;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SenderState {
+    /// 要求側がpropertyを消すのを待っている
+    Sending,
+    Done,
+}
+
+/// selectionの所有者側
+#[derive(Clone, Debug, PartialEq)]
+pub struct IncrSender {
+    requestor: u32,
+    property: u32,
+    type_: u32,
+    value: PropertyValue,
+    /// 次に送る位置で、formatの単位
+    offset: usize,
+    /// 一度に送る長さで、formatの単位
+    chunk_len: usize,
+    state: SenderState,
+}
+
+impl IncrSender {
+    /// SelectionRequestのrequestorのpropertyに最初に書くChangePropertyも返す
+    /// maximum_request_lengthは4byte単位で、値が1つのrequestに収まればINCRを使わずにそのまま書く
+    pub fn start(requestor: u32, property: u32, type_: u32, value: PropertyValue, incr: u32, maximum_request_length: u32) -> (Self, ChangePropertyRequest) {
+        let unit = value.format() as usize / 8;
+        let chunk_bytes = (maximum_request_length as usize * 4).saturating_sub(CHANGE_PROPERTY_HEADER);
+        let chunk_len = (chunk_bytes / unit).max(1);
+        let mut sender = IncrSender {
+            requestor,
+            property,
+            type_,
+            value,
+            offset: 0,
+            chunk_len,
+            state: SenderState::Sending,
+        };
+        let request = if sender.value.len() <= chunk_len {
+            sender.state = SenderState::Done;
+            sender.change(type_, sender.value.clone())
+        } else {
+            //値は全体の大きさの下限
+            sender.change(incr, PropertyValue::Format32(vec![sender.value.byte_len() as u32]))
+        };
+        (sender, request)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.state == SenderState::Done
+    }
+
+    /// requestorのpropertyが消されたら次の塊を返す
+    /// 全部送ったあとは長さ0の値を書いて終わる
+    pub fn handle_event(&mut self, event: &Event) -> Option<ChangePropertyRequest> {
+        match event {
+            Event::PropertyNotify(event) if event.window == self.requestor && event.atom == self.property && event.state == PropertyNotifyState::Deleted => {}
+            _ => return None,
+        }
+        if self.state == SenderState::Done {
+            return None;
+        }
+        let end = (self.offset + self.chunk_len).min(self.value.len());
+        if self.offset == end {
+            self.state = SenderState::Done;
+        }
+        let chunk = self.value.slice(self.offset..end);
+        self.offset = end;
+        Some(self.change(self.type_, chunk))
+    }
+
+    fn change(&self, type_: u32, data: PropertyValue) -> ChangePropertyRequest {
+        ChangePropertyRequest {
+            mode: ChangePropertyMode::Replace,
+            window: self.requestor,
+            property: self.property,
+            type_,
+            data,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ReceiverState {
+    /// SelectionNotifyを待っている
+    Waiting,
+    /// SelectionNotifyのあとの最初のGetPropertyの返事を待っている
+    Converted,
+    /// INCRの塊を待っている
+    Receiving,
+    Done,
+}
+
+/// selectionを要求する側
+#[derive(Clone, Debug, PartialEq)]
+pub struct IncrReceiver {
+    requestor: u32,
+    property: u32,
+    incr: u32,
+    type_: Option<u32>,
+    value: Option<PropertyValue>,
+    state: ReceiverState,
+}
+
+impl IncrReceiver {
+    /// ConvertSelectionに指定したrequestorとproperty
+    pub fn new(requestor: u32, property: u32, incr: u32) -> Self {
+        IncrReceiver {
+            requestor,
+            property,
+            incr,
+            type_: None,
+            value: None,
+            state: ReceiverState::Waiting,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.state == ReceiverState::Done
+    }
+
+    /// 受け取った値のtypeと値
+    /// 変換できなかったときはNone
+    pub fn value(&self) -> Option<(u32, &PropertyValue)> {
+        match (self.state, self.type_, self.value.as_ref()) {
+            (ReceiverState::Done, Some(type_), Some(value)) => Some((type_, value)),
+            _ => None,
+        }
+    }
+
+    /// SelectionNotifyか、INCRの途中のPropertyNotifyのNewValueならpropertyを読んで消すGetPropertyを返す
+    pub fn handle_event(&mut self, event: &Event) -> Option<GetPropertyRequest> {
+        match (self.state, event) {
+            (ReceiverState::Waiting, Event::SelectionNotify(event)) if event.requestor == self.requestor => {
+                if event.property != Some(self.property) {
+                    //propertyがNoneなら変換できなかった
+                    self.state = ReceiverState::Done;
+                    return None;
+                }
+                self.state = ReceiverState::Converted;
+            }
+            (ReceiverState::Receiving, Event::PropertyNotify(event)) if event.window == self.requestor && event.atom == self.property && event.state == PropertyNotifyState::NewValue => {}
+            _ => return None,
+        }
+        Some(GetPropertyRequest {
+            delete: true,
+            window: self.requestor,
+            property: self.property,
+            type_: None,
+            long_offset: 0,
+            long_length: u32::MAX / 4,
+        })
+    }
+
+    /// handle_eventが返したGetPropertyの返事
+    /// 塊ごとにformatが変わればInvalidValue
+    pub fn handle_reply(&mut self, response: GetPropertyResponse) -> Result<()> {
+        match self.state {
+            ReceiverState::Converted if response.type_ == Some(self.incr) => {
+                self.state = ReceiverState::Receiving;
+            }
+            ReceiverState::Converted => {
+                self.type_ = response.type_;
+                self.value = response.value;
+                self.state = ReceiverState::Done;
+            }
+            ReceiverState::Receiving => {
+                let chunk = match response.value {
+                    Some(chunk) => chunk,
+                    None => return Err(Error::InvalidValue("IncrReceiver")),
+                };
+                if chunk.is_empty() {
+                    if self.value.is_none() {
+                        self.value = Some(chunk);
+                    }
+                    self.type_ = response.type_;
+                    self.state = ReceiverState::Done;
+                    return Ok(());
+                }
+                self.value = match (self.value.take(), chunk) {
+                    (None, chunk) => Some(chunk),
+                    (Some(PropertyValue::Format8(mut value)), PropertyValue::Format8(chunk)) => { value.extend(chunk); Some(PropertyValue::Format8(value)) }
+                    (Some(PropertyValue::Format16(mut value)), PropertyValue::Format16(chunk)) => { value.extend(chunk); Some(PropertyValue::Format16(value)) }
+                    (Some(PropertyValue::Format32(mut value)), PropertyValue::Format32(chunk)) => { value.extend(chunk); Some(PropertyValue::Format32(value)) }
+                    _ => return Err(Error::InvalidValue("IncrReceiver")),
+                };
+            }
+            ReceiverState::Waiting | ReceiverState::Done => return Err(Error::InvalidValue("IncrReceiver")),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+use std::collections::HashSet;
+use std::io::BufWriter;
+use std::iter::FromIterator;
+
+use crate::atom::{self, AtomTable};
+use crate::event::{Event, SelectionNotifyEvent};
+use crate::read_util::{ByteOrder, Writable};
+use crate::request::change_property::{ChangePropertyRequest, PropertyValue};
+use crate::request::change_window_attributes::ChangeWindowAttributesRequest;
+use crate::request::create_window::{CreateWindowClass, CreateWindowRequest, CreateWindowValue, CreateWindowValueMaskValue};
+use crate::setup;
+#[cfg(test)]
+use crate::test_util::{create_window, screen};
+use crate::window::WindowTree;
+use super::*;
+
+#[test]
+fn transfer_test() {
+    /// 所有者はclient 1、要求側はclient 2でwindow 0x00400001のpropertyを使う
+    /// 受け取った値と、所有者が書いたChangePropertyの数を返す
+    fn transfer(value: PropertyValue, maximum_request_length: u32) -> (Option<(u32, PropertyValue)>, usize) {
+        let atoms = AtomTable::new();
+        let incr = atoms.intern("INCR", false).unwrap().unwrap();
+        let mut tree = WindowTree::new(&[screen()]);
+        tree.create_window(2, &create_window(0x00400001, 1, 0, 0, 10, 10)).unwrap();
+        let select = ChangeWindowAttributesRequest {
+            window: 0x00400001,
+            value_mask: HashSet::from_iter([CreateWindowValueMaskValue::EventMask].to_vec()),
+            value: CreateWindowValue { event_mask: HashSet::from_iter([setup::Event::PropertyChange].to_vec()), ..Default::default() },
+        };
+        tree.change_window_attributes(1, &select).unwrap();
+        tree.change_window_attributes(2, &select).unwrap();
+
+        let mut time = 0;
+        let mut writes = 0;
+        let mut write = |tree: &mut WindowTree, request: ChangePropertyRequest| {
+            let mut buffer = Vec::new();
+            ChangePropertyRequest::write(&mut BufWriter::new(&mut buffer), request.clone(), &ByteOrder::MSBFirst).unwrap();
+            assert!(buffer.len() <= maximum_request_length as usize * 4);
+            time += 1;
+            writes += 1;
+            tree.change_property(&atoms, &request, time).unwrap();
+        };
+        let mut receiver = IncrReceiver::new(0x00400001, atom::CUT_BUFFER0, incr);
+        let (mut sender, request) = IncrSender::start(0x00400001, atom::CUT_BUFFER0, atom::STRING, value, incr, maximum_request_length);
+        write(&mut tree, request);
+        tree.take_events();
+        //所有者がSendEventで送るSelectionNotify
+        let mut events = vec![(2, Event::SelectionNotify(SelectionNotifyEvent { sequence_number: 0, time: 0, requestor: 0x00400001, selection: atom::PRIMARY, target: atom::STRING, property: Some(atom::CUT_BUFFER0) }))];
+        while !events.is_empty() {
+            for (client, event) in events {
+                if client == 1 {
+                    if let Some(request) = sender.handle_event(&event) {
+                        write(&mut tree, request);
+                    }
+                } else if let Some(request) = receiver.handle_event(&event) {
+                    let response = tree.get_property(&atoms, &request, 0).unwrap();
+                    receiver.handle_reply(response).unwrap();
+                }
+            }
+            events = tree.take_events();
+        }
+        assert!(sender.is_done());
+        assert!(receiver.is_done());
+        assert!(tree.get(0x00400001).unwrap().properties.get(atom::CUT_BUFFER0).is_none());
+        (receiver.value().map(|(type_, value)| (type_, value.clone())), writes)
+    }
+
+    //1つのrequestに収まればINCRを使わない
+    let value = PropertyValue::Format8(b"hello".to_vec());
+    assert_eq!(transfer(value.clone(), 16), (Some((atom::STRING, value)), 1));
+
+    //1回に40byteずつ送り、最後に長さ0の値を書く
+    let value = PropertyValue::Format8((0..1000).map(|i| i as u8).collect());
+    assert_eq!(transfer(value.clone(), 16), (Some((atom::STRING, value)), 1 + 25 + 1));
+
+    let value = PropertyValue::Format32((0..100).collect());
+    assert_eq!(transfer(value.clone(), 16), (Some((atom::STRING, value)), 1 + 10 + 1));
+
+    let value = PropertyValue::Format16((0..0x8000).collect());
+    assert_eq!(transfer(value.clone(), 0x4000), (Some((atom::STRING, value)), 1 + 2 + 1));
+}
+
+#[test]
+fn refused_test() {
+    let mut receiver = IncrReceiver::new(0x00400001, atom::CUT_BUFFER0, 69);
+    let event = Event::SelectionNotify(SelectionNotifyEvent { sequence_number: 0, time: 0, requestor: 0x00400001, selection: atom::PRIMARY, target: atom::STRING, property: None });
+    assert!(receiver.handle_event(&event).is_none());
+    assert!(receiver.is_done());
+    assert!(receiver.value().is_none());
+}
+
+#[test]
+fn format_mismatch_test() {
+    let mut receiver = IncrReceiver::new(0x00400001, atom::CUT_BUFFER0, 69);
+    let event = Event::SelectionNotify(SelectionNotifyEvent { sequence_number: 0, time: 0, requestor: 0x00400001, selection: atom::PRIMARY, target: atom::STRING, property: Some(atom::CUT_BUFFER0) });
+    assert!(receiver.handle_event(&event).is_some());
+    receiver.handle_reply(GetPropertyResponse { sequence_number: 0, type_: Some(69), bytes_after: 0, value: Some(PropertyValue::Format32(vec![100])) }).unwrap();
+    receiver.handle_reply(GetPropertyResponse { sequence_number: 0, type_: Some(atom::STRING), bytes_after: 0, value: Some(PropertyValue::Format8(vec![1])) }).unwrap();
+    assert!(matches!(receiver.handle_reply(GetPropertyResponse { sequence_number: 0, type_: Some(atom::STRING), bytes_after: 0, value: Some(PropertyValue::Format16(vec![1])) }), Err(Error::InvalidValue(_))));
+}
@@ -0,0 +1,144 @@
+/// selectionの所有者と、SetSelectionOwner、ConvertSelectionの規則
+/// selectionのeventはevent maskに関係なく所有者や要求したclientに送る
+use std::collections::HashMap;
+
+use crate::Result;
+use crate::atom::AtomTable;
+use crate::event::{Event, SelectionClearEvent, SelectionNotifyEvent, SelectionRequestEvent};
+use crate::request::convert_selection::ConvertSelectionRequest;
+use crate::request::get_selection_owner::{GetSelectionOwnerRequest, GetSelectionOwnerResponse};
+use crate::request::set_selection_owner::SetSelectionOwnerRequest;
+use crate::resources::ClientId;
+use crate::time;
+use crate::window::WindowTree;
+
+pub mod incr;
+
+mod test;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Selection {
+    pub owner: Option<u32>,
+    pub client: Option<ClientId>,
+    pub last_change_time: u32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SelectionTable {
+    selections: HashMap<u32, Selection>,
+    events: Vec<(ClientId, Event)>,
+}
+
+impl SelectionTable {
+    pub fn new() -> Self {
+        SelectionTable::default()
+    }
+
+    pub fn get(&self, selection: u32) -> Option<&Selection> {
+        self.selections.get(&selection)
+    }
+
+    /// 送り先のclientとeventを取り出す
+    pub fn take_events(&mut self) -> Vec<(ClientId, Event)> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// nowはserverの現在時刻
+    /// timeが最後に変更された時刻より前か、現在時刻より後なら何もしない
+    pub fn set_selection_owner(&mut self, client: ClientId, windows: &WindowTree, atoms: &AtomTable, request: &SetSelectionOwnerRequest, now: u32) -> Result<()> {
+        if let Some(owner) = request.owner {
+            windows.lookup(owner)?;
+        }
+        atoms.name(request.selection)?;
+        let time = time::resolve(request.time, now);
+        if time::is_later(time, now) {
+            return Ok(());
+        }
+        if let Some(current) = self.selections.get(&request.selection) {
+            if time::is_later(current.last_change_time, time) {
+                return Ok(());
+            }
+            //所有者のclientが変わるか、所有者がいなくなれば前の所有者に知らせる
+            if let (Some(owner), Some(current_client)) = (current.owner, current.client) {
+                if request.owner.is_none() || current_client != client {
+                    self.events.push((current_client, Event::SelectionClear(SelectionClearEvent {
+                        sequence_number: 0,
+                        time,
+                        owner,
+                        selection: request.selection,
+                    })));
+                }
+            }
+        }
+        self.selections.insert(request.selection, Selection {
+            owner: request.owner,
+            client: request.owner.map(|_| client),
+            last_change_time: time,
+        });
+        Ok(())
+    }
+
+    pub fn get_selection_owner(&self, atoms: &AtomTable, request: &GetSelectionOwnerRequest) -> Result<GetSelectionOwnerResponse> {
+        atoms.name(request.selection)?;
+        Ok(GetSelectionOwnerResponse {
+            sequence_number: 0,
+            owner: self.selections.get(&request.selection).and_then(|selection| selection.owner),
+        })
+    }
+
+    /// 所有者がいればSelectionRequestを送り、いなければ要求したclientにpropertyがNoneのSelectionNotifyを送る
+    pub fn convert_selection(&mut self, client: ClientId, windows: &WindowTree, atoms: &AtomTable, request: &ConvertSelectionRequest) -> Result<()> {
+        windows.lookup(request.requestor)?;
+        atoms.name(request.selection)?;
+        atoms.name(request.target)?;
+        if let Some(property) = request.property {
+            atoms.name(property)?;
+        }
+        let owner = self.selections.get(&request.selection).and_then(|selection| selection.owner.zip(selection.client));
+        match owner {
+            Some((owner, owner_client)) => {
+                self.events.push((owner_client, Event::SelectionRequest(SelectionRequestEvent {
+                    sequence_number: 0,
+                    time: request.time,
+                    owner,
+                    requestor: request.requestor,
+                    selection: request.selection,
+                    target: request.target,
+                    property: request.property,
+                })));
+            }
+            None => {
+                self.events.push((client, Event::SelectionNotify(SelectionNotifyEvent {
+                    sequence_number: 0,
+                    time: request.time,
+                    requestor: request.requestor,
+                    selection: request.selection,
+                    target: request.target,
+                    property: None,
+                })));
+            }
+        }
+        Ok(())
+    }
+
+    /// 破棄したwindowが所有していたselectionを手放す
+    /// 最後に変更された時刻はそのまま
+    pub fn remove_windows(&mut self, windows: &[u32]) {
+        for selection in self.selections.values_mut() {
+            if selection.owner.map(|owner| windows.contains(&owner)).unwrap_or(false) {
+                selection.owner = None;
+                selection.client = None;
+            }
+        }
+    }
+
+    /// 接続が切れたclientが所有していたselectionを手放す
+    pub fn remove_client(&mut self, client: ClientId) {
+        for selection in self.selections.values_mut() {
+            if selection.client == Some(client) {
+                selection.owner = None;
+                selection.client = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+use std::collections::HashSet;
+
+use crate::Error;
+use crate::atom::{self, AtomTable};
+use crate::error::ErrorCode;
+use crate::event::{Event, SelectionClearEvent, SelectionNotifyEvent, SelectionRequestEvent};
+use crate::request::convert_selection::ConvertSelectionRequest;
+use crate::request::create_window::{CreateWindowClass, CreateWindowRequest, CreateWindowValue};
+use crate::request::destroy_window::DestroyWindowRequest;
+use crate::request::get_selection_owner::{GetSelectionOwnerRequest, GetSelectionOwnerResponse};
+use crate::request::set_selection_owner::SetSelectionOwnerRequest;
+#[cfg(test)]
+use crate::test_util::{create_window, screen};
+use crate::window::WindowTree;
+use super::*;
+
+#[test]
+fn selection_test() {
+    fn create(wid: u32) -> CreateWindowRequest {
+        create_window(wid, 1, 0, 0, 10, 10)
+    }
+    fn owner(selections: &SelectionTable, atoms: &AtomTable) -> Option<u32> {
+        selections.get_selection_owner(atoms, &GetSelectionOwnerRequest { selection: atom::PRIMARY }).unwrap().owner
+    }
+
+    let atoms = AtomTable::new();
+    let mut tree = WindowTree::new(&[screen()]);
+    tree.create_window(1, &create(0x00200001)).unwrap();
+    tree.create_window(2, &create(0x00400001)).unwrap();
+    let mut selections = SelectionTable::new();
+
+    //所有者がいなければ要求したclientにSelectionNotifyが返る
+    let convert = ConvertSelectionRequest { requestor: 0x00400001, selection: atom::PRIMARY, target: atom::STRING, property: Some(atom::CUT_BUFFER0), time: 0 };
+    selections.convert_selection(2, &tree, &atoms, &convert).unwrap();
+    assert_eq!(selections.take_events(), vec![
+        (2, Event::SelectionNotify(SelectionNotifyEvent { sequence_number: 0, time: 0, requestor: 0x00400001, selection: atom::PRIMARY, target: atom::STRING, property: None })),
+    ]);
+
+    let set = SetSelectionOwnerRequest { owner: Some(0x00200001), selection: atom::PRIMARY, time: 0 };
+    selections.set_selection_owner(1, &tree, &atoms, &set, 100).unwrap();
+    assert_eq!(owner(&selections, &atoms), Some(0x00200001));
+    assert_eq!(selections.get(atom::PRIMARY), Some(&Selection { owner: Some(0x00200001), client: Some(1), last_change_time: 100 }));
+    selections.convert_selection(2, &tree, &atoms, &convert).unwrap();
+    assert_eq!(selections.take_events(), vec![
+        (1, Event::SelectionRequest(SelectionRequestEvent { sequence_number: 0, time: 0, owner: 0x00200001, requestor: 0x00400001, selection: atom::PRIMARY, target: atom::STRING, property: Some(atom::CUT_BUFFER0) })),
+    ]);
+
+    //古いtimestampや未来のtimestampは無視する
+    let stale = SetSelectionOwnerRequest { owner: Some(0x00400001), selection: atom::PRIMARY, time: 99 };
+    selections.set_selection_owner(2, &tree, &atoms, &stale, 200).unwrap();
+    let future = SetSelectionOwnerRequest { owner: Some(0x00400001), selection: atom::PRIMARY, time: 201 };
+    selections.set_selection_owner(2, &tree, &atoms, &future, 200).unwrap();
+    assert_eq!(owner(&selections, &atoms), Some(0x00200001));
+    assert!(selections.take_events().is_empty());
+
+    //所有者が変われば前の所有者にSelectionClearを送る
+    let set = SetSelectionOwnerRequest { owner: Some(0x00400001), selection: atom::PRIMARY, time: 150 };
+    selections.set_selection_owner(2, &tree, &atoms, &set, 200).unwrap();
+    assert_eq!(owner(&selections, &atoms), Some(0x00400001));
+    assert_eq!(selections.take_events(), vec![
+        (1, Event::SelectionClear(SelectionClearEvent { sequence_number: 0, time: 150, owner: 0x00200001, selection: atom::PRIMARY })),
+    ]);
+    //同じclientが所有し直してもSelectionClearは送らない
+    selections.set_selection_owner(2, &tree, &atoms, &SetSelectionOwnerRequest { time: 160, ..set.clone() }, 200).unwrap();
+    assert!(selections.take_events().is_empty());
+
+    assert!(matches!(selections.set_selection_owner(1, &tree, &atoms, &SetSelectionOwnerRequest { owner: Some(5), ..set.clone() }, 200), Err(Error::ProtocolError(ErrorCode::Window, 5))));
+    assert!(matches!(selections.set_selection_owner(1, &tree, &atoms, &SetSelectionOwnerRequest { selection: 1000, ..set.clone() }, 200), Err(Error::ProtocolError(ErrorCode::Atom, 1000))));
+    assert!(matches!(selections.get_selection_owner(&atoms, &GetSelectionOwnerRequest { selection: 1000 }), Err(Error::ProtocolError(ErrorCode::Atom, 1000))));
+    assert!(matches!(selections.convert_selection(2, &tree, &atoms, &ConvertSelectionRequest { target: 1000, ..convert.clone() }), Err(Error::ProtocolError(ErrorCode::Atom, 1000))));
+
+    //所有しているwindowが破棄されれば所有者はいなくなるが、時刻はそのまま
+    let destroyed = tree.destroy_window(&DestroyWindowRequest { window: 0x00400001 }).unwrap();
+    selections.remove_windows(&destroyed);
+    assert_eq!(selections.get(atom::PRIMARY), Some(&Selection { owner: None, client: None, last_change_time: 160 }));
+    assert!(selections.take_events().is_empty());
+
+    selections.set_selection_owner(1, &tree, &atoms, &SetSelectionOwnerRequest { owner: Some(0x00200001), selection: atom::SECONDARY, time: 0 }, 300).unwrap();
+    selections.remove_client(1);
+    assert_eq!(selections.get_selection_owner(&atoms, &GetSelectionOwnerRequest { selection: atom::SECONDARY }).unwrap(), GetSelectionOwnerResponse { sequence_number: 0, owner: None });
+}
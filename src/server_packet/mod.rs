@@ -0,0 +1,169 @@
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufReader, Read, Write};
+use std::marker::PhantomData;
+
+use crate::{Error, Result};
+use crate::error::XError;
+use crate::event::EventPacket;
+use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable};
+use crate::request::ReplyRequest;
+
+mod test;
+
+/// 型が決まる前のreply
+/// dataは先頭の1から始まるpacket全体
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reply {
+    pub sequence_number: u16,
+    pub data: Vec<u8>,
+}
+
+impl Reply {
+    pub fn decode<T: Readable>(&self, order: &ByteOrder) -> Result<T> {
+        T::read(&mut BufReader::new(&self.data[..]), order)
+    }
+}
+
+/// serverからclientに送られるpacket
+#[derive(Clone, Debug, PartialEq)]
+pub enum ServerPacket {
+    Reply(Reply),
+    Error(XError),
+    Event(EventPacket),
+}
+
+impl Readable for ServerPacket {
+    fn read(stream: &mut BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
+        let mut data = vec![0; 32];
+        read_specified_length(stream, &mut data[..], 32)?;
+        //replyとGenericEventは32byteより後ろに続きがある
+        if data[0] == 1 || data[0] & 0x7f == 35 {
+            let length = order.decode::<u32>(&data[4..8]) as usize;
+            data.resize(32 + (length << 2), 0);
+            read_specified_length(stream, &mut data[32..], length << 2)?;
+        }
+        match data[0] {
+            0 => Ok(ServerPacket::Error(XError::read(&mut BufReader::new(&data[..]), order)?)),
+            1 => Ok(ServerPacket::Reply(Reply {
+                sequence_number: order.decode(&data[2..4]),
+                data,
+            })),
+            _ => Ok(ServerPacket::Event(EventPacket::read(&mut BufReader::new(&data[..]), order)?)),
+        }
+    }
+}
+
+impl Writable for ServerPacket {
+    fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
+        match data {
+            ServerPacket::Reply(reply) => stream.write_all(&reply.data[..]).map_err(Error::IoError),
+            ServerPacket::Error(error) => XError::write(stream, error, order),
+            ServerPacket::Event(event) => EventPacket::write(stream, event, order),
+        }
+    }
+}
+
+/// replyを受け取るための引換券
+#[derive(Debug, PartialEq)]
+pub struct Cookie<T> {
+    pub sequence: u64,
+    reply: PhantomData<T>,
+}
+
+/// client側で送ったrequestの数を数えて、受け取ったreplyとerrorを対応するrequestに振り分ける
+/// sequence numberはpacket上では16bitなので、最後に送ったrequestから遡って64bitに復元する
+#[derive(Debug, Default)]
+pub struct ReplyDispatcher {
+    sent: u64,
+    received: u64,
+    replies: HashMap<u64, VecDeque<Reply>>,
+    errors: HashMap<u64, XError>,
+}
+
+impl ReplyDispatcher {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// replyの無いrequestを送ったときに呼ぶ
+    pub fn send_request(&mut self) -> u64 {
+        self.sent += 1;
+        self.sent
+    }
+
+    /// replyのあるrequestを送ったときに呼ぶ
+    pub fn send_reply_request<R: ReplyRequest>(&mut self, _request: &R) -> Cookie<R::Reply> {
+        let sequence = self.send_request();
+        self.replies.insert(sequence, VecDeque::new());
+        Cookie { sequence, reply: PhantomData }
+    }
+
+    pub fn last_sent(&self) -> u64 {
+        self.sent
+    }
+
+    pub fn last_received(&self) -> u64 {
+        self.received
+    }
+
+    pub fn widen(&self, sequence_number: u16) -> u64 {
+        let distance = (self.sent as u16).wrapping_sub(sequence_number) as u64;
+        self.sent.saturating_sub(distance)
+    }
+
+    /// 待っているrequestへのreplyかerrorなら保持してNone
+    /// それ以外(eventや、replyの無いrequestへのerror)はそのまま返す
+    pub fn dispatch(&mut self, packet: ServerPacket) -> Option<ServerPacket> {
+        match packet {
+            ServerPacket::Reply(reply) => {
+                let sequence = self.widen(reply.sequence_number);
+                self.received = self.received.max(sequence);
+                match self.replies.get_mut(&sequence) {
+                    Some(queue) => {
+                        queue.push_back(reply);
+                        None
+                    }
+                    None => Some(ServerPacket::Reply(reply)),
+                }
+            }
+            ServerPacket::Error(error) => {
+                let sequence = self.widen(error.sequence_number);
+                self.received = self.received.max(sequence);
+                if self.replies.contains_key(&sequence) {
+                    self.errors.insert(sequence, error);
+                    None
+                } else {
+                    Some(ServerPacket::Error(error))
+                }
+            }
+            ServerPacket::Event(event) => {
+                //eventのsequence numberまでのrequestは処理済み
+                if let Some(sequence_number) = event.event.sequence_number() {
+                    self.received = self.received.max(self.widen(sequence_number));
+                }
+                Some(ServerPacket::Event(event))
+            }
+        }
+    }
+
+    pub fn read_packet(&mut self, stream: &mut BufReader<impl Read>, order: &ByteOrder) -> Result<Option<ServerPacket>> {
+        let packet = stream.read_value(order)?;
+        Ok(self.dispatch(packet))
+    }
+
+    /// 届いたreplyを型付けして取り出す
+    /// まだ届いていなければNone、errorが返ってきていればopcodeを含めたErr(Error::XError)
+    pub fn take_reply<T: Readable>(&mut self, cookie: &Cookie<T>, order: &ByteOrder) -> Option<Result<T>> {
+        if let Some(error) = self.errors.remove(&cookie.sequence) {
+            self.replies.remove(&cookie.sequence);
+            return Some(Err(Error::XError(error)));
+        }
+        let queue = self.replies.get_mut(&cookie.sequence)?;
+        let reply = queue.pop_front();
+        //ListFontsWithInfoのように複数のreplyが返るrequestでは1つずつ取り出すので、後続のrequestの応答が来るまでは残しておく
+        if queue.is_empty() && self.received > cookie.sequence {
+            self.replies.remove(&cookie.sequence);
+        }
+        reply.map(|reply| reply.decode(order))
+    }
+}
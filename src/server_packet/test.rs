@@ -0,0 +1,212 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+mod server_packet {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::error::{ErrorCode, XError};
+    use crate::event::{Event, EventPacket, GenericEvent, MapRequestEvent};
+    use crate::read_util::{ByteOrder, Readable, Writable};
+    use crate::server_packet::{Reply, ServerPacket};
+
+    #[test]
+    fn read_test() {
+        let mut input = [0; 100];
+        input[..12].copy_from_slice(&[1, 24, 0, 3, 0, 0, 0, 1, 0, 0, 0, 1]);
+        input[32..36].copy_from_slice(&[1, 2, 3, 4]);
+        input[36..40].copy_from_slice(&[0, 1, 0, 4]);
+        input[68..80].copy_from_slice(&[20, 0, 0, 5, 0, 0, 0, 1, 0, 0, 0, 2]);
+        let mut stream = BufReader::new(&input[..]);
+        let value = ServerPacket::read(&mut stream, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, ServerPacket::Reply(Reply { sequence_number: 3, data: input[..36].to_vec() }));
+        let value = ServerPacket::read(&mut stream, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, ServerPacket::Error(XError {
+            code: ErrorCode::Request,
+            sequence_number: 4,
+            bad_value: 0,
+            minor_opcode: 0,
+            major_opcode: 0,
+        }));
+        let value = ServerPacket::read(&mut stream, &ByteOrder::MSBFirst).unwrap();
+        assert_eq!(value, ServerPacket::Event(EventPacket {
+            send_event: false,
+            event: Event::MapRequest(MapRequestEvent { sequence_number: 5, parent: 1, window: 2 }),
+        }));
+    }
+
+    #[test]
+    fn read_generic_event_test() {
+        let input = [
+            35, 130, 2, 0, 1, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4,
+        ];
+        let value = ServerPacket::read(&mut BufReader::new(&input[..]), &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(value, ServerPacket::Event(EventPacket {
+            send_event: false,
+            event: Event::Generic(GenericEvent {
+                extension: 130,
+                sequence_number: 2,
+                event_type: 3,
+                data: [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4].to_vec(),
+            }),
+        }));
+    }
+
+    #[test]
+    fn write_test() {
+        let value = ServerPacket::Error(XError {
+            code: ErrorCode::Window,
+            sequence_number: 4,
+            bad_value: 2,
+            minor_opcode: 0,
+            major_opcode: 8,
+        });
+        let mut buffer = [0; 32];
+        ServerPacket::write(&mut BufWriter::new(&mut buffer[..]), value, &ByteOrder::LSBFirst).unwrap();
+        assert_eq!(buffer, [0, 3, 4, 0, 2, 0, 0, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    }
+}
+
+mod reply_dispatcher {
+    use std::io::{BufReader, BufWriter};
+
+    use crate::Error;
+    use crate::error::{ErrorCode, XError};
+    use crate::event::{Event, EventPacket, MapRequestEvent};
+    use crate::read_util::{ByteOrder, Writable};
+    use crate::request::get_geometry::{GetGeometryRequest, GetGeometryResponse};
+    use crate::request::list_fonts_with_info::{FontWithInfo, ListFontsWithInfoReply, ListFontsWithInfoRequest, ListFontsWithInfoResponse};
+    use crate::request::query_font::{CharInfo, DrawDirection, FontInfo};
+    use crate::request::query_extension::{QueryExtensionRequest, QueryExtensionResponse};
+    use crate::server_packet::{Reply, ReplyDispatcher, ServerPacket};
+
+    #[test]
+    fn widen_test() {
+        let mut dispatcher = ReplyDispatcher::new();
+        for _ in 0..0x1_0005 {
+            dispatcher.send_request();
+        }
+        assert_eq!(dispatcher.widen(0x0005), 0x1_0005);
+        assert_eq!(dispatcher.widen(0x0001), 0x1_0001);
+        assert_eq!(dispatcher.widen(0xfffe), 0xfffe);
+    }
+
+    #[test]
+    fn pipeline_test() {
+        let mut dispatcher = ReplyDispatcher::new();
+        let geometry = dispatcher.send_reply_request(&GetGeometryRequest { drawable: 1 });
+        dispatcher.send_request();
+        let extension = dispatcher.send_reply_request(&QueryExtensionRequest { name: "BIG-REQUESTS".to_string() });
+        let mut input = [0; 128];
+        input[..20].copy_from_slice(&[1, 24, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 8, 0, 4, 0]);
+        input[32..43].copy_from_slice(&[0, 3, 2, 0, 1, 0, 0, 0, 0, 0, 4]);
+        input[64..74].copy_from_slice(&[1, 0, 3, 0, 0, 0, 0, 0, 1, 133]);
+        input[96..108].copy_from_slice(&[20, 0, 3, 0, 1, 0, 0, 0, 2, 0, 0, 0]);
+        let order = ByteOrder::LSBFirst;
+        let mut stream = BufReader::new(&input[..]);
+        assert_eq!(dispatcher.read_packet(&mut stream, &order).unwrap(), None);
+        assert_eq!(dispatcher.read_packet(&mut stream, &order).unwrap(), Some(ServerPacket::Error(XError {
+            code: ErrorCode::Window,
+            sequence_number: 2,
+            bad_value: 1,
+            minor_opcode: 0,
+            major_opcode: 4,
+        })));
+        assert_eq!(dispatcher.read_packet(&mut stream, &order).unwrap(), None);
+        assert_eq!(dispatcher.read_packet(&mut stream, &order).unwrap(), Some(ServerPacket::Event(EventPacket {
+            send_event: false,
+            event: Event::MapRequest(MapRequestEvent { sequence_number: 3, parent: 1, window: 2 }),
+        })));
+        //届いた順と違う順番で取り出せる
+        assert_eq!(dispatcher.take_reply(&extension, &order).unwrap().unwrap(), QueryExtensionResponse {
+            sequence_number: 3,
+            present: true,
+            major_opcode: 133,
+            first_event: 0,
+            first_error: 0,
+        });
+        assert_eq!(dispatcher.take_reply(&geometry, &order).unwrap().unwrap(), GetGeometryResponse {
+            depth: 24,
+            sequence_number: 1,
+            root: 1,
+            x: 0,
+            y: 0,
+            width: 8,
+            height: 4,
+            border_width: 0,
+        });
+        assert!(dispatcher.take_reply(&geometry, &order).is_none());
+        assert_eq!(dispatcher.last_received(), 3);
+    }
+
+    #[test]
+    fn error_test() {
+        let mut dispatcher = ReplyDispatcher::new();
+        let geometry = dispatcher.send_reply_request(&GetGeometryRequest { drawable: 1 });
+        let input = [0, 9, 0, 1, 0, 0, 0, 1, 0, 0, 14, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let order = ByteOrder::MSBFirst;
+        assert_eq!(dispatcher.read_packet(&mut BufReader::new(&input[..]), &order).unwrap(), None);
+        match dispatcher.take_reply(&geometry, &order) {
+            Some(Err(Error::XError(XError { code: ErrorCode::Drawable, bad_value: 1, major_opcode: 14, minor_opcode: 0, .. }))) => {}
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn event_sequence_test() {
+        let mut dispatcher = ReplyDispatcher::new();
+        let geometry = dispatcher.send_reply_request(&GetGeometryRequest { drawable: 1 });
+        dispatcher.send_request();
+        let mut input = [0; 64];
+        input[..20].copy_from_slice(&[1, 24, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 8, 0, 4, 0]);
+        input[32..44].copy_from_slice(&[20, 0, 2, 0, 1, 0, 0, 0, 2, 0, 0, 0]);
+        let order = ByteOrder::LSBFirst;
+        let mut stream = BufReader::new(&input[..]);
+        assert_eq!(dispatcher.read_packet(&mut stream, &order).unwrap(), None);
+        assert!(dispatcher.take_reply(&geometry, &order).unwrap().is_ok());
+        //まだ続きのreplyが来るかもしれない
+        assert!(dispatcher.replies.contains_key(&geometry.sequence));
+        //replyの無いrequestのsequence numberを持つeventで、それより前の応答は終わったと分かる
+        assert!(dispatcher.read_packet(&mut stream, &order).unwrap().is_some());
+        assert_eq!(dispatcher.last_received(), 2);
+        assert!(dispatcher.take_reply(&geometry, &order).is_none());
+        assert!(dispatcher.replies.is_empty());
+    }
+
+    #[test]
+    fn multiple_replies_test() {
+        let mut dispatcher = ReplyDispatcher::new();
+        let fonts = dispatcher.send_reply_request(&ListFontsWithInfoRequest { max_names: 10, pattern: "*".to_string() });
+        let bounds = CharInfo { left_side_bearing: 0, right_side_bearing: 6, character_width: 6, ascent: 11, descent: 2, attributes: 0 };
+        let info = FontInfo {
+            min_bounds: bounds,
+            max_bounds: bounds,
+            min_char_or_byte2: 0,
+            max_char_or_byte2: 0xff,
+            default_char: 0,
+            draw_direction: DrawDirection::LeftToRight,
+            min_byte1: 0,
+            max_byte1: 0,
+            all_chars_exist: false,
+            font_ascent: 11,
+            font_descent: 2,
+            properties: vec![],
+        };
+        let expected = vec![
+            FontWithInfo { name: "fixed".to_string(), info: info.clone(), replies_hint: 1 },
+            FontWithInfo { name: "6x13".to_string(), info, replies_hint: 0 },
+        ];
+        let order = ByteOrder::MSBFirst;
+        let mut input = Vec::new();
+        ListFontsWithInfoResponse::write(&mut BufWriter::new(&mut input), ListFontsWithInfoResponse { sequence_number: 1, fonts: expected.clone() }, &order).unwrap();
+        let mut stream = BufReader::new(&input[..]);
+        for _ in 0..3 {
+            assert_eq!(dispatcher.read_packet(&mut stream, &order).unwrap(), None);
+        }
+        //fontごとの返事と最後の返事を1つずつ取り出す
+        for font in expected {
+            assert_eq!(dispatcher.take_reply(&fonts, &order).unwrap().unwrap(), ListFontsWithInfoReply { sequence_number: 1, font: Some(font) });
+        }
+        assert_eq!(dispatcher.take_reply(&fonts, &order).unwrap().unwrap(), ListFontsWithInfoReply { sequence_number: 1, font: None });
+        assert!(dispatcher.take_reply(&fonts, &order).is_none());
+    }
+}
@@ -3,6 +3,7 @@ use std::collections::HashSet;
 use std::io::{Read, Write};
 
 use crate::{Error, Result};
+use crate::error::ErrorCode;
 use crate::read_util::{ByteOrder, read_specified_length, Readable, ReadableRead, Writable, WritableWrite};
 
 mod test;
@@ -15,6 +16,7 @@ pub struct ConnectionSetupInformation {
     pub authorization_protocol_data: String,
 }
 
+#[allow(clippy::redundant_closure)]
 pub fn read_setup(stream: &mut std::io::BufReader<impl Read>, buffer: &mut [u8]) -> Result<(ByteOrder, ConnectionSetupInformation)> {
     assert!(buffer.len() >= 10);
     read_specified_length(stream, buffer, 2)?;
@@ -63,6 +65,7 @@ pub fn read_setup(stream: &mut std::io::BufReader<impl Read>, buffer: &mut [u8])
     Ok((order, information))
 }
 
+#[allow(clippy::needless_as_bytes, clippy::redundant_closure)]
 pub fn write_setup(stream: &mut std::io::BufWriter<impl Write>, buffer: &mut [u8], order: &ByteOrder, info: ConnectionSetupInformation) -> Result<()> {
     assert!(buffer.len() >= 12);
     buffer[0] =
@@ -94,6 +97,7 @@ pub struct ConnectionSetupFailed {
     pub reason: String,
 }
 
+#[allow(clippy::redundant_closure)]
 impl Readable for ConnectionSetupFailed {
     fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {//最初のopcodeは読まない
         let n = stream.read_value::<u8>(order)? as usize;
@@ -112,6 +116,7 @@ impl Readable for ConnectionSetupFailed {
     }
 }
 
+#[allow(clippy::redundant_closure)]
 impl Writable for ConnectionSetupFailed {
     fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {//最初のopcodeも送る
         stream.write_value(0u8, order)?;
@@ -134,6 +139,7 @@ pub struct ConnectionSetupAuthenticate {
     pub reason: String,
 }
 
+#[allow(clippy::redundant_closure)]
 impl Readable for ConnectionSetupAuthenticate {
     fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {//最初のopcodeは読まない
         read_specified_length(stream, &mut [0; 5], 5)?;
@@ -150,6 +156,7 @@ impl Readable for ConnectionSetupAuthenticate {
     }
 }
 
+#[allow(clippy::redundant_closure)]
 impl Writable for ConnectionSetupAuthenticate {
     fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {//最初のopcodeも送る
         stream.write_value(2u8, order)?;
@@ -184,6 +191,7 @@ impl Readable for ImageByteOrder {
     }
 }
 
+#[allow(clippy::redundant_guards)]
 impl Writable for ImageByteOrder {
     fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, _order: &ByteOrder) -> Result<()> {
         let buffer = [
@@ -218,6 +226,7 @@ impl Readable for BitmapFormatBitOrder {
     }
 }
 
+#[allow(clippy::redundant_guards)]
 impl Writable for BitmapFormatBitOrder {
     fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, _order: &ByteOrder) -> Result<()> {
         let buffer = [
@@ -255,6 +264,7 @@ impl Readable for Format {
     }
 }
 
+#[allow(clippy::redundant_closure)]
 impl Writable for Format {
     fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
         stream.write_value(data.depth, order)?;
@@ -365,6 +375,7 @@ impl Readable for VisualType {
     }
 }
 
+#[allow(clippy::redundant_closure)]
 impl Writable for VisualType {
     fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
         stream.write_value(data.visual_id, order)?;
@@ -402,6 +413,7 @@ impl Readable for Depth {
     }
 }
 
+#[allow(clippy::redundant_closure)]
 impl Writable for Depth {
     fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
         stream.write_value(data.depth, order)?;
@@ -446,6 +458,7 @@ pub enum Event {
     OwnerGrabButton,
 }
 
+#[allow(clippy::needless_range_loop)]
 impl Readable for HashSet<Event> {
     fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
         const VALUES: [Event; 25] = [
@@ -476,7 +489,7 @@ impl Readable for HashSet<Event> {
             Event::OwnerGrabButton,
         ];
         let value = stream.read_value::<u32>(order)?;
-        if value & 0xFE000000 != 0 { return Err(Error::InvalidValue("Set of Event")); }
+        if value & 0xFE000000 != 0 { return Err(Error::ProtocolError(ErrorCode::Value, value)); }
         let count = {
             let value = (value & 0x55555555) + ((value >> 1) & 0x55555555);
             let value = (value & 0x33333333) + ((value >> 2) & 0x33333333);
@@ -494,6 +507,7 @@ impl Readable for HashSet<Event> {
     }
 }
 
+#[allow(clippy::needless_range_loop)]
 impl Writable for HashSet<Event> {
     fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
         const VALUES: [Event; 25] = [
@@ -643,6 +657,7 @@ pub struct ConnectionSetupSuccess {
     pub roots: Vec<Screen>,
 }
 
+#[allow(clippy::redundant_closure)]
 impl Readable for ConnectionSetupSuccess {
     fn read(stream: &mut std::io::BufReader<impl Read>, order: &ByteOrder) -> Result<Self> {
         read_specified_length(stream, &mut [0; 1], 1)?;
@@ -697,6 +712,7 @@ impl Readable for ConnectionSetupSuccess {
     }
 }
 
+#[allow(clippy::needless_as_bytes, clippy::redundant_closure)]
 impl Writable for ConnectionSetupSuccess {
     fn write(stream: &mut std::io::BufWriter<impl Write>, data: Self, order: &ByteOrder) -> Result<()> {
         stream.write_value(1u8, order)?;
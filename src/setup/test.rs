@@ -8,6 +8,7 @@ mod setup {
     use crate::setup::{ConnectionSetupInformation, read_setup, write_setup};
 
     #[test]
+    #[allow(clippy::double_parens)]
     fn read_setup_test() {
         let input = vec![0o102, 0, 0, 11, 0, 0, 0, 0, 0, 0, 0, 0];
         assert_eq!(
@@ -361,6 +362,7 @@ mod setup_success {
     }
 
     #[test]
+    #[allow(clippy::useless_vec)]
     fn depth_write_test() {
         let value1 = VisualType {
             visual_id: 1,
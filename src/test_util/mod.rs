@@ -0,0 +1,64 @@
+/// 複数のmoduleのtestで使うfixture
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+use crate::request::create_window::{CreateWindowClass, CreateWindowRequest, CreateWindowValue, CreateWindowValueMaskValue};
+use crate::setup::{self, BackingStores, Class, Depth, Screen, VisualType};
+
+/// root 1、640x480、depth 24でvisual 3のscreen
+pub(crate) fn screen() -> Screen {
+    Screen {
+        root: 1,
+        default_colormap: 2,
+        white_pixel: 0xffffff,
+        black_pixel: 0,
+        current_input_masks: Default::default(),
+        width_in_pixels: 640,
+        height_in_pixels: 480,
+        width_in_millimeters: 160,
+        height_in_millimeters: 120,
+        min_installed_maps: 1,
+        max_installed_maps: 1,
+        root_visual: 3,
+        backing_stores: BackingStores::Never,
+        save_unders: false,
+        root_depth: 24,
+        allowed_depths: vec![Depth {
+            depth: 24,
+            visuals: vec![VisualType {
+                visual_id: 3,
+                class: Class::TrueColor,
+                bits_per_rgb_value: 8,
+                colormap_entries: 256,
+                red_mask: 0xff0000,
+                green_mask: 0x00ff00,
+                blue_mask: 0x0000ff,
+            }],
+        }],
+    }
+}
+
+/// borderが無く、属性を指定しないInputOutputのwindow
+pub(crate) fn create_window(wid: u32, parent: u32, x: i16, y: i16, width: u16, height: u16) -> CreateWindowRequest {
+    CreateWindowRequest {
+        depth: 0,
+        wid,
+        parent,
+        x,
+        y,
+        width,
+        height,
+        border_width: 0,
+        class: CreateWindowClass::InputOutput,
+        visual: None,
+        value_mask: HashSet::new(),
+        value: CreateWindowValue::default(),
+    }
+}
+
+/// 作るときにevent_maskを選択する
+pub(crate) fn select_input(mut request: CreateWindowRequest, event_mask: &[setup::Event]) -> CreateWindowRequest {
+    request.value_mask.insert(CreateWindowValueMaskValue::EventMask);
+    request.value.event_mask = HashSet::from_iter(event_mask.to_vec());
+    request
+}
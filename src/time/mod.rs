@@ -0,0 +1,20 @@
+/// requestのtimestampの扱い
+/// timestampはmillisecondで32bitを一周するので、差が半周未満なら後の時刻とみなす
+mod test;
+
+/// requestのtimeが0ならserverの現在時刻
+pub const CURRENT_TIME: u32 = 0;
+
+/// timeがotherより後ならtrue
+pub fn is_later(time: u32, other: u32) -> bool {
+    time != other && time.wrapping_sub(other) < 0x80000000
+}
+
+/// CurrentTimeをserverの現在時刻nowに置き換える
+pub fn resolve(time: u32, now: u32) -> u32 {
+    if time == CURRENT_TIME {
+        now
+    } else {
+        time
+    }
+}
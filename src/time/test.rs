@@ -0,0 +1,16 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+use super::*;
+
+#[test]
+fn is_later_test() {
+    assert!(is_later(2, 1));
+    assert!(!is_later(1, 2));
+    assert!(!is_later(1, 1));
+    //一周した直後の時刻は一周前の時刻より後
+    assert!(is_later(5, 0xfffffff0));
+    assert!(!is_later(0xfffffff0, 5));
+    assert_eq!(resolve(CURRENT_TIME, 100), 100);
+    assert_eq!(resolve(50, 100), 50);
+}
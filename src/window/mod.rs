@@ -0,0 +1,973 @@
+/// windowの木構造を持ち、windowに関するrequestを実行する
+/// 生成したeventのsequence_numberは0なので、clientに送るときにそのclientの値に書き換える
+use std::collections::{HashMap, HashSet};
+
+use crate::{Error, Result};
+use crate::atom::AtomTable;
+use crate::error::ErrorCode;
+use crate::event::{CirculateEvent, CirculatePlace, ColormapNotifyEvent, ColormapNotifyState, ConfigureNotifyEvent, ConfigureRequestEvent, CreateNotifyEvent, DestroyNotifyEvent, Event, GravityNotifyEvent, MapNotifyEvent, MapRequestEvent, PropertyNotifyEvent, PropertyNotifyState, ReparentNotifyEvent, ResizeRequestEvent, UnmapNotifyEvent};
+use crate::property::PropertyStore;
+use crate::request::change_property::ChangePropertyRequest;
+use crate::request::change_window_attributes::ChangeWindowAttributesRequest;
+use crate::request::circulate_window::{CirculateWindowDirection, CirculateWindowRequest};
+use crate::request::configure_window::{ConfigureWindowRequest, ConfigureWindowValueMaskValue, ConfigureWindowValueStackMode};
+use crate::request::create_window::{CreateWindowClass, CreateWindowRequest, CreateWindowValue, CreateWindowValueColormap, CreateWindowValueMaskValue, CreateWindowValueWinGravity};
+use crate::request::delete_property::DeletePropertyRequest;
+use crate::request::destroy_subwindows::DestroySubwindowsRequest;
+use crate::request::destroy_window::DestroyWindowRequest;
+use crate::request::get_geometry::{GetGeometryRequest, GetGeometryResponse};
+use crate::request::get_property::{GetPropertyRequest, GetPropertyResponse};
+use crate::request::get_window_attributes::{GetWindowAttributesMapState, GetWindowAttributesRequest, GetWindowAttributesResponse};
+use crate::request::list_properties::{ListPropertiesRequest, ListPropertiesResponse};
+use crate::request::map_subwindows::MapSubwindowsRequest;
+use crate::request::map_window::MapWindowRequest;
+use crate::request::query_tree::{QueryTreeRequest, QueryTreeResponse};
+use crate::request::reparent_window::ReparentWindowRequest;
+use crate::request::rotate_properties::RotatePropertiesRequest;
+use crate::request::translate_coordinates::{TranslateCoordinatesRequest, TranslateCoordinatesResponse};
+use crate::request::unmap_subwindows::UnmapSubwindowsRequest;
+use crate::request::unmap_window::UnmapWindowRequest;
+use crate::resources::ClientId;
+use crate::setup::{self, Screen};
+
+mod test;
+
+/// 同時に1つのclientしか選択できないevent
+const EXCLUSIVE_EVENTS: [setup::Event; 3] = [setup::Event::SubstructureRedirect, setup::Event::ResizeRedirect, setup::Event::ButtonPress];
+
+/// InputOnlyのwindowには指定できない属性
+const INPUT_OUTPUT_ATTRIBUTES: [CreateWindowValueMaskValue; 10] = [
+    CreateWindowValueMaskValue::BackgroundPixmap,
+    CreateWindowValueMaskValue::BackgroundPixel,
+    CreateWindowValueMaskValue::BorderPixmap,
+    CreateWindowValueMaskValue::BorderPixel,
+    CreateWindowValueMaskValue::BitGravity,
+    CreateWindowValueMaskValue::BackingStore,
+    CreateWindowValueMaskValue::BackingPlanes,
+    CreateWindowValueMaskValue::BackingPixel,
+    CreateWindowValueMaskValue::SaveUnder,
+    CreateWindowValueMaskValue::Colormap,
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Window {
+    pub id: u32,
+    pub parent: Option<u32>,
+    /// 下から上への重なり順
+    pub children: Vec<u32>,
+    /// borderの外側の左上の、親の内側からの位置
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    pub border_width: u16,
+    pub depth: u8,
+    pub class: CreateWindowClass,
+    pub visual: u32,
+    /// event_maskは使わず、clientごとにevent_masksで持つ
+    pub attributes: CreateWindowValue,
+    pub event_masks: HashMap<ClientId, HashSet<setup::Event>>,
+    pub mapped: bool,
+    pub properties: PropertyStore,
+}
+
+impl Window {
+    /// borderを含めた親の座標系での矩形
+    fn outer_rect(&self) -> (i32, i32, i32, i32) {
+        let border = self.border_width as i32 * 2;
+        (self.x as i32, self.y as i32, self.width as i32 + border, self.height as i32 + border)
+    }
+
+    fn overlaps(&self, other: &Window) -> bool {
+        let (ax, ay, aw, ah) = self.outer_rect();
+        let (bx, by, bw, bh) = other.outer_rect();
+        ax < bx + bw && bx < ax + aw && ay < by + bh && by < ay + ah
+    }
+
+    /// maskを選択しているclient
+    pub fn clients_selecting(&self, mask: &setup::Event) -> Vec<ClientId> {
+        let mut clients = self.event_masks.iter()
+            .filter(|(_, masks)| masks.contains(mask))
+            .map(|(client, _)| *client)
+            .collect::<Vec<_>>();
+        clients.sort();
+        clients
+    }
+
+    /// 他のclientが選択しているSubstructureRedirect、ResizeRedirect、ButtonPressは選択できない
+    fn select_input(&mut self, client: ClientId, event_mask: &HashSet<setup::Event>) -> Result<()> {
+        for mask in EXCLUSIVE_EVENTS.iter() {
+            if event_mask.contains(mask) && self.clients_selecting(mask).iter().any(|c| *c != client) {
+                return Err(Error::ProtocolError(ErrorCode::Access, self.id));
+            }
+        }
+        if event_mask.is_empty() {
+            self.event_masks.remove(&client);
+        } else {
+            self.event_masks.insert(client, event_mask.clone());
+        }
+        Ok(())
+    }
+
+    pub fn all_event_masks(&self) -> HashSet<setup::Event> {
+        self.event_masks.values().flatten().cloned().collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct WindowTree {
+    screens: Vec<Screen>,
+    windows: HashMap<u32, Window>,
+    events: Vec<(ClientId, Event)>,
+}
+
+impl WindowTree {
+    /// screenごとにroot windowを作る
+    pub fn new(screens: &[Screen]) -> Self {
+        let mut windows = HashMap::new();
+        for screen in screens {
+            let attributes = CreateWindowValue {
+                colormap: CreateWindowValueColormap::Colormap(screen.default_colormap),
+                background_pixel: screen.black_pixel,
+                ..Default::default()
+            };
+            windows.insert(screen.root, Window {
+                id: screen.root,
+                parent: None,
+                children: Vec::new(),
+                x: 0,
+                y: 0,
+                width: screen.width_in_pixels,
+                height: screen.height_in_pixels,
+                border_width: 0,
+                depth: screen.root_depth,
+                class: CreateWindowClass::InputOutput,
+                visual: screen.root_visual,
+                attributes,
+                event_masks: HashMap::new(),
+                mapped: true,
+                properties: PropertyStore::new(),
+            });
+        }
+        WindowTree {
+            screens: screens.to_vec(),
+            windows,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn get(&self, window: u32) -> Option<&Window> {
+        self.windows.get(&window)
+    }
+
+    /// 無ければBadWindow
+    pub fn lookup(&self, window: u32) -> Result<&Window> {
+        self.windows.get(&window).ok_or(Error::ProtocolError(ErrorCode::Window, window))
+    }
+
+    /// これまでに生成したeventを送り先のclientと一緒に取り出す
+    pub fn take_events(&mut self) -> Vec<(ClientId, Event)> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// screenの順のroot window
+    pub fn roots(&self) -> Vec<u32> {
+        self.screens.iter().map(|screen| screen.root).collect()
+    }
+
+    pub fn root_of(&self, window: u32) -> Option<u32> {
+        let mut current = self.windows.get(&window)?;
+        while let Some(parent) = current.parent {
+            current = &self.windows[&parent];
+        }
+        Some(current.id)
+    }
+
+    fn screen_of(&self, window: u32) -> Option<&Screen> {
+        let root = self.root_of(window)?;
+        self.screens.iter().find(|screen| screen.root == root)
+    }
+
+    /// windowから根までのwindow(window自身を含む)
+    pub fn ancestors(&self, window: u32) -> Vec<u32> {
+        let mut result = Vec::new();
+        let mut current = self.windows.get(&window);
+        while let Some(w) = current {
+            result.push(w.id);
+            current = w.parent.map(|parent| &self.windows[&parent]);
+        }
+        result
+    }
+
+    /// windowがancestorの子孫ならtrue
+    pub fn is_inferior(&self, window: u32, ancestor: u32) -> bool {
+        let mut current = self.windows.get(&window).and_then(|w| w.parent);
+        while let Some(id) = current {
+            if id == ancestor {
+                return true;
+            }
+            current = self.windows[&id].parent;
+        }
+        false
+    }
+
+    /// 自分と全ての祖先がmapされていればviewable
+    pub fn is_viewable(&self, window: u32) -> bool {
+        if !self.windows.contains_key(&window) {
+            return false;
+        }
+        let mut current = self.windows.get(&window);
+        while let Some(w) = current {
+            if !w.mapped {
+                return false;
+            }
+            current = w.parent.map(|parent| &self.windows[&parent]);
+        }
+        true
+    }
+
+    /// 内側の左上のrootからの座標
+    pub fn origin(&self, window: u32) -> Option<(i32, i32)> {
+        let mut current = self.windows.get(&window)?;
+        let (mut x, mut y) = (0, 0);
+        while let Some(parent) = current.parent {
+            x += current.x as i32 + current.border_width as i32;
+            y += current.y as i32 + current.border_width as i32;
+            current = &self.windows[&parent];
+        }
+        Some((x, y))
+    }
+
+    /// rootの座標(x, y)を含む一番深いmapされたwindow
+    /// 子は上にあるものから調べる
+    pub fn window_at(&self, root: u32, x: i32, y: i32) -> u32 {
+        let (mut x, mut y) = (x, y);
+        let mut current = root;
+        'descend: loop {
+            for child in self.windows[&current].children.iter().rev() {
+                let child = &self.windows[child];
+                let (cx, cy, cw, ch) = child.outer_rect();
+                if child.mapped && cx <= x && x < cx + cw && cy <= y && y < cy + ch {
+                    x -= cx + child.border_width as i32;
+                    y -= cy + child.border_width as i32;
+                    current = child.id;
+                    continue 'descend;
+                }
+            }
+            return current;
+        }
+    }
+
+    /// 接続が切れたclientが選択していたeventを消す
+    pub fn remove_client(&mut self, client: ClientId) {
+        for window in self.windows.values_mut() {
+            window.event_masks.remove(&client);
+        }
+    }
+
+    fn send(&mut self, window: u32, mask: setup::Event, event: Event) {
+        for client in self.windows[&window].clients_selecting(&mask) {
+            self.events.push((client, event.clone()));
+        }
+    }
+
+    /// windowのStructureNotifyと親のSubstructureNotifyを選択しているclientに送る
+    /// eventにはeventのfieldに入れるwindowを渡す
+    fn notify(&mut self, window: u32, event: impl Fn(u32) -> Event) {
+        self.send(window, setup::Event::StructureNotify, event(window));
+        if let Some(parent) = self.windows[&window].parent {
+            self.send(parent, setup::Event::SubstructureNotify, event(parent));
+        }
+    }
+
+    /// clientとは別のclientがmaskを選択していれば、そのclient
+    fn redirect_client(&self, client: ClientId, window: u32, mask: setup::Event) -> Option<ClientId> {
+        self.windows[&window].clients_selecting(&mask).into_iter().find(|c| *c != client)
+    }
+
+    /// 親のSubstructureRedirectを別のclientが選択していれば、そのclient
+    fn substructure_redirect_client(&self, client: ClientId, window: u32) -> Option<ClientId> {
+        let window = &self.windows[&window];
+        if window.attributes.override_redirect {
+            return None;
+        }
+        window.parent.and_then(|parent| self.redirect_client(client, parent, setup::Event::SubstructureRedirect))
+    }
+
+    fn below_sibling(&self, window: u32) -> Option<u32> {
+        let parent = self.windows[&window].parent?;
+        let children = &self.windows[&parent].children;
+        let index = children.iter().position(|child| *child == window)?;
+        if index == 0 { None } else { Some(children[index - 1]) }
+    }
+
+    /// upperがlowerより上にあり、両方mapされていて重なっていればtrue
+    fn occludes(&self, upper: u32, lower: u32) -> bool {
+        let (upper_window, lower_window) = (&self.windows[&upper], &self.windows[&lower]);
+        if upper == lower || !upper_window.mapped || !lower_window.mapped || upper_window.parent != lower_window.parent {
+            return false;
+        }
+        let children = match upper_window.parent {
+            Some(parent) => &self.windows[&parent].children,
+            None => return false,
+        };
+        let upper_index = children.iter().position(|child| *child == upper);
+        let lower_index = children.iter().position(|child| *child == lower);
+        upper_index > lower_index && upper_window.overlaps(lower_window)
+    }
+
+    fn siblings(&self, window: u32) -> Vec<u32> {
+        match self.windows[&window].parent {
+            Some(parent) => self.windows[&parent].children.iter().filter(|child| **child != window).cloned().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn check_attributes(class: &CreateWindowClass, value_mask: &HashSet<CreateWindowValueMaskValue>, window: u32) -> Result<()> {
+        if *class == CreateWindowClass::InputOnly && INPUT_OUTPUT_ATTRIBUTES.iter().any(|mask| value_mask.contains(mask)) {
+            return Err(Error::ProtocolError(ErrorCode::Match, window));
+        }
+        Ok(())
+    }
+
+    fn select_input(&mut self, client: ClientId, window: u32, event_mask: &HashSet<setup::Event>) -> Result<()> {
+        let target = self.windows.get_mut(&window).unwrap();
+        target.select_input(client, event_mask)
+    }
+
+    /// idが他のresourceと重なっていないかは呼び出し側でResourceTableを使って確かめる
+    pub fn create_window(&mut self, client: ClientId, request: &CreateWindowRequest) -> Result<()> {
+        let parent = self.lookup(request.parent)?;
+        if self.windows.contains_key(&request.wid) {
+            return Err(Error::ProtocolError(ErrorCode::IDChoice, request.wid));
+        }
+        if request.width == 0 || request.height == 0 {
+            return Err(Error::ProtocolError(ErrorCode::Value, 0));
+        }
+        let class = match request.class {
+            CreateWindowClass::CopyFromParent => parent.class.clone(),
+            ref other => other.clone(),
+        };
+        let visual = request.visual.unwrap_or(parent.visual);
+        let depth = match class {
+            CreateWindowClass::InputOnly => {
+                if request.depth != 0 || request.border_width != 0 {
+                    return Err(Error::ProtocolError(ErrorCode::Match, request.wid));
+                }
+                0
+            }
+            _ => {
+                if parent.class == CreateWindowClass::InputOnly {
+                    return Err(Error::ProtocolError(ErrorCode::Match, request.wid));
+                }
+                let depth = if request.depth == 0 { parent.depth } else { request.depth };
+                if depth != parent.depth || visual != parent.visual {
+                    let supported = self.screen_of(request.parent)
+                        .map(|screen| screen.allowed_depths.iter().any(|d| d.depth == depth && d.visuals.iter().any(|v| v.visual_id == visual)))
+                        .unwrap_or(false);
+                    if !supported {
+                        return Err(Error::ProtocolError(ErrorCode::Match, request.wid));
+                    }
+                }
+                depth
+            }
+        };
+        Self::check_attributes(&class, &request.value_mask, request.wid)?;
+        let mut attributes = request.value.clone();
+        attributes.event_mask = HashSet::new();
+        if attributes.colormap == CreateWindowValueColormap::CopyFromParent {
+            attributes.colormap = parent.attributes.colormap.clone();
+        }
+        let mut window = Window {
+            id: request.wid,
+            parent: Some(request.parent),
+            children: Vec::new(),
+            x: request.x,
+            y: request.y,
+            width: request.width,
+            height: request.height,
+            border_width: request.border_width,
+            depth,
+            class,
+            visual,
+            attributes,
+            event_masks: HashMap::new(),
+            mapped: false,
+            properties: PropertyStore::new(),
+        };
+        //木に加える前に確かめる
+        if request.value_mask.contains(&CreateWindowValueMaskValue::EventMask) {
+            window.select_input(client, &request.value.event_mask)?;
+        }
+        self.windows.insert(request.wid, window);
+        self.windows.get_mut(&request.parent).unwrap().children.push(request.wid);
+        let event = Event::CreateNotify(CreateNotifyEvent {
+            sequence_number: 0,
+            parent: request.parent,
+            window: request.wid,
+            x: request.x,
+            y: request.y,
+            width: request.width,
+            height: request.height,
+            border_width: request.border_width,
+            override_redirect: request.value.override_redirect,
+        });
+        self.send(request.parent, setup::Event::SubstructureNotify, event);
+        Ok(())
+    }
+
+    pub fn change_window_attributes(&mut self, client: ClientId, request: &ChangeWindowAttributesRequest) -> Result<()> {
+        let window = self.lookup(request.window)?;
+        Self::check_attributes(&window.class, &request.value_mask, request.window)?;
+        let parent_colormap = window.parent.map(|parent| self.windows[&parent].attributes.colormap.clone());
+        if request.value_mask.contains(&CreateWindowValueMaskValue::EventMask) {
+            self.select_input(client, request.window, &request.value.event_mask)?;
+        }
+        let value = &request.value;
+        let window = self.windows.get_mut(&request.window).unwrap();
+        let attributes = &mut window.attributes;
+        for mask in request.value_mask.iter() {
+            match mask {
+                CreateWindowValueMaskValue::BackgroundPixmap => attributes.background_pixmap = value.background_pixmap.clone(),
+                CreateWindowValueMaskValue::BackgroundPixel => attributes.background_pixel = value.background_pixel,
+                CreateWindowValueMaskValue::BorderPixmap => attributes.border_pixmap = value.border_pixmap.clone(),
+                CreateWindowValueMaskValue::BorderPixel => attributes.border_pixel = value.border_pixel,
+                CreateWindowValueMaskValue::BitGravity => attributes.bit_gravity = value.bit_gravity.clone(),
+                CreateWindowValueMaskValue::WinGravity => attributes.win_gravity = value.win_gravity.clone(),
+                CreateWindowValueMaskValue::BackingStore => attributes.backing_store = value.backing_store.clone(),
+                CreateWindowValueMaskValue::BackingPlanes => attributes.backing_planes = value.backing_planes,
+                CreateWindowValueMaskValue::BackingPixel => attributes.backing_pixel = value.backing_pixel,
+                CreateWindowValueMaskValue::OverrideRedirect => attributes.override_redirect = value.override_redirect,
+                CreateWindowValueMaskValue::SaveUnder => attributes.save_under = value.save_under,
+                CreateWindowValueMaskValue::EventMask => {}
+                CreateWindowValueMaskValue::DoNotPropagateMask => attributes.do_not_propagate_mask = value.do_not_propagate_mask.clone(),
+                CreateWindowValueMaskValue::Colormap => {}
+                CreateWindowValueMaskValue::Cursor => attributes.cursor = value.cursor,
+            }
+        }
+        if request.value_mask.contains(&CreateWindowValueMaskValue::Colormap) {
+            let colormap = match value.colormap {
+                CreateWindowValueColormap::CopyFromParent => parent_colormap.ok_or(Error::ProtocolError(ErrorCode::Match, request.window))?,
+                ref other => other.clone(),
+            };
+            attributes.colormap = colormap.clone();
+            let colormap = match colormap {
+                CreateWindowValueColormap::Colormap(colormap) => Some(colormap),
+                CreateWindowValueColormap::CopyFromParent => None,
+            };
+            let event = Event::ColormapNotify(ColormapNotifyEvent {
+                sequence_number: 0,
+                window: request.window,
+                colormap,
+                new: true,
+                state: ColormapNotifyState::Uninstalled,
+            });
+            self.send(request.window, setup::Event::ColormapChange, event);
+        }
+        Ok(())
+    }
+
+    /// colormapのinstallはまだ管理していないので、map_is_installedは常にfalse
+    pub fn get_window_attributes(&self, client: ClientId, request: &GetWindowAttributesRequest) -> Result<GetWindowAttributesResponse> {
+        let window = self.lookup(request.window)?;
+        let map_state = if !window.mapped {
+            GetWindowAttributesMapState::Unmapped
+        } else if self.is_viewable(request.window) {
+            GetWindowAttributesMapState::Viewable
+        } else {
+            GetWindowAttributesMapState::Unviewable
+        };
+        let colormap = match window.attributes.colormap {
+            CreateWindowValueColormap::Colormap(colormap) => Some(colormap),
+            CreateWindowValueColormap::CopyFromParent => None,
+        };
+        Ok(GetWindowAttributesResponse {
+            backing_store: window.attributes.backing_store.clone(),
+            sequence_number: 0,
+            visual: window.visual,
+            class: window.class.clone(),
+            bit_gravity: window.attributes.bit_gravity.clone(),
+            win_gravity: window.attributes.win_gravity.clone(),
+            backing_planes: window.attributes.backing_planes,
+            backing_pixel: window.attributes.backing_pixel,
+            save_under: window.attributes.save_under,
+            map_is_installed: false,
+            map_state,
+            override_redirect: window.attributes.override_redirect,
+            colormap,
+            all_event_masks: window.all_event_masks(),
+            your_event_mask: window.event_masks.get(&client).cloned().unwrap_or_default(),
+            do_not_propagate_mask: window.attributes.do_not_propagate_mask.clone(),
+        })
+    }
+
+    /// 破棄したwindowを子孫から順に返すので、呼び出し側でResourceTableからも消す
+    pub fn destroy_window(&mut self, request: &DestroyWindowRequest) -> Result<Vec<u32>> {
+        let window = self.lookup(request.window)?;
+        let parent = match window.parent {
+            Some(parent) => parent,
+            None => return Ok(Vec::new()),
+        };
+        if window.mapped {
+            self.unmap(request.window, false);
+        }
+        let mut destroyed = Vec::new();
+        self.destroy(request.window, &mut destroyed);
+        for id in destroyed.iter() {
+            self.windows.remove(id);
+        }
+        self.windows.get_mut(&parent).unwrap().children.retain(|child| *child != request.window);
+        Ok(destroyed)
+    }
+
+    fn destroy(&mut self, window: u32, destroyed: &mut Vec<u32>) {
+        for child in self.windows[&window].children.clone() {
+            self.destroy(child, destroyed);
+        }
+        self.notify(window, |event| Event::DestroyNotify(DestroyNotifyEvent {
+            sequence_number: 0,
+            event,
+            window,
+        }));
+        destroyed.push(window);
+    }
+
+    pub fn destroy_subwindows(&mut self, request: &DestroySubwindowsRequest) -> Result<Vec<u32>> {
+        let mut destroyed = Vec::new();
+        for child in self.lookup(request.window)?.children.clone() {
+            destroyed.append(&mut self.destroy_window(&DestroyWindowRequest { window: child })?);
+        }
+        Ok(destroyed)
+    }
+
+    pub fn map_window(&mut self, client: ClientId, request: &MapWindowRequest) -> Result<()> {
+        let window = self.lookup(request.window)?;
+        if window.mapped {
+            return Ok(());
+        }
+        if let Some(redirect) = self.substructure_redirect_client(client, request.window) {
+            let event = Event::MapRequest(MapRequestEvent {
+                sequence_number: 0,
+                parent: window.parent.unwrap(),
+                window: request.window,
+            });
+            self.events.push((redirect, event));
+            return Ok(());
+        }
+        let override_redirect = window.attributes.override_redirect;
+        self.windows.get_mut(&request.window).unwrap().mapped = true;
+        self.notify(request.window, |event| Event::MapNotify(MapNotifyEvent {
+            sequence_number: 0,
+            event,
+            window: request.window,
+            override_redirect,
+        }));
+        Ok(())
+    }
+
+    pub fn map_subwindows(&mut self, client: ClientId, request: &MapSubwindowsRequest) -> Result<()> {
+        for child in self.lookup(request.window)?.children.clone().into_iter().rev() {
+            self.map_window(client, &MapWindowRequest { window: child })?;
+        }
+        Ok(())
+    }
+
+    pub fn unmap_window(&mut self, request: &UnmapWindowRequest) -> Result<()> {
+        let window = self.lookup(request.window)?;
+        if window.mapped && window.parent.is_some() {
+            self.unmap(request.window, false);
+        }
+        Ok(())
+    }
+
+    fn unmap(&mut self, window: u32, from_configure: bool) {
+        self.windows.get_mut(&window).unwrap().mapped = false;
+        self.notify(window, |event| Event::UnmapNotify(UnmapNotifyEvent {
+            sequence_number: 0,
+            event,
+            window,
+            from_configure,
+        }));
+    }
+
+    pub fn unmap_subwindows(&mut self, request: &UnmapSubwindowsRequest) -> Result<()> {
+        for child in self.lookup(request.window)?.children.clone() {
+            self.unmap_window(&UnmapWindowRequest { window: child })?;
+        }
+        Ok(())
+    }
+
+    pub fn reparent_window(&mut self, client: ClientId, request: &ReparentWindowRequest) -> Result<()> {
+        let window = self.lookup(request.window)?;
+        let parent = self.lookup(request.parent)?;
+        let old_parent = match window.parent {
+            Some(old_parent) => old_parent,
+            None => return Err(Error::ProtocolError(ErrorCode::Match, request.window)),
+        };
+        if request.parent == request.window
+            || self.is_inferior(request.parent, request.window)
+            || self.root_of(request.parent) != self.root_of(request.window)
+            || (window.class == CreateWindowClass::InputOutput && parent.class == CreateWindowClass::InputOnly) {
+            return Err(Error::ProtocolError(ErrorCode::Match, request.window));
+        }
+        let mapped = window.mapped;
+        let override_redirect = window.attributes.override_redirect;
+        if mapped {
+            self.unmap(request.window, false);
+        }
+        self.windows.get_mut(&old_parent).unwrap().children.retain(|child| *child != request.window);
+        self.windows.get_mut(&request.parent).unwrap().children.push(request.window);
+        let window = self.windows.get_mut(&request.window).unwrap();
+        window.parent = Some(request.parent);
+        window.x = request.x;
+        window.y = request.y;
+        let event = |event| Event::ReparentNotify(ReparentNotifyEvent {
+            sequence_number: 0,
+            event,
+            window: request.window,
+            parent: request.parent,
+            x: request.x,
+            y: request.y,
+            override_redirect,
+        });
+        self.send(request.window, setup::Event::StructureNotify, event(request.window));
+        self.send(old_parent, setup::Event::SubstructureNotify, event(old_parent));
+        self.send(request.parent, setup::Event::SubstructureNotify, event(request.parent));
+        if mapped {
+            self.map_window(client, &MapWindowRequest { window: request.window })?;
+        }
+        Ok(())
+    }
+
+    pub fn configure_window(&mut self, client: ClientId, request: &ConfigureWindowRequest) -> Result<()> {
+        let window = self.lookup(request.window)?;
+        let parent = match window.parent {
+            Some(parent) => parent,
+            None => return Ok(()),
+        };
+        let (old_x, old_y, old_width, old_height, old_border_width) = (window.x, window.y, window.width, window.height, window.border_width);
+        let mask = &request.value_mask;
+        let value = &request.value;
+        let x = if mask.contains(&ConfigureWindowValueMaskValue::X) { value.x } else { window.x };
+        let y = if mask.contains(&ConfigureWindowValueMaskValue::Y) { value.y } else { window.y };
+        let mut width = if mask.contains(&ConfigureWindowValueMaskValue::Width) { value.width } else { window.width };
+        let mut height = if mask.contains(&ConfigureWindowValueMaskValue::Height) { value.height } else { window.height };
+        let border_width = if mask.contains(&ConfigureWindowValueMaskValue::BorderWidth) { value.border_width } else { window.border_width };
+        if width == 0 || height == 0 {
+            return Err(Error::ProtocolError(ErrorCode::Value, 0));
+        }
+        if window.class == CreateWindowClass::InputOnly && border_width != 0 {
+            return Err(Error::ProtocolError(ErrorCode::Match, request.window));
+        }
+        let sibling = if mask.contains(&ConfigureWindowValueMaskValue::Sibling) {
+            if !mask.contains(&ConfigureWindowValueMaskValue::StackMode) {
+                return Err(Error::ProtocolError(ErrorCode::Match, value.sibling));
+            }
+            let sibling = self.lookup(value.sibling)?;
+            if sibling.id == request.window || sibling.parent != Some(parent) {
+                return Err(Error::ProtocolError(ErrorCode::Match, value.sibling));
+            }
+            Some(value.sibling)
+        } else {
+            None
+        };
+        let stack_mode = if mask.contains(&ConfigureWindowValueMaskValue::StackMode) { Some(value.stack_mode.clone()) } else { None };
+
+        if let Some(redirect) = self.substructure_redirect_client(client, request.window) {
+            let event = Event::ConfigureRequest(ConfigureRequestEvent {
+                stack_mode: stack_mode.unwrap_or(ConfigureWindowValueStackMode::Above),
+                sequence_number: 0,
+                parent,
+                window: request.window,
+                sibling,
+                x,
+                y,
+                width,
+                height,
+                border_width,
+                value_mask: mask.clone(),
+            });
+            self.events.push((redirect, event));
+            return Ok(());
+        }
+        if width != old_width || height != old_height {
+            if let Some(redirect) = self.redirect_client(client, request.window, setup::Event::ResizeRedirect) {
+                let event = Event::ResizeRequest(ResizeRequestEvent {
+                    sequence_number: 0,
+                    window: request.window,
+                    width,
+                    height,
+                });
+                self.events.push((redirect, event));
+                width = old_width;
+                height = old_height;
+            }
+        }
+
+        let window = self.windows.get_mut(&request.window).unwrap();
+        window.x = x;
+        window.y = y;
+        window.width = width;
+        window.height = height;
+        window.border_width = border_width;
+        if let Some(stack_mode) = stack_mode {
+            self.restack(request.window, sibling, stack_mode);
+        }
+        if width != old_width || height != old_height {
+            //内側の左上の移動量
+            let dx = (x as i32 + border_width as i32) - (old_x as i32 + old_border_width as i32);
+            let dy = (y as i32 + border_width as i32) - (old_y as i32 + old_border_width as i32);
+            self.apply_gravity(request.window, width as i32 - old_width as i32, height as i32 - old_height as i32, dx, dy);
+        }
+        self.configure_notify(request.window);
+        Ok(())
+    }
+
+    fn configure_notify(&mut self, window: u32) {
+        let above_sibling = self.below_sibling(window);
+        let w = &self.windows[&window];
+        let (x, y, width, height, border_width, override_redirect) = (w.x, w.y, w.width, w.height, w.border_width, w.attributes.override_redirect);
+        self.notify(window, |event| Event::ConfigureNotify(ConfigureNotifyEvent {
+            sequence_number: 0,
+            event,
+            window,
+            above_sibling,
+            x,
+            y,
+            width,
+            height,
+            border_width,
+            override_redirect,
+        }));
+    }
+
+    /// TopIf、BottomIf、Oppositeの判定は変更後の大きさで行う
+    fn restack(&mut self, window: u32, sibling: Option<u32>, stack_mode: ConfigureWindowValueStackMode) {
+        let others = match sibling {
+            Some(sibling) => vec![sibling],
+            None => self.siblings(window),
+        };
+        let occluded = others.iter().any(|other| self.occludes(*other, window));
+        let occluding = others.iter().any(|other| self.occludes(window, *other));
+        let parent = self.windows[&window].parent.unwrap();
+        let children = &mut self.windows.get_mut(&parent).unwrap().children;
+        let top = children.len() - 1;
+        let index = match (stack_mode, sibling) {
+            (ConfigureWindowValueStackMode::Above, Some(sibling)) => {
+                children.retain(|child| *child != window);
+                children.iter().position(|child| *child == sibling).unwrap() + 1
+            }
+            (ConfigureWindowValueStackMode::Below, Some(sibling)) => {
+                children.retain(|child| *child != window);
+                children.iter().position(|child| *child == sibling).unwrap()
+            }
+            (ConfigureWindowValueStackMode::Above, None) => top,
+            (ConfigureWindowValueStackMode::Below, None) => 0,
+            (ConfigureWindowValueStackMode::TopIf, _) if occluded => top,
+            (ConfigureWindowValueStackMode::BottomIf, _) if occluding => 0,
+            (ConfigureWindowValueStackMode::Opposite, _) if occluded => top,
+            (ConfigureWindowValueStackMode::Opposite, _) if occluding => 0,
+            _ => return,
+        };
+        children.retain(|child| *child != window);
+        children.insert(index, window);
+    }
+
+    /// 大きさが変わったwindowの子をwin_gravityに従って動かす
+    fn apply_gravity(&mut self, window: u32, dw: i32, dh: i32, dx: i32, dy: i32) {
+        for child in self.windows[&window].children.clone() {
+            let (gx, gy) = match self.windows[&child].attributes.win_gravity {
+                CreateWindowValueWinGravity::Unmap => {
+                    if self.windows[&child].mapped {
+                        self.unmap(child, true);
+                    }
+                    continue;
+                }
+                CreateWindowValueWinGravity::NorthWest => (0, 0),
+                CreateWindowValueWinGravity::North => (dw / 2, 0),
+                CreateWindowValueWinGravity::NorthEast => (dw, 0),
+                CreateWindowValueWinGravity::West => (0, dh / 2),
+                CreateWindowValueWinGravity::Center => (dw / 2, dh / 2),
+                CreateWindowValueWinGravity::East => (dw, dh / 2),
+                CreateWindowValueWinGravity::SouthWest => (0, dh),
+                CreateWindowValueWinGravity::South => (dw / 2, dh),
+                CreateWindowValueWinGravity::SouthEast => (dw, dh),
+                CreateWindowValueWinGravity::Static => (-dx, -dy),
+            };
+            if gx == 0 && gy == 0 {
+                continue;
+            }
+            let w = self.windows.get_mut(&child).unwrap();
+            w.x = (w.x as i32 + gx) as i16;
+            w.y = (w.y as i32 + gy) as i16;
+            let (x, y) = (w.x, w.y);
+            self.notify(child, |event| Event::GravityNotify(GravityNotifyEvent {
+                sequence_number: 0,
+                event,
+                window: child,
+                x,
+                y,
+            }));
+        }
+    }
+
+    pub fn circulate_window(&mut self, client: ClientId, request: &CirculateWindowRequest) -> Result<()> {
+        let children = self.lookup(request.window)?.children.clone();
+        let (target, place) = match request.direction {
+            CirculateWindowDirection::RaiseLowest => {
+                let target = children.iter().find(|child| children.iter().any(|other| self.occludes(*other, **child)));
+                (target, CirculatePlace::Top)
+            }
+            CirculateWindowDirection::LowerHighest => {
+                let target = children.iter().rev().find(|child| children.iter().any(|other| self.occludes(**child, *other)));
+                (target, CirculatePlace::Bottom)
+            }
+        };
+        let target = match target {
+            Some(target) => *target,
+            None => return Ok(()),
+        };
+        if let Some(redirect) = self.redirect_client(client, request.window, setup::Event::SubstructureRedirect) {
+            let event = Event::CirculateRequest(CirculateEvent {
+                sequence_number: 0,
+                event: request.window,
+                window: target,
+                place,
+            });
+            self.events.push((redirect, event));
+            return Ok(());
+        }
+        let children = &mut self.windows.get_mut(&request.window).unwrap().children;
+        children.retain(|child| *child != target);
+        match place {
+            CirculatePlace::Top => children.push(target),
+            CirculatePlace::Bottom => children.insert(0, target),
+        }
+        self.notify(target, |event| Event::CirculateNotify(CirculateEvent {
+            sequence_number: 0,
+            event,
+            window: target,
+            place: place.clone(),
+        }));
+        Ok(())
+    }
+
+    pub fn query_tree(&self, request: &QueryTreeRequest) -> Result<QueryTreeResponse> {
+        let window = self.lookup(request.window)?;
+        Ok(QueryTreeResponse {
+            sequence_number: 0,
+            root: self.root_of(request.window).unwrap(),
+            parent: window.parent,
+            children: window.children.clone(),
+        })
+    }
+
+    /// pixmapはここでは管理していないので、windowでなければBadDrawable
+    pub fn get_geometry(&self, request: &GetGeometryRequest) -> Result<GetGeometryResponse> {
+        let window = self.windows.get(&request.drawable).ok_or(Error::ProtocolError(ErrorCode::Drawable, request.drawable))?;
+        Ok(GetGeometryResponse {
+            depth: window.depth,
+            sequence_number: 0,
+            root: self.root_of(request.drawable).unwrap(),
+            x: window.x,
+            y: window.y,
+            width: window.width,
+            height: window.height,
+            border_width: window.border_width,
+        })
+    }
+
+    pub fn translate_coordinates(&self, request: &TranslateCoordinatesRequest) -> Result<TranslateCoordinatesResponse> {
+        self.lookup(request.src_window)?;
+        let dst_window = self.lookup(request.dst_window)?;
+        if self.root_of(request.src_window) != self.root_of(request.dst_window) {
+            return Ok(TranslateCoordinatesResponse {
+                same_screen: false,
+                sequence_number: 0,
+                child: None,
+                dst_x: 0,
+                dst_y: 0,
+            });
+        }
+        let (src_x, src_y) = self.origin(request.src_window).unwrap();
+        let (dst_x, dst_y) = self.origin(request.dst_window).unwrap();
+        let x = request.src_x as i32 + src_x - dst_x;
+        let y = request.src_y as i32 + src_y - dst_y;
+        let child = dst_window.children.iter().rev().find(|child| {
+            let child = &self.windows[*child];
+            let (cx, cy, cw, ch) = child.outer_rect();
+            child.mapped && cx <= x && x < cx + cw && cy <= y && y < cy + ch
+        }).cloned();
+        Ok(TranslateCoordinatesResponse {
+            same_screen: true,
+            sequence_number: 0,
+            child,
+            dst_x: x as i16,
+            dst_y: y as i16,
+        })
+    }
+
+    fn property_notify(&mut self, window: u32, atom: u32, time: u32, state: PropertyNotifyState) {
+        let event = Event::PropertyNotify(PropertyNotifyEvent {
+            sequence_number: 0,
+            window,
+            atom,
+            time,
+            state,
+        });
+        self.send(window, setup::Event::PropertyChange, event);
+    }
+
+    /// timeはPropertyNotifyに入れるserverの時刻
+    pub fn change_property(&mut self, atoms: &AtomTable, request: &ChangePropertyRequest, time: u32) -> Result<()> {
+        self.lookup(request.window)?;
+        atoms.name(request.property)?;
+        atoms.name(request.type_)?;
+        let window = self.windows.get_mut(&request.window).unwrap();
+        window.properties.change(request.property, request.type_, request.mode.clone(), request.data.clone())?;
+        self.property_notify(request.window, request.property, time, PropertyNotifyState::NewValue);
+        Ok(())
+    }
+
+    pub fn delete_property(&mut self, atoms: &AtomTable, request: &DeletePropertyRequest, time: u32) -> Result<()> {
+        self.lookup(request.window)?;
+        atoms.name(request.property)?;
+        if self.windows.get_mut(&request.window).unwrap().properties.delete(request.property).is_some() {
+            self.property_notify(request.window, request.property, time, PropertyNotifyState::Deleted);
+        }
+        Ok(())
+    }
+
+    pub fn get_property(&mut self, atoms: &AtomTable, request: &GetPropertyRequest, time: u32) -> Result<GetPropertyResponse> {
+        self.lookup(request.window)?;
+        atoms.name(request.property)?;
+        if let Some(type_) = request.type_ {
+            atoms.name(type_)?;
+        }
+        let (response, deleted) = self.windows.get_mut(&request.window).unwrap().properties.read(request)?;
+        if deleted {
+            self.property_notify(request.window, request.property, time, PropertyNotifyState::Deleted);
+        }
+        Ok(response)
+    }
+
+    pub fn list_properties(&self, request: &ListPropertiesRequest) -> Result<ListPropertiesResponse> {
+        Ok(ListPropertiesResponse {
+            sequence_number: 0,
+            atoms: self.lookup(request.window)?.properties.list(),
+        })
+    }
+
+    pub fn rotate_properties(&mut self, atoms: &AtomTable, request: &RotatePropertiesRequest, time: u32) -> Result<()> {
+        self.lookup(request.window)?;
+        for property in request.properties.iter() {
+            atoms.name(*property)?;
+        }
+        if self.windows.get_mut(&request.window).unwrap().properties.rotate(&request.properties, request.delta)? {
+            for property in request.properties.iter() {
+                self.property_notify(request.window, *property, time, PropertyNotifyState::NewValue);
+            }
+        }
+        Ok(())
+    }
+}
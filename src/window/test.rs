@@ -0,0 +1,298 @@
+#![allow(unused_imports)]
+#![deny(dead_code)]
+
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+use crate::Error;
+use crate::atom::{self, AtomTable};
+use crate::error::ErrorCode;
+use crate::event::{CirculateEvent, CirculatePlace, ConfigureNotifyEvent, ConfigureRequestEvent, CreateNotifyEvent, DestroyNotifyEvent, Event, GravityNotifyEvent, MapNotifyEvent, MapRequestEvent, PropertyNotifyEvent, PropertyNotifyState, ReparentNotifyEvent, UnmapNotifyEvent};
+use crate::request::change_property::{ChangePropertyMode, ChangePropertyRequest, PropertyValue};
+use crate::request::change_window_attributes::ChangeWindowAttributesRequest;
+use crate::request::circulate_window::{CirculateWindowDirection, CirculateWindowRequest};
+use crate::request::configure_window::{ConfigureWindowRequest, ConfigureWindowValue, ConfigureWindowValueMaskValue, ConfigureWindowValueStackMode};
+use crate::request::create_window::{CreateWindowClass, CreateWindowRequest, CreateWindowValue, CreateWindowValueMaskValue, CreateWindowValueWinGravity};
+use crate::request::delete_property::DeletePropertyRequest;
+use crate::request::destroy_window::DestroyWindowRequest;
+use crate::request::get_geometry::{GetGeometryRequest, GetGeometryResponse};
+use crate::request::get_property::GetPropertyRequest;
+use crate::request::get_window_attributes::{GetWindowAttributesMapState, GetWindowAttributesRequest};
+use crate::request::list_properties::{ListPropertiesRequest, ListPropertiesResponse};
+use crate::request::map_window::MapWindowRequest;
+use crate::request::query_tree::{QueryTreeRequest, QueryTreeResponse};
+use crate::request::reparent_window::ReparentWindowRequest;
+use crate::request::rotate_properties::RotatePropertiesRequest;
+use crate::request::translate_coordinates::{TranslateCoordinatesRequest, TranslateCoordinatesResponse};
+use crate::request::unmap_window::UnmapWindowRequest;
+use crate::setup;
+#[cfg(test)]
+use crate::test_util::{create_window, screen, select_input};
+use super::WindowTree;
+
+#[test]
+fn create_window_test() {
+    fn create(wid: u32, parent: u32, class: CreateWindowClass, border_width: u16) -> CreateWindowRequest {
+        CreateWindowRequest { class, border_width, ..create_window(wid, parent, 10, 20, 100, 50) }
+    }
+
+    let mut tree = WindowTree::new(&[screen()]);
+    let select = ChangeWindowAttributesRequest {
+        window: 1,
+        value_mask: HashSet::from_iter([CreateWindowValueMaskValue::EventMask].to_vec()),
+        value: CreateWindowValue { event_mask: HashSet::from_iter([setup::Event::SubstructureNotify].to_vec()), ..Default::default() },
+    };
+    tree.change_window_attributes(9, &select).unwrap();
+    tree.create_window(1, &create(0x00200001, 1, CreateWindowClass::CopyFromParent, 2)).unwrap();
+    tree.create_window(1, &create(0x00200002, 1, CreateWindowClass::InputOnly, 0)).unwrap();
+    assert_eq!(tree.take_events(), vec![
+        (9, Event::CreateNotify(CreateNotifyEvent { sequence_number: 0, parent: 1, window: 0x00200001, x: 10, y: 20, width: 100, height: 50, border_width: 2, override_redirect: false })),
+        (9, Event::CreateNotify(CreateNotifyEvent { sequence_number: 0, parent: 1, window: 0x00200002, x: 10, y: 20, width: 100, height: 50, border_width: 0, override_redirect: false })),
+    ]);
+    assert_eq!(tree.query_tree(&QueryTreeRequest { window: 1 }).unwrap(), QueryTreeResponse { sequence_number: 0, root: 1, parent: None, children: vec![0x00200001, 0x00200002] });
+    assert_eq!(tree.get_geometry(&GetGeometryRequest { drawable: 0x00200001 }).unwrap(), GetGeometryResponse { depth: 24, sequence_number: 0, root: 1, x: 10, y: 20, width: 100, height: 50, border_width: 2 });
+    assert_eq!(tree.get(0x00200002).unwrap().depth, 0);
+
+    assert!(matches!(tree.create_window(1, &create(0x00200003, 5, CreateWindowClass::InputOutput, 0)), Err(Error::ProtocolError(ErrorCode::Window, 5))));
+    assert!(matches!(tree.create_window(1, &create(0x00200003, 1, CreateWindowClass::InputOnly, 1)), Err(Error::ProtocolError(ErrorCode::Match, _))));
+    assert!(matches!(tree.create_window(1, &create(0x00200003, 0x00200002, CreateWindowClass::InputOutput, 0)), Err(Error::ProtocolError(ErrorCode::Match, _))));
+    assert!(matches!(tree.create_window(1, &CreateWindowRequest { width: 0, ..create(0x00200003, 1, CreateWindowClass::InputOutput, 0) }), Err(Error::ProtocolError(ErrorCode::Value, 0))));
+    assert!(matches!(tree.create_window(1, &CreateWindowRequest { depth: 8, ..create(0x00200003, 1, CreateWindowClass::InputOutput, 0) }), Err(Error::ProtocolError(ErrorCode::Match, _))));
+    assert!(matches!(tree.get_geometry(&GetGeometryRequest { drawable: 0x00200003 }), Err(Error::ProtocolError(ErrorCode::Drawable, 0x00200003))));
+    assert!(tree.take_events().is_empty());
+
+    //SubstructureRedirectは1つのclientしか選択できない
+    let redirect = ChangeWindowAttributesRequest {
+        window: 1,
+        value_mask: HashSet::from_iter([CreateWindowValueMaskValue::EventMask].to_vec()),
+        value: CreateWindowValue { event_mask: HashSet::from_iter([setup::Event::SubstructureRedirect].to_vec()), ..Default::default() },
+    };
+    tree.change_window_attributes(9, &redirect).unwrap();
+    assert!(matches!(tree.change_window_attributes(1, &redirect), Err(Error::ProtocolError(ErrorCode::Access, 1))));
+}
+
+#[test]
+fn map_window_test() {
+    fn create(wid: u32, parent: u32, event_mask: Vec<setup::Event>) -> CreateWindowRequest {
+        select_input(create_window(wid, parent, 0, 0, 10, 10), &event_mask)
+    }
+
+    let mut tree = WindowTree::new(&[screen()]);
+    tree.create_window(1, &create(0x00200001, 1, vec![setup::Event::StructureNotify, setup::Event::SubstructureNotify])).unwrap();
+    tree.create_window(1, &create(0x00200002, 0x00200001, vec![])).unwrap();
+    tree.take_events();
+
+    tree.map_window(1, &MapWindowRequest { window: 0x00200002 }).unwrap();
+    assert!(!tree.is_viewable(0x00200002));
+    let attributes = tree.get_window_attributes(1, &GetWindowAttributesRequest { window: 0x00200002 }).unwrap();
+    assert_eq!(attributes.map_state, GetWindowAttributesMapState::Unviewable);
+    tree.map_window(1, &MapWindowRequest { window: 0x00200001 }).unwrap();
+    assert!(tree.is_viewable(0x00200002));
+    assert_eq!(tree.take_events(), vec![
+        (1, Event::MapNotify(MapNotifyEvent { sequence_number: 0, event: 0x00200001, window: 0x00200002, override_redirect: false })),
+        (1, Event::MapNotify(MapNotifyEvent { sequence_number: 0, event: 0x00200001, window: 0x00200001, override_redirect: false })),
+    ]);
+    let attributes = tree.get_window_attributes(1, &GetWindowAttributesRequest { window: 0x00200001 }).unwrap();
+    assert_eq!(attributes.map_state, GetWindowAttributesMapState::Viewable);
+    assert_eq!(attributes.your_event_mask, HashSet::from_iter([setup::Event::StructureNotify, setup::Event::SubstructureNotify].to_vec()));
+    assert_eq!(attributes.colormap, Some(2));
+
+    //別のclientがSubstructureRedirectを選択しているとMapRequestになる
+    tree.unmap_window(&UnmapWindowRequest { window: 0x00200002 }).unwrap();
+    assert_eq!(tree.take_events(), vec![
+        (1, Event::UnmapNotify(UnmapNotifyEvent { sequence_number: 0, event: 0x00200001, window: 0x00200002, from_configure: false })),
+    ]);
+    tree.create_window(2, &create(0x00400001, 1, vec![])).unwrap();
+    let redirect = ChangeWindowAttributesRequest {
+        window: 0x00200001,
+        value_mask: HashSet::from_iter([CreateWindowValueMaskValue::EventMask].to_vec()),
+        value: CreateWindowValue { event_mask: HashSet::from_iter([setup::Event::SubstructureRedirect].to_vec()), ..Default::default() },
+    };
+    tree.change_window_attributes(2, &redirect).unwrap();
+    tree.map_window(1, &MapWindowRequest { window: 0x00200002 }).unwrap();
+    assert!(!tree.get(0x00200002).unwrap().mapped);
+    assert_eq!(tree.take_events(), vec![
+        (2, Event::MapRequest(MapRequestEvent { sequence_number: 0, parent: 0x00200001, window: 0x00200002 })),
+    ]);
+    tree.map_window(2, &MapWindowRequest { window: 0x00200002 }).unwrap();
+    assert!(tree.get(0x00200002).unwrap().mapped);
+}
+
+#[test]
+fn configure_window_test() {
+    fn create(wid: u32, parent: u32, x: i16, win_gravity: CreateWindowValueWinGravity) -> CreateWindowRequest {
+        let mut request = create_window(wid, parent, x, 0, 100, 100);
+        request.value_mask.insert(CreateWindowValueMaskValue::WinGravity);
+        request.value.win_gravity = win_gravity;
+        request
+    }
+    fn configure(window: u32, value_mask: Vec<ConfigureWindowValueMaskValue>, value: ConfigureWindowValue) -> ConfigureWindowRequest {
+        ConfigureWindowRequest { window, value_mask: HashSet::from_iter(value_mask), value }
+    }
+
+    let mut tree = WindowTree::new(&[screen()]);
+    for (wid, x) in [(0x00200001, 0), (0x00200002, 50), (0x00200003, 300)].iter() {
+        tree.create_window(1, &create(*wid, 1, *x, CreateWindowValueWinGravity::NorthWest)).unwrap();
+        tree.map_window(1, &MapWindowRequest { window: *wid }).unwrap();
+    }
+    let select = ChangeWindowAttributesRequest {
+        window: 1,
+        value_mask: HashSet::from_iter([CreateWindowValueMaskValue::EventMask].to_vec()),
+        value: CreateWindowValue { event_mask: HashSet::from_iter([setup::Event::SubstructureNotify].to_vec()), ..Default::default() },
+    };
+    tree.change_window_attributes(9, &select).unwrap();
+
+    tree.configure_window(1, &configure(0x00200001, vec![ConfigureWindowValueMaskValue::Sibling, ConfigureWindowValueMaskValue::StackMode], ConfigureWindowValue { sibling: 0x00200002, stack_mode: ConfigureWindowValueStackMode::Above, ..Default::default() })).unwrap();
+    assert_eq!(tree.get(1).unwrap().children, vec![0x00200002, 0x00200001, 0x00200003]);
+    assert_eq!(tree.take_events(), vec![
+        (9, Event::ConfigureNotify(ConfigureNotifyEvent { sequence_number: 0, event: 1, window: 0x00200001, above_sibling: Some(0x00200002), x: 0, y: 0, width: 100, height: 100, border_width: 0, override_redirect: false })),
+    ]);
+
+    //0x00200003は何にも隠されていないのでTopIfでは動かない
+    tree.configure_window(1, &configure(0x00200003, vec![ConfigureWindowValueMaskValue::StackMode], ConfigureWindowValue { stack_mode: ConfigureWindowValueStackMode::BottomIf, ..Default::default() })).unwrap();
+    assert_eq!(tree.get(1).unwrap().children, vec![0x00200002, 0x00200001, 0x00200003]);
+    tree.configure_window(1, &configure(0x00200002, vec![ConfigureWindowValueMaskValue::StackMode], ConfigureWindowValue { stack_mode: ConfigureWindowValueStackMode::TopIf, ..Default::default() })).unwrap();
+    assert_eq!(tree.get(1).unwrap().children, vec![0x00200001, 0x00200003, 0x00200002]);
+    tree.configure_window(1, &configure(0x00200002, vec![ConfigureWindowValueMaskValue::StackMode], ConfigureWindowValue { stack_mode: ConfigureWindowValueStackMode::Opposite, ..Default::default() })).unwrap();
+    assert_eq!(tree.get(1).unwrap().children, vec![0x00200002, 0x00200001, 0x00200003]);
+    tree.take_events();
+
+    assert!(matches!(tree.configure_window(1, &configure(0x00200001, vec![ConfigureWindowValueMaskValue::Sibling], ConfigureWindowValue { sibling: 0x00200002, ..Default::default() })), Err(Error::ProtocolError(ErrorCode::Match, _))));
+    assert!(matches!(tree.configure_window(1, &configure(0x00200001, vec![ConfigureWindowValueMaskValue::Width], ConfigureWindowValue::default())), Err(Error::ProtocolError(ErrorCode::Value, _))));
+
+    //大きさが変わると子がwin_gravityに従って動く
+    tree.create_window(1, &create(0x00200004, 0x00200003, 10, CreateWindowValueWinGravity::SouthEast)).unwrap();
+    tree.create_window(1, &create(0x00200005, 0x00200003, 10, CreateWindowValueWinGravity::NorthWest)).unwrap();
+    let select = ChangeWindowAttributesRequest { window: 0x00200004, value: CreateWindowValue { event_mask: HashSet::from_iter([setup::Event::StructureNotify].to_vec()), ..Default::default() }, ..select };
+    tree.change_window_attributes(9, &select).unwrap();
+    tree.configure_window(1, &configure(0x00200003, vec![ConfigureWindowValueMaskValue::Width, ConfigureWindowValueMaskValue::Height], ConfigureWindowValue { width: 120, height: 90, ..Default::default() })).unwrap();
+    assert_eq!(tree.take_events(), vec![
+        (9, Event::GravityNotify(GravityNotifyEvent { sequence_number: 0, event: 0x00200004, window: 0x00200004, x: 30, y: -10 })),
+        (9, Event::ConfigureNotify(ConfigureNotifyEvent { sequence_number: 0, event: 1, window: 0x00200003, above_sibling: Some(0x00200001), x: 300, y: 0, width: 120, height: 90, border_width: 0, override_redirect: false })),
+    ]);
+    assert_eq!((tree.get(0x00200005).unwrap().x, tree.get(0x00200005).unwrap().y), (10, 0));
+
+    //別のclientがSubstructureRedirectを選択しているとConfigureRequestになる
+    let redirect = ChangeWindowAttributesRequest { window: 1, value: CreateWindowValue { event_mask: HashSet::from_iter([setup::Event::SubstructureRedirect].to_vec()), ..Default::default() }, ..select };
+    tree.change_window_attributes(9, &redirect).unwrap();
+    tree.configure_window(1, &configure(0x00200001, vec![ConfigureWindowValueMaskValue::X], ConfigureWindowValue { x: -5, ..Default::default() })).unwrap();
+    assert_eq!(tree.get(0x00200001).unwrap().x, 0);
+    assert_eq!(tree.take_events(), vec![
+        (9, Event::ConfigureRequest(ConfigureRequestEvent {
+            stack_mode: ConfigureWindowValueStackMode::Above,
+            sequence_number: 0,
+            parent: 1,
+            window: 0x00200001,
+            sibling: None,
+            x: -5,
+            y: 0,
+            width: 100,
+            height: 100,
+            border_width: 0,
+            value_mask: HashSet::from_iter([ConfigureWindowValueMaskValue::X].to_vec()),
+        })),
+    ]);
+}
+
+#[test]
+fn circulate_window_test() {
+    fn create(wid: u32, x: i16, event_mask: Vec<setup::Event>) -> CreateWindowRequest {
+        CreateWindowRequest { border_width: 1, ..select_input(create_window(wid, 1, x, 0, 100, 100), &event_mask) }
+    }
+
+    let mut tree = WindowTree::new(&[screen()]);
+    tree.create_window(1, &create(0x00200001, 0, vec![setup::Event::StructureNotify])).unwrap();
+    tree.create_window(1, &create(0x00200002, 101, vec![])).unwrap();
+    tree.create_window(1, &create(0x00200003, 300, vec![])).unwrap();
+    for wid in [0x00200001, 0x00200002, 0x00200003].iter() {
+        tree.map_window(1, &MapWindowRequest { window: *wid }).unwrap();
+    }
+    tree.take_events();
+
+    //borderの分だけ0x00200001と0x00200002が重なっている
+    tree.circulate_window(1, &CirculateWindowRequest { direction: CirculateWindowDirection::RaiseLowest, window: 1 }).unwrap();
+    assert_eq!(tree.get(1).unwrap().children, vec![0x00200002, 0x00200003, 0x00200001]);
+    assert_eq!(tree.take_events(), vec![
+        (1, Event::CirculateNotify(CirculateEvent { sequence_number: 0, event: 0x00200001, window: 0x00200001, place: CirculatePlace::Top })),
+    ]);
+    tree.circulate_window(1, &CirculateWindowRequest { direction: CirculateWindowDirection::LowerHighest, window: 1 }).unwrap();
+    assert_eq!(tree.get(1).unwrap().children, vec![0x00200001, 0x00200002, 0x00200003]);
+    assert_eq!(tree.take_events(), vec![
+        (1, Event::CirculateNotify(CirculateEvent { sequence_number: 0, event: 0x00200001, window: 0x00200001, place: CirculatePlace::Bottom })),
+    ]);
+    tree.circulate_window(1, &CirculateWindowRequest { direction: CirculateWindowDirection::RaiseLowest, window: 0x00200003 }).unwrap();
+    assert!(tree.take_events().is_empty());
+}
+
+#[test]
+fn reparent_window_test() {
+    fn create(wid: u32, parent: u32, x: i16, y: i16) -> CreateWindowRequest {
+        CreateWindowRequest { border_width: 2, ..create_window(wid, parent, x, y, 100, 100) }
+    }
+
+    let mut tree = WindowTree::new(&[screen()]);
+    tree.create_window(1, &create(0x00200001, 1, 10, 10)).unwrap();
+    tree.create_window(1, &create(0x00200002, 1, 200, 10)).unwrap();
+    tree.create_window(1, &create(0x00200003, 0x00200001, 5, 5)).unwrap();
+    for wid in [0x00200001, 0x00200002, 0x00200003].iter() {
+        tree.map_window(1, &MapWindowRequest { window: *wid }).unwrap();
+    }
+    let select = ChangeWindowAttributesRequest {
+        window: 0x00200003,
+        value_mask: HashSet::from_iter([CreateWindowValueMaskValue::EventMask].to_vec()),
+        value: CreateWindowValue { event_mask: HashSet::from_iter([setup::Event::StructureNotify].to_vec()), ..Default::default() },
+    };
+    tree.change_window_attributes(9, &select).unwrap();
+    tree.take_events();
+
+    assert_eq!(tree.translate_coordinates(&TranslateCoordinatesRequest { src_window: 0x00200003, dst_window: 1, src_x: 1, src_y: 1 }).unwrap(),
+               TranslateCoordinatesResponse { same_screen: true, sequence_number: 0, child: Some(0x00200001), dst_x: 20, dst_y: 20 });
+    assert_eq!(tree.translate_coordinates(&TranslateCoordinatesRequest { src_window: 1, dst_window: 0x00200002, src_x: 0, src_y: 0 }).unwrap(),
+               TranslateCoordinatesResponse { same_screen: true, sequence_number: 0, child: None, dst_x: -202, dst_y: -12 });
+
+    assert!(matches!(tree.reparent_window(1, &ReparentWindowRequest { window: 0x00200001, parent: 0x00200003, x: 0, y: 0 }), Err(Error::ProtocolError(ErrorCode::Match, _))));
+    tree.reparent_window(1, &ReparentWindowRequest { window: 0x00200003, parent: 0x00200002, x: 7, y: 8 }).unwrap();
+    assert_eq!(tree.take_events(), vec![
+        (9, Event::UnmapNotify(UnmapNotifyEvent { sequence_number: 0, event: 0x00200003, window: 0x00200003, from_configure: false })),
+        (9, Event::ReparentNotify(ReparentNotifyEvent { sequence_number: 0, event: 0x00200003, window: 0x00200003, parent: 0x00200002, x: 7, y: 8, override_redirect: false })),
+        (9, Event::MapNotify(MapNotifyEvent { sequence_number: 0, event: 0x00200003, window: 0x00200003, override_redirect: false })),
+    ]);
+    assert_eq!(tree.query_tree(&QueryTreeRequest { window: 0x00200003 }).unwrap().parent, Some(0x00200002));
+    assert!(tree.get(0x00200001).unwrap().children.is_empty());
+    assert_eq!(tree.origin(0x00200003), Some((211, 22)));
+
+    let destroyed = tree.destroy_window(&DestroyWindowRequest { window: 0x00200002 }).unwrap();
+    assert_eq!(destroyed, vec![0x00200003, 0x00200002]);
+    assert_eq!(tree.take_events(), vec![
+        (9, Event::DestroyNotify(DestroyNotifyEvent { sequence_number: 0, event: 0x00200003, window: 0x00200003 })),
+    ]);
+    assert_eq!(tree.get(1).unwrap().children, vec![0x00200001]);
+    assert!(tree.get(0x00200003).is_none());
+}
+
+#[test]
+fn property_test() {
+
+    let atoms = AtomTable::new();
+    let mut tree = WindowTree::new(&[screen()]);
+    let select = ChangeWindowAttributesRequest {
+        window: 1,
+        value_mask: HashSet::from_iter([CreateWindowValueMaskValue::EventMask].to_vec()),
+        value: CreateWindowValue { event_mask: HashSet::from_iter([setup::Event::PropertyChange].to_vec()), ..Default::default() },
+    };
+    tree.change_window_attributes(9, &select).unwrap();
+
+    let change = ChangePropertyRequest { mode: ChangePropertyMode::Replace, window: 1, property: atom::WM_NAME, type_: atom::STRING, data: PropertyValue::Format8(b"root".to_vec()) };
+    tree.change_property(&atoms, &change, 100).unwrap();
+    assert!(matches!(tree.change_property(&atoms, &ChangePropertyRequest { property: 1000, ..change.clone() }, 101), Err(Error::ProtocolError(ErrorCode::Atom, 1000))));
+    assert!(matches!(tree.change_property(&atoms, &ChangePropertyRequest { window: 2, ..change.clone() }, 101), Err(Error::ProtocolError(ErrorCode::Window, 2))));
+    tree.rotate_properties(&atoms, &RotatePropertiesRequest { window: 1, delta: 1, properties: vec![atom::WM_NAME] }, 102).unwrap();
+    assert_eq!(tree.list_properties(&ListPropertiesRequest { window: 1 }).unwrap(), ListPropertiesResponse { sequence_number: 0, atoms: vec![atom::WM_NAME] });
+    let get = GetPropertyRequest { delete: true, window: 1, property: atom::WM_NAME, type_: None, long_offset: 0, long_length: 1 };
+    assert_eq!(tree.get_property(&atoms, &get, 103).unwrap().value, Some(PropertyValue::Format8(b"root".to_vec())));
+    tree.delete_property(&atoms, &DeletePropertyRequest { window: 1, property: atom::WM_NAME }, 104).unwrap();
+    assert_eq!(tree.take_events(), vec![
+        (9, Event::PropertyNotify(PropertyNotifyEvent { sequence_number: 0, window: 1, atom: atom::WM_NAME, time: 100, state: PropertyNotifyState::NewValue })),
+        (9, Event::PropertyNotify(PropertyNotifyEvent { sequence_number: 0, window: 1, atom: atom::WM_NAME, time: 103, state: PropertyNotifyState::Deleted })),
+    ]);
+}
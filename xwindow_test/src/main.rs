@@ -5,8 +5,12 @@ use std::net::TcpListener;
 use xwindow::read_util::{ReadableRead, WritableWrite};
 use xwindow::request::query_extension::QueryExtensionResponse;
 use xwindow::request::Request;
+use xwindow::request::get_atom_name::GetAtomNameResponse;
+use xwindow::request::intern_atom::InternAtomResponse;
 use xwindow::setup::{BackingStores, BitmapFormatBitOrder, Class, ConnectionSetupFailed, ConnectionSetupResponse, ConnectionSetupSuccess, Depth, ImageByteOrder, read_setup, Screen, VisualType};
 use xwindow::request::get_property::GetPropertyResponse;
+use xwindow::atom::AtomTable;
+use xwindow::motion::MOTION_BUFFER_SIZE;
 
 fn main() {
     let listener = TcpListener::bind("127.0.0.1:6000").unwrap();
@@ -30,7 +34,7 @@ fn main() {
                 release_number: 1,
                 resource_id_base: 0,
                 resource_id_mask: 0x1f_ff_ff_ff,
-                motion_buffer_size: u32::MAX,
+                motion_buffer_size: MOTION_BUFFER_SIZE as u32,
                 maximum_request_length: u16::MAX,
                 image_byte_order: ImageByteOrder::LSBFirst,
                 bitmap_format_bit_order: BitmapFormatBitOrder::LeastSignificant,
@@ -97,12 +101,10 @@ fn main() {
                     if let Ok(Request::GetProperty(req)) = reader.read_value(&order) {
                         println!("{:#?}", req);
                         writer.write_value(GetPropertyResponse {
-                            format: 0,
                             sequence_number: 3,
                             type_: None,
                             bytes_after: 0,
-                            length_of_value_in_format_units: 0,
-                            value: vec![]
+                            value: None,
                         }, &order).unwrap();
                         writer.flush();
                     } else {
@@ -121,6 +123,37 @@ fn main() {
                     } else {
                         println!("request is not queryExtension");
                     }
+                    let atoms = AtomTable::new();
+                    let mut sequence_number = 4u16;
+                    loop {
+                        sequence_number = sequence_number.wrapping_add(1);
+                        let result = match reader.read_value(&order) {
+                            Ok(Request::InternAtom(req)) => {
+                                println!("{:#?}", req);
+                                atoms.intern_atom(&req).map(|response| writer.write_value(InternAtomResponse { sequence_number, ..response }, &order)).map_err(|e| (e, 16))
+                            }
+                            Ok(Request::GetAtomName(req)) => {
+                                println!("{:#?}", req);
+                                atoms.get_atom_name(&req).map(|response| writer.write_value(GetAtomNameResponse { sequence_number, ..response }, &order)).map_err(|e| (e, 17))
+                            }
+                            Ok(_) => {
+                                println!("request is not handled");
+                                break;
+                            }
+                            Err(e) => {
+                                println!("{:#?}", e);
+                                break;
+                            }
+                        };
+                        match result {
+                            Ok(result) => result.unwrap(),
+                            Err((e, major_opcode)) => {
+                                let error = e.to_x_error(sequence_number, major_opcode, 0).unwrap();
+                                writer.write_value(error, &order).unwrap();
+                            }
+                        }
+                        writer.flush().unwrap();
+                    }
                 }
                 Err(err) => {
                     println!("{:#?}", err);
@@ -152,6 +185,5 @@ fn main() {
         }
         println!("{}", hex);
         println!("{}", asc);
-        //TODO:次は 16:InternAtom
     }
 }
\ No newline at end of file